@@ -20,12 +20,12 @@ pub async fn test(iterations: u64, size: u64){
     gpu_data.disable_shapes();
     for _i in 0..iterations{
         let sample = Sample::from_data(vec!{tensor.clone()}, vec!{1.0}, tensor.get_shape());
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
     }
-    let mut gpu_buffers = GpuBuffers::init(2, MemoryMetric::GB, &mut gpu_data, 0).await;
-    gpu_buffers.set_shader(&GpuOperations::Add);
-    gpu_buffers.prepare();
-    let buffers_output = gpu_buffers.run().await;
+    let mut gpu_buffers = GpuBuffers::init(2, MemoryMetric::GB, &mut gpu_data, 0).await.unwrap();
+    gpu_buffers.set_shader(&GpuOperations::Add).unwrap();
+    gpu_buffers.prepare().unwrap();
+    let buffers_output = gpu_buffers.run().await.unwrap();
 
 
     let buffers_duration = buffers_start.elapsed();
@@ -35,9 +35,9 @@ pub async fn test(iterations: u64, size: u64){
     let mut gpu_runner = GpuRunner::with_capacity((iterations*size) as usize, 2, MemoryMetric::GB);
     for _i in 0..iterations{
         let sample = Sample::from_data(vec!{tensor.clone()}, vec!{1.0}, tensor.get_shape());
-        gpu_runner.append(sample);
+        gpu_runner.append(sample).unwrap();
     } 
-    let runner_output = gpu_runner.add().await;
+    let runner_output = gpu_runner.add().await.unwrap();
 
 
     let runner_duration = runer_start.elapsed();
@@ -25,22 +25,22 @@ async fn test(iterations: u32, samples: u32, neurons: u32){
     for _i in 0..iterations{
         let sample = Sample::from_data(vec!{weights.clone(), inputs.clone(), biases.clone()}, vec!{}, inputs.get_shape());
 
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
     }
     let prep_duration = prep_init.elapsed();
 
     let buffer_init = Instant::now();
-    let mut buffers = GpuBuffers::init(2, MemoryMetric::GB, &mut gpu_data, 0).await;
-    buffers.set_shader(&GpuOperations::ForwardNoActiv);
-    buffers.prepare();
+    let mut buffers = GpuBuffers::init(2, MemoryMetric::GB, &mut gpu_data, 0).await.unwrap();
+    buffers.set_shader(&GpuOperations::ForwardNoActiv).unwrap();
+    buffers.prepare().unwrap();
     let buffer_duration = buffer_init.elapsed();
 
     let buffer_update = Instant::now();
-    buffers.update(&mut gpu_data, 0);
+    buffers.update(&mut gpu_data, 0).unwrap();
     let buffer_update_duration = buffer_update.elapsed();
 
     let gpu_runtime_init = Instant::now();
-    let _full_gpu_output: Vec<Tensor<f32>> = buffers.run().await;
+    let _full_gpu_output: Vec<Tensor<f32>> = buffers.run().await.unwrap();
     let gpu_duration = gpu_runtime_init.elapsed();
     let start_duration = start_init.elapsed();
 
@@ -1,5 +1,59 @@
 use flashlight_tensor::prelude::*;
-use std::time::Instant;
+use std::{sync::Arc, time::{Duration, Instant}};
+
+/// Matmul without workgroup tiles, every invocation reads a whole row of A and column of B from
+/// global memory, the same as matmul shader before tiling
+const NAIVE_MATMUL: &str = "
+@group(0) @binding(0)
+var<storage, read> input: array<f32>;
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>, @builtin(num_workgroups) num_workgroups: vec3<u32>){
+    let idx = invocation_index(global_id, num_workgroups);
+    if (idx >= arrayLength(&output)) {
+        return;
+    }
+
+    let rows = matrix_rows(0u);
+    let depth = matrix_cols(0u);
+    let cols = matrix_cols(1u);
+
+    let sample = idx / (rows * cols);
+    let row = (idx % (rows * cols)) / cols;
+    let col = idx % cols;
+
+    var sum = 0.0;
+    for (var k = 0u; k < depth; k++) {
+        sum += input[matrix_index(0u, sample, row, k)] * input[matrix_index(1u, sample, k, col)];
+    }
+    output[idx] = sum;
+}
+";
+
+/// Returns the shortest time of runs of matmul, naive if operation is given, tiled otherwise
+/// Operation is run once before, so compilation of pipeline is not measured
+async fn best_of(runner: &mut GpuRunner, runs: usize, naive: Option<&Arc<CustomOperation>>) -> Duration{
+    let mut best = Duration::MAX;
+
+    for run in 0..=runs{
+        let start = Instant::now();
+        let output = match naive{
+            Some(operation) => runner.custom(operation).await.unwrap(),
+            None => runner.matmul().await.unwrap(),
+        };
+        let duration = start.elapsed();
+
+        assert_eq!(output[0].get_data()[0], output[0].get_shape()[0] as f32);
+        if run > 0{
+            best = best.min(duration);
+        }
+    }
+
+    best
+}
 
 #[tokio::main]
 async fn main(){
@@ -24,7 +78,7 @@ async fn main(){
 
         let sample = Sample::from_data(vec!{tensor1, tensor2}, vec!{}, &[m, n]);
         
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
     }
     
     let duration_insert = start_insert.elapsed();
@@ -33,9 +87,9 @@ async fn main(){
     
     let start_preparation = Instant::now();
 
-    let mut buffers = GpuBuffers::init(2, MemoryMetric::GB, &mut gpu_data, 0).await;
-    buffers.set_shader(&GpuOperations::Matmul);
-    buffers.prepare();
+    let mut buffers = GpuBuffers::init(2, MemoryMetric::GB, &mut gpu_data, 0).await.unwrap();
+    buffers.set_shader(&GpuOperations::Matmul).unwrap();
+    buffers.prepare().unwrap();
 
     let duration_preparation = start_preparation.elapsed();
 
@@ -43,7 +97,7 @@ async fn main(){
     
     let start_run = Instant::now();
     
-    buffers.run().await;
+    buffers.run().await.unwrap();
 
     let duration_run = start_run.elapsed();
 
@@ -67,7 +121,7 @@ async fn main(){
 
         let sample = Sample::from_data(vec!{tensor1, tensor2}, vec!{}, &[m, n]);
         
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
         
     }
     let duration_insert2 = start_insert.elapsed();
@@ -75,14 +129,14 @@ async fn main(){
     println!("Cpu_Insert: {:?}", duration_insert2);
 
     let start_preparation = Instant::now();
-    buffers.update(&mut gpu_data, 0);
+    buffers.update(&mut gpu_data, 0).unwrap();
     let duration_preparation = start_preparation.elapsed();
 
     println!("Gpu_update: {:?}", duration_preparation);
 
     let start_run = Instant::now();
     
-    buffers.run().await;
+    buffers.run().await.unwrap();
 
     let duration_run = start_run.elapsed();
 
@@ -107,4 +161,40 @@ async fn main(){
     let duration = start.elapsed();
 
     println!("Cpu runtime: {:?}\n\n", duration);
+
+    let size = 256;
+    let samples = 10;
+    let runs = 5;
+
+    println!("naive and tiled matmul, samples: {}, sizes [{}, {}], [{}, {}], best of {} runs\n", samples, size, size, size, size, runs);
+
+    let naive = CustomOperation::new("naive_matmul", NAIVE_MATMUL, CustomLayout{metadata: true, ..Default::default()}, |shapes, _| {
+        Some(vec!{shapes[0][0], shapes[1][1]})
+    }).unwrap();
+    let naive = Arc::new(naive);
+
+    let mut runner = GpuRunner::init(2, MemoryMetric::GB);
+    for _i in 0..samples{
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[size, size]), Tensor::fill(1.0, &[size, size])}, vec!{}, &[])).unwrap();
+    }
+    let naive_duration = best_of(&mut runner, runs, Some(&naive)).await;
+
+    println!("Gpu naive: {:?}", naive_duration);
+
+    for tile_size in TILE_SIZES{
+        let config = GpuConfig{
+            tile_size,
+            ..Default::default()
+        };
+        let context = GpuContext::init_with_config(2, &MemoryMetric::GB, &config).await.unwrap();
+
+        let mut runner = GpuRunner::from_context(context, 2, MemoryMetric::GB);
+        for _i in 0..samples{
+            runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[size, size]), Tensor::fill(1.0, &[size, size])}, vec!{}, &[])).unwrap();
+        }
+
+        let duration = best_of(&mut runner, runs, None).await;
+
+        println!("Gpu tile {}: {:?}, {:.2}x faster than naive", tile_size, duration, naive_duration.as_secs_f64() / duration.as_secs_f64());
+    }
 }
@@ -35,16 +35,16 @@ async fn backward_bias(iterations: u32, samples: u32, neurons: u32){
     for _i in 0..iterations{
         let sample = Sample::from_data(vec!{bias.clone(), grad_output.clone(), linear_cache.clone()}, vec!{learning_rate}, &[]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
     }
     let prep_duration = prep_init.elapsed();
 
     let first_runtime = Instant::now();
-    runner.backward_bias().await;
+    runner.backward_bias().await.unwrap();
     let first_duration = first_runtime.elapsed();
 
     let second_runtime = Instant::now();
-    let _full_gpu_output = runner.backward_bias().await;
+    let _full_gpu_output = runner.backward_bias().await.unwrap();
     let second_duration = second_runtime.elapsed();
 
     let start_duration = start_init.elapsed();
@@ -73,16 +73,16 @@ async fn backward_weights(iterations: u32, samples: u32, neurons: u32){
     for _i in 0..iterations{
         let sample = Sample::from_data(vec!{weights.clone(), grad_output.clone(), linear_cache.clone()}, vec!{learning_rate}, &[]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
     }
     let prep_duration = prep_init.elapsed();
 
     let first_runtime = Instant::now();
-    runner.backward_weight().await;
+    runner.backward_weight().await.unwrap();
     let first_duration = first_runtime.elapsed();
 
     let second_runtime = Instant::now();
-    let _full_gpu_output = runner.backward_weight().await;
+    let _full_gpu_output = runner.backward_weight().await.unwrap();
     let second_duration = second_runtime.elapsed();
 
     let start_duration = start_init.elapsed();
@@ -108,16 +108,16 @@ async fn backward_gradient(iterations: u32, samples: u32, neurons: u32){
     for _i in 0..iterations{
         let sample = Sample::from_data(vec!{weights.clone(), grad_output.clone()}, vec!{}, &[]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
     }
     let prep_duration = prep_init.elapsed();
 
     let first_runtime = Instant::now();
-    runner.backward_grad().await;
+    runner.backward_grad().await.unwrap();
     let first_duration = first_runtime.elapsed();
 
     let second_runtime = Instant::now();
-    let _full_gpu_output = runner.backward_grad().await;
+    let _full_gpu_output = runner.backward_grad().await.unwrap();
     let second_duration = second_runtime.elapsed();
 
     let start_duration = start_init.elapsed();
@@ -144,16 +144,16 @@ async fn forward_propagation(iterations: u32, samples: u32, neurons: u32){
     for _i in 0..iterations{
         let sample = Sample::from_data(vec!{weights.clone(), inputs.clone(), biases.clone()}, vec!{}, &[]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
     }
     let prep_duration = prep_init.elapsed();
 
     let first_runtime = Instant::now();
-    runner.forward_no_activ().await;
+    runner.forward_no_activ().await.unwrap();
     let first_duration = first_runtime.elapsed();
 
     let second_runtime = Instant::now();
-    let _full_gpu_output = runner.forward_no_activ().await;
+    let _full_gpu_output = runner.forward_no_activ().await.unwrap();
     let second_duration = second_runtime.elapsed();
 
     let start_duration = start_init.elapsed();
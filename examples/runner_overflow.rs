@@ -1,4 +1,5 @@
 use flashlight_tensor::{prelude::*};
+use std::time::Instant;
 
 #[tokio::main]
 async fn main(){
@@ -10,7 +11,11 @@ async fn main(){
     let mut gpu_runner = GpuRunner::with_capacity((iterations*size) as usize, 2, MemoryMetric::GB);
     for _i in 0..iterations{
         let sample = Sample::from_data(vec!{tensor.clone()}, vec!{1.0}, tensor.get_shape());
-        gpu_runner.append(sample);
+        gpu_runner.append(sample).unwrap();
     } 
-    let _runner_output = gpu_runner.add().await;
+
+    // chunks are pipelined, next chunk runs on gpu while previous one is read back
+    let start = Instant::now();
+    let _runner_output = gpu_runner.add().await.unwrap();
+    println!("chunks: {}, time: {:?}", gpu_runner.gpu_data.chunks, start.elapsed());
 }
@@ -0,0 +1,120 @@
+use std::{fmt::Debug, iter::Sum, ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign}};
+
+use crate::tensor::Tensor;
+
+/// Floating point type of tensors, that math and machine learning functions are implemented for
+/// Implemented for f32 and f64, so the same functions compute in single or double precision
+/// Functions of f64 tensors are methods of traits, like FloatRelu, while Tensor<f32> has them
+/// inherent, so tensors of unannotated float literals stay f32
+pub trait Float:
+    Copy + Default + Debug + PartialEq + PartialOrd + Sum
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self>
+    + AddAssign + SubAssign + MulAssign + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const INFINITY: Self;
+    const NEG_INFINITY: Self;
+
+    /// Returns value nearest to f64 value
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn log10(self) -> Self;
+    fn log(self, base: Self) -> Self;
+    fn tanh(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn abs(self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn min(self, other: Self) -> Self;
+}
+
+macro_rules! impl_float{
+    ($($float:ident),*) => {
+        $(
+            impl Float for $float{
+                const ZERO: Self = 0.0;
+                const ONE: Self = 1.0;
+                const INFINITY: Self = $float::INFINITY;
+                const NEG_INFINITY: Self = $float::NEG_INFINITY;
+
+                fn from_f64(value: f64) -> Self{
+                    value as $float
+                }
+                fn to_f64(self) -> f64{
+                    self as f64
+                }
+
+                fn exp(self) -> Self{
+                    $float::exp(self)
+                }
+                fn ln(self) -> Self{
+                    $float::ln(self)
+                }
+                fn log10(self) -> Self{
+                    $float::log10(self)
+                }
+                fn log(self, base: Self) -> Self{
+                    $float::log(self, base)
+                }
+                fn tanh(self) -> Self{
+                    $float::tanh(self)
+                }
+                fn sqrt(self) -> Self{
+                    $float::sqrt(self)
+                }
+                fn abs(self) -> Self{
+                    $float::abs(self)
+                }
+                fn max(self, other: Self) -> Self{
+                    $float::max(self, other)
+                }
+                fn min(self, other: Self) -> Self{
+                    $float::min(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32, f64);
+
+impl Tensor<f32>{
+    /// Returns tensor with every value converted to f64, without loss of precision
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::fill(0.5, &[2, 2]);
+    /// let b: Tensor<f64> = a.to_f64();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{0.5; 4});
+    /// ```
+    pub fn to_f64(&self) -> Tensor<f64>{
+        let data: Vec<f64> = self.get_data().iter().map(|value| *value as f64).collect();
+
+        Tensor::from_data(&data, self.get_shape()).unwrap()
+    }
+}
+
+impl Tensor<f64>{
+    /// Returns tensor with every value rounded to f32
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f64> = Tensor::from_data(&[0.5, 0.1], &[2]).unwrap();
+    /// let b: Tensor<f32> = a.to_f32();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{0.5, 0.1});
+    /// ```
+    pub fn to_f32(&self) -> Tensor<f32>{
+        let data: Vec<f32> = self.get_data().iter().map(|value| *value as f32).collect();
+
+        Tensor::from_data(&data, self.get_shape()).unwrap()
+    }
+}
@@ -0,0 +1,137 @@
+use ::half::{bf16, f16};
+
+use crate::tensor::Tensor;
+
+impl Tensor<f32>{
+    /// Returns tensor with every value rounded to half precision
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 0.1], &[2]).unwrap();
+    /// let b: Tensor<f16> = a.to_f16();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{f16::from_f32(1.0), f16::from_f32(0.1)});
+    /// ```
+    pub fn to_f16(&self) -> Tensor<f16>{
+        let data: Vec<f16> = self.get_data().iter().map(|value| f16::from_f32(*value)).collect();
+
+        Tensor::from_data(&data, self.get_shape()).unwrap()
+    }
+    /// Returns tensor with every value rounded to bfloat16, with range of f32 and 8 bits of
+    /// mantissa
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 1e30], &[2]).unwrap();
+    /// let b: Tensor<bf16> = a.to_bf16();
+    ///
+    /// assert_eq!(b.get_data()[0], bf16::ONE);
+    /// assert!(b.get_data()[1].is_finite());
+    /// ```
+    pub fn to_bf16(&self) -> Tensor<bf16>{
+        let data: Vec<bf16> = self.get_data().iter().map(|value| bf16::from_f32(*value)).collect();
+
+        Tensor::from_data(&data, self.get_shape()).unwrap()
+    }
+}
+
+/// Returns product of [m, k] and [k, n] matrices of f32 values, None if shapes do not match
+/// Products are accumulated in f32, so half precision tensors are rounded once per output
+fn matrix_mul_f32(lhs: &[f32], lhs_shape: &[u32], rhs: &[f32], rhs_shape: &[u32]) -> Option<(Vec<f32>, Vec<u32>)>{
+    if lhs_shape.len() != 2 || rhs_shape.len() != 2 || lhs_shape[1] != rhs_shape[0]{
+        return None;
+    }
+
+    let (m, k, n) = (lhs_shape[0] as usize, lhs_shape[1] as usize, rhs_shape[1] as usize);
+
+    let mut return_data: Vec<f32> = vec![0.0; m * n];
+    for i in 0..m{
+        for l in 0..k{
+            let value = lhs[i * k + l];
+            for j in 0..n{
+                return_data[i * n + j] += value * rhs[l * n + j];
+            }
+        }
+    }
+
+    Some((return_data, vec!{m as u32, n as u32}))
+}
+
+impl Tensor<f16>{
+    /// Returns tensor with every value converted to f32, without loss of precision
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f16> = Tensor::fill(f16::from_f32(0.5), &[2, 2]);
+    ///
+    /// assert_eq!(a.to_f32().get_data(), &vec!{0.5; 4});
+    /// ```
+    pub fn to_f32(&self) -> Tensor<f32>{
+        let data: Vec<f32> = self.get_data().iter().map(|value| value.to_f32()).collect();
+
+        Tensor::from_data(&data, self.get_shape()).unwrap()
+    }
+    /// Performs matrix multiplication on matrix with another matrix
+    /// Products are accumulated in f32 and every output is rounded to half precision once
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f16> = Tensor::fill(f16::from_f32(0.1), &[2, 1000]);
+    /// let b: Tensor<f16> = Tensor::fill(f16::ONE, &[1000, 3]);
+    ///
+    /// let result: Tensor<f16> = a.matrix_mul(&b).unwrap();
+    ///
+    /// assert_eq!(result.get_shape(), &vec!{2, 3});
+    /// assert!((result.get_data()[0].to_f32() - 100.0).abs() < 0.1);
+    /// ```
+    pub fn matrix_mul(&self, tens2: &Tensor<f16>) -> Option<Tensor<f16>>{
+        let (data, shape) = matrix_mul_f32(self.to_f32().get_data(), self.get_shape(), tens2.to_f32().get_data(), tens2.get_shape())?;
+
+        Tensor::from_data(&data, &shape).map(|result| result.to_f16())
+    }
+}
+
+impl Tensor<bf16>{
+    /// Returns tensor with every value converted to f32, without loss of precision
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<bf16> = Tensor::fill(bf16::from_f32(0.5), &[2, 2]);
+    ///
+    /// assert_eq!(a.to_f32().get_data(), &vec!{0.5; 4});
+    /// ```
+    pub fn to_f32(&self) -> Tensor<f32>{
+        let data: Vec<f32> = self.get_data().iter().map(|value| value.to_f32()).collect();
+
+        Tensor::from_data(&data, self.get_shape()).unwrap()
+    }
+    /// Performs matrix multiplication on matrix with another matrix
+    /// Products are accumulated in f32 and every output is rounded to bfloat16 once
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<bf16> = Tensor::fill(bf16::from_f32(2.0), &[2, 300]);
+    /// let b: Tensor<bf16> = Tensor::fill(bf16::ONE, &[300, 3]);
+    ///
+    /// let result: Tensor<bf16> = a.matrix_mul(&b).unwrap();
+    ///
+    /// assert_eq!(result.get_data()[0].to_f32(), 600.0);
+    /// ```
+    pub fn matrix_mul(&self, tens2: &Tensor<bf16>) -> Option<Tensor<bf16>>{
+        let (data, shape) = matrix_mul_f32(self.to_f32().get_data(), self.get_shape(), tens2.to_f32().get_data(), tens2.get_shape())?;
+
+        Tensor::from_data(&data, &shape).map(|result| result.to_bf16())
+    }
+}
@@ -0,0 +1,312 @@
+use crate::tensor::Tensor;
+
+use super::{linear, linear_backward, descent};
+
+/// Returns tensor as [batch, rows, cols], where matrix is a batch of one
+/// None if tensor is not 2 or 3 dimensional
+fn as_batch(tensor: &Tensor<f32>) -> Option<Tensor<f32>>{
+    let mut return_tensor = tensor.clone();
+
+    match tensor.get_shape().len(){
+        2 => return_tensor.set_shape(&[1, tensor.get_shape()[0], tensor.get_shape()[1]]),
+        3 => {},
+        _ => return None,
+    }
+
+    Some(return_tensor)
+}
+
+/// Returns batch of matrices with rank of original tensor
+fn from_batch(mut tensor: Tensor<f32>, rank: usize) -> Tensor<f32>{
+    if rank == 2{
+        let shape = tensor.get_shape()[1..].to_vec();
+        tensor.set_shape(&shape);
+    }
+
+    tensor
+}
+
+/// Set scores of keys after the query to -inf, scores have shape [batch, keys, queries]
+/// Last query can see all keys
+fn apply_causal_mask(scores: &mut Tensor<f32>){
+    let (batch, keys, queries) = (scores.get_shape()[0] as usize, scores.get_shape()[1] as usize, scores.get_shape()[2] as usize);
+    let offset = keys.saturating_sub(queries);
+
+    let mut data = scores.get_data().clone();
+    for b in 0..batch{
+        for key in 0..keys{
+            for query in 0..queries{
+                if key > query + offset{
+                    data[b * keys * queries + key * queries + query] = f32::NEG_INFINITY;
+                }
+            }
+        }
+    }
+
+    scores.set_data(&data);
+}
+
+/// Scaled dot-product attention, softmax(K^T @ Q / sqrt(d)) weighted sum of values
+///
+/// query: [d, queries], key: [d, keys], value: [d_value, keys]
+/// or the same with leading [batch] dim, for example heads
+///
+/// Returns output [d_value, queries] and attention weights [keys, queries], where every column
+/// sums to 1, needed in backward pass.
+/// With causal, query i can't attend to keys after i.
+/// None if shapes do not match
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// let query: Tensor<f32> = Tensor::fill(1.0, &[4, 3]);
+/// let key: Tensor<f32> = Tensor::fill(1.0, &[4, 3]);
+/// let value: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0], &[1, 3]).unwrap();
+///
+/// let (output, weights) = scaled_dot_product_attention(&query, &key, &value, true).unwrap();
+///
+/// assert_eq!(output.get_data(), &vec!{1.0, 1.5, 2.0});
+/// assert_eq!(weights.get_shape(), &vec!{3, 3});
+/// ```
+pub fn scaled_dot_product_attention(query: &Tensor<f32>, key: &Tensor<f32>, value: &Tensor<f32>, causal: bool) -> Option<(Tensor<f32>, Tensor<f32>)>{
+    let rank = query.get_shape().len();
+    if key.get_shape().len() != rank || value.get_shape().len() != rank{
+        return None;
+    }
+
+    let query = as_batch(query)?;
+    let key = as_batch(key)?;
+    let value = as_batch(value)?;
+
+    let scale = 1.0 / (query.get_shape()[1] as f32).sqrt();
+
+    let mut scores = key.batch_matrix_transpose()?.batch_matrix_mul(&query)?.mul(scale);
+    if causal{
+        apply_causal_mask(&mut scores);
+    }
+
+    let weights = scores.softmax();
+    let output = value.batch_matrix_mul(&weights)?;
+
+    Some((from_batch(output, rank), from_batch(weights, rank)))
+}
+
+/// Returns gradient of query, key and value of scaled dot-product attention
+/// weights are attention weights returned by forward pass
+/// None if shapes do not match
+pub fn scaled_dot_product_attention_backward(query: &Tensor<f32>, key: &Tensor<f32>, value: &Tensor<f32>, weights: &Tensor<f32>, grad_output: &Tensor<f32>) -> Option<(Tensor<f32>, Tensor<f32>, Tensor<f32>)>{
+    let rank = query.get_shape().len();
+    if key.get_shape().len() != rank || value.get_shape().len() != rank{
+        return None;
+    }
+
+    let query = as_batch(query)?;
+    let key = as_batch(key)?;
+    let value = as_batch(value)?;
+    let weights = as_batch(weights)?;
+    let grad_output = as_batch(grad_output)?;
+
+    let scale = 1.0 / (query.get_shape()[1] as f32).sqrt();
+
+    let grad_value = grad_output.batch_matrix_mul(&weights.batch_matrix_transpose()?)?;
+    let grad_weights = value.batch_matrix_transpose()?.batch_matrix_mul(&grad_output)?;
+
+    // masked weights are 0, so their scores get no gradient
+    let grad_scores = weights.softmax_backward(&grad_weights)?.mul(scale);
+
+    let grad_query = key.batch_matrix_mul(&grad_scores)?;
+    let grad_key = query.batch_matrix_mul(&grad_scores.batch_matrix_transpose()?)?;
+
+    Some((from_batch(grad_query, rank), from_batch(grad_key, rank), from_batch(grad_value, rank)))
+}
+
+/// Gradients of multi-head attention parameters
+#[derive(Clone)]
+pub struct MultiHeadAttentionGrads{
+    pub weight_q: Tensor<f32>,
+    pub weight_k: Tensor<f32>,
+    pub weight_v: Tensor<f32>,
+    pub weight_o: Tensor<f32>,
+    pub bias_q: Tensor<f32>,
+    pub bias_k: Tensor<f32>,
+    pub bias_v: Tensor<f32>,
+    pub bias_o: Tensor<f32>,
+}
+
+/// Gradient of query, key, value and gradients of parameters
+pub type MultiHeadAttentionBackward = (Tensor<f32>, Tensor<f32>, Tensor<f32>, MultiHeadAttentionGrads);
+
+/// Values of forward pass needed for backward pass
+#[derive(Clone)]
+struct AttentionCache{
+    query: Tensor<f32>,
+    key: Tensor<f32>,
+    value: Tensor<f32>,
+
+    heads_q: Tensor<f32>,
+    heads_k: Tensor<f32>,
+    heads_v: Tensor<f32>,
+    weights: Tensor<f32>,
+    concat: Tensor<f32>,
+}
+
+/// Multi-head attention over [d_model, sequence] matrices, with tokens as columns
+/// Projections have shape [d_model, d_model] and biases [d_model, 1]
+/// Stores cache of the last forward pass for backward pass
+#[derive(Clone)]
+pub struct MultiHeadAttention{
+    pub heads: u32,
+    pub causal: bool,
+
+    pub weight_q: Tensor<f32>,
+    pub weight_k: Tensor<f32>,
+    pub weight_v: Tensor<f32>,
+    pub weight_o: Tensor<f32>,
+    pub bias_q: Tensor<f32>,
+    pub bias_k: Tensor<f32>,
+    pub bias_v: Tensor<f32>,
+    pub bias_o: Tensor<f32>,
+
+    cache: Option<AttentionCache>,
+}
+
+impl MultiHeadAttention{
+    /// Create new MultiHeadAttention with weights in range (-1/sqrt(d_model), 1/sqrt(d_model))
+    /// and zero biases, without causal mask
+    /// None if d_model is not divisible by heads
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let attention = MultiHeadAttention::new(8, 2).unwrap();
+    ///
+    /// assert_eq!(attention.weight_q.get_shape(), &vec!{8, 8});
+    /// assert!(MultiHeadAttention::new(8, 3).is_none());
+    /// ```
+    pub fn new(d_model: u32, heads: u32) -> Option<Self>{
+        if heads == 0 || !d_model.is_multiple_of(heads){
+            return None;
+        }
+
+        let range = 1.0 / (d_model as f32).sqrt();
+
+        Some(Self{
+            heads,
+            causal: false,
+
+            weight_q: Tensor::rand(range, &[d_model, d_model]),
+            weight_k: Tensor::rand(range, &[d_model, d_model]),
+            weight_v: Tensor::rand(range, &[d_model, d_model]),
+            weight_o: Tensor::rand(range, &[d_model, d_model]),
+            bias_q: Tensor::fill(0.0, &[d_model, 1]),
+            bias_k: Tensor::fill(0.0, &[d_model, 1]),
+            bias_v: Tensor::fill(0.0, &[d_model, 1]),
+            bias_o: Tensor::fill(0.0, &[d_model, 1]),
+
+            cache: None,
+        })
+    }
+    /// Returns size of model dimension
+    pub fn d_model(&self) -> u32{
+        self.weight_q.get_shape()[0]
+    }
+    /// Returns [d_model, sequence] projection split into [heads, d_model/heads, sequence]
+    fn split_heads(&self, mut tensor: Tensor<f32>) -> Tensor<f32>{
+        let sequence = tensor.get_shape()[1];
+        tensor.set_shape(&[self.heads, self.d_model() / self.heads, sequence]);
+
+        tensor
+    }
+    /// Returns [heads, d_model/heads, sequence] tensor with heads concatenated along rows
+    fn merge_heads(&self, mut tensor: Tensor<f32>) -> Tensor<f32>{
+        let sequence = tensor.get_shape()[2];
+        tensor.set_shape(&[self.d_model(), sequence]);
+
+        tensor
+    }
+    /// Returns attention output [d_model, queries]
+    /// query: [d_model, queries], key and value: [d_model, keys]
+    /// For self attention use the same tensor for all of them
+    /// None if shapes do not match
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut attention = MultiHeadAttention::new(8, 2).unwrap();
+    /// attention.causal = true;
+    ///
+    /// let input: Tensor<f32> = Tensor::rand(1.0, &[8, 5]);
+    ///
+    /// let output = attention.forward(&input, &input, &input).unwrap();
+    /// let (grad_query, grad_key, grad_value, grads) = attention.backward(&Tensor::fill(1.0, &[8, 5])).unwrap();
+    ///
+    /// assert_eq!(output.get_shape(), &vec!{8, 5});
+    /// assert_eq!(grad_query.get_shape(), &vec!{8, 5});
+    /// assert_eq!(grads.weight_o.get_shape(), &vec!{8, 8});
+    /// ```
+    pub fn forward(&mut self, query: &Tensor<f32>, key: &Tensor<f32>, value: &Tensor<f32>) -> Option<Tensor<f32>>{
+        let heads_q = self.split_heads(linear(&self.weight_q, query, &self.bias_q)?);
+        let heads_k = self.split_heads(linear(&self.weight_k, key, &self.bias_k)?);
+        let heads_v = self.split_heads(linear(&self.weight_v, value, &self.bias_v)?);
+
+        let (heads_output, weights) = scaled_dot_product_attention(&heads_q, &heads_k, &heads_v, self.causal)?;
+        let concat = self.merge_heads(heads_output);
+
+        let output = linear(&self.weight_o, &concat, &self.bias_o)?;
+
+        self.cache = Some(AttentionCache{
+            query: query.clone(),
+            key: key.clone(),
+            value: value.clone(),
+
+            heads_q,
+            heads_k,
+            heads_v,
+            weights,
+            concat,
+        });
+
+        Some(output)
+    }
+    /// Returns gradient of query, key, value and gradients of parameters
+    /// For self attention gradient of input is a sum of the first three
+    /// None if forward was not called or shapes do not match
+    pub fn backward(&self, grad_output: &Tensor<f32>) -> Option<MultiHeadAttentionBackward>{
+        let cache = self.cache.as_ref()?;
+
+        let (grad_concat, weight_o, bias_o) = linear_backward(&self.weight_o, &cache.concat, grad_output)?;
+
+        let (grad_heads_q, grad_heads_k, grad_heads_v) = scaled_dot_product_attention_backward(&cache.heads_q, &cache.heads_k, &cache.heads_v, &cache.weights, &self.split_heads(grad_concat))?;
+
+        let (grad_query, weight_q, bias_q) = linear_backward(&self.weight_q, &cache.query, &self.merge_heads(grad_heads_q))?;
+        let (grad_key, weight_k, bias_k) = linear_backward(&self.weight_k, &cache.key, &self.merge_heads(grad_heads_k))?;
+        let (grad_value, weight_v, bias_v) = linear_backward(&self.weight_v, &cache.value, &self.merge_heads(grad_heads_v))?;
+
+        let grads = MultiHeadAttentionGrads{
+            weight_q,
+            weight_k,
+            weight_v,
+            weight_o,
+            bias_q,
+            bias_k,
+            bias_v,
+            bias_o,
+        };
+
+        Some((grad_query, grad_key, grad_value, grads))
+    }
+    /// Apply gradient descent step to parameters
+    pub fn update(&mut self, grads: &MultiHeadAttentionGrads, learning_rate: f32){
+        self.weight_q = descent(&self.weight_q, &grads.weight_q, learning_rate);
+        self.weight_k = descent(&self.weight_k, &grads.weight_k, learning_rate);
+        self.weight_v = descent(&self.weight_v, &grads.weight_v, learning_rate);
+        self.weight_o = descent(&self.weight_o, &grads.weight_o, learning_rate);
+        self.bias_q = descent(&self.bias_q, &grads.bias_q, learning_rate);
+        self.bias_k = descent(&self.bias_k, &grads.bias_k, learning_rate);
+        self.bias_v = descent(&self.bias_v, &grads.bias_v, learning_rate);
+        self.bias_o = descent(&self.bias_o, &grads.bias_o, learning_rate);
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+
+use crate::tensor::Tensor;
+
+/// Sparse gradient of embedding table, holding only rows of used ids
+#[derive(Clone)]
+pub struct EmbeddingGrad{
+    /// Sorted unique ids of rows
+    pub ids: Vec<u32>,
+    /// Gradient of every row in ids, [ids.len(), embedding_dim]
+    pub values: Tensor<f32>,
+}
+
+impl EmbeddingGrad{
+    /// Create sparse gradient from map of row id to row gradient
+    fn from_rows(rows: BTreeMap<u32, Vec<f32>>, embedding_dim: u32) -> Self{
+        let ids: Vec<u32> = rows.keys().copied().collect();
+        let data: Vec<f32> = rows.into_values().flatten().collect();
+
+        Self{
+            values: Tensor::from_data(&data, &[ids.len() as u32, embedding_dim]).unwrap(),
+            ids,
+        }
+    }
+    /// Returns map of row id to row gradient
+    fn to_rows(&self) -> BTreeMap<u32, Vec<f32>>{
+        let dim = self.embedding_dim() as usize;
+
+        self.ids.iter().enumerate()
+            .map(|(i, id)| (*id, self.values.get_data()[i * dim..(i + 1) * dim].to_vec()))
+            .collect()
+    }
+    /// Returns size of embedding vector
+    pub fn embedding_dim(&self) -> u32{
+        self.values.get_shape()[1]
+    }
+    /// Add gradient of other batch, rows with the same id are summed
+    /// None if embedding dims differ
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let embedding = Embedding::new(10, 3);
+    ///
+    /// let mut grad = embedding.backward(&[1, 2], &Tensor::fill(1.0, &[3, 2])).unwrap();
+    /// let other = embedding.backward(&[2, 5], &Tensor::fill(1.0, &[3, 2])).unwrap();
+    ///
+    /// grad.accumulate(&other).unwrap();
+    ///
+    /// assert_eq!(grad.ids, vec!{1, 2, 5});
+    /// assert_eq!(grad.values.get_data()[3..6].to_vec(), vec!{2.0, 2.0, 2.0});
+    /// ```
+    pub fn accumulate(&mut self, other: &EmbeddingGrad) -> Option<()>{
+        if self.embedding_dim() != other.embedding_dim(){
+            return None;
+        }
+
+        let mut rows = self.to_rows();
+        for (id, row) in other.to_rows(){
+            let entry = rows.entry(id).or_insert_with(|| vec![0.0; row.len()]);
+            for (a, b) in entry.iter_mut().zip(row.iter()){
+                *a += b;
+            }
+        }
+
+        *self = EmbeddingGrad::from_rows(rows, self.embedding_dim());
+
+        Some(())
+    }
+    /// Returns dense [vocab_size, embedding_dim] gradient, with zeros in unused rows
+    pub fn to_dense(&self, vocab_size: u32) -> Tensor<f32>{
+        let dim = self.embedding_dim() as usize;
+        let mut data: Vec<f32> = vec![0.0; vocab_size as usize * dim];
+
+        for (i, id) in self.ids.iter().enumerate(){
+            let id = *id as usize;
+            data[id * dim..(id + 1) * dim].copy_from_slice(&self.values.get_data()[i * dim..(i + 1) * dim]);
+        }
+
+        Tensor::from_data(&data, &[vocab_size, self.embedding_dim()]).unwrap()
+    }
+}
+
+/// Embedding lookup table, with one [embedding_dim] row for every id
+#[derive(Clone)]
+pub struct Embedding{
+    /// [vocab_size, embedding_dim]
+    pub weight: Tensor<f32>,
+}
+
+impl Embedding{
+    /// Create new Embedding with weights in range (-1, 1)
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let embedding = Embedding::new(10, 3);
+    ///
+    /// assert_eq!(embedding.weight.get_shape(), &vec!{10, 3});
+    /// ```
+    pub fn new(vocab_size: u32, embedding_dim: u32) -> Self{
+        Self{
+            weight: Tensor::rand(1.0, &[vocab_size, embedding_dim]),
+        }
+    }
+    /// Create new Embedding from [vocab_size, embedding_dim] weights
+    pub fn from_weight(weight: Tensor<f32>) -> Option<Self>{
+        if weight.get_shape().len() != 2{
+            return None;
+        }
+
+        Some(Self{
+            weight,
+        })
+    }
+    /// Returns number of rows in the table
+    pub fn vocab_size(&self) -> u32{
+        self.weight.get_shape()[0]
+    }
+    /// Returns size of embedding vector
+    pub fn embedding_dim(&self) -> u32{
+        self.weight.get_shape()[1]
+    }
+    /// Returns [embedding_dim, ids.len()] matrix, with embedding of every id as a column,
+    /// so it can be used as input of other layers
+    /// None if any id is out of range
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let weight: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[3, 2]).unwrap();
+    /// let embedding = Embedding::from_weight(weight).unwrap();
+    ///
+    /// let output = embedding.forward(&[2, 0]).unwrap();
+    ///
+    /// assert_eq!(output.get_data(), &vec!{5.0, 1.0, 6.0, 2.0});
+    /// assert_eq!(output.get_shape(), &vec!{2, 2});
+    /// ```
+    pub fn forward(&self, ids: &[u32]) -> Option<Tensor<f32>>{
+        if ids.iter().any(|id| *id >= self.vocab_size()){
+            return None;
+        }
+
+        let dim = self.embedding_dim() as usize;
+        let mut return_data: Vec<f32> = vec![0.0; dim * ids.len()];
+
+        for (col, id) in ids.iter().enumerate(){
+            for row in 0..dim{
+                return_data[row * ids.len() + col] = self.weight.get_data()[*id as usize * dim + row];
+            }
+        }
+
+        Tensor::from_data(&return_data, &[dim as u32, ids.len() as u32])
+    }
+    /// Returns sparse gradient of table from [embedding_dim, ids.len()] gradient of forward output
+    /// Gradients of repeated ids are summed
+    /// None if shapes do not match or any id is out of range
+    pub fn backward(&self, ids: &[u32], grad_output: &Tensor<f32>) -> Option<EmbeddingGrad>{
+        if grad_output.get_shape() != &vec!{self.embedding_dim(), ids.len() as u32}{
+            return None;
+        }
+        if ids.iter().any(|id| *id >= self.vocab_size()){
+            return None;
+        }
+
+        let dim = self.embedding_dim() as usize;
+        let mut rows: BTreeMap<u32, Vec<f32>> = BTreeMap::new();
+
+        for (col, id) in ids.iter().enumerate(){
+            let row = rows.entry(*id).or_insert_with(|| vec![0.0; dim]);
+            for (i, value) in row.iter_mut().enumerate(){
+                *value += grad_output.get_data()[i * ids.len() + col];
+            }
+        }
+
+        Some(EmbeddingGrad::from_rows(rows, self.embedding_dim()))
+    }
+    /// Apply gradient descent step only to rows present in sparse gradient
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut embedding = Embedding::from_weight(Tensor::fill(1.0, &[3, 2])).unwrap();
+    ///
+    /// let grad = embedding.backward(&[1, 1], &Tensor::fill(1.0, &[2, 2])).unwrap();
+    /// embedding.update(&grad, 0.5);
+    ///
+    /// assert_eq!(embedding.weight.get_data(), &vec!{1.0, 1.0, 0.0, 0.0, 1.0, 1.0});
+    /// ```
+    pub fn update(&mut self, grad: &EmbeddingGrad, learning_rate: f32){
+        let dim = self.embedding_dim() as usize;
+        let mut data = self.weight.get_data().clone();
+
+        for (i, id) in grad.ids.iter().enumerate(){
+            for j in 0..dim{
+                data[*id as usize * dim + j] -= learning_rate * grad.values.get_data()[i * dim + j];
+            }
+        }
+
+        self.weight.set_data(&data);
+    }
+}
@@ -1,5 +1,14 @@
 pub mod relu;
 pub mod sigmoid;
+pub mod normalization;
+pub mod regularization;
+pub mod tanh;
+pub mod recurrent;
+pub mod softmax;
+pub mod embedding;
+pub mod attention;
+
+use crate::tensor::Tensor;
 
 pub fn forward_shape(weight_shapes: &[u32], input_shapes:&[u32]) -> Vec<u32>{
     vec!{weight_shapes[0], input_shapes[1]}
@@ -13,3 +22,20 @@ pub fn backward_bias_shape(bias_shapes: &[u32]) -> Vec<u32>{
 pub fn backward_grad_shape(cache_shape: &[u32]) -> Vec<u32>{
     cache_shape.to_vec()
 }
+
+/// Returns weight @ input + bias
+pub(crate) fn linear(weight: &Tensor<f32>, input: &Tensor<f32>, bias: &Tensor<f32>) -> Option<Tensor<f32>>{
+    weight.matrix_mul(input)?.tens_broadcast_add(bias)
+}
+/// Returns gradient of input, weight and bias of weight @ input + bias
+pub(crate) fn linear_backward(weight: &Tensor<f32>, input: &Tensor<f32>, grad_output: &Tensor<f32>) -> Option<(Tensor<f32>, Tensor<f32>, Tensor<f32>)>{
+    let grad_input = weight.matrix_transpose()?.matrix_mul(grad_output)?;
+    let grad_weight = grad_output.matrix_mul(&input.matrix_transpose()?)?;
+    let grad_bias = grad_output.matrix_col_sum()?;
+
+    Some((grad_input, grad_weight, grad_bias))
+}
+/// Returns parameter after gradient descent step
+pub(crate) fn descent(param: &Tensor<f32>, grad: &Tensor<f32>, learning_rate: f32) -> Tensor<f32>{
+    param.tens_sub(&grad.mul(learning_rate)).unwrap()
+}
@@ -0,0 +1,527 @@
+use crate::{cpu::float::Float, tensor::Tensor};
+
+/// Returns normalized data, mean of each group and inverse standard deviation of each group
+fn normalize_groups<T: Float>(data: &[T], group_ids: &[usize], groups: usize, eps: T) -> (Vec<T>, Vec<T>, Vec<T>){
+    let mut mean: Vec<T> = vec![T::ZERO; groups];
+    let mut count: Vec<T> = vec![T::ZERO; groups];
+
+    for i in 0..data.len(){
+        mean[group_ids[i]] += data[i];
+        count[group_ids[i]] += T::ONE;
+    }
+    for g in 0..groups{
+        mean[g] /= count[g].max(T::ONE);
+    }
+
+    let mut var: Vec<T> = vec![T::ZERO; groups];
+    for i in 0..data.len(){
+        let diff = data[i] - mean[group_ids[i]];
+        var[group_ids[i]] += diff * diff;
+    }
+
+    let inv_std: Vec<T> = var.iter().zip(count.iter())
+        .map(|(v, c)| T::ONE / (*v / c.max(T::ONE) + eps).sqrt())
+        .collect();
+
+    let normalized: Vec<T> = data.iter().zip(group_ids.iter())
+        .map(|(x, g)| (*x - mean[*g]) * inv_std[*g])
+        .collect();
+
+    (normalized, mean, inv_std)
+}
+
+/// Returns gradient of input from gradient of normalized data
+///
+/// dx = inv_std/M * (M*dxhat - sum(dxhat) - xhat*sum(dxhat*xhat))
+fn normalize_groups_backward<T: Float>(grad_normalized: &[T], normalized: &[T], group_ids: &[usize], groups: usize, inv_std: &[T]) -> Vec<T>{
+    let mut count: Vec<T> = vec![T::ZERO; groups];
+    let mut grad_sum: Vec<T> = vec![T::ZERO; groups];
+    let mut grad_dot: Vec<T> = vec![T::ZERO; groups];
+
+    for i in 0..grad_normalized.len(){
+        count[group_ids[i]] += T::ONE;
+        grad_sum[group_ids[i]] += grad_normalized[i];
+        grad_dot[group_ids[i]] += grad_normalized[i] * normalized[i];
+    }
+
+    let mut grad_input: Vec<T> = Vec::with_capacity(grad_normalized.len());
+    for i in 0..grad_normalized.len(){
+        let g = group_ids[i];
+        grad_input.push(inv_std[g] / count[g] * (count[g] * grad_normalized[i] - grad_sum[g] - normalized[i] * grad_dot[g]));
+    }
+
+    grad_input
+}
+
+/// Group ids and param ids for batch norm on [features, batch] matrix
+fn batch_norm_ids(shape: &[u32]) -> (Vec<usize>, Vec<usize>, usize){
+    let rows = shape[0] as usize;
+    let cols = shape[1] as usize;
+
+    let ids: Vec<usize> = (0..rows*cols).map(|i| i / cols).collect();
+
+    (ids.clone(), ids, rows)
+}
+
+/// Group ids and param ids for layer norm over trailing dims of size param_size
+fn layer_norm_ids(data_len: usize, param_size: usize) -> (Vec<usize>, Vec<usize>, usize){
+    let group_ids: Vec<usize> = (0..data_len).map(|i| i / param_size).collect();
+    let param_ids: Vec<usize> = (0..data_len).map(|i| i % param_size).collect();
+
+    (group_ids, param_ids, data_len / param_size)
+}
+
+/// Group ids and param ids for group norm on [channels, batch] matrix
+fn group_norm_ids(shape: &[u32], groups: u32) -> (Vec<usize>, Vec<usize>, usize){
+    let channels = shape[0] as usize;
+    let cols = shape[1] as usize;
+    let channels_per_group = channels / groups as usize;
+
+    let group_ids: Vec<usize> = (0..channels*cols).map(|i| (i / cols / channels_per_group) * cols + i % cols).collect();
+    let param_ids: Vec<usize> = (0..channels*cols).map(|i| i / cols).collect();
+
+    (group_ids, param_ids, groups as usize * cols)
+}
+
+fn affine<T: Float>(normalized: &[T], param_ids: &[usize], gamma: &Tensor<T>, beta: &Tensor<T>) -> Vec<T>{
+    normalized.iter().zip(param_ids.iter())
+        .map(|(x, p)| *x * gamma.get_data()[*p] + beta.get_data()[*p])
+        .collect()
+}
+
+/// Returns gradient of input, gamma and beta
+fn affine_backward<T: Float>(input: &Tensor<T>, gamma: &Tensor<T>, grad_output: &Tensor<T>, group_ids: &[usize], param_ids: &[usize], groups: usize, eps: T) -> (Tensor<T>, Tensor<T>, Tensor<T>){
+    let (normalized, _, inv_std) = normalize_groups(input.get_data(), group_ids, groups, eps);
+
+    let mut grad_gamma: Vec<T> = vec![T::ZERO; gamma.count_data()];
+    let mut grad_beta: Vec<T> = vec![T::ZERO; gamma.count_data()];
+    let mut grad_normalized: Vec<T> = Vec::with_capacity(normalized.len());
+
+    for i in 0..normalized.len(){
+        let grad = grad_output.get_data()[i];
+        grad_gamma[param_ids[i]] += grad * normalized[i];
+        grad_beta[param_ids[i]] += grad;
+        grad_normalized.push(grad * gamma.get_data()[param_ids[i]]);
+    }
+
+    let grad_input = normalize_groups_backward(&grad_normalized, &normalized, group_ids, groups, &inv_std);
+
+    (
+        Tensor::from_data(&grad_input, input.get_shape()).unwrap(),
+        Tensor::from_data(&grad_gamma, gamma.get_shape()).unwrap(),
+        Tensor::from_data(&grad_beta, gamma.get_shape()).unwrap(),
+    )
+}
+
+impl Tensor<f32>{
+    /// Returns a [features, batch] matrix normalized over batch for each feature using
+    /// statistics of that batch, scaled by gamma and shifted by beta
+    /// gamma and beta have shape [features, 1]
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 3.0, 2.0, 6.0], &[2, 2]).unwrap();
+    /// let gamma: Tensor<f32> = Tensor::fill(1.0, &[2, 1]);
+    /// let beta: Tensor<f32> = Tensor::fill(0.0, &[2, 1]);
+    ///
+    /// let b = a.batch_norm(&gamma, &beta, 0.0).unwrap();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{-1.0, 1.0, -1.0, 1.0});
+    /// ```
+    pub fn batch_norm(&self, gamma: &Tensor<f32>, beta: &Tensor<f32>, eps: f32) -> Option<Tensor<f32>>{
+        FloatNormalization::batch_norm(self, gamma, beta, eps)
+    }
+    /// Returns a [features, batch] matrix normalized with running mean and running variance
+    /// (inference mode of batch norm)
+    /// all parameters have shape [features, 1]
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 3.0, 2.0, 6.0], &[2, 2]).unwrap();
+    /// let mean: Tensor<f32> = Tensor::from_data(&[1.0, 2.0], &[2, 1]).unwrap();
+    /// let var: Tensor<f32> = Tensor::from_data(&[4.0, 16.0], &[2, 1]).unwrap();
+    /// let gamma: Tensor<f32> = Tensor::fill(1.0, &[2, 1]);
+    /// let beta: Tensor<f32> = Tensor::fill(0.0, &[2, 1]);
+    ///
+    /// let b = a.batch_norm_eval(&mean, &var, &gamma, &beta, 0.0).unwrap();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{0.0, 1.0, 0.0, 1.0});
+    /// ```
+    pub fn batch_norm_eval(&self, running_mean: &Tensor<f32>, running_var: &Tensor<f32>, gamma: &Tensor<f32>, beta: &Tensor<f32>, eps: f32) -> Option<Tensor<f32>>{
+        FloatNormalization::batch_norm_eval(self, running_mean, running_var, gamma, beta, eps)
+    }
+    /// Returns gradient of input, gamma and beta of batch norm
+    /// self is the input of forward pass
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 3.0, 2.0, 6.0], &[2, 2]).unwrap();
+    /// let gamma: Tensor<f32> = Tensor::fill(1.0, &[2, 1]);
+    /// let grad: Tensor<f32> = Tensor::fill(1.0, &[2, 2]);
+    ///
+    /// let (grad_input, grad_gamma, grad_beta) = a.batch_norm_backward(&gamma, &grad, 0.0).unwrap();
+    ///
+    /// assert_eq!(grad_input.get_data(), &vec!{0.0, 0.0, 0.0, 0.0});
+    /// assert_eq!(grad_gamma.get_data(), &vec!{0.0, 0.0});
+    /// assert_eq!(grad_beta.get_data(), &vec!{2.0, 2.0});
+    /// ```
+    pub fn batch_norm_backward(&self, gamma: &Tensor<f32>, grad_output: &Tensor<f32>, eps: f32) -> Option<(Tensor<f32>, Tensor<f32>, Tensor<f32>)>{
+        FloatNormalization::batch_norm_backward(self, gamma, grad_output, eps)
+    }
+    /// Returns a tensor normalized over trailing dims with shape of gamma,
+    /// scaled by gamma and shifted by beta
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 3.0, 2.0, 6.0], &[2, 2]).unwrap();
+    /// let gamma: Tensor<f32> = Tensor::fill(1.0, &[2]);
+    /// let beta: Tensor<f32> = Tensor::fill(0.0, &[2]);
+    ///
+    /// let b = a.layer_norm(&gamma, &beta, 0.0).unwrap();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{-1.0, 1.0, -1.0, 1.0});
+    /// ```
+    pub fn layer_norm(&self, gamma: &Tensor<f32>, beta: &Tensor<f32>, eps: f32) -> Option<Tensor<f32>>{
+        FloatNormalization::layer_norm(self, gamma, beta, eps)
+    }
+    /// Returns gradient of input, gamma and beta of layer norm
+    /// self is the input of forward pass
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 3.0, 2.0, 6.0], &[2, 2]).unwrap();
+    /// let gamma: Tensor<f32> = Tensor::fill(1.0, &[2]);
+    /// let grad: Tensor<f32> = Tensor::fill(1.0, &[2, 2]);
+    ///
+    /// let (grad_input, grad_gamma, grad_beta) = a.layer_norm_backward(&gamma, &grad, 0.0).unwrap();
+    ///
+    /// assert_eq!(grad_input.get_data(), &vec!{0.0, 0.0, 0.0, 0.0});
+    /// assert_eq!(grad_gamma.get_data(), &vec!{-2.0, 2.0});
+    /// assert_eq!(grad_beta.get_data(), &vec!{2.0, 2.0});
+    /// ```
+    pub fn layer_norm_backward(&self, gamma: &Tensor<f32>, grad_output: &Tensor<f32>, eps: f32) -> Option<(Tensor<f32>, Tensor<f32>, Tensor<f32>)>{
+        FloatNormalization::layer_norm_backward(self, gamma, grad_output, eps)
+    }
+    /// Returns a [channels, batch] matrix where channels are split into groups
+    /// and each group is normalized separately for each batch element,
+    /// scaled by gamma and shifted by beta
+    /// gamma and beta have shape [channels, 1]
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0, 6.0], &[2, 2]).unwrap();
+    /// let gamma: Tensor<f32> = Tensor::fill(1.0, &[2, 1]);
+    /// let beta: Tensor<f32> = Tensor::fill(0.0, &[2, 1]);
+    ///
+    /// let b = a.group_norm(1, &gamma, &beta, 0.0).unwrap();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{-1.0, -1.0, 1.0, 1.0});
+    /// ```
+    pub fn group_norm(&self, groups: u32, gamma: &Tensor<f32>, beta: &Tensor<f32>, eps: f32) -> Option<Tensor<f32>>{
+        FloatNormalization::group_norm(self, groups, gamma, beta, eps)
+    }
+    /// Returns gradient of input, gamma and beta of group norm
+    /// self is the input of forward pass
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0, 6.0], &[2, 2]).unwrap();
+    /// let gamma: Tensor<f32> = Tensor::fill(1.0, &[2, 1]);
+    /// let grad: Tensor<f32> = Tensor::fill(1.0, &[2, 2]);
+    ///
+    /// let (grad_input, grad_gamma, grad_beta) = a.group_norm_backward(1, &gamma, &grad, 0.0).unwrap();
+    ///
+    /// assert_eq!(grad_input.get_data(), &vec!{0.0, 0.0, 0.0, 0.0});
+    /// assert_eq!(grad_beta.get_data(), &vec!{2.0, 2.0});
+    /// ```
+    pub fn group_norm_backward(&self, groups: u32, gamma: &Tensor<f32>, grad_output: &Tensor<f32>, eps: f32) -> Option<(Tensor<f32>, Tensor<f32>, Tensor<f32>)>{
+        FloatNormalization::group_norm_backward(self, groups, gamma, grad_output, eps)
+    }
+}
+
+/// Batch, layer and group normalization of tensors of every Float type
+pub trait FloatNormalization<T: Float>{
+    /// Returns a [features, batch] matrix normalized over batch for each feature using
+    fn batch_norm(&self, gamma: &Tensor<T>, beta: &Tensor<T>, eps: T) -> Option<Tensor<T>>;
+    /// Returns a [features, batch] matrix normalized with running mean and running variance
+    fn batch_norm_eval(&self, running_mean: &Tensor<T>, running_var: &Tensor<T>, gamma: &Tensor<T>, beta: &Tensor<T>, eps: T) -> Option<Tensor<T>>;
+    /// Returns gradient of input, gamma and beta of batch norm
+    fn batch_norm_backward(&self, gamma: &Tensor<T>, grad_output: &Tensor<T>, eps: T) -> Option<(Tensor<T>, Tensor<T>, Tensor<T>)>;
+    /// Returns a tensor normalized over trailing dims with shape of gamma,
+    fn layer_norm(&self, gamma: &Tensor<T>, beta: &Tensor<T>, eps: T) -> Option<Tensor<T>>;
+    /// Returns gradient of input, gamma and beta of layer norm
+    fn layer_norm_backward(&self, gamma: &Tensor<T>, grad_output: &Tensor<T>, eps: T) -> Option<(Tensor<T>, Tensor<T>, Tensor<T>)>;
+    /// Returns a [channels, batch] matrix where channels are split into groups
+    fn group_norm(&self, groups: u32, gamma: &Tensor<T>, beta: &Tensor<T>, eps: T) -> Option<Tensor<T>>;
+    /// Returns gradient of input, gamma and beta of group norm
+    fn group_norm_backward(&self, groups: u32, gamma: &Tensor<T>, grad_output: &Tensor<T>, eps: T) -> Option<(Tensor<T>, Tensor<T>, Tensor<T>)>;
+}
+
+impl<T: Float> FloatNormalization<T> for Tensor<T>{
+    fn batch_norm(&self, gamma: &Tensor<T>, beta: &Tensor<T>, eps: T) -> Option<Tensor<T>>{
+        if self.get_shape().len() != 2 || gamma.count_data() != self.get_shape()[0] as usize || beta.count_data() != gamma.count_data(){
+            return None;
+        }
+
+        let (group_ids, param_ids, groups) = batch_norm_ids(self.get_shape());
+        let (normalized, _, _) = normalize_groups(self.get_data(), &group_ids, groups, eps);
+
+        Tensor::from_data(&affine(&normalized, &param_ids, gamma, beta), self.get_shape())
+    }
+    fn batch_norm_eval(&self, running_mean: &Tensor<T>, running_var: &Tensor<T>, gamma: &Tensor<T>, beta: &Tensor<T>, eps: T) -> Option<Tensor<T>>{
+        if self.get_shape().len() != 2{
+            return None;
+        }
+        let features = self.get_shape()[0] as usize;
+        if running_mean.count_data() != features || running_var.count_data() != features || gamma.count_data() != features || beta.count_data() != features{
+            return None;
+        }
+
+        let cols = self.get_shape()[1] as usize;
+        let mut return_data: Vec<T> = Vec::with_capacity(self.count_data());
+
+        for i in 0..self.count_data(){
+            let f = i / cols;
+            let normalized = (self.get_data()[i] - running_mean.get_data()[f]) / (running_var.get_data()[f] + eps).sqrt();
+            return_data.push(normalized * gamma.get_data()[f] + beta.get_data()[f]);
+        }
+
+        Tensor::from_data(&return_data, self.get_shape())
+    }
+    fn batch_norm_backward(&self, gamma: &Tensor<T>, grad_output: &Tensor<T>, eps: T) -> Option<(Tensor<T>, Tensor<T>, Tensor<T>)>{
+        if self.get_shape().len() != 2 || gamma.count_data() != self.get_shape()[0] as usize || self.get_shape() != grad_output.get_shape(){
+            return None;
+        }
+
+        let (group_ids, param_ids, groups) = batch_norm_ids(self.get_shape());
+
+        Some(affine_backward(self, gamma, grad_output, &group_ids, &param_ids, groups, eps))
+    }
+    fn layer_norm(&self, gamma: &Tensor<T>, beta: &Tensor<T>, eps: T) -> Option<Tensor<T>>{
+        if !trailing_shape_match(self.get_shape(), gamma.get_shape()) || gamma.get_shape() != beta.get_shape(){
+            return None;
+        }
+
+        let (group_ids, param_ids, groups) = layer_norm_ids(self.count_data(), gamma.count_data());
+        let (normalized, _, _) = normalize_groups(self.get_data(), &group_ids, groups, eps);
+
+        Tensor::from_data(&affine(&normalized, &param_ids, gamma, beta), self.get_shape())
+    }
+    fn layer_norm_backward(&self, gamma: &Tensor<T>, grad_output: &Tensor<T>, eps: T) -> Option<(Tensor<T>, Tensor<T>, Tensor<T>)>{
+        if !trailing_shape_match(self.get_shape(), gamma.get_shape()) || self.get_shape() != grad_output.get_shape(){
+            return None;
+        }
+
+        let (group_ids, param_ids, groups) = layer_norm_ids(self.count_data(), gamma.count_data());
+
+        Some(affine_backward(self, gamma, grad_output, &group_ids, &param_ids, groups, eps))
+    }
+    fn group_norm(&self, groups: u32, gamma: &Tensor<T>, beta: &Tensor<T>, eps: T) -> Option<Tensor<T>>{
+        if self.get_shape().len() != 2 || groups == 0 || !self.get_shape()[0].is_multiple_of(groups){
+            return None;
+        }
+        if gamma.count_data() != self.get_shape()[0] as usize || beta.count_data() != gamma.count_data(){
+            return None;
+        }
+
+        let (group_ids, param_ids, group_count) = group_norm_ids(self.get_shape(), groups);
+        let (normalized, _, _) = normalize_groups(self.get_data(), &group_ids, group_count, eps);
+
+        Tensor::from_data(&affine(&normalized, &param_ids, gamma, beta), self.get_shape())
+    }
+    fn group_norm_backward(&self, groups: u32, gamma: &Tensor<T>, grad_output: &Tensor<T>, eps: T) -> Option<(Tensor<T>, Tensor<T>, Tensor<T>)>{
+        if self.get_shape().len() != 2 || groups == 0 || !self.get_shape()[0].is_multiple_of(groups){
+            return None;
+        }
+        if gamma.count_data() != self.get_shape()[0] as usize || self.get_shape() != grad_output.get_shape(){
+            return None;
+        }
+
+        let (group_ids, param_ids, group_count) = group_norm_ids(self.get_shape(), groups);
+
+        Some(affine_backward(self, gamma, grad_output, &group_ids, &param_ids, group_count, eps))
+    }
+}
+
+fn trailing_shape_match(shape: &[u32], trailing: &[u32]) -> bool{
+    if trailing.is_empty() || trailing.len() > shape.len(){
+        return false;
+    }
+
+    shape[shape.len() - trailing.len()..] == *trailing
+}
+
+/// Batch normalization layer for [features, batch] matrices
+/// with learnable gamma and beta and running statistics used in eval mode
+pub struct BatchNorm{
+    pub gamma: Tensor<f32>,
+    pub beta: Tensor<f32>,
+    pub running_mean: Tensor<f32>,
+    pub running_var: Tensor<f32>,
+
+    pub momentum: f32,
+    pub eps: f32,
+    pub training: bool,
+}
+
+impl BatchNorm{
+    /// Create new BatchNorm with gamma = 1, beta = 0, momentum = 0.1 and eps = 1e-5
+    /// in training mode
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let norm = BatchNorm::new(3);
+    ///
+    /// assert_eq!(norm.gamma.get_shape(), &vec!{3, 1});
+    /// ```
+    pub fn new(features: u32) -> Self{
+        Self{
+            gamma: Tensor::fill(1.0, &[features, 1]),
+            beta: Tensor::fill(0.0, &[features, 1]),
+            running_mean: Tensor::fill(0.0, &[features, 1]),
+            running_var: Tensor::fill(1.0, &[features, 1]),
+
+            momentum: 0.1,
+            eps: 1e-5,
+            training: true,
+        }
+    }
+    /// Switch layer to training mode, where batch statistics are used and running statistics
+    /// are updated
+    pub fn train(&mut self){
+        self.training = true;
+    }
+    /// Switch layer to eval mode, where running statistics are used
+    pub fn eval(&mut self){
+        self.training = false;
+    }
+
+    /// Normalize [features, batch] input
+    /// In training mode updates running statistics with
+    /// running = (1 - momentum) * running + momentum * batch
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut norm = BatchNorm::new(1);
+    /// norm.momentum = 1.0;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 3.0], &[1, 2]).unwrap();
+    /// let b = norm.forward(&a).unwrap();
+    ///
+    /// assert_eq!(norm.running_mean.get_data(), &vec!{2.0});
+    /// assert_eq!(norm.running_var.get_data(), &vec!{2.0});
+    /// ```
+    pub fn forward(&mut self, input: &Tensor<f32>) -> Option<Tensor<f32>>{
+        if !self.training{
+            return input.batch_norm_eval(&self.running_mean, &self.running_var, &self.gamma, &self.beta, self.eps);
+        }
+
+        let output = input.batch_norm(&self.gamma, &self.beta, self.eps)?;
+
+        let cols = input.get_shape()[1] as usize;
+        let features = input.get_shape()[0] as usize;
+
+        let mut running_mean: Vec<f32> = Vec::with_capacity(features);
+        let mut running_var: Vec<f32> = Vec::with_capacity(features);
+
+        for f in 0..features{
+            let row = &input.get_data()[f*cols..(f+1)*cols];
+            let mean = row.iter().sum::<f32>() / cols as f32;
+            // unbiased variance, as in most frameworks
+            let var = row.iter().map(|x| (x - mean) * (x - mean)).sum::<f32>() / (cols.max(2) - 1) as f32;
+
+            running_mean.push((1.0 - self.momentum) * self.running_mean.get_data()[f] + self.momentum * mean);
+            running_var.push((1.0 - self.momentum) * self.running_var.get_data()[f] + self.momentum * var);
+        }
+
+        self.running_mean.set_data(&running_mean);
+        self.running_var.set_data(&running_var);
+
+        Some(output)
+    }
+
+    /// Returns gradient of input, gamma and beta
+    /// input is the input of forward pass
+    pub fn backward(&self, input: &Tensor<f32>, grad_output: &Tensor<f32>) -> Option<(Tensor<f32>, Tensor<f32>, Tensor<f32>)>{
+        input.batch_norm_backward(&self.gamma, grad_output, self.eps)
+    }
+}
+
+/// Layer normalization over trailing dims with learnable gamma and beta
+pub struct LayerNorm{
+    pub gamma: Tensor<f32>,
+    pub beta: Tensor<f32>,
+
+    pub eps: f32,
+}
+
+impl LayerNorm{
+    /// Create new LayerNorm normalizing over trailing dims equal to normalized_shape
+    /// with gamma = 1, beta = 0 and eps = 1e-5
+    pub fn new(normalized_shape: &[u32]) -> Self{
+        Self{
+            gamma: Tensor::fill(1.0, normalized_shape),
+            beta: Tensor::fill(0.0, normalized_shape),
+
+            eps: 1e-5,
+        }
+    }
+    /// Normalize input
+    pub fn forward(&self, input: &Tensor<f32>) -> Option<Tensor<f32>>{
+        input.layer_norm(&self.gamma, &self.beta, self.eps)
+    }
+    /// Returns gradient of input, gamma and beta
+    /// input is the input of forward pass
+    pub fn backward(&self, input: &Tensor<f32>, grad_output: &Tensor<f32>) -> Option<(Tensor<f32>, Tensor<f32>, Tensor<f32>)>{
+        input.layer_norm_backward(&self.gamma, grad_output, self.eps)
+    }
+}
+
+/// Group normalization for [channels, batch] matrices with learnable gamma and beta
+pub struct GroupNorm{
+    pub groups: u32,
+    pub gamma: Tensor<f32>,
+    pub beta: Tensor<f32>,
+
+    pub eps: f32,
+}
+
+impl GroupNorm{
+    /// Create new GroupNorm with gamma = 1, beta = 0 and eps = 1e-5
+    pub fn new(groups: u32, channels: u32) -> Self{
+        Self{
+            groups,
+            gamma: Tensor::fill(1.0, &[channels, 1]),
+            beta: Tensor::fill(0.0, &[channels, 1]),
+
+            eps: 1e-5,
+        }
+    }
+    /// Normalize input
+    pub fn forward(&self, input: &Tensor<f32>) -> Option<Tensor<f32>>{
+        input.group_norm(self.groups, &self.gamma, &self.beta, self.eps)
+    }
+    /// Returns gradient of input, gamma and beta
+    /// input is the input of forward pass
+    pub fn backward(&self, input: &Tensor<f32>, grad_output: &Tensor<f32>) -> Option<(Tensor<f32>, Tensor<f32>, Tensor<f32>)>{
+        input.group_norm_backward(self.groups, &self.gamma, grad_output, self.eps)
+    }
+}
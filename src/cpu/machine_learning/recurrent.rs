@@ -0,0 +1,700 @@
+use crate::tensor::Tensor;
+
+use super::{linear, linear_backward, descent};
+
+/// Returns rows [start, end) of matrix
+fn rows(tensor: &Tensor<f32>, start: u32, end: u32) -> Tensor<f32>{
+    let cols = tensor.get_shape()[1];
+    let data = &tensor.get_data()[(start * cols) as usize..(end * cols) as usize];
+
+    Tensor::from_data(data, &[end - start, cols]).unwrap()
+}
+
+/// Returns matrices stacked along rows
+fn stack_rows(tensors: &[Tensor<f32>]) -> Option<Tensor<f32>>{
+    let mut return_tensor = tensors[0].clone();
+    for tensor in &tensors[1..]{
+        return_tensor = return_tensor.append(tensor)?;
+    }
+
+    Some(return_tensor)
+}
+
+/// Returns 1 - x
+fn one_minus(tensor: &Tensor<f32>) -> Tensor<f32>{
+    tensor.mul(-1.0).add(1.0)
+}
+
+/// Returns derivative of sigmoid using its output, s * (1 - s)
+fn sigmoid_grad(output: &Tensor<f32>) -> Tensor<f32>{
+    output.tens_mul(&one_minus(output)).unwrap()
+}
+
+/// Returns derivative of tanh using its output, 1 - t^2
+fn tanh_grad(output: &Tensor<f32>) -> Tensor<f32>{
+    one_minus(&output.tens_mul(output).unwrap())
+}
+
+/// Returns weight_ih, weight_hh, bias_ih and bias_hh for cell with gates stacked along rows
+/// initialized in range (-1/sqrt(hidden_size), 1/sqrt(hidden_size))
+fn init_weights(input_size: u32, hidden_size: u32, gates: u32) -> (Tensor<f32>, Tensor<f32>, Tensor<f32>, Tensor<f32>){
+    let range = 1.0 / (hidden_size as f32).sqrt();
+
+    (
+        Tensor::rand(range, &[gates * hidden_size, input_size]),
+        Tensor::rand(range, &[gates * hidden_size, hidden_size]),
+        Tensor::rand(range, &[gates * hidden_size, 1]),
+        Tensor::rand(range, &[gates * hidden_size, 1]),
+    )
+}
+
+/// Gradients of recurrent cell parameters
+#[derive(Clone)]
+pub struct RecurrentGrads{
+    pub weight_ih: Tensor<f32>,
+    pub weight_hh: Tensor<f32>,
+    pub bias_ih: Tensor<f32>,
+    pub bias_hh: Tensor<f32>,
+}
+
+impl RecurrentGrads{
+    /// Create zero gradients with shapes of given parameters
+    fn zeros(weight_ih: &Tensor<f32>, weight_hh: &Tensor<f32>, bias_ih: &Tensor<f32>, bias_hh: &Tensor<f32>) -> Self{
+        Self{
+            weight_ih: Tensor::fill(0.0, weight_ih.get_shape()),
+            weight_hh: Tensor::fill(0.0, weight_hh.get_shape()),
+            bias_ih: Tensor::fill(0.0, bias_ih.get_shape()),
+            bias_hh: Tensor::fill(0.0, bias_hh.get_shape()),
+        }
+    }
+    /// Add gradients of other time step
+    fn accumulate(&mut self, other: &RecurrentGrads){
+        self.weight_ih.tens_add_mut(&other.weight_ih);
+        self.weight_hh.tens_add_mut(&other.weight_hh);
+        self.bias_ih.tens_add_mut(&other.bias_ih);
+        self.bias_hh.tens_add_mut(&other.bias_hh);
+    }
+}
+
+/// Vanilla recurrent cell, h' = tanh(W_ih @ x + b_ih + W_hh @ h + b_hh)
+/// inputs have shape [input_size, batch] and hidden states [hidden_size, batch]
+#[derive(Clone)]
+pub struct RNNCell{
+    pub weight_ih: Tensor<f32>,
+    pub weight_hh: Tensor<f32>,
+    pub bias_ih: Tensor<f32>,
+    pub bias_hh: Tensor<f32>,
+}
+
+/// Values of RNNCell forward pass needed for backward pass
+#[derive(Clone)]
+pub struct RNNCellCache{
+    input: Tensor<f32>,
+    hidden: Tensor<f32>,
+    output: Tensor<f32>,
+}
+
+impl RNNCell{
+    /// Create new RNNCell with weights in range (-1/sqrt(hidden_size), 1/sqrt(hidden_size))
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let cell = RNNCell::new(3, 2);
+    ///
+    /// assert_eq!(cell.weight_ih.get_shape(), &vec!{2, 3});
+    /// assert_eq!(cell.weight_hh.get_shape(), &vec!{2, 2});
+    /// ```
+    pub fn new(input_size: u32, hidden_size: u32) -> Self{
+        let (weight_ih, weight_hh, bias_ih, bias_hh) = init_weights(input_size, hidden_size, 1);
+
+        Self{
+            weight_ih,
+            weight_hh,
+            bias_ih,
+            bias_hh,
+        }
+    }
+    /// Returns size of hidden state
+    pub fn hidden_size(&self) -> u32{
+        self.weight_hh.get_shape()[1]
+    }
+    /// Returns next hidden state and cache for backward pass
+    /// None if shapes do not match
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let cell = RNNCell::new(3, 2);
+    ///
+    /// let input: Tensor<f32> = Tensor::fill(1.0, &[3, 4]);
+    /// let hidden: Tensor<f32> = Tensor::fill(0.0, &[2, 4]);
+    ///
+    /// let (output, _cache) = cell.forward(&input, &hidden).unwrap();
+    ///
+    /// assert_eq!(output.get_shape(), &vec!{2, 4});
+    /// ```
+    pub fn forward(&self, input: &Tensor<f32>, hidden: &Tensor<f32>) -> Option<(Tensor<f32>, RNNCellCache)>{
+        let linear_ih = linear(&self.weight_ih, input, &self.bias_ih)?;
+        let linear_hh = linear(&self.weight_hh, hidden, &self.bias_hh)?;
+
+        let output = linear_ih.tens_add(&linear_hh)?.tanh();
+
+        let cache = RNNCellCache{
+            input: input.clone(),
+            hidden: hidden.clone(),
+            output: output.clone(),
+        };
+
+        Some((output, cache))
+    }
+    /// Returns gradient of input, gradient of previous hidden state and gradients of parameters
+    /// from gradient of next hidden state
+    pub fn backward(&self, cache: &RNNCellCache, grad_hidden: &Tensor<f32>) -> Option<(Tensor<f32>, Tensor<f32>, RecurrentGrads)>{
+        let grad_linear = grad_hidden.tens_mul(&tanh_grad(&cache.output))?;
+
+        let (grad_input, weight_ih, bias_ih) = linear_backward(&self.weight_ih, &cache.input, &grad_linear)?;
+        let (grad_hidden, weight_hh, bias_hh) = linear_backward(&self.weight_hh, &cache.hidden, &grad_linear)?;
+
+        Some((grad_input, grad_hidden, RecurrentGrads{weight_ih, weight_hh, bias_ih, bias_hh}))
+    }
+    /// Apply gradient descent step to parameters
+    pub fn update(&mut self, grads: &RecurrentGrads, learning_rate: f32){
+        self.weight_ih = descent(&self.weight_ih, &grads.weight_ih, learning_rate);
+        self.weight_hh = descent(&self.weight_hh, &grads.weight_hh, learning_rate);
+        self.bias_ih = descent(&self.bias_ih, &grads.bias_ih, learning_rate);
+        self.bias_hh = descent(&self.bias_hh, &grads.bias_hh, learning_rate);
+    }
+}
+
+/// Gated recurrent unit cell, with gates stacked along rows in order reset, update, new
+///
+/// r = sigmoid(W_ir @ x + b_ir + W_hr @ h + b_hr)
+/// z = sigmoid(W_iz @ x + b_iz + W_hz @ h + b_hz)
+/// n = tanh(W_in @ x + b_in + r * (W_hn @ h + b_hn))
+/// h' = (1 - z) * n + z * h
+#[derive(Clone)]
+pub struct GRUCell{
+    pub weight_ih: Tensor<f32>,
+    pub weight_hh: Tensor<f32>,
+    pub bias_ih: Tensor<f32>,
+    pub bias_hh: Tensor<f32>,
+}
+
+/// Values of GRUCell forward pass needed for backward pass
+#[derive(Clone)]
+pub struct GRUCellCache{
+    input: Tensor<f32>,
+    hidden: Tensor<f32>,
+    reset: Tensor<f32>,
+    update: Tensor<f32>,
+    new: Tensor<f32>,
+    hidden_new: Tensor<f32>,
+}
+
+impl GRUCell{
+    /// Create new GRUCell with weights in range (-1/sqrt(hidden_size), 1/sqrt(hidden_size))
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let cell = GRUCell::new(3, 2);
+    ///
+    /// assert_eq!(cell.weight_ih.get_shape(), &vec!{6, 3});
+    /// assert_eq!(cell.weight_hh.get_shape(), &vec!{6, 2});
+    /// ```
+    pub fn new(input_size: u32, hidden_size: u32) -> Self{
+        let (weight_ih, weight_hh, bias_ih, bias_hh) = init_weights(input_size, hidden_size, 3);
+
+        Self{
+            weight_ih,
+            weight_hh,
+            bias_ih,
+            bias_hh,
+        }
+    }
+    /// Returns size of hidden state
+    pub fn hidden_size(&self) -> u32{
+        self.weight_hh.get_shape()[1]
+    }
+    /// Returns next hidden state and cache for backward pass
+    /// None if shapes do not match
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let cell = GRUCell::new(3, 2);
+    ///
+    /// let input: Tensor<f32> = Tensor::fill(1.0, &[3, 4]);
+    /// let hidden: Tensor<f32> = Tensor::fill(0.0, &[2, 4]);
+    ///
+    /// let (output, _cache) = cell.forward(&input, &hidden).unwrap();
+    ///
+    /// assert_eq!(output.get_shape(), &vec!{2, 4});
+    /// ```
+    pub fn forward(&self, input: &Tensor<f32>, hidden: &Tensor<f32>) -> Option<(Tensor<f32>, GRUCellCache)>{
+        let h = self.hidden_size();
+
+        let linear_ih = linear(&self.weight_ih, input, &self.bias_ih)?;
+        let linear_hh = linear(&self.weight_hh, hidden, &self.bias_hh)?;
+
+        let reset = rows(&linear_ih, 0, h).tens_add(&rows(&linear_hh, 0, h))?.sigmoid();
+        let update = rows(&linear_ih, h, 2 * h).tens_add(&rows(&linear_hh, h, 2 * h))?.sigmoid();
+
+        let hidden_new = rows(&linear_hh, 2 * h, 3 * h);
+        let new = rows(&linear_ih, 2 * h, 3 * h).tens_add(&reset.tens_mul(&hidden_new)?)?.tanh();
+
+        let output = one_minus(&update).tens_mul(&new)?.tens_add(&update.tens_mul(hidden)?)?;
+
+        let cache = GRUCellCache{
+            input: input.clone(),
+            hidden: hidden.clone(),
+            reset,
+            update,
+            new,
+            hidden_new,
+        };
+
+        Some((output, cache))
+    }
+    /// Returns gradient of input, gradient of previous hidden state and gradients of parameters
+    /// from gradient of next hidden state
+    pub fn backward(&self, cache: &GRUCellCache, grad_hidden: &Tensor<f32>) -> Option<(Tensor<f32>, Tensor<f32>, RecurrentGrads)>{
+        let grad_new = grad_hidden.tens_mul(&one_minus(&cache.update))?;
+        let grad_update = grad_hidden.tens_mul(&cache.hidden.tens_sub(&cache.new)?)?;
+        let grad_hidden_direct = grad_hidden.tens_mul(&cache.update)?;
+
+        let grad_new_linear = grad_new.tens_mul(&tanh_grad(&cache.new))?;
+        let grad_reset = grad_new_linear.tens_mul(&cache.hidden_new)?;
+
+        let grad_reset_linear = grad_reset.tens_mul(&sigmoid_grad(&cache.reset))?;
+        let grad_update_linear = grad_update.tens_mul(&sigmoid_grad(&cache.update))?;
+
+        let grad_linear_ih = stack_rows(&[grad_reset_linear.clone(), grad_update_linear.clone(), grad_new_linear.clone()])?;
+        let grad_linear_hh = stack_rows(&[grad_reset_linear, grad_update_linear, grad_new_linear.tens_mul(&cache.reset)?])?;
+
+        let (grad_input, weight_ih, bias_ih) = linear_backward(&self.weight_ih, &cache.input, &grad_linear_ih)?;
+        let (grad_hidden_gates, weight_hh, bias_hh) = linear_backward(&self.weight_hh, &cache.hidden, &grad_linear_hh)?;
+
+        let grad_hidden = grad_hidden_gates.tens_add(&grad_hidden_direct)?;
+
+        Some((grad_input, grad_hidden, RecurrentGrads{weight_ih, weight_hh, bias_ih, bias_hh}))
+    }
+    /// Apply gradient descent step to parameters
+    pub fn update(&mut self, grads: &RecurrentGrads, learning_rate: f32){
+        self.weight_ih = descent(&self.weight_ih, &grads.weight_ih, learning_rate);
+        self.weight_hh = descent(&self.weight_hh, &grads.weight_hh, learning_rate);
+        self.bias_ih = descent(&self.bias_ih, &grads.bias_ih, learning_rate);
+        self.bias_hh = descent(&self.bias_hh, &grads.bias_hh, learning_rate);
+    }
+}
+
+/// Long short-term memory cell, with gates stacked along rows in order input, forget, cell, output
+///
+/// i = sigmoid(...), f = sigmoid(...), g = tanh(...), o = sigmoid(...)
+/// c' = f * c + i * g
+/// h' = o * tanh(c')
+#[derive(Clone)]
+pub struct LSTMCell{
+    pub weight_ih: Tensor<f32>,
+    pub weight_hh: Tensor<f32>,
+    pub bias_ih: Tensor<f32>,
+    pub bias_hh: Tensor<f32>,
+}
+
+/// Gradient of input, previous hidden state, previous cell state and gradients of parameters
+pub type LSTMCellGrads = (Tensor<f32>, Tensor<f32>, Tensor<f32>, RecurrentGrads);
+
+/// Values of LSTMCell forward pass needed for backward pass
+#[derive(Clone)]
+pub struct LSTMCellCache{
+    input: Tensor<f32>,
+    hidden: Tensor<f32>,
+    cell: Tensor<f32>,
+    input_gate: Tensor<f32>,
+    forget_gate: Tensor<f32>,
+    cell_gate: Tensor<f32>,
+    output_gate: Tensor<f32>,
+    cell_activ: Tensor<f32>,
+}
+
+impl LSTMCell{
+    /// Create new LSTMCell with weights in range (-1/sqrt(hidden_size), 1/sqrt(hidden_size))
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let cell = LSTMCell::new(3, 2);
+    ///
+    /// assert_eq!(cell.weight_ih.get_shape(), &vec!{8, 3});
+    /// assert_eq!(cell.weight_hh.get_shape(), &vec!{8, 2});
+    /// ```
+    pub fn new(input_size: u32, hidden_size: u32) -> Self{
+        let (weight_ih, weight_hh, bias_ih, bias_hh) = init_weights(input_size, hidden_size, 4);
+
+        Self{
+            weight_ih,
+            weight_hh,
+            bias_ih,
+            bias_hh,
+        }
+    }
+    /// Returns size of hidden state
+    pub fn hidden_size(&self) -> u32{
+        self.weight_hh.get_shape()[1]
+    }
+    /// Returns next hidden state, next cell state and cache for backward pass
+    /// None if shapes do not match
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let cell = LSTMCell::new(3, 2);
+    ///
+    /// let input: Tensor<f32> = Tensor::fill(1.0, &[3, 4]);
+    /// let hidden: Tensor<f32> = Tensor::fill(0.0, &[2, 4]);
+    /// let cell_state: Tensor<f32> = Tensor::fill(0.0, &[2, 4]);
+    ///
+    /// let (output, next_cell, _cache) = cell.forward(&input, &hidden, &cell_state).unwrap();
+    ///
+    /// assert_eq!(output.get_shape(), &vec!{2, 4});
+    /// assert_eq!(next_cell.get_shape(), &vec!{2, 4});
+    /// ```
+    pub fn forward(&self, input: &Tensor<f32>, hidden: &Tensor<f32>, cell: &Tensor<f32>) -> Option<(Tensor<f32>, Tensor<f32>, LSTMCellCache)>{
+        let h = self.hidden_size();
+
+        let gates = linear(&self.weight_ih, input, &self.bias_ih)?.tens_add(&linear(&self.weight_hh, hidden, &self.bias_hh)?)?;
+
+        let input_gate = rows(&gates, 0, h).sigmoid();
+        let forget_gate = rows(&gates, h, 2 * h).sigmoid();
+        let cell_gate = rows(&gates, 2 * h, 3 * h).tanh();
+        let output_gate = rows(&gates, 3 * h, 4 * h).sigmoid();
+
+        let next_cell = forget_gate.tens_mul(cell)?.tens_add(&input_gate.tens_mul(&cell_gate)?)?;
+        let cell_activ = next_cell.tanh();
+        let output = output_gate.tens_mul(&cell_activ)?;
+
+        let cache = LSTMCellCache{
+            input: input.clone(),
+            hidden: hidden.clone(),
+            cell: cell.clone(),
+            input_gate,
+            forget_gate,
+            cell_gate,
+            output_gate,
+            cell_activ,
+        };
+
+        Some((output, next_cell, cache))
+    }
+    /// Returns gradient of input, gradient of previous hidden state, gradient of previous cell state
+    /// and gradients of parameters from gradients of next hidden and cell state
+    pub fn backward(&self, cache: &LSTMCellCache, grad_hidden: &Tensor<f32>, grad_cell: &Tensor<f32>) -> Option<LSTMCellGrads>{
+        let grad_output_gate = grad_hidden.tens_mul(&cache.cell_activ)?;
+        let grad_next_cell = grad_cell.tens_add(&grad_hidden.tens_mul(&cache.output_gate)?.tens_mul(&tanh_grad(&cache.cell_activ))?)?;
+
+        let grad_input_gate = grad_next_cell.tens_mul(&cache.cell_gate)?;
+        let grad_forget_gate = grad_next_cell.tens_mul(&cache.cell)?;
+        let grad_cell_gate = grad_next_cell.tens_mul(&cache.input_gate)?;
+        let grad_prev_cell = grad_next_cell.tens_mul(&cache.forget_gate)?;
+
+        let grad_gates = stack_rows(&[
+            grad_input_gate.tens_mul(&sigmoid_grad(&cache.input_gate))?,
+            grad_forget_gate.tens_mul(&sigmoid_grad(&cache.forget_gate))?,
+            grad_cell_gate.tens_mul(&tanh_grad(&cache.cell_gate))?,
+            grad_output_gate.tens_mul(&sigmoid_grad(&cache.output_gate))?,
+        ])?;
+
+        let (grad_input, weight_ih, bias_ih) = linear_backward(&self.weight_ih, &cache.input, &grad_gates)?;
+        let (grad_prev_hidden, weight_hh, bias_hh) = linear_backward(&self.weight_hh, &cache.hidden, &grad_gates)?;
+
+        Some((grad_input, grad_prev_hidden, grad_prev_cell, RecurrentGrads{weight_ih, weight_hh, bias_ih, bias_hh}))
+    }
+    /// Apply gradient descent step to parameters
+    pub fn update(&mut self, grads: &RecurrentGrads, learning_rate: f32){
+        self.weight_ih = descent(&self.weight_ih, &grads.weight_ih, learning_rate);
+        self.weight_hh = descent(&self.weight_hh, &grads.weight_hh, learning_rate);
+        self.bias_ih = descent(&self.bias_ih, &grads.bias_ih, learning_rate);
+        self.bias_hh = descent(&self.bias_hh, &grads.bias_hh, learning_rate);
+    }
+}
+
+/// Returns initial state, or zeros with batch of first input
+fn initial_state(state: Option<&Tensor<f32>>, hidden_size: u32, inputs: &[Tensor<f32>]) -> Tensor<f32>{
+    match state{
+        Some(state) => state.clone(),
+        None => Tensor::fill(0.0, &[hidden_size, inputs[0].get_shape()[1]]),
+    }
+}
+
+/// Vanilla recurrent layer over whole sequence, that stores caches of the last forward pass
+/// for backpropagation through time
+#[derive(Clone)]
+pub struct RNN{
+    pub cell: RNNCell,
+
+    caches: Vec<RNNCellCache>,
+}
+
+impl RNN{
+    /// Create new RNN layer
+    pub fn new(input_size: u32, hidden_size: u32) -> Self{
+        Self{
+            cell: RNNCell::new(input_size, hidden_size),
+
+            caches: Vec::new(),
+        }
+    }
+    /// Returns hidden state of every time step
+    /// inputs are time steps of shape [input_size, batch], hidden is initial state, zeros if None
+    /// None if sequence is empty or shapes do not match
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut rnn = RNN::new(3, 2);
+    ///
+    /// let inputs: Vec<Tensor<f32>> = vec!{Tensor::fill(1.0, &[3, 4]); 5};
+    ///
+    /// let outputs = rnn.forward(&inputs, None).unwrap();
+    /// let grad_outputs: Vec<Tensor<f32>> = vec!{Tensor::fill(1.0, &[2, 4]); 5};
+    ///
+    /// let (grad_inputs, grads) = rnn.backward(&grad_outputs).unwrap();
+    ///
+    /// assert_eq!(outputs.len(), 5);
+    /// assert_eq!(grad_inputs[0].get_shape(), &vec!{3, 4});
+    /// assert_eq!(grads.weight_hh.get_shape(), &vec!{2, 2});
+    /// ```
+    pub fn forward(&mut self, inputs: &[Tensor<f32>], hidden: Option<&Tensor<f32>>) -> Option<Vec<Tensor<f32>>>{
+        if inputs.is_empty(){
+            return None;
+        }
+
+        let mut hidden = initial_state(hidden, self.cell.hidden_size(), inputs);
+
+        self.caches.clear();
+        let mut outputs: Vec<Tensor<f32>> = Vec::with_capacity(inputs.len());
+
+        for input in inputs{
+            let (output, cache) = self.cell.forward(input, &hidden)?;
+
+            self.caches.push(cache);
+            outputs.push(output.clone());
+            hidden = output;
+        }
+
+        Some(outputs)
+    }
+    /// Backpropagation through time
+    /// Returns gradient of every input and gradients of parameters summed over time steps
+    /// grad_outputs are gradients of hidden state of every time step
+    /// None if forward was not called or lengths do not match
+    pub fn backward(&self, grad_outputs: &[Tensor<f32>]) -> Option<(Vec<Tensor<f32>>, RecurrentGrads)>{
+        if self.caches.is_empty() || grad_outputs.len() != self.caches.len(){
+            return None;
+        }
+
+        let mut grads = RecurrentGrads::zeros(&self.cell.weight_ih, &self.cell.weight_hh, &self.cell.bias_ih, &self.cell.bias_hh);
+        let mut grad_inputs: Vec<Tensor<f32>> = Vec::with_capacity(grad_outputs.len());
+        let mut grad_next = Tensor::fill(0.0, grad_outputs[0].get_shape());
+
+        for t in (0..self.caches.len()).rev(){
+            let grad_hidden = grad_outputs[t].tens_add(&grad_next)?;
+            let (grad_input, grad_prev, step_grads) = self.cell.backward(&self.caches[t], &grad_hidden)?;
+
+            grads.accumulate(&step_grads);
+            grad_inputs.push(grad_input);
+            grad_next = grad_prev;
+        }
+
+        grad_inputs.reverse();
+
+        Some((grad_inputs, grads))
+    }
+}
+
+/// GRU layer over whole sequence, that stores caches of the last forward pass
+/// for backpropagation through time
+#[derive(Clone)]
+pub struct GRU{
+    pub cell: GRUCell,
+
+    caches: Vec<GRUCellCache>,
+}
+
+impl GRU{
+    /// Create new GRU layer
+    pub fn new(input_size: u32, hidden_size: u32) -> Self{
+        Self{
+            cell: GRUCell::new(input_size, hidden_size),
+
+            caches: Vec::new(),
+        }
+    }
+    /// Returns hidden state of every time step
+    /// inputs are time steps of shape [input_size, batch], hidden is initial state, zeros if None
+    /// None if sequence is empty or shapes do not match
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut gru = GRU::new(3, 2);
+    ///
+    /// let inputs: Vec<Tensor<f32>> = vec!{Tensor::fill(1.0, &[3, 4]); 5};
+    ///
+    /// let outputs = gru.forward(&inputs, None).unwrap();
+    /// let grad_outputs: Vec<Tensor<f32>> = vec!{Tensor::fill(1.0, &[2, 4]); 5};
+    ///
+    /// let (grad_inputs, grads) = gru.backward(&grad_outputs).unwrap();
+    ///
+    /// assert_eq!(outputs.len(), 5);
+    /// assert_eq!(grad_inputs[0].get_shape(), &vec!{3, 4});
+    /// assert_eq!(grads.weight_hh.get_shape(), &vec!{6, 2});
+    /// ```
+    pub fn forward(&mut self, inputs: &[Tensor<f32>], hidden: Option<&Tensor<f32>>) -> Option<Vec<Tensor<f32>>>{
+        if inputs.is_empty(){
+            return None;
+        }
+
+        let mut hidden = initial_state(hidden, self.cell.hidden_size(), inputs);
+
+        self.caches.clear();
+        let mut outputs: Vec<Tensor<f32>> = Vec::with_capacity(inputs.len());
+
+        for input in inputs{
+            let (output, cache) = self.cell.forward(input, &hidden)?;
+
+            self.caches.push(cache);
+            outputs.push(output.clone());
+            hidden = output;
+        }
+
+        Some(outputs)
+    }
+    /// Backpropagation through time
+    /// Returns gradient of every input and gradients of parameters summed over time steps
+    /// grad_outputs are gradients of hidden state of every time step
+    /// None if forward was not called or lengths do not match
+    pub fn backward(&self, grad_outputs: &[Tensor<f32>]) -> Option<(Vec<Tensor<f32>>, RecurrentGrads)>{
+        if self.caches.is_empty() || grad_outputs.len() != self.caches.len(){
+            return None;
+        }
+
+        let mut grads = RecurrentGrads::zeros(&self.cell.weight_ih, &self.cell.weight_hh, &self.cell.bias_ih, &self.cell.bias_hh);
+        let mut grad_inputs: Vec<Tensor<f32>> = Vec::with_capacity(grad_outputs.len());
+        let mut grad_next = Tensor::fill(0.0, grad_outputs[0].get_shape());
+
+        for t in (0..self.caches.len()).rev(){
+            let grad_hidden = grad_outputs[t].tens_add(&grad_next)?;
+            let (grad_input, grad_prev, step_grads) = self.cell.backward(&self.caches[t], &grad_hidden)?;
+
+            grads.accumulate(&step_grads);
+            grad_inputs.push(grad_input);
+            grad_next = grad_prev;
+        }
+
+        grad_inputs.reverse();
+
+        Some((grad_inputs, grads))
+    }
+}
+
+/// LSTM layer over whole sequence, that stores caches of the last forward pass
+/// for backpropagation through time
+#[derive(Clone)]
+pub struct LSTM{
+    pub cell: LSTMCell,
+
+    caches: Vec<LSTMCellCache>,
+}
+
+impl LSTM{
+    /// Create new LSTM layer
+    pub fn new(input_size: u32, hidden_size: u32) -> Self{
+        Self{
+            cell: LSTMCell::new(input_size, hidden_size),
+
+            caches: Vec::new(),
+        }
+    }
+    /// Returns hidden state of every time step
+    /// inputs are time steps of shape [input_size, batch], hidden and cell are initial states,
+    /// zeros if None
+    /// None if sequence is empty or shapes do not match
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut lstm = LSTM::new(3, 2);
+    ///
+    /// let inputs: Vec<Tensor<f32>> = vec!{Tensor::fill(1.0, &[3, 4]); 5};
+    ///
+    /// let outputs = lstm.forward(&inputs, None, None).unwrap();
+    /// let grad_outputs: Vec<Tensor<f32>> = vec!{Tensor::fill(1.0, &[2, 4]); 5};
+    ///
+    /// let (grad_inputs, grads) = lstm.backward(&grad_outputs).unwrap();
+    ///
+    /// assert_eq!(outputs.len(), 5);
+    /// assert_eq!(grad_inputs[0].get_shape(), &vec!{3, 4});
+    /// assert_eq!(grads.weight_hh.get_shape(), &vec!{8, 2});
+    /// ```
+    pub fn forward(&mut self, inputs: &[Tensor<f32>], hidden: Option<&Tensor<f32>>, cell: Option<&Tensor<f32>>) -> Option<Vec<Tensor<f32>>>{
+        if inputs.is_empty(){
+            return None;
+        }
+
+        let mut hidden = initial_state(hidden, self.cell.hidden_size(), inputs);
+        let mut cell = initial_state(cell, self.cell.hidden_size(), inputs);
+
+        self.caches.clear();
+        let mut outputs: Vec<Tensor<f32>> = Vec::with_capacity(inputs.len());
+
+        for input in inputs{
+            let (output, next_cell, cache) = self.cell.forward(input, &hidden, &cell)?;
+
+            self.caches.push(cache);
+            outputs.push(output.clone());
+            hidden = output;
+            cell = next_cell;
+        }
+
+        Some(outputs)
+    }
+    /// Backpropagation through time
+    /// Returns gradient of every input and gradients of parameters summed over time steps
+    /// grad_outputs are gradients of hidden state of every time step
+    /// None if forward was not called or lengths do not match
+    pub fn backward(&self, grad_outputs: &[Tensor<f32>]) -> Option<(Vec<Tensor<f32>>, RecurrentGrads)>{
+        if self.caches.is_empty() || grad_outputs.len() != self.caches.len(){
+            return None;
+        }
+
+        let mut grads = RecurrentGrads::zeros(&self.cell.weight_ih, &self.cell.weight_hh, &self.cell.bias_ih, &self.cell.bias_hh);
+        let mut grad_inputs: Vec<Tensor<f32>> = Vec::with_capacity(grad_outputs.len());
+        let mut grad_next = Tensor::fill(0.0, grad_outputs[0].get_shape());
+        let mut grad_cell = Tensor::fill(0.0, grad_outputs[0].get_shape());
+
+        for t in (0..self.caches.len()).rev(){
+            let grad_hidden = grad_outputs[t].tens_add(&grad_next)?;
+            let (grad_input, grad_prev, grad_prev_cell, step_grads) = self.cell.backward(&self.caches[t], &grad_hidden, &grad_cell)?;
+
+            grads.accumulate(&step_grads);
+            grad_inputs.push(grad_input);
+            grad_next = grad_prev;
+            grad_cell = grad_prev_cell;
+        }
+
+        grad_inputs.reverse();
+
+        Some((grad_inputs, grads))
+    }
+}
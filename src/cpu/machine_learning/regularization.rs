@@ -0,0 +1,304 @@
+use rand::Rng;
+
+use crate::tensor::Tensor;
+
+// SELU constants, alpha_dropout keeps mean and variance of SELU activations
+const SELU_ALPHA: f32 = 1.673_263_2;
+const SELU_SCALE: f32 = 1.050_701;
+
+impl Tensor<f32>{
+    /// Returns a tensor with each element zeroed with probability p and remaining elements scaled
+    /// by 1/(1-p), and the mask used, so it can be used in backward pass.
+    /// When training is false, returns unchanged tensor and mask of ones
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::fill(1.0, &[2, 2]);
+    ///
+    /// let (b, mask) = a.dropout(0.5, true);
+    ///
+    /// assert!(b.get_data().iter().all(|x| *x == 0.0 || *x == 2.0));
+    /// assert_eq!(b.get_data(), mask.get_data());
+    /// ```
+    pub fn dropout(&self, p: f32, training: bool) -> (Tensor<f32>, Tensor<f32>){
+        if !training || p <= 0.0{
+            return (self.clone(), Tensor::fill(1.0, self.get_shape()));
+        }
+        if p >= 1.0{
+            return (Tensor::fill(0.0, self.get_shape()), Tensor::fill(0.0, self.get_shape()));
+        }
+
+        let mut rng = rand::rng();
+        let scale = 1.0 / (1.0 - p);
+
+        let mask_data: Vec<f32> = (0..self.count_data())
+            .map(|_| if rng.random::<f32>() < p { 0.0 } else { scale })
+            .collect();
+
+        let mask = Tensor::from_data(&mask_data, self.get_shape()).unwrap();
+
+        (self.tens_mul(&mask).unwrap(), mask)
+    }
+
+    /// Returns a tensor after alpha dropout, used with SELU activation.
+    /// Dropped elements are set to SELU saturation value and output is transformed to keep mean
+    /// and variance of input.
+    /// Returns output, and mask with scale a for kept elements, used in backward pass.
+    /// When training is false, returns unchanged tensor and mask of ones
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::fill(1.0, &[2, 2]);
+    ///
+    /// let (b, mask) = a.alpha_dropout(0.5, false);
+    ///
+    /// assert_eq!(b.get_data(), a.get_data());
+    /// ```
+    pub fn alpha_dropout(&self, p: f32, training: bool) -> (Tensor<f32>, Tensor<f32>){
+        if !training || p <= 0.0{
+            return (self.clone(), Tensor::fill(1.0, self.get_shape()));
+        }
+
+        let alpha_prime = -SELU_ALPHA * SELU_SCALE;
+        let p = p.min(0.999_999);
+
+        let a = 1.0 / ((1.0 - p) * (1.0 + p * alpha_prime * alpha_prime)).sqrt();
+        let b = -a * alpha_prime * p;
+
+        let mut rng = rand::rng();
+
+        let mut return_data: Vec<f32> = Vec::with_capacity(self.count_data());
+        let mut mask_data: Vec<f32> = Vec::with_capacity(self.count_data());
+
+        for i in 0..self.count_data(){
+            if rng.random::<f32>() < p{
+                return_data.push(a * alpha_prime + b);
+                mask_data.push(0.0);
+            }
+            else{
+                return_data.push(a * self.get_data()[i] + b);
+                mask_data.push(a);
+            }
+        }
+
+        (Tensor::from_data(&return_data, self.get_shape()).unwrap(), Tensor::from_data(&mask_data, self.get_shape()).unwrap())
+    }
+
+    /// Returns L1 penalty of weights, lambda * sum(|w|)
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[-1.0, 2.0], &[2]).unwrap();
+    ///
+    /// assert_eq!(a.l1_penalty(0.5), 1.5);
+    /// ```
+    pub fn l1_penalty(&self, lambda: f32) -> f32{
+        lambda * self.get_data().iter().map(|x| x.abs()).sum::<f32>()
+    }
+
+    /// Returns L2 penalty of weights, lambda/2 * sum(w^2)
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[-1.0, 2.0], &[2]).unwrap();
+    ///
+    /// assert_eq!(a.l2_penalty(0.5), 1.25);
+    /// ```
+    pub fn l2_penalty(&self, lambda: f32) -> f32{
+        lambda * 0.5 * self.get_data().iter().map(|x| x * x).sum::<f32>()
+    }
+
+    /// Returns gradient with added gradient of L1 penalty of weights (self), lambda * sign(w)
+    /// None if different sizes
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let weights: Tensor<f32> = Tensor::from_data(&[-1.0, 0.0, 2.0], &[3]).unwrap();
+    /// let grad: Tensor<f32> = Tensor::fill(1.0, &[3]);
+    ///
+    /// let b = weights.l1_grad(&grad, 0.5).unwrap();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{0.5, 1.0, 1.5});
+    /// ```
+    pub fn l1_grad(&self, grad: &Tensor<f32>, lambda: f32) -> Option<Tensor<f32>>{
+        if self.get_shape() != grad.get_shape(){
+            return None;
+        }
+
+        let return_data: Vec<f32> = self.get_data().iter().zip(grad.get_data().iter())
+            .map(|(w, g)| {
+                let sign = if *w > 0.0 { 1.0 } else if *w < 0.0 { -1.0 } else { 0.0 };
+                g + lambda * sign
+            })
+            .collect();
+
+        Tensor::from_data(&return_data, self.get_shape())
+    }
+
+    /// Returns gradient with added gradient of L2 penalty of weights (self), lambda * w
+    /// None if different sizes
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let weights: Tensor<f32> = Tensor::from_data(&[-1.0, 0.0, 2.0], &[3]).unwrap();
+    /// let grad: Tensor<f32> = Tensor::fill(1.0, &[3]);
+    ///
+    /// let b = weights.l2_grad(&grad, 0.5).unwrap();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{0.5, 1.0, 2.0});
+    /// ```
+    pub fn l2_grad(&self, grad: &Tensor<f32>, lambda: f32) -> Option<Tensor<f32>>{
+        if self.get_shape() != grad.get_shape(){
+            return None;
+        }
+
+        let return_data: Vec<f32> = self.get_data().iter().zip(grad.get_data().iter())
+            .map(|(w, g)| g + lambda * w)
+            .collect();
+
+        Tensor::from_data(&return_data, self.get_shape())
+    }
+}
+
+/// Dropout layer, that stores mask of the last forward pass for backward pass
+pub struct Dropout{
+    pub p: f32,
+    pub training: bool,
+    pub alpha: bool,
+
+    mask: Option<Tensor<f32>>,
+}
+
+impl Dropout{
+    /// Create new Dropout with probability p of zeroing element, in training mode
+    pub fn new(p: f32) -> Self{
+        Self{
+            p,
+            training: true,
+            alpha: false,
+
+            mask: None,
+        }
+    }
+    /// Create new alpha Dropout with probability p of dropping element, in training mode
+    pub fn alpha(p: f32) -> Self{
+        Self{
+            p,
+            training: true,
+            alpha: true,
+
+            mask: None,
+        }
+    }
+    /// Switch layer to training mode
+    pub fn train(&mut self){
+        self.training = true;
+    }
+    /// Switch layer to eval mode, where input is returned unchanged
+    pub fn eval(&mut self){
+        self.training = false;
+    }
+
+    /// Apply dropout and store mask for backward pass
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut dropout = Dropout::new(0.5);
+    ///
+    /// let a: Tensor<f32> = Tensor::fill(1.0, &[2, 2]);
+    /// let b = dropout.forward(&a);
+    ///
+    /// let grad = dropout.backward(&Tensor::fill(1.0, &[2, 2])).unwrap();
+    ///
+    /// assert_eq!(b.get_data(), grad.get_data());
+    /// ```
+    pub fn forward(&mut self, input: &Tensor<f32>) -> Tensor<f32>{
+        let (output, mask) = if self.alpha{
+            input.alpha_dropout(self.p, self.training)
+        }
+        else{
+            input.dropout(self.p, self.training)
+        };
+
+        self.mask = Some(mask);
+
+        output
+    }
+
+    /// Returns gradient of input, None if forward was not called or shapes differ
+    pub fn backward(&self, grad_output: &Tensor<f32>) -> Option<Tensor<f32>>{
+        grad_output.tens_mul(self.mask.as_ref()?)
+    }
+
+    /// Returns mask of last forward pass
+    pub fn get_mask(&self) -> Option<&Tensor<f32>>{
+        self.mask.as_ref()
+    }
+}
+
+/// Clip every element of every gradient to range [-clip_value, clip_value]
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// let mut grads: Vec<Tensor<f32>> = vec!{Tensor::from_data(&[-5.0, 0.5, 5.0], &[3]).unwrap()};
+///
+/// clip_grad_value(&mut grads, 1.0);
+///
+/// assert_eq!(grads[0].get_data(), &vec!{-1.0, 0.5, 1.0});
+/// ```
+pub fn clip_grad_value(grads: &mut [Tensor<f32>], clip_value: f32){
+    for grad in grads.iter_mut(){
+        let clipped: Vec<f32> = grad.get_data().iter()
+            .map(|x| x.clamp(-clip_value, clip_value))
+            .collect();
+
+        grad.set_data(&clipped);
+    }
+}
+
+/// Scale all gradients, so their global L2 norm is at most max_norm
+/// Returns global norm before clipping
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// let mut grads: Vec<Tensor<f32>> = vec!{Tensor::from_data(&[3.0], &[1]).unwrap(), Tensor::from_data(&[4.0], &[1]).unwrap()};
+///
+/// let norm = clip_grad_norm(&mut grads, 1.0);
+///
+/// assert_eq!(norm, 5.0);
+/// assert_eq!(grads[0].get_data(), &vec!{0.6});
+/// assert_eq!(grads[1].get_data(), &vec!{0.8});
+/// ```
+pub fn clip_grad_norm(grads: &mut [Tensor<f32>], max_norm: f32) -> f32{
+    let total_norm = grads.iter()
+        .map(|grad| grad.get_data().iter().map(|x| x * x).sum::<f32>())
+        .sum::<f32>()
+        .sqrt();
+
+    if total_norm > max_norm && total_norm > 0.0{
+        let scale = max_norm / total_norm;
+        for grad in grads.iter_mut(){
+            grad.mul_mut(scale);
+        }
+    }
+
+    total_norm
+}
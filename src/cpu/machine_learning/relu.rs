@@ -1,16 +1,16 @@
-use crate::tensor::Tensor;
+use crate::{cpu::float::Float, tensor::Tensor};
 
-fn relu(x: f32) -> f32{
-    if x<0.0{
-        return 0.0;
+fn relu<T: Float>(x: T) -> T{
+    if x<T::ZERO{
+        return T::ZERO;
     }
     return x;
 }
-fn relu_der(x: f32) -> f32{
-    if x<0.0{
-        return 0.0;
+fn relu_der<T: Float>(x: T) -> T{
+    if x<T::ZERO{
+        return T::ZERO;
     }
-    return 1.0;
+    return T::ONE;
 }
 
 impl Tensor<f32>{
@@ -26,13 +26,8 @@ impl Tensor<f32>{
     /// assert_eq!(b.get_data(), &vec!{0.0, 0.0, 20.0});
     /// ```
     pub fn relu(&self) -> Tensor<f32>{
-        let data_vec: Vec<f32> = self.get_data().iter()
-            .map(|val| relu(*val))
-            .collect();
-
-        Tensor::from_data(&data_vec, &self.get_shape()).unwrap()
+        FloatRelu::relu(self)
     }
-
     /// Returns a tensor with data transformed using derivative of ReLU function
     ///
     /// # Example
@@ -44,9 +39,29 @@ impl Tensor<f32>{
     ///
     /// assert_eq!(b.get_data(), &vec!{0.0, 1.0, 1.0});
     /// ```
-
     pub fn relu_der(&self) -> Tensor<f32>{
-        let data_vec: Vec<f32> = self.get_data().iter()
+        FloatRelu::relu_der(self)
+    }
+}
+
+/// ReLU of tensors of every Float type
+pub trait FloatRelu<T: Float>{
+    /// Returns a tensor with data transformed using ReLU function
+    fn relu(&self) -> Tensor<T>;
+    /// Returns a tensor with data transformed using derivative of ReLU function
+    fn relu_der(&self) -> Tensor<T>;
+}
+
+impl<T: Float> FloatRelu<T> for Tensor<T>{
+    fn relu(&self) -> Tensor<T>{
+        let data_vec: Vec<T> = self.get_data().iter()
+            .map(|val| relu(*val))
+            .collect();
+
+        Tensor::from_data(&data_vec, &self.get_shape()).unwrap()
+    }
+    fn relu_der(&self) -> Tensor<T>{
+        let data_vec: Vec<T> = self.get_data().iter()
             .map(|val| relu_der(*val))
             .collect();
 
@@ -1,10 +1,10 @@
-use crate::tensor::Tensor;
+use crate::{cpu::float::Float, tensor::Tensor};
 
-fn sigmoid(x: f32) -> f32{
-    1.0 / (1.0 + (-x).exp())
+fn sigmoid<T: Float>(x: T) -> T{
+    T::ONE / (T::ONE + (-x).exp())
 }
-fn sigmoid_der(x: f32) -> f32{
-    sigmoid(x) * (1.0 - sigmoid(x))
+fn sigmoid_der<T: Float>(x: T) -> T{
+    sigmoid(x) * (T::ONE - sigmoid(x))
 }
 
 impl Tensor<f32>{
@@ -20,13 +20,8 @@ impl Tensor<f32>{
     /// assert_eq!(b.get_data(), &vec!{0.0, 0.5, 1.0});
     /// ```
     pub fn sigmoid(&self) -> Tensor<f32>{
-        let data_vec: Vec<f32> = self.get_data().iter()
-            .map(|val| sigmoid(*val))
-            .collect();
-
-        Tensor::from_data(&data_vec, &self.get_shape()).unwrap()
+        FloatSigmoid::sigmoid(self)
     }
-
     /// Returns a tensor with data transformed using sigmoid function
     ///
     /// # Example
@@ -39,7 +34,28 @@ impl Tensor<f32>{
     /// assert_eq!(b.get_data(), &vec!{0.0, 0.25, 0.0});
     /// ```
     pub fn sigmoid_der(&self) -> Tensor<f32>{
-        let data_vec: Vec<f32> = self.get_data().iter()
+        FloatSigmoid::sigmoid_der(self)
+    }
+}
+
+/// Sigmoid of tensors of every Float type
+pub trait FloatSigmoid<T: Float>{
+    /// Returns a tensor with data transformed using sigmoid function
+    fn sigmoid(&self) -> Tensor<T>;
+    /// Returns a tensor with data transformed using sigmoid function
+    fn sigmoid_der(&self) -> Tensor<T>;
+}
+
+impl<T: Float> FloatSigmoid<T> for Tensor<T>{
+    fn sigmoid(&self) -> Tensor<T>{
+        let data_vec: Vec<T> = self.get_data().iter()
+            .map(|val| sigmoid(*val))
+            .collect();
+
+        Tensor::from_data(&data_vec, &self.get_shape()).unwrap()
+    }
+    fn sigmoid_der(&self) -> Tensor<T>{
+        let data_vec: Vec<T> = self.get_data().iter()
             .map(|val| sigmoid_der(*val))
             .collect();
 
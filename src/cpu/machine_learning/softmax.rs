@@ -0,0 +1,114 @@
+use crate::{cpu::float::Float, tensor::Tensor};
+
+/// Returns rows and cols of matrices softmax is computed over, rows are normalized together
+fn softmax_dims(shape: &[u32]) -> (usize, usize){
+    if shape.len() < 2{
+        return (shape.iter().product::<u32>() as usize, 1);
+    }
+
+    (shape[shape.len() - 2] as usize, shape[shape.len() - 1] as usize)
+}
+
+impl Tensor<f32>{
+    /// Returns a tensor with softmax applied over every column of [classes, batch] matrix.
+    /// For tensors with more dims, it's applied to every column of every matrix in last two dims,
+    /// and for vectors over whole vector
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[0.0, 1.0, 0.0, 1.0], &[2, 2]).unwrap();
+    /// let b = a.softmax();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{0.5, 0.5, 0.5, 0.5});
+    /// ```
+    pub fn softmax(&self) -> Tensor<f32>{
+        FloatSoftmax::softmax(self)
+    }
+    /// Returns gradient of softmax input, self is the output of softmax
+    /// dx = s * (grad - sum(grad * s)) over every normalized column
+    /// None if shapes differ
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[0.0, 1.0], &[2, 1]).unwrap().softmax();
+    /// let grad: Tensor<f32> = Tensor::fill(1.0, &[2, 1]);
+    ///
+    /// let b = a.softmax_backward(&grad).unwrap();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{0.0, 0.0});
+    /// ```
+    pub fn softmax_backward(&self, grad_output: &Tensor<f32>) -> Option<Tensor<f32>>{
+        FloatSoftmax::softmax_backward(self, grad_output)
+    }
+}
+
+/// Softmax of tensors of every Float type
+pub trait FloatSoftmax<T: Float>{
+    /// Returns a tensor with softmax applied over every column of [classes, batch] matrix.
+    fn softmax(&self) -> Tensor<T>;
+    /// Returns gradient of softmax input, self is the output of softmax
+    fn softmax_backward(&self, grad_output: &Tensor<T>) -> Option<Tensor<T>>;
+}
+
+impl<T: Float> FloatSoftmax<T> for Tensor<T>{
+    fn softmax(&self) -> Tensor<T>{
+        let (rows, cols) = softmax_dims(self.get_shape());
+        let data = self.get_data();
+
+        let mut return_data: Vec<T> = vec![T::ZERO; data.len()];
+
+        for matrix in 0..data.len() / (rows * cols).max(1){
+            let offset = matrix * rows * cols;
+
+            for col in 0..cols{
+                let max = (0..rows)
+                    .map(|row| data[offset + row * cols + col])
+                    .fold(T::NEG_INFINITY, T::max);
+
+                let mut sum = T::ZERO;
+                for row in 0..rows{
+                    let value = (data[offset + row * cols + col] - max).exp();
+                    return_data[offset + row * cols + col] = value;
+                    sum += value;
+                }
+                for row in 0..rows{
+                    return_data[offset + row * cols + col] /= sum;
+                }
+            }
+        }
+
+        Tensor::from_data(&return_data, self.get_shape()).unwrap()
+    }
+    fn softmax_backward(&self, grad_output: &Tensor<T>) -> Option<Tensor<T>>{
+        if self.get_shape() != grad_output.get_shape(){
+            return None;
+        }
+
+        let (rows, cols) = softmax_dims(self.get_shape());
+        let output = self.get_data();
+        let grad = grad_output.get_data();
+
+        let mut return_data: Vec<T> = vec![T::ZERO; output.len()];
+
+        for matrix in 0..output.len() / (rows * cols).max(1){
+            let offset = matrix * rows * cols;
+
+            for col in 0..cols{
+                let dot: T = (0..rows)
+                    .map(|row| output[offset + row * cols + col] * grad[offset + row * cols + col])
+                    .sum();
+
+                for row in 0..rows{
+                    let id = offset + row * cols + col;
+                    return_data[id] = output[id] * (grad[id] - dot);
+                }
+            }
+        }
+
+        Tensor::from_data(&return_data, self.get_shape())
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{cpu::float::Float, tensor::Tensor};
+
+fn tanh_der<T: Float>(x: T) -> T{
+    T::ONE - x.tanh() * x.tanh()
+}
+
+impl Tensor<f32>{
+    /// Returns a tensor with data transformed using tanh function
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[-200.0, 0.0, 200.0], &[3]).unwrap();
+    /// let b = a.tanh();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{-1.0, 0.0, 1.0});
+    /// ```
+    pub fn tanh(&self) -> Tensor<f32>{
+        FloatTanh::tanh(self)
+    }
+    /// Returns a tensor with data transformed using derivative of tanh function
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[-200.0, 0.0, 200.0], &[3]).unwrap();
+    /// let b = a.tanh_der();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{0.0, 1.0, 0.0});
+    /// ```
+    pub fn tanh_der(&self) -> Tensor<f32>{
+        FloatTanh::tanh_der(self)
+    }
+}
+
+/// Tanh of tensors of every Float type
+pub trait FloatTanh<T: Float>{
+    /// Returns a tensor with data transformed using tanh function
+    fn tanh(&self) -> Tensor<T>;
+    /// Returns a tensor with data transformed using derivative of tanh function
+    fn tanh_der(&self) -> Tensor<T>;
+}
+
+impl<T: Float> FloatTanh<T> for Tensor<T>{
+    fn tanh(&self) -> Tensor<T>{
+        let data_vec: Vec<T> = self.get_data().iter()
+            .map(|val| val.tanh())
+            .collect();
+
+        Tensor::from_data(&data_vec, self.get_shape()).unwrap()
+    }
+    fn tanh_der(&self) -> Tensor<T>{
+        let data_vec: Vec<T> = self.get_data().iter()
+            .map(|val| tanh_der(*val))
+            .collect();
+
+        Tensor::from_data(&data_vec, self.get_shape()).unwrap()
+    }
+}
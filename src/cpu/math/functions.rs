@@ -1,4 +1,4 @@
-use crate::tensor::*;
+use crate::{cpu::float::Float, tensor::*};
 
 impl Tensor<f32>{
     /// Each element transformed to natural log of that element
@@ -16,13 +16,7 @@ impl Tensor<f32>{
     /// assert_eq!(b.get_data(), &vec!{0.0, 1.0, 2.0})
     /// ```
     pub fn nlog(&self) -> Tensor<f32>{
-        let mut return_data = Vec::with_capacity(self.get_data().len());
-
-        for i in 0..self.get_data().len(){
-            return_data.push(self.get_data()[i].log10());
-        }
-
-        Tensor::from_data(&return_data, self.get_shape()).unwrap()
+        FloatFunctions::nlog(self)
     }
     /// Each element transformed to natural log of that element
     ///
@@ -41,13 +35,7 @@ impl Tensor<f32>{
     /// assert_eq!(a.get_data(), &vec!{0.0, 1.0, 2.0})
     /// ```
     pub fn nlog_mut(&mut self){
-        let mut return_data = Vec::with_capacity(self.get_data().len());
-
-        for i in 0..self.get_data().len(){
-            return_data.push(self.get_data()[i].log10());
-        }
-
-        self.set_data(&return_data);
+        FloatFunctions::nlog_mut(self)
     }
     /// Each element transformed to log of x of that element
     ///
@@ -64,13 +52,7 @@ impl Tensor<f32>{
     /// assert_eq!(b.get_data(), &vec!{0.0, 1.0, 2.0})
     /// ```
     pub fn log(&self, x: f32) -> Tensor<f32>{
-        let mut return_data = Vec::with_capacity(self.get_data().len());
-
-        for i in 0..self.get_data().len(){
-            return_data.push(self.get_data()[i].log(x));
-        }
-
-        Tensor::from_data(&return_data, self.get_shape()).unwrap()
+        FloatFunctions::log(self, x)
     }
     /// Each element transformed to log of x of that element
     ///
@@ -89,6 +71,51 @@ impl Tensor<f32>{
     /// assert_eq!(a.get_data(), &vec!{0.0, 1.0, 2.0})
     /// ```
     pub fn log_mut(&mut self, x: f32){
+        FloatFunctions::log_mut(self, x)
+    }
+}
+
+/// Logarithms of tensors of every Float type
+pub trait FloatFunctions<T: Float>{
+    /// Each element transformed to natural log of that element
+    fn nlog(&self) -> Tensor<T>;
+    /// Each element transformed to natural log of that element
+    fn nlog_mut(&mut self);
+    /// Each element transformed to log of x of that element
+    fn log(&self, x: T) -> Tensor<T>;
+    /// Each element transformed to log of x of that element
+    fn log_mut(&mut self, x: T);
+}
+
+impl<T: Float> FloatFunctions<T> for Tensor<T>{
+    fn nlog(&self) -> Tensor<T>{
+        let mut return_data = Vec::with_capacity(self.get_data().len());
+
+        for i in 0..self.get_data().len(){
+            return_data.push(self.get_data()[i].log10());
+        }
+
+        Tensor::from_data(&return_data, self.get_shape()).unwrap()
+    }
+    fn nlog_mut(&mut self){
+        let mut return_data = Vec::with_capacity(self.get_data().len());
+
+        for i in 0..self.get_data().len(){
+            return_data.push(self.get_data()[i].log10());
+        }
+
+        self.set_data(&return_data);
+    }
+    fn log(&self, x: T) -> Tensor<T>{
+        let mut return_data = Vec::with_capacity(self.get_data().len());
+
+        for i in 0..self.get_data().len(){
+            return_data.push(self.get_data()[i].log(x));
+        }
+
+        Tensor::from_data(&return_data, self.get_shape()).unwrap()
+    }
+    fn log_mut(&mut self, x: T){
         let mut return_data = Vec::with_capacity(self.get_data().len());
 
         for i in 0..self.get_data().len(){
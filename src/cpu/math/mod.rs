@@ -3,3 +3,4 @@ pub mod division;
 pub mod multiplication;
 pub mod subtraction;
 pub mod functions;
+pub mod reduction;
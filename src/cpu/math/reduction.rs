@@ -0,0 +1,282 @@
+use crate::{cpu::float::Float, tensor::*};
+
+/// Reduce every lane of tensor along axis into one value, output keeps axis with size 1
+fn reduce_axis<T: Float>(tensor: &Tensor<T>, axis: u32, reduce: impl Fn(&[T]) -> T) -> Option<Tensor<T>>{
+    let shape = tensor.get_shape();
+    if axis as usize >= shape.len(){
+        return None;
+    }
+
+    let axis_len = shape[axis as usize] as usize;
+    let inner: usize = shape[axis as usize + 1..].iter().product::<u32>() as usize;
+    let outer: usize = shape[..axis as usize].iter().product::<u32>() as usize;
+
+    let mut return_data: Vec<T> = Vec::with_capacity(outer * inner);
+    let mut lane: Vec<T> = Vec::with_capacity(axis_len);
+
+    for o in 0..outer{
+        for i in 0..inner{
+            lane.clear();
+            for a in 0..axis_len{
+                lane.push(tensor.get_data()[(o * axis_len + a) * inner + i]);
+            }
+            return_data.push(reduce(&lane));
+        }
+    }
+
+    let mut return_shape = shape.clone();
+    return_shape[axis as usize] = 1;
+
+    Tensor::from_data(&return_data, &return_shape)
+}
+
+fn mean_of<T: Float>(data: &[T]) -> T{
+    data.iter().copied().sum::<T>() / T::from_f64(data.len() as f64)
+}
+
+fn max_of<T: Float>(data: &[T]) -> T{
+    data.iter().fold(T::NEG_INFINITY, |max, value| max.max(*value))
+}
+
+fn min_of<T: Float>(data: &[T]) -> T{
+    data.iter().fold(T::INFINITY, |min, value| min.min(*value))
+}
+
+/// Index of first biggest value
+fn argmax_of<T: Float>(data: &[T]) -> u32{
+    let mut index = 0;
+    for i in 1..data.len(){
+        if data[i] > data[index]{
+            index = i;
+        }
+    }
+
+    index as u32
+}
+
+/// Population variance
+fn variance_of<T: Float>(data: &[T]) -> T{
+    let mean = mean_of(data);
+    data.iter().map(|value| (*value - mean) * (*value - mean)).sum::<T>() / T::from_f64(data.len() as f64)
+}
+
+impl Tensor<f32>{
+    /// Returns sums of tensor along axis, axis is kept with size 1
+    /// None if axis is out of tensor rank
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]).unwrap();
+    ///
+    /// let b: Tensor<f32> = a.sum_axis(0).unwrap();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{5.0, 7.0, 9.0});
+    /// assert_eq!(b.get_shape(), &vec!{1, 3});
+    /// ```
+    pub fn sum_axis(&self, axis: u32) -> Option<Tensor<f32>>{
+        FloatReduction::sum_axis(self, axis)
+    }
+    /// Returns mean of all elements in tensor
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0, 6.0], &[2, 2]).unwrap();
+    ///
+    /// assert_eq!(a.mean(), 3.0);
+    /// ```
+    pub fn mean(&self) -> f32{
+        FloatReduction::mean(self)
+    }
+    /// Returns means of tensor along axis, axis is kept with size 1
+    /// None if axis is out of tensor rank
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]).unwrap();
+    ///
+    /// let b: Tensor<f32> = a.mean_axis(1).unwrap();
+    ///
+    /// assert_eq!(b.get_data(), &vec!{2.0, 5.0});
+    /// assert_eq!(b.get_shape(), &vec!{2, 1});
+    /// ```
+    pub fn mean_axis(&self, axis: u32) -> Option<Tensor<f32>>{
+        FloatReduction::mean_axis(self, axis)
+    }
+    /// Returns biggest element in tensor
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 7.0, -3.0, 6.0], &[2, 2]).unwrap();
+    ///
+    /// assert_eq!(a.max(), 7.0);
+    /// ```
+    pub fn max(&self) -> f32{
+        FloatReduction::max(self)
+    }
+    /// Returns biggest elements of tensor along axis, axis is kept with size 1
+    /// None if axis is out of tensor rank
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 7.0, -3.0, 6.0], &[2, 2]).unwrap();
+    ///
+    /// assert_eq!(a.max_axis(0).unwrap().get_data(), &vec!{1.0, 7.0});
+    /// ```
+    pub fn max_axis(&self, axis: u32) -> Option<Tensor<f32>>{
+        FloatReduction::max_axis(self, axis)
+    }
+    /// Returns smallest element in tensor
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 7.0, -3.0, 6.0], &[2, 2]).unwrap();
+    ///
+    /// assert_eq!(a.min(), -3.0);
+    /// ```
+    pub fn min(&self) -> f32{
+        FloatReduction::min(self)
+    }
+    /// Returns smallest elements of tensor along axis, axis is kept with size 1
+    /// None if axis is out of tensor rank
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 7.0, -3.0, 6.0], &[2, 2]).unwrap();
+    ///
+    /// assert_eq!(a.min_axis(1).unwrap().get_data(), &vec!{1.0, -3.0});
+    /// ```
+    pub fn min_axis(&self, axis: u32) -> Option<Tensor<f32>>{
+        FloatReduction::min_axis(self, axis)
+    }
+    /// Returns flat index of first biggest element in tensor
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 7.0, -3.0, 7.0], &[2, 2]).unwrap();
+    ///
+    /// assert_eq!(a.argmax(), 1);
+    /// ```
+    pub fn argmax(&self) -> u32{
+        FloatReduction::argmax(self)
+    }
+    /// Returns indexes of first biggest elements along axis, as f32, axis is kept with size 1
+    /// None if axis is out of tensor rank
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 7.0, -3.0, 6.0], &[2, 2]).unwrap();
+    ///
+    /// assert_eq!(a.argmax_axis(0).unwrap().get_data(), &vec!{0.0, 0.0});
+    /// assert_eq!(a.argmax_axis(1).unwrap().get_data(), &vec!{1.0, 1.0});
+    /// ```
+    pub fn argmax_axis(&self, axis: u32) -> Option<Tensor<f32>>{
+        FloatReduction::argmax_axis(self, axis)
+    }
+    /// Returns population variance of all elements in tensor
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 3.0, 1.0, 3.0], &[2, 2]).unwrap();
+    ///
+    /// assert_eq!(a.variance(), 1.0);
+    /// ```
+    pub fn variance(&self) -> f32{
+        FloatReduction::variance(self)
+    }
+    /// Returns population variances of tensor along axis, axis is kept with size 1
+    /// None if axis is out of tensor rank
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 3.0, 2.0, 2.0], &[2, 2]).unwrap();
+    ///
+    /// assert_eq!(a.variance_axis(1).unwrap().get_data(), &vec!{1.0, 0.0});
+    /// ```
+    pub fn variance_axis(&self, axis: u32) -> Option<Tensor<f32>>{
+        FloatReduction::variance_axis(self, axis)
+    }
+}
+
+/// Reductions of tensors of every Float type
+pub trait FloatReduction<T: Float>{
+    /// Returns sums of tensor along axis, axis is kept with size 1
+    fn sum_axis(&self, axis: u32) -> Option<Tensor<T>>;
+    /// Returns mean of all elements in tensor
+    fn mean(&self) -> T;
+    /// Returns means of tensor along axis, axis is kept with size 1
+    fn mean_axis(&self, axis: u32) -> Option<Tensor<T>>;
+    /// Returns biggest element in tensor
+    fn max(&self) -> T;
+    /// Returns biggest elements of tensor along axis, axis is kept with size 1
+    fn max_axis(&self, axis: u32) -> Option<Tensor<T>>;
+    /// Returns smallest element in tensor
+    fn min(&self) -> T;
+    /// Returns smallest elements of tensor along axis, axis is kept with size 1
+    fn min_axis(&self, axis: u32) -> Option<Tensor<T>>;
+    /// Returns flat index of first biggest element in tensor
+    fn argmax(&self) -> u32;
+    /// Returns indexes of first biggest elements along axis, as f32, axis is kept with size 1
+    fn argmax_axis(&self, axis: u32) -> Option<Tensor<T>>;
+    /// Returns population variance of all elements in tensor
+    fn variance(&self) -> T;
+    /// Returns population variances of tensor along axis, axis is kept with size 1
+    fn variance_axis(&self, axis: u32) -> Option<Tensor<T>>;
+}
+
+impl<T: Float> FloatReduction<T> for Tensor<T>{
+    fn sum_axis(&self, axis: u32) -> Option<Tensor<T>>{
+        reduce_axis(self, axis, |lane| lane.iter().copied().sum())
+    }
+    fn mean(&self) -> T{
+        mean_of(self.get_data())
+    }
+    fn mean_axis(&self, axis: u32) -> Option<Tensor<T>>{
+        reduce_axis(self, axis, mean_of)
+    }
+    fn max(&self) -> T{
+        max_of(self.get_data())
+    }
+    fn max_axis(&self, axis: u32) -> Option<Tensor<T>>{
+        reduce_axis(self, axis, max_of)
+    }
+    fn min(&self) -> T{
+        min_of(self.get_data())
+    }
+    fn min_axis(&self, axis: u32) -> Option<Tensor<T>>{
+        reduce_axis(self, axis, min_of)
+    }
+    fn argmax(&self) -> u32{
+        argmax_of(self.get_data())
+    }
+    fn argmax_axis(&self, axis: u32) -> Option<Tensor<T>>{
+        reduce_axis(self, axis, |lane| T::from_f64(argmax_of(lane) as f64))
+    }
+    fn variance(&self) -> T{
+        variance_of(self.get_data())
+    }
+    fn variance_axis(&self, axis: u32) -> Option<Tensor<T>>{
+        reduce_axis(self, axis, variance_of)
+    }
+}
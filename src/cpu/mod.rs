@@ -2,3 +2,6 @@ pub mod math;
 pub mod subtypes;
 pub mod machine_learning;
 pub mod broadcasting;
+pub mod float;
+#[cfg(feature = "half")]
+pub mod half;
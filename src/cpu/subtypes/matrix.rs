@@ -1,4 +1,4 @@
-use crate::tensor::*;
+use crate::{cpu::{float::Float, subtypes::vector::FloatVector}, tensor::*};
 
 impl<T: Default + Clone> Tensor<T>{
     /// Get matrix on position
@@ -161,6 +161,39 @@ impl<T: Default + Clone> Tensor<T>{
 
         Some(Tensor::from_data(&return_data, &new_sizes).unwrap())
     }
+
+    /// Returns tensor with every matrix of [batch, rows, cols] tensor transposed
+    /// or None if tensor is not 3 dimensional
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let data: Vec<f32> = vec!{1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0};
+    /// let tensor: Tensor<f32> = Tensor::from_data(&data, &[2, 2, 2]).unwrap();
+    ///
+    /// let result = tensor.batch_matrix_transpose().unwrap();
+    ///
+    /// assert_eq!(result.get_data(), &vec!{1.0, 3.0, 2.0, 4.0, 5.0, 7.0, 6.0, 8.0});
+    /// ```
+    pub fn batch_matrix_transpose(&self) -> Option<Tensor<T>>{
+        if self.get_shape().len() != 3{
+            return None;
+        }
+
+        let (batch, rows, cols) = (self.get_shape()[0] as usize, self.get_shape()[1] as usize, self.get_shape()[2] as usize);
+        let mut return_data: Vec<T> = Vec::with_capacity(self.get_data().len());
+
+        for b in 0..batch{
+            for col in 0..cols{
+                for row in 0..rows{
+                    return_data.push(self.get_data()[b * rows * cols + row * cols + col].clone());
+                }
+            }
+        }
+
+        Tensor::from_data(&return_data, &[batch as u32, cols as u32, rows as u32])
+    }
 }
 
 impl<T> Tensor<T>
@@ -233,6 +266,38 @@ impl Tensor<f32>{
     /// assert_eq!(result.get_shape(), &expected_sizes);
     /// ```
     pub fn matrix_mul(&self, tens2: &Tensor<f32>) -> Option<Tensor<f32>>{
+        FloatMatrix::matrix_mul(self, tens2)
+    }
+    /// Returns matrix multiplication of every pair of matrices of [batch, m, k] and [batch, k, n] tensors
+    /// or None if shapes do not match
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let a: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0, 4.0], &[2, 1, 2]).unwrap();
+    /// let b: Tensor<f32> = Tensor::from_data(&[1.0, 1.0, 2.0, 2.0], &[2, 2, 1]).unwrap();
+    ///
+    /// let result = a.batch_matrix_mul(&b).unwrap();
+    ///
+    /// assert_eq!(result.get_data(), &vec!{3.0, 14.0});
+    /// assert_eq!(result.get_shape(), &vec!{2, 1, 1});
+    /// ```
+    pub fn batch_matrix_mul(&self, tens2: &Tensor<f32>) -> Option<Tensor<f32>>{
+        FloatMatrix::batch_matrix_mul(self, tens2)
+    }
+}
+
+/// Matrix multiplication of tensors of every Float type
+pub trait FloatMatrix<T: Float>{
+    /// Persorms matrix multiplication on matrix with another matrix
+    fn matrix_mul(&self, tens2: &Tensor<T>) -> Option<Tensor<T>>;
+    /// Returns matrix multiplication of every pair of matrices of [batch, m, k] and [batch, k, n] tensors
+    fn batch_matrix_mul(&self, tens2: &Tensor<T>) -> Option<Tensor<T>>;
+}
+
+impl<T: Float> FloatMatrix<T> for Tensor<T>{
+    fn matrix_mul(&self, tens2: &Tensor<T>) -> Option<Tensor<T>>{
         if self.get_shape().len() != 2{
             return None;
         }
@@ -243,7 +308,7 @@ impl Tensor<f32>{
             return None;
         }
 
-        let mut return_data: Vec<f32> = Vec::with_capacity((self.get_shape()[0] * tens2.get_shape()[1]) as usize);
+        let mut return_data: Vec<T> = Vec::with_capacity((self.get_shape()[0] * tens2.get_shape()[1]) as usize);
         for i in 0..self.get_shape()[0]{
             for j in 0..tens2.get_shape()[1]{
 
@@ -260,6 +325,34 @@ impl Tensor<f32>{
         
         Tensor::from_data(&return_data, &sizes)
     }
+    fn batch_matrix_mul(&self, tens2: &Tensor<T>) -> Option<Tensor<T>>{
+        if self.get_shape().len() != 3 || tens2.get_shape().len() != 3{
+            return None;
+        }
+        if self.get_shape()[0] != tens2.get_shape()[0] || self.get_shape()[2] != tens2.get_shape()[1]{
+            return None;
+        }
+
+        let batch = self.get_shape()[0] as usize;
+        let (m, k, n) = (self.get_shape()[1] as usize, self.get_shape()[2] as usize, tens2.get_shape()[2] as usize);
+
+        let mut return_data: Vec<T> = vec![T::ZERO; batch * m * n];
+        for b in 0..batch{
+            let lhs = &self.get_data()[b * m * k..(b + 1) * m * k];
+            let rhs = &tens2.get_data()[b * k * n..(b + 1) * k * n];
+
+            for i in 0..m{
+                for l in 0..k{
+                    let value = lhs[i * k + l];
+                    for j in 0..n{
+                        return_data[b * m * n + i * n + j] += value * rhs[l * n + j];
+                    }
+                }
+            }
+        }
+
+        Tensor::from_data(&return_data, &[batch as u32, m as u32, n as u32])
+    }
 }
 
 impl<T> Tensor<T>
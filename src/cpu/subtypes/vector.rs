@@ -1,4 +1,4 @@
-use crate::tensor::*;
+use crate::{cpu::float::Float, tensor::*};
 
 impl<T: Default + Clone> Tensor<T>{
     /// Get vector from Tensor on position
@@ -72,6 +72,18 @@ impl Tensor<f32>{
     /// assert_eq!(result, expected);
     /// ```
     pub fn dot_product(&self, tens2: &Tensor<f32>) -> Option<f32>{
+        FloatVector::dot_product(self, tens2)
+    }
+}
+
+/// Vector functions of tensors of every Float type
+pub trait FloatVector<T: Float>{
+    /// Get dot product from tensors if tensors have one dimenstion
+    fn dot_product(&self, tens2: &Tensor<T>) -> Option<T>;
+}
+
+impl<T: Float> FloatVector<T> for Tensor<T>{
+    fn dot_product(&self, tens2: &Tensor<T>) -> Option<T>{
         if self.get_shape().len() != 1{
             return None;
         }
@@ -79,9 +91,9 @@ impl Tensor<f32>{
             return None;
         }
         
-        let mut dot: f32 = 0.0;
+        let mut dot: T = T::ZERO;
         for i in 0..self.get_shape()[0] as u32{
-            dot += self.value(&[i]).unwrap() * tens2.value(&[i]).unwrap();
+            dot += *self.value(&[i]).unwrap() * *tens2.value(&[i]).unwrap();
         }
 
         Some(dot)
@@ -3,12 +3,14 @@
 pub use crate::{
     tensor::*,
     cpu::{
+        float::*,
         math::{
             functions::*,
             addition::*,
             division::*,
             multiplication::*,
             subtraction::*,
+            reduction::*,
         },
         subtypes::{
             matrix::*,
@@ -18,6 +20,13 @@ pub use crate::{
         machine_learning::{
             relu::*,
             sigmoid::*,
+            normalization::*,
+            regularization::*,
+            tanh::*,
+            recurrent::*,
+            softmax::*,
+            embedding::*,
+            attention::*,
         },
         broadcasting::{
             helpers::*,
@@ -26,13 +35,28 @@ pub use crate::{
     },
     wgpu::runners::{
         sample::*,
+        precision::*,
         gpu_data::*,
+        gpu_config::*,
+        pipeline_cache::*,
+        readback::*,
+        profiling::*,
+        gpu_context::*,
         gpu_buffers::*,
+        gpu_tensor::*,
+        gpu_batch::*,
+        gpu_graph::*,
+        elementwise::*,
         shaders::*,
+        custom_op::*,
         helpers::*,
+        gpu_error::*,
+        backend::*,
+        conformance::*,
         gpu_runner::{
             *,
             runner_ops::{
+                custom::*,
                 subtypes::*,
                 broadcasting::*,
                 math::{
@@ -42,15 +66,22 @@ pub use crate::{
                     functions::*,
                     multiplication::*,
                     subtraction::*,
+                    reduction::*,
                 },
                 machine_learning::{
                     forward_prop::*,
                     backward_bias::*,
                     backward_grad::*,
                     backward_weight::*,
+                    normalization::*,
+                    recurrent::*,
                 },
             },
         },
     },
 };
 
+#[cfg(feature = "half")]
+pub use crate::cpu::half::*;
+#[cfg(feature = "half")]
+pub use ::half::{bf16, f16};
@@ -0,0 +1,373 @@
+use std::borrow::Cow;
+
+use crate::{cpu::{float::Float, machine_learning::{descent, linear}}, prelude::{FloatMatrix, FloatRelu, GRUCell, GpuData, GpuError, GpuOperations, GpuPrecision, LSTMCell, RNNCell}, tensor::Tensor};
+
+const BATCH_NORM: u32 = 0;
+const LAYER_NORM: u32 = 1;
+const GROUP_NORM: u32 = 2;
+
+/// Backend, that runs operations of GpuRunner
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend{
+    /// Gpu if adapter is found, cpu otherwise
+    #[default]
+    Auto,
+    /// Always gpu, error if there is no adapter
+    Gpu,
+    /// Cpu module, with the same output shapes and batching as gpu
+    Cpu,
+}
+
+fn invalid(operation: &GpuOperations) -> GpuError{
+    GpuError::InvalidShapes(format!("inputs do not fit {:?}", operation))
+}
+
+/// Returns shapes of inputs of one sample, split the same way shader of operation reads them
+fn input_shapes(operation: &GpuOperations, flat_shapes: &[u32]) -> Vec<Vec<u32>>{
+    match operation{
+        GpuOperations::Add | GpuOperations::Sub | GpuOperations::Mul | GpuOperations::Div |
+        GpuOperations::Log | GpuOperations::NLog |
+        GpuOperations::ReLU | GpuOperations::ReLUDer | GpuOperations::Sigmoid | GpuOperations::SigmoidDer |
+        GpuOperations::MatrixTranspose | GpuOperations::MatrixRowSum | GpuOperations::MatrixRowProd |
+        GpuOperations::MatrixColSum | GpuOperations::MatrixColProd |
+        GpuOperations::ReduceSum | GpuOperations::ReduceMean | GpuOperations::ReduceMax |
+        GpuOperations::ReduceMin | GpuOperations::ReduceArgmax | GpuOperations::ReduceVariance => vec!{flat_shapes.to_vec()},
+
+        GpuOperations::TensAdd | GpuOperations::TensSub | GpuOperations::TensMul | GpuOperations::TensDiv |
+        GpuOperations::BroadcastAdd | GpuOperations::BroadcastSub | GpuOperations::BroadcastMul | GpuOperations::BroadcastDiv |
+        GpuOperations::BackwardRelu | GpuOperations::BackwardSigmoid => {
+            let half = flat_shapes.len() / 2;
+            vec!{flat_shapes[..half].to_vec(), flat_shapes[half..].to_vec()}
+        },
+
+        _ => flat_shapes.chunks(2).map(|shape| shape.to_vec()).collect(),
+    }
+}
+
+/// Split inputs of one sample into tensors with shapes
+fn split_tensors<T: Float>(operation: &GpuOperations, sample: &[T], shapes: &[Vec<u32>]) -> Result<Vec<Tensor<T>>, GpuError>{
+    let mut offset = 0;
+    shapes.iter().map(|shape| {
+        let len = shape.iter().product::<u32>() as usize;
+        let tensor = sample.get(offset..offset + len).and_then(|data| Tensor::from_data(data, shape)).ok_or_else(|| invalid(operation));
+        offset += len;
+        tensor
+    }).collect()
+}
+
+/// Split flat inputs of GpuData into tensors of every sample
+/// Samples of ragged batch are split by their own shapes, and shared inputs are inserted into
+/// every sample
+/// Flat and shared inputs are passed in, so samples of f64 data are split the same way
+fn split_samples<T: Float>(operation: &GpuOperations, data: &GpuData, flat_inputs: &[T], shared_inputs: &[T]) -> Result<Vec<Vec<Tensor<T>>>, GpuError>{
+    if data.ragged || !data.shared.is_empty(){
+        let shared = data.shared.iter()
+            .map(|shared| {
+                let len = shared.shape.iter().product::<u32>() as usize;
+                Tensor::from_data(&shared_inputs[shared.offset..shared.offset + len], &shared.shape).ok_or_else(|| invalid(operation))
+            })
+            .collect::<Result<Vec<Tensor<T>>, GpuError>>()?;
+
+        return (0..data.samples_count as usize).map(|sample| {
+            let shapes: Vec<Vec<u32>> = data.sample_input_shapes(sample).into_iter()
+                .enumerate()
+                .filter(|(input, _)| !data.is_shared(*input))
+                .map(|(_, shape)| shape)
+                .collect();
+
+            let mut inputs = split_tensors(operation, &flat_inputs[data.sample_range(sample)], &shapes)?;
+            for (shared, tensor) in data.shared.iter().zip(shared.iter()){
+                inputs.insert(shared.input, tensor.clone());
+            }
+
+            Ok(inputs)
+        }).collect();
+    }
+
+    let shapes = input_shapes(operation, &data.flat_shapes);
+    let sample_len: usize = shapes.iter().map(|shape| shape.iter().product::<u32>() as usize).sum();
+
+    if sample_len == 0 || flat_inputs.len() != sample_len * data.samples_count as usize{
+        return Err(invalid(operation));
+    }
+
+    flat_inputs.chunks(sample_len).map(|sample| split_tensors(operation, sample, &shapes)).collect()
+}
+
+/// Returns param with shape of trailing dims of input, as layer norm of gpu accepts params with
+/// leading dims of 1
+fn trailing_param(input: &Tensor<f32>, param: &Tensor<f32>) -> Option<Tensor<f32>>{
+    let shape = input.get_shape();
+    let mut start = 0;
+    while start < shape.len() && shape[start..].iter().product::<u32>() as usize != param.count_data(){
+        start += 1;
+    }
+
+    Tensor::from_data(param.get_data(), &shape[start..])
+}
+
+/// Returns (eps, groups, mode) from params in norm shader layout
+fn norm_params(params: &[f32]) -> (f32, u32, u32){
+    (
+        *params.first().unwrap_or(&1e-5),
+        (*params.get(1).unwrap_or(&1.0) as u32).max(1),
+        *params.get(3).unwrap_or(&0.0) as u32,
+    )
+}
+
+/// Returns gradient of input, gamma and beta of normalization selected by mode
+fn norm_backward(input: &Tensor<f32>, gamma: &Tensor<f32>, grad_output: &Tensor<f32>, params: &[f32]) -> Option<(Tensor<f32>, Tensor<f32>, Tensor<f32>)>{
+    let (eps, groups, mode) = norm_params(params);
+
+    match mode{
+        BATCH_NORM => input.batch_norm_backward(gamma, grad_output, eps),
+        LAYER_NORM => input.layer_norm_backward(&trailing_param(input, gamma)?, grad_output, eps),
+        GROUP_NORM => input.group_norm_backward(groups, gamma, grad_output, eps),
+        _ => None,
+    }
+}
+
+/// Run reduction of input into output shape
+/// Output with other rank than input is reduction of all elements, otherwise input is reduced
+/// along axis, where output has size 1
+fn run_reduction(operation: &GpuOperations, input: &Tensor<f32>, output_shape: &[u32]) -> Option<Tensor<f32>>{
+    let shape = input.get_shape();
+
+    if output_shape.len() != shape.len(){
+        let value = match operation{
+            GpuOperations::ReduceSum => input.sum(),
+            GpuOperations::ReduceMean => input.mean(),
+            GpuOperations::ReduceMax => input.max(),
+            GpuOperations::ReduceMin => input.min(),
+            GpuOperations::ReduceArgmax => input.argmax() as f32,
+            GpuOperations::ReduceVariance => input.variance(),
+            _ => return None,
+        };
+        return Tensor::from_data(&[value], &[1]);
+    }
+
+    let axis = (0..shape.len()).find(|dim| output_shape[*dim] == 1 && shape[*dim] != 1)
+        .or_else(|| output_shape.iter().position(|size| *size == 1))? as u32;
+
+    match operation{
+        GpuOperations::ReduceSum => input.sum_axis(axis),
+        GpuOperations::ReduceMean => input.mean_axis(axis),
+        GpuOperations::ReduceMax => input.max_axis(axis),
+        GpuOperations::ReduceMin => input.min_axis(axis),
+        GpuOperations::ReduceArgmax => input.argmax_axis(axis),
+        GpuOperations::ReduceVariance => input.variance_axis(axis),
+        _ => None,
+    }
+}
+
+/// Run operation on inputs of one sample
+fn run_sample(operation: &GpuOperations, inputs: &[Tensor<f32>], params: &[f32], output_shape: &[u32]) -> Option<Tensor<f32>>{
+    let param = *params.first().unwrap_or(&0.0);
+
+    match operation{
+        GpuOperations::Add => Some(inputs[0].add(param)),
+        GpuOperations::Sub => Some(inputs[0].sub(param)),
+        GpuOperations::Mul => Some(inputs[0].mul(param)),
+        GpuOperations::Div => Some(inputs[0].div(param)),
+        GpuOperations::Log => Some(inputs[0].log(param)),
+        GpuOperations::NLog => Some(inputs[0].nlog()),
+        GpuOperations::TensAdd => inputs[0].tens_add(&inputs[1]),
+        GpuOperations::TensSub => inputs[0].tens_sub(&inputs[1]),
+        GpuOperations::TensMul => inputs[0].tens_mul(&inputs[1]),
+        GpuOperations::TensDiv => inputs[0].tens_div(&inputs[1]),
+
+        GpuOperations::BroadcastAdd => inputs[0].tens_broadcast_add(&inputs[1]),
+        GpuOperations::BroadcastSub => inputs[0].tens_broadcast_sub(&inputs[1]),
+        GpuOperations::BroadcastMul => inputs[0].tens_broadcast_mul(&inputs[1]),
+        GpuOperations::BroadcastDiv => inputs[0].tens_broadcast_div(&inputs[1]),
+
+        GpuOperations::Matmul => inputs[0].matrix_mul(&inputs[1]),
+        GpuOperations::MatrixTranspose => inputs[0].matrix_transpose(),
+        GpuOperations::MatrixRowSum => inputs[0].matrix_row_sum(),
+        GpuOperations::MatrixRowProd => inputs[0].matrix_row_prod(),
+        GpuOperations::MatrixColSum => inputs[0].matrix_col_sum(),
+        GpuOperations::MatrixColProd => inputs[0].matrix_col_prod(),
+
+        GpuOperations::ReLU => Some(inputs[0].relu()),
+        GpuOperations::ReLUDer => Some(inputs[0].relu_der()),
+        GpuOperations::Sigmoid => Some(inputs[0].sigmoid()),
+        GpuOperations::SigmoidDer => Some(inputs[0].sigmoid_der()),
+
+        GpuOperations::ForwardNoActiv => linear(&inputs[0], &inputs[1], &inputs[2]),
+        GpuOperations::ForwardRelu => Some(linear(&inputs[0], &inputs[1], &inputs[2])?.relu()),
+        GpuOperations::ForwardSigmoid => Some(linear(&inputs[0], &inputs[1], &inputs[2])?.sigmoid()),
+        GpuOperations::BackwardGradient => inputs[0].matrix_transpose()?.matrix_mul(&inputs[1]),
+        GpuOperations::BackwardRelu => inputs[0].relu_der().tens_broadcast_mul(&inputs[1]),
+        GpuOperations::BackwardSigmoid => inputs[0].sigmoid_der().tens_broadcast_mul(&inputs[1]),
+
+        GpuOperations::NormForward => {
+            let (eps, groups, mode) = norm_params(params);
+            match mode{
+                BATCH_NORM => inputs[0].batch_norm(&inputs[1], &inputs[2], eps),
+                LAYER_NORM => inputs[0].layer_norm(&trailing_param(&inputs[0], &inputs[1])?, &trailing_param(&inputs[0], &inputs[2])?, eps),
+                GROUP_NORM => inputs[0].group_norm(groups, &inputs[1], &inputs[2], eps),
+                _ => None,
+            }
+        },
+        GpuOperations::BatchNormEval => inputs[0].batch_norm_eval(&inputs[1], &inputs[2], &inputs[3], &inputs[4], param),
+        GpuOperations::NormBackward => Some(norm_backward(&inputs[0], &inputs[1], &inputs[2], params)?.0),
+
+        GpuOperations::RnnCell => {
+            let cell = RNNCell{weight_ih: inputs[0].clone(), weight_hh: inputs[1].clone(), bias_ih: inputs[2].clone(), bias_hh: inputs[3].clone()};
+            Some(cell.forward(&inputs[4], &inputs[5])?.0)
+        },
+        GpuOperations::GruCell => {
+            let cell = GRUCell{weight_ih: inputs[0].clone(), weight_hh: inputs[1].clone(), bias_ih: inputs[2].clone(), bias_hh: inputs[3].clone()};
+            Some(cell.forward(&inputs[4], &inputs[5])?.0)
+        },
+        GpuOperations::LstmCell => {
+            let cell = LSTMCell{weight_ih: inputs[0].clone(), weight_hh: inputs[1].clone(), bias_ih: inputs[2].clone(), bias_hh: inputs[3].clone()};
+            let (hidden, cell_state, _) = cell.forward(&inputs[4], &inputs[5], inputs.get(6)?)?;
+            hidden.append(&cell_state)
+        },
+
+        GpuOperations::ReduceSum | GpuOperations::ReduceMean | GpuOperations::ReduceMax |
+        GpuOperations::ReduceMin | GpuOperations::ReduceArgmax | GpuOperations::ReduceVariance => run_reduction(operation, &inputs[0], output_shape),
+
+        _ => None,
+    }
+}
+
+/// Run operation on f64 inputs of one sample
+/// Only operations with f64 variants in GpuRunner are implemented
+fn run_sample_f64(operation: &GpuOperations, inputs: &[Tensor<f64>]) -> Option<Tensor<f64>>{
+    match operation{
+        GpuOperations::TensAdd => inputs[0].tens_add(&inputs[1]),
+        GpuOperations::TensSub => inputs[0].tens_sub(&inputs[1]),
+        GpuOperations::TensMul => inputs[0].tens_mul(&inputs[1]),
+        GpuOperations::TensDiv => inputs[0].tens_div(&inputs[1]),
+
+        GpuOperations::BroadcastAdd => inputs[0].tens_broadcast_add(&inputs[1]),
+        GpuOperations::BroadcastSub => inputs[0].tens_broadcast_sub(&inputs[1]),
+        GpuOperations::BroadcastMul => inputs[0].tens_broadcast_mul(&inputs[1]),
+        GpuOperations::BroadcastDiv => inputs[0].tens_broadcast_div(&inputs[1]),
+
+        GpuOperations::Matmul => inputs[0].matrix_mul(&inputs[1]),
+
+        GpuOperations::ForwardNoActiv => inputs[0].matrix_mul(&inputs[1])?.tens_broadcast_add(&inputs[2]),
+        GpuOperations::ForwardRelu => Some(inputs[0].matrix_mul(&inputs[1])?.tens_broadcast_add(&inputs[2])?.relu()),
+
+        _ => None,
+    }
+}
+
+/// Run operation, that merges all samples into one output
+/// Parameters are taken from first sample and gradients are averaged over samples, the same way
+/// as in shaders
+fn run_merged(operation: &GpuOperations, samples: &[Vec<Tensor<f32>>], params: &[f32]) -> Option<Tensor<f32>>{
+    let first = samples.first()?;
+    let learning_rate = *params.first().unwrap_or(&0.0);
+
+    let mut grads: Vec<Tensor<f32>> = Vec::new();
+    for sample in samples{
+        let sample_grads = match operation{
+            GpuOperations::BackwardWeight => vec!{sample[1].matrix_mul(&sample[2].matrix_transpose()?)?},
+            GpuOperations::BackwardBias => vec!{sample[1].matrix_col_sum()?.div(sample[2].get_shape()[0] as f32)},
+            GpuOperations::NormBackwardParams => {
+                let (_, grad_gamma, grad_beta) = norm_backward(&sample[2], &sample[0], &sample[3], params)?;
+                vec!{grad_gamma, grad_beta}
+            },
+            _ => return None,
+        };
+
+        if grads.is_empty(){
+            grads = sample_grads;
+        }
+        else{
+            for (grad, sample_grad) in grads.iter_mut().zip(sample_grads.iter()){
+                *grad = grad.tens_add(&Tensor::from_data(sample_grad.get_data(), grad.get_shape())?)?;
+            }
+        }
+    }
+
+    let grads: Vec<Tensor<f32>> = grads.iter().map(|grad| grad.div(samples.len() as f32)).collect();
+
+    match operation{
+        GpuOperations::BackwardWeight => {
+            let grad = first[0].l2_grad(&grads[0], *params.get(1).unwrap_or(&0.0))?;
+            let grad = first[0].l1_grad(&grad, *params.get(2).unwrap_or(&0.0))?;
+
+            Some(descent(&first[0], &grad, learning_rate))
+        },
+        GpuOperations::BackwardBias => Some(descent(&first[0], &Tensor::from_data(grads[0].get_data(), first[0].get_shape())?, learning_rate)),
+        GpuOperations::NormBackwardParams => {
+            let learning_rate = *params.get(2).unwrap_or(&0.0);
+
+            let gamma = descent(&first[0], &Tensor::from_data(grads[0].get_data(), first[0].get_shape())?, learning_rate);
+            let beta = descent(&first[1], &Tensor::from_data(grads[1].get_data(), first[1].get_shape())?, learning_rate);
+
+            gamma.append(&beta)
+        },
+        _ => None,
+    }
+}
+
+/// Run operation of GpuData on cpu
+/// Outputs have the same shapes and batching as outputs of gpu, one tensor per sample, or one
+/// tensor for operations with single output
+/// Error if inputs do not fit operation, or operation is custom
+pub fn run_cpu(operation: &GpuOperations, data: &GpuData) -> Result<Vec<Tensor<f32>>, GpuError>{
+    if data.samples_count == 0{
+        return Err(GpuError::NoData);
+    }
+    if let GpuOperations::Custom(custom) = operation{
+        return Err(GpuError::InvalidKernel(format!("custom operation {} has no cpu implementation", custom.get_name())));
+    }
+
+    let samples = split_samples(operation, data, &data.flat_inputs, &data.shared_inputs)?;
+
+    if data.single_output{
+        let output = run_merged(operation, &samples, &data.params).ok_or_else(|| invalid(operation))?;
+        return Ok(vec!{Tensor::from_data(output.get_data(), &data.output_shape).ok_or_else(|| invalid(operation))?});
+    }
+
+    samples.iter().enumerate()
+        .map(|(i, inputs)| {
+            let output_shape = match data.ragged{
+                true => &data.sample_output_shapes[i],
+                false => &data.output_shape,
+            };
+
+            let output = run_sample(operation, inputs, &data.params, output_shape).ok_or_else(|| invalid(operation))?;
+            Tensor::from_data(output.get_data(), output_shape).ok_or_else(|| invalid(operation))
+        })
+        .collect()
+}
+
+/// Run operation of GpuData on cpu in f64
+/// Samples appended with append_f64 are used without rounding, f32 samples are converted
+/// Error if inputs do not fit operation, or operation has no f64 variant
+pub fn run_cpu_f64(operation: &GpuOperations, data: &GpuData) -> Result<Vec<Tensor<f64>>, GpuError>{
+    if data.samples_count == 0{
+        return Err(GpuError::NoData);
+    }
+    if !operation.supports_precision(GpuPrecision::F64){
+        return Err(GpuError::InvalidConfig(format!("{:?} has no f64 implementation", operation)));
+    }
+
+    let flat_inputs: Cow<[f64]> = match data.is_f64(){
+        true => Cow::Borrowed(&data.flat_inputs_f64),
+        false => Cow::Owned(data.flat_inputs.iter().map(|value| *value as f64).collect()),
+    };
+    let shared_inputs: Cow<[f64]> = match data.shared_inputs_f64.is_empty(){
+        true => Cow::Owned(data.shared_inputs.iter().map(|value| *value as f64).collect()),
+        false => Cow::Borrowed(&data.shared_inputs_f64),
+    };
+
+    split_samples(operation, data, &flat_inputs, &shared_inputs)?.iter().enumerate()
+        .map(|(i, inputs)| {
+            let output_shape = match data.ragged{
+                true => &data.sample_output_shapes[i],
+                false => &data.output_shape,
+            };
+
+            let output = run_sample_f64(operation, inputs).ok_or_else(|| invalid(operation))?;
+            Tensor::from_data(output.get_data(), output_shape).ok_or_else(|| invalid(operation))
+        })
+        .collect()
+}
@@ -0,0 +1,467 @@
+use std::{fmt, sync::Arc};
+
+use rand::Rng;
+
+use crate::{prelude::{Backend, GpuContext, GpuError, GpuOperations, GpuRunner, MemoryMetric, Sample}, tensor::Tensor};
+
+/// Runner operations checked by conformance, with operation of their shader
+pub const CONFORMANCE_OPERATIONS: &[(&str, GpuOperations)] = &[
+    ("add", GpuOperations::Add),
+    ("sub", GpuOperations::Sub),
+    ("mul", GpuOperations::Mul),
+    ("div", GpuOperations::Div),
+    ("log", GpuOperations::Log),
+    ("nlog", GpuOperations::NLog),
+    ("tens_add", GpuOperations::TensAdd),
+    ("tens_sub", GpuOperations::TensSub),
+    ("tens_mul", GpuOperations::TensMul),
+    ("tens_div", GpuOperations::TensDiv),
+
+    ("tens_broadcast_add", GpuOperations::BroadcastAdd),
+    ("tens_broadcast_sub", GpuOperations::BroadcastSub),
+    ("tens_broadcast_mul", GpuOperations::BroadcastMul),
+    ("tens_broadcast_div", GpuOperations::BroadcastDiv),
+
+    ("matmul", GpuOperations::Matmul),
+    ("matrix_transpose", GpuOperations::MatrixTranspose),
+    ("matrix_row_sum", GpuOperations::MatrixRowSum),
+    ("matrix_row_prod", GpuOperations::MatrixRowProd),
+    ("matrix_col_sum", GpuOperations::MatrixColSum),
+    ("matrix_col_prod", GpuOperations::MatrixColProd),
+
+    ("reduce_sum", GpuOperations::ReduceSum),
+    ("reduce_sum_axis", GpuOperations::ReduceSum),
+    ("reduce_mean", GpuOperations::ReduceMean),
+    ("reduce_mean_axis", GpuOperations::ReduceMean),
+    ("reduce_max", GpuOperations::ReduceMax),
+    ("reduce_max_axis", GpuOperations::ReduceMax),
+    ("reduce_min", GpuOperations::ReduceMin),
+    ("reduce_min_axis", GpuOperations::ReduceMin),
+    ("reduce_argmax", GpuOperations::ReduceArgmax),
+    ("reduce_argmax_axis", GpuOperations::ReduceArgmax),
+    ("reduce_variance", GpuOperations::ReduceVariance),
+    ("reduce_variance_axis", GpuOperations::ReduceVariance),
+
+    ("relu", GpuOperations::ReLU),
+    ("relu_der", GpuOperations::ReLUDer),
+    ("sigmoid", GpuOperations::Sigmoid),
+    ("sigmoid_der", GpuOperations::SigmoidDer),
+
+    ("forward_no_activ", GpuOperations::ForwardNoActiv),
+    ("forward_relu", GpuOperations::ForwardRelu),
+    ("forward_sigmoid", GpuOperations::ForwardSigmoid),
+    ("backward_weight", GpuOperations::BackwardWeight),
+    ("backward_bias", GpuOperations::BackwardBias),
+    ("backward_grad", GpuOperations::BackwardGradient),
+    ("backward_relu", GpuOperations::BackwardRelu),
+    ("backward_sigmoid", GpuOperations::BackwardSigmoid),
+
+    ("batch_norm", GpuOperations::NormForward),
+    ("layer_norm", GpuOperations::NormForward),
+    ("group_norm", GpuOperations::NormForward),
+    ("batch_norm_eval", GpuOperations::BatchNormEval),
+    ("backward_batch_norm", GpuOperations::NormBackward),
+    ("backward_layer_norm", GpuOperations::NormBackward),
+    ("backward_group_norm", GpuOperations::NormBackward),
+    ("backward_batch_norm_params", GpuOperations::NormBackwardParams),
+    ("backward_layer_norm_params", GpuOperations::NormBackwardParams),
+    ("backward_group_norm_params", GpuOperations::NormBackwardParams),
+
+    ("rnn_cell", GpuOperations::RnnCell),
+    ("gru_cell", GpuOperations::GruCell),
+    ("lstm_cell", GpuOperations::LstmCell),
+];
+
+/// Settings of conformance run
+#[derive(Debug, Clone)]
+pub struct ConformanceConfig{
+    /// Random cases of every operation and sample count
+    pub cases: usize,
+    /// Sample counts appended to runner
+    pub samples: Vec<usize>,
+    /// Max size of every dimension of random inputs
+    pub max_dim: u32,
+    /// Samples in one chunk, counts smaller than samples split run into chunks
+    pub samples_per_chunk: Vec<usize>,
+    /// Element passes if |gpu - cpu| <= abs_tolerance + rel_tolerance * |cpu|
+    pub abs_tolerance: f32,
+    pub rel_tolerance: f32,
+}
+
+impl Default for ConformanceConfig{
+    fn default() -> Self{
+        Self{
+            cases: 3,
+            samples: vec!{1, 3},
+            max_dim: 16,
+            samples_per_chunk: vec!{1, usize::MAX},
+            abs_tolerance: 1e-4,
+            rel_tolerance: 1e-3,
+        }
+    }
+}
+
+/// Result of conformance of one runner operation
+#[derive(Debug, Clone)]
+pub struct ConformanceReport{
+    pub name: &'static str,
+    pub operation: GpuOperations,
+    /// Count of runs compared
+    pub runs: usize,
+    pub max_abs_error: f32,
+    pub max_rel_error: f32,
+    /// Count of elements out of tolerance
+    pub failed_elements: usize,
+    /// First run, where outputs had different count or shapes
+    pub mismatch: Option<String>,
+}
+
+impl ConformanceReport{
+    fn new(name: &'static str, operation: GpuOperations) -> Self{
+        Self{
+            name,
+            operation,
+            runs: 0,
+            max_abs_error: 0.0,
+            max_rel_error: 0.0,
+            failed_elements: 0,
+            mismatch: None,
+        }
+    }
+    /// Returns true if all outputs had the same shapes and all elements were in tolerance
+    pub fn passed(&self) -> bool{
+        self.mismatch.is_none() && self.failed_elements == 0
+    }
+    /// Compare gpu outputs with cpu outputs of one run
+    fn compare(&mut self, gpu: &[Tensor<f32>], cpu: &[Tensor<f32>], config: &ConformanceConfig, run: &str){
+        self.runs += 1;
+
+        if gpu.len() != cpu.len(){
+            self.mismatch.get_or_insert(format!("{}: {} gpu outputs, {} cpu outputs", run, gpu.len(), cpu.len()));
+            return;
+        }
+
+        for (gpu_tensor, cpu_tensor) in gpu.iter().zip(cpu.iter()){
+            if gpu_tensor.get_shape() != cpu_tensor.get_shape(){
+                self.mismatch.get_or_insert(format!("{}: gpu shape {:?}, cpu shape {:?}", run, gpu_tensor.get_shape(), cpu_tensor.get_shape()));
+                continue;
+            }
+
+            for (a, b) in gpu_tensor.get_data().iter().zip(cpu_tensor.get_data()){
+                let abs_error = (a - b).abs();
+
+                // NaN compares as error, unless both backends return NaN
+                let within = abs_error <= config.abs_tolerance + config.rel_tolerance * b.abs() || (a.is_nan() && b.is_nan());
+                if !within{
+                    self.failed_elements += 1;
+                }
+
+                if abs_error.is_finite(){
+                    self.max_abs_error = self.max_abs_error.max(abs_error);
+                    self.max_rel_error = self.max_rel_error.max(abs_error / b.abs().max(f32::EPSILON));
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ConformanceReport{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        let status = if self.passed() { "ok" } else { "FAILED" };
+
+        write!(f, "{:<28} {:<7} runs: {:<4} max abs: {:<12e} max rel: {:<12e} failed elements: {}",
+            self.name, status, self.runs, self.max_abs_error, self.max_rel_error, self.failed_elements)?;
+
+        if let Some(mismatch) = &self.mismatch{
+            write!(f, " ({})", mismatch)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns random tensor with values in range
+fn rand_range(shape: &[u32], min: f32, max: f32) -> Tensor<f32>{
+    let mut rng = rand::rng();
+    let data: Vec<f32> = (0..shape.iter().product::<u32>()).map(|_| rng.random_range(min..max)).collect();
+
+    Tensor::from_data(&data, shape).unwrap()
+}
+
+/// Random dims of one case, shared by every sample of it
+struct CaseDims{
+    m: u32,
+    k: u32,
+    n: u32,
+    h: u32,
+    /// Shape of element-wise and reduction operands
+    shape: Vec<u32>,
+    /// Shape with dims randomly set to 1
+    broadcast_shape: Vec<u32>,
+    axis: f32,
+}
+
+impl CaseDims{
+    fn random(max_dim: u32) -> Self{
+        let mut rng = rand::rng();
+        let mut dim = || rng.random_range(1..=max_dim.max(1));
+
+        let (m, k, n, h) = (dim(), dim(), dim(), dim());
+
+        // element-wise and broadcast shaders read shapes from metadata, so they are checked on
+        // tensors of rank 1 to 4, with dims of broadcast operand randomly set to 1
+        let shape: Vec<u32> = (0..rng.random_range(1..=4)).map(|_| rng.random_range(1..=max_dim.max(1))).collect();
+        let broadcast_shape: Vec<u32> = shape.iter().map(|dim| if rng.random_bool(0.5) {1} else {*dim}).collect();
+        let axis = rng.random_range(0..shape.len()) as f32;
+
+        Self{m, k, n, h, shape, broadcast_shape, axis}
+    }
+}
+
+/// Returns random inputs and params of one sample of runner operation, with dims of case
+fn random_sample(name: &str, dims: &CaseDims) -> (Vec<Tensor<f32>>, Vec<f32>){
+    let CaseDims{m, k, n, h, ..} = *dims;
+    let (shape, broadcast_shape, axis) = (&dims.shape, &dims.broadcast_shape, dims.axis);
+    let groups = 2;
+    let i = k;
+
+    let matrix = Tensor::rand(1.0, &[m, n]);
+    let positive = rand_range(&[m, n], 0.5, 2.0);
+
+    let tensor = Tensor::rand(1.0, shape);
+    let column = Tensor::rand(1.0, &[m, 1]);
+    let param_column = rand_range(&[m, 1], 0.5, 1.5);
+
+    match name{
+        "add" | "sub" | "mul" | "div" => (vec!{matrix}, vec!{rand_range(&[1], 0.5, 2.0).get_data()[0]}),
+        "log" => (vec!{positive}, vec!{2.0}),
+        "nlog" => (vec!{positive}, vec!{}),
+        "tens_add" | "tens_sub" | "tens_mul" => (vec!{tensor, Tensor::rand(1.0, shape)}, vec!{}),
+        "tens_div" => (vec!{tensor, rand_range(shape, 0.5, 2.0)}, vec!{}),
+
+        "tens_broadcast_add" | "tens_broadcast_sub" | "tens_broadcast_mul" => (vec!{tensor, Tensor::rand(1.0, broadcast_shape)}, vec!{}),
+        "tens_broadcast_div" => (vec!{tensor, rand_range(broadcast_shape, 0.5, 1.5)}, vec!{}),
+
+        "matmul" => (vec!{Tensor::rand(1.0, &[m, k]), Tensor::rand(1.0, &[k, n])}, vec!{}),
+        "matrix_row_prod" | "matrix_col_prod" => (vec!{rand_range(&[m, n], 0.8, 1.2)}, vec!{}),
+        "matrix_transpose" | "matrix_row_sum" | "matrix_col_sum" |
+        "relu" | "relu_der" | "sigmoid" | "sigmoid_der" => (vec!{matrix}, vec!{}),
+
+        "reduce_sum" | "reduce_mean" | "reduce_max" | "reduce_min" | "reduce_argmax" | "reduce_variance" => (vec!{tensor}, vec!{}),
+        "reduce_sum_axis" | "reduce_mean_axis" | "reduce_max_axis" | "reduce_min_axis" | "reduce_argmax_axis" | "reduce_variance_axis" => (vec!{tensor}, vec!{axis}),
+
+        "forward_no_activ" | "forward_relu" | "forward_sigmoid" => (vec!{Tensor::rand(1.0, &[m, k]), Tensor::rand(1.0, &[k, n]), column}, vec!{}),
+        "backward_weight" => (vec!{Tensor::rand(1.0, &[m, k]), matrix, Tensor::rand(1.0, &[k, n])}, vec!{0.1, 0.01, 0.01}),
+        "backward_bias" => (vec!{column, matrix, Tensor::rand(1.0, &[k, n])}, vec!{0.1}),
+        "backward_grad" => (vec!{Tensor::rand(1.0, &[m, k]), matrix}, vec!{}),
+        "backward_relu" | "backward_sigmoid" => (vec!{matrix, Tensor::rand(1.0, &[m, n])}, vec!{}),
+
+        "batch_norm" => (vec!{matrix, param_column, column}, vec!{1e-3}),
+        "layer_norm" => (vec!{matrix, rand_range(&[1, n], 0.5, 1.5), Tensor::rand(1.0, &[1, n])}, vec!{1e-3}),
+        "group_norm" => (vec!{Tensor::rand(1.0, &[groups * m, n]), rand_range(&[groups * m, 1], 0.5, 1.5), Tensor::rand(1.0, &[groups * m, 1])}, vec!{1e-3, groups as f32}),
+        "batch_norm_eval" => (vec!{matrix, column.clone(), param_column.clone(), param_column, column}, vec!{1e-3}),
+        "backward_batch_norm" => (vec!{matrix, param_column, Tensor::rand(1.0, &[m, n])}, vec!{1e-3}),
+        "backward_layer_norm" => (vec!{matrix, rand_range(&[1, n], 0.5, 1.5), Tensor::rand(1.0, &[m, n])}, vec!{1e-3}),
+        "backward_group_norm" => (vec!{Tensor::rand(1.0, &[groups * m, n]), rand_range(&[groups * m, 1], 0.5, 1.5), Tensor::rand(1.0, &[groups * m, n])}, vec!{1e-3, groups as f32}),
+        "backward_batch_norm_params" => (vec!{param_column, column, matrix, Tensor::rand(1.0, &[m, n])}, vec!{1e-3, 1.0, 0.1}),
+        "backward_layer_norm_params" => (vec!{rand_range(&[1, n], 0.5, 1.5), Tensor::rand(1.0, &[1, n]), matrix, Tensor::rand(1.0, &[m, n])}, vec!{1e-3, 1.0, 0.1}),
+        "backward_group_norm_params" => (vec!{rand_range(&[groups * m, 1], 0.5, 1.5), Tensor::rand(1.0, &[groups * m, 1]), Tensor::rand(1.0, &[groups * m, n]), Tensor::rand(1.0, &[groups * m, n])}, vec!{1e-3, groups as f32, 0.1}),
+
+        "rnn_cell" | "gru_cell" | "lstm_cell" => {
+            let gates = match name{
+                "gru_cell" => 3,
+                "lstm_cell" => 4,
+                _ => 1,
+            };
+            let mut inputs = vec!{
+                Tensor::rand(1.0, &[gates * h, i]),
+                Tensor::rand(1.0, &[gates * h, h]),
+                Tensor::rand(1.0, &[gates * h, 1]),
+                Tensor::rand(1.0, &[gates * h, 1]),
+                Tensor::rand(1.0, &[i, n]),
+                Tensor::rand(1.0, &[h, n]),
+            };
+            if name == "lstm_cell"{
+                inputs.push(Tensor::rand(1.0, &[h, n]));
+            }
+
+            (inputs, vec!{})
+        },
+
+        _ => (vec!{}, vec!{}),
+    }
+}
+
+/// Returns count of leading inputs of operation, that are weights updated by it, so they are the
+/// same in every sample
+fn updated_inputs(name: &str) -> usize{
+    match name{
+        "backward_weight" | "backward_bias" => 1,
+        "backward_batch_norm_params" | "backward_layer_norm_params" | "backward_group_norm_params" => 2,
+        _ => 0,
+    }
+}
+
+/// Run runner operation with its name
+async fn run_named(runner: &mut GpuRunner, name: &str) -> Result<Vec<Tensor<f32>>, GpuError>{
+    match name{
+        "add" => runner.add().await,
+        "sub" => runner.sub().await,
+        "mul" => runner.mul().await,
+        "div" => runner.div().await,
+        "log" => runner.log().await,
+        "nlog" => runner.nlog().await,
+        "tens_add" => runner.tens_add().await,
+        "tens_sub" => runner.tens_sub().await,
+        "tens_mul" => runner.tens_mul().await,
+        "tens_div" => runner.tens_div().await,
+
+        "tens_broadcast_add" => runner.tens_broadcast_add().await,
+        "tens_broadcast_sub" => runner.tens_broadcast_sub().await,
+        "tens_broadcast_mul" => runner.tens_broadcast_mul().await,
+        "tens_broadcast_div" => runner.tens_broadcast_div().await,
+
+        "matmul" => runner.matmul().await,
+        "matrix_transpose" => runner.matrix_transpose().await,
+        "matrix_row_sum" => runner.matrix_row_sum().await,
+        "matrix_row_prod" => runner.matrix_row_prod().await,
+        "matrix_col_sum" => runner.matrix_col_sum().await,
+        "matrix_col_prod" => runner.matrix_col_prod().await,
+
+        "reduce_sum" => runner.reduce_sum().await,
+        "reduce_sum_axis" => runner.reduce_sum_axis().await,
+        "reduce_mean" => runner.reduce_mean().await,
+        "reduce_mean_axis" => runner.reduce_mean_axis().await,
+        "reduce_max" => runner.reduce_max().await,
+        "reduce_max_axis" => runner.reduce_max_axis().await,
+        "reduce_min" => runner.reduce_min().await,
+        "reduce_min_axis" => runner.reduce_min_axis().await,
+        "reduce_argmax" => runner.reduce_argmax().await,
+        "reduce_argmax_axis" => runner.reduce_argmax_axis().await,
+        "reduce_variance" => runner.reduce_variance().await,
+        "reduce_variance_axis" => runner.reduce_variance_axis().await,
+
+        "relu" => runner.relu().await,
+        "relu_der" => runner.relu_der().await,
+        "sigmoid" => runner.sigmoid().await,
+        "sigmoid_der" => runner.sigmoid_der().await,
+
+        "forward_no_activ" => runner.forward_no_activ().await,
+        "forward_relu" => runner.forward_relu().await,
+        "forward_sigmoid" => runner.forward_sigmoid().await,
+        "backward_weight" => runner.backward_weight().await,
+        "backward_bias" => runner.backward_bias().await,
+        "backward_grad" => runner.backward_grad().await,
+        "backward_relu" => runner.backward_relu().await,
+        "backward_sigmoid" => runner.backward_sigmoid().await,
+
+        "batch_norm" => runner.batch_norm().await,
+        "layer_norm" => runner.layer_norm().await,
+        "group_norm" => runner.group_norm().await,
+        "batch_norm_eval" => runner.batch_norm_eval().await,
+        "backward_batch_norm" => runner.backward_batch_norm().await,
+        "backward_layer_norm" => runner.backward_layer_norm().await,
+        "backward_group_norm" => runner.backward_group_norm().await,
+        "backward_batch_norm_params" => runner.backward_batch_norm_params().await,
+        "backward_layer_norm_params" => runner.backward_layer_norm_params().await,
+        "backward_group_norm_params" => runner.backward_group_norm_params().await,
+
+        "rnn_cell" => runner.rnn_cell().await,
+        "gru_cell" => runner.gru_cell().await,
+        "lstm_cell" => runner.lstm_cell().await,
+
+        _ => Err(GpuError::InvalidShapes(format!("unknown operation {}", name))),
+    }
+}
+
+/// Check one runner operation, by running random samples on gpu of context and on cpu backend
+/// Error if gpu operation fails
+pub async fn check_operation(context: Arc<GpuContext>, name: &'static str, config: &ConformanceConfig) -> Result<ConformanceReport, GpuError>{
+    let operation = CONFORMANCE_OPERATIONS.iter()
+        .find(|(op_name, _)| *op_name == name)
+        .map(|(_, operation)| operation.clone())
+        .ok_or(GpuError::InvalidShapes(format!("unknown operation {}", name)))?;
+
+    let mut report = ConformanceReport::new(name, operation);
+
+    for _ in 0..config.cases{
+        let dims = CaseDims::random(config.max_dim);
+        let (inputs, params) = random_sample(name, &dims);
+        let sample_bytes: usize = inputs.iter().map(|tensor| tensor.count_data() * size_of::<f32>()).sum();
+
+        for &samples in &config.samples{
+            for &samples_per_chunk in &config.samples_per_chunk{
+                let buffer_size = (sample_bytes.saturating_mul(samples_per_chunk.min(samples).max(1))) as u64;
+
+                let mut gpu_runner = GpuRunner::from_context(context.clone(), buffer_size, MemoryMetric::B);
+                gpu_runner.set_backend(Backend::Gpu);
+                let mut cpu_runner = GpuRunner::with_backend(Backend::Cpu, buffer_size, MemoryMetric::B);
+
+                // every sample has its own values, so kernels reading inputs of other sample fail,
+                // only weights updated by operation are shared
+                for _ in 0..samples{
+                    let (mut sample_inputs, _) = random_sample(name, &dims);
+                    let updated = updated_inputs(name);
+                    sample_inputs[..updated].clone_from_slice(&inputs[..updated]);
+
+                    gpu_runner.append(Sample::from_data(sample_inputs.clone(), params.clone(), &[]))?;
+                    cpu_runner.append(Sample::from_data(sample_inputs, params.clone(), &[]))?;
+                }
+
+                let gpu_output = run_named(&mut gpu_runner, name).await?;
+                let cpu_output = run_named(&mut cpu_runner, name).await?;
+
+                let shapes: Vec<&Vec<u32>> = inputs.iter().map(|tensor| tensor.get_shape()).collect();
+                let run = format!("shapes {:?}, samples {}, buffer {} B", shapes, samples, buffer_size);
+
+                report.compare(&gpu_output, &cpu_output, config, &run);
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Check every runner operation on gpu of context against cpu backend, and return report of
+/// every operation
+/// Fallback adapter from GpuContext::init_fallback gives the same results on every machine
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main(){
+///     if std::env::var("CI").is_ok() {
+///         eprintln!("Skipping GPU test in CI");
+///         return;
+///     }
+///
+///     let context = match GpuContext::init_fallback(256, &MemoryMetric::MB).await{
+///         Ok(context) => context,
+///         Err(_) => GpuContext::init(256, &MemoryMetric::MB).await.unwrap(),
+///     };
+///
+///     let config = ConformanceConfig{
+///         cases: 1,
+///         ..Default::default()
+///     };
+///
+///     let reports = run_conformance(context, &config).await.unwrap();
+///
+///     for report in &reports{
+///         println!("{}", report);
+///     }
+///
+///     assert!(reports.iter().all(|report| report.passed()));
+/// }
+/// ```
+pub async fn run_conformance(context: Arc<GpuContext>, config: &ConformanceConfig) -> Result<Vec<ConformanceReport>, GpuError>{
+    let mut reports: Vec<ConformanceReport> = Vec::with_capacity(CONFORMANCE_OPERATIONS.len());
+
+    for (name, _) in CONFORMANCE_OPERATIONS{
+        reports.push(check_operation(context.clone(), name, config).await?);
+    }
+
+    Ok(reports)
+}
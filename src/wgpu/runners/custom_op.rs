@@ -0,0 +1,143 @@
+use std::{fmt, hash::{Hash, Hasher}};
+
+use super::{gpu_error::GpuError, shaders::PRELUDE_SRC};
+
+/// Bindings and output of custom shader
+/// Every shader reads inputs of samples at binding 0 and writes outputs at binding 3, the same as
+/// shaders of built-in operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CustomLayout{
+    /// Shader reads flat shapes of sample at binding 1
+    pub shapes: bool,
+    /// Shader reads params of sample as uniform at binding 2
+    pub params: bool,
+    /// Shader is prefixed with shared prelude, that reads shapes, strides and offsets of tensors
+    /// from metadata at binding 4
+    pub metadata: bool,
+    /// Shader writes one output for all samples, like gradients of weights
+    /// Outputs of chunks are merged by mean weighted with their sample counts
+    pub single_output: bool,
+}
+
+/// Function, that returns output shape of one sample from shapes of its inputs and its params
+/// None if inputs do not fit operation
+pub type OutputShapeFn = dyn Fn(&[Vec<u32>], &[f32]) -> Option<Vec<u32>> + Send + Sync;
+
+/// Operation with shader supplied by caller, that runs through GpuRunner with the same chunking
+/// and batching of samples as built-in operations
+/// Operations are cached by name, layout and source, so every custom operation is compiled once
+/// per context
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use flashlight_tensor::prelude::*;
+///
+/// let source = "
+/// @group(0) @binding(0)
+/// var<storage, read> input: array<f32>;
+///
+/// @group(0) @binding(3)
+/// var<storage, read_write> output: array<f32>;
+///
+/// @compute @workgroup_size(64)
+/// fn main(@builtin(global_invocation_id) global_id: vec3<u32>, @builtin(num_workgroups) num_workgroups: vec3<u32>){
+///     let idx = invocation_index(global_id, num_workgroups);
+///     if (idx >= arrayLength(&output)) {
+///         return;
+///     }
+///
+///     let a = input[broadcast_index(0u, idx / tensor_len(output_tensor()), idx % tensor_len(output_tensor()))];
+///     output[idx] = a * a;
+/// }
+/// ";
+///
+/// let layout = CustomLayout{metadata: true, ..Default::default()};
+/// let square = CustomOperation::new("square", source, layout, |shapes, _| Some(shapes[0].clone())).unwrap();
+///
+/// let operation = GpuOperations::Custom(Arc::new(square));
+/// assert!(operation.shader_src().contains("fn broadcast_index"));
+/// ```
+pub struct CustomOperation{
+    name: String,
+    source: String,
+    layout: CustomLayout,
+    output_shape: Box<OutputShapeFn>,
+}
+
+impl CustomOperation{
+    /// Create operation from WGSL source with compute entry point main
+    /// Source is prefixed with shared prelude, if layout uses metadata. Shader is validated, when
+    /// operation is first run
+    /// Error if bindings declared in source do not match layout
+    pub fn new(name: &str, source: &str, layout: CustomLayout, output_shape: impl Fn(&[Vec<u32>], &[f32]) -> Option<Vec<u32>> + Send + Sync + 'static) -> Result<Self, GpuError>{
+        let bindings = [
+            (0, true, "input"),
+            (1, layout.shapes, "shapes"),
+            (2, layout.params, "params"),
+            (3, true, "output"),
+        ];
+        for (binding, used, buffer) in bindings{
+            if source.contains(&format!("@binding({})", binding)) != used{
+                let declared = if used {"does not declare"} else {"declares"};
+                return Err(GpuError::InvalidKernel(format!("{} {} {} at binding {}", name, declared, buffer, binding)));
+            }
+        }
+        if source.contains("@binding(4)"){
+            return Err(GpuError::InvalidKernel(format!("{} declares binding 4, that is declared by prelude with metadata", name)));
+        }
+
+        let source = match layout.metadata{
+            true => format!("{}{}", PRELUDE_SRC, source),
+            false => source.to_string(),
+        };
+
+        Ok(Self{
+            name: name.to_string(),
+            source,
+            layout,
+            output_shape: Box::new(output_shape),
+        })
+    }
+    /// Returns name of operation
+    pub fn get_name(&self) -> &str{
+        &self.name
+    }
+    /// Returns WGSL source of operation, with prelude if it uses metadata
+    pub fn get_source(&self) -> &str{
+        &self.source
+    }
+    /// Returns layout of operation
+    pub fn get_layout(&self) -> &CustomLayout{
+        &self.layout
+    }
+    /// Returns output shape of one sample, None if inputs do not fit operation
+    pub fn output_shape(&self, input_shapes: &[Vec<u32>], params: &[f32]) -> Option<Vec<u32>>{
+        (self.output_shape)(input_shapes, params)
+    }
+}
+
+impl fmt::Debug for CustomOperation{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        f.debug_struct("CustomOperation")
+            .field("name", &self.name)
+            .field("layout", &self.layout)
+            .finish()
+    }
+}
+
+impl PartialEq for CustomOperation{
+    fn eq(&self, other: &Self) -> bool{
+        self.name == other.name && self.layout == other.layout && self.source == other.source
+    }
+}
+
+impl Eq for CustomOperation{}
+
+impl Hash for CustomOperation{
+    fn hash<H: Hasher>(&self, state: &mut H){
+        self.name.hash(state);
+        self.layout.hash(state);
+        self.source.hash(state);
+    }
+}
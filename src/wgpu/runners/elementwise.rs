@@ -0,0 +1,267 @@
+use std::{ops::{Add, Div, Mul, Neg, Sub}, sync::Arc};
+
+use crate::tensor::Tensor;
+
+use super::{gpu_context::GpuContext, gpu_error::GpuError, gpu_graph::GpuGraph};
+
+/// Element-wise expression over inputs of ElementwiseKernel
+/// Expressions are built with operators and functions below, and are written into WGSL of kernel
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// let x = Expr::input(0);
+/// let y = Expr::input(1);
+///
+/// let expression = (x * y + 1.0).relu();
+///
+/// assert_eq!(expression.to_wgsl(&["x", "y"]), "max(((x * y) + 1.0), 0.0)");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr{
+    /// Input of kernel at index
+    Input(usize),
+    /// Constant value, it is written into shader, so kernels with other constants compile
+    /// other pipelines
+    Const(f32),
+    Neg(Box<Expr>),
+    /// WGSL binary operator, such as "+"
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    /// Call of WGSL built-in function, such as "clamp"
+    Call(&'static str, Vec<Expr>),
+}
+
+impl Expr{
+    /// Input of kernel at index
+    pub fn input(index: usize) -> Self{
+        Expr::Input(index)
+    }
+    /// Constant value
+    pub fn constant(value: f32) -> Self{
+        Expr::Const(value)
+    }
+    /// Call of WGSL built-in function with f32 arguments and f32 result
+    pub fn call(function: &'static str, args: Vec<Expr>) -> Self{
+        Expr::Call(function, args)
+    }
+    pub fn relu(self) -> Self{
+        Expr::call("max", vec!{self, Expr::Const(0.0)})
+    }
+    pub fn sigmoid(self) -> Self{
+        1.0 / (1.0 + (-self).exp())
+    }
+    pub fn tanh(self) -> Self{
+        Expr::call("tanh", vec!{self})
+    }
+    pub fn exp(self) -> Self{
+        Expr::call("exp", vec!{self})
+    }
+    /// Natural logarithm
+    pub fn ln(self) -> Self{
+        Expr::call("log", vec!{self})
+    }
+    pub fn sqrt(self) -> Self{
+        Expr::call("sqrt", vec!{self})
+    }
+    pub fn abs(self) -> Self{
+        Expr::call("abs", vec!{self})
+    }
+    pub fn pow(self, exponent: Expr) -> Self{
+        Expr::call("pow", vec!{self, exponent})
+    }
+    pub fn max(self, other: Expr) -> Self{
+        Expr::call("max", vec!{self, other})
+    }
+    pub fn min(self, other: Expr) -> Self{
+        Expr::call("min", vec!{self, other})
+    }
+    /// Returns the biggest input index in expression, None if it has no inputs
+    pub fn max_input(&self) -> Option<usize>{
+        match self{
+            Expr::Input(index) => Some(*index),
+            Expr::Const(_) => None,
+            Expr::Neg(a) => a.max_input(),
+            Expr::Binary(_, a, b) => a.max_input().max(b.max_input()),
+            Expr::Call(_, args) => args.iter().filter_map(|arg| arg.max_input()).max(),
+        }
+    }
+    /// Returns WGSL of expression, inputs are named by names
+    /// Panics if input index is out of names
+    pub fn to_wgsl(&self, names: &[&str]) -> String{
+        match self{
+            Expr::Input(index) => names[*index].to_string(),
+            // non finite values have no WGSL literal
+            Expr::Const(value) if value.is_finite() => format!("{:?}", value),
+            Expr::Const(value) => format!("bitcast<f32>({}u)", value.to_bits()),
+            Expr::Neg(a) => format!("(-{})", a.to_wgsl(names)),
+            Expr::Binary(operator, a, b) => format!("({} {} {})", a.to_wgsl(names), operator, b.to_wgsl(names)),
+            Expr::Call(function, args) => {
+                let args: Vec<String> = args.iter().map(|arg| arg.to_wgsl(names)).collect();
+                format!("{}({})", function, args.join(", "))
+            },
+        }
+    }
+}
+
+macro_rules! expr_operator{
+    ($trait: ident, $function: ident, $operator: literal) => {
+        impl $trait<Expr> for Expr{
+            type Output = Expr;
+
+            fn $function(self, other: Expr) -> Expr{
+                Expr::Binary($operator, Box::new(self), Box::new(other))
+            }
+        }
+        impl $trait<f32> for Expr{
+            type Output = Expr;
+
+            fn $function(self, other: f32) -> Expr{
+                Expr::Binary($operator, Box::new(self), Box::new(Expr::Const(other)))
+            }
+        }
+        impl $trait<Expr> for f32{
+            type Output = Expr;
+
+            fn $function(self, other: Expr) -> Expr{
+                Expr::Binary($operator, Box::new(Expr::Const(self)), Box::new(other))
+            }
+        }
+    };
+}
+
+expr_operator!(Add, add, "+");
+expr_operator!(Sub, sub, "-");
+expr_operator!(Mul, mul, "*");
+expr_operator!(Div, div, "/");
+
+impl Neg for Expr{
+    type Output = Expr;
+
+    fn neg(self) -> Expr{
+        Expr::Neg(Box::new(self))
+    }
+}
+
+/// Returns true if name can be used as name of WGSL function parameter
+fn is_identifier(name: &str) -> bool{
+    let mut chars = name.chars();
+    match chars.next(){
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {},
+        _ => return false,
+    }
+
+    name != "_" && !name.starts_with("__") && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Custom element-wise operation, from WGSL expression or Expr
+/// Kernel is wrapped with bindings and broadcast indexing of shared prelude, inputs are broadcast
+/// to shape of output like GpuGraph::add. Generated shaders are cached by context, so kernel is
+/// compiled once, and it can be fused with other element-wise operations of GpuGraph
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main(){
+///     if std::env::var("CI").is_ok() {
+///         eprintln!("Skipping GPU test in CI");
+///         return;
+///     }
+///     let context = GpuContext::init(1, &MemoryMetric::GB).await.unwrap();
+///
+///     let kernel = ElementwiseKernel::from_wgsl(&["x", "y"], "x * y + 1.0").unwrap();
+///
+///     let x: Tensor<f32> = Tensor::fill(2.0, &[2, 3]);
+///     let y: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0], &[3]).unwrap();
+///
+///     let output: Tensor<f32> = kernel.run(context, &[&x, &y]).await.unwrap();
+///
+///     assert_eq!(output.get_data(), &vec!{3.0, 5.0, 7.0, 3.0, 5.0, 7.0});
+///     assert_eq!(output.get_shape(), &vec!{2, 3});
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementwiseKernel{
+    inputs: Vec<String>,
+    expression: String,
+}
+
+impl ElementwiseKernel{
+    /// Create kernel from WGSL expression of type f32, that reads inputs by names
+    /// Expression is validated when kernel is first compiled
+    /// Error if there are no inputs, or name is not WGSL identifier or is repeated
+    pub fn from_wgsl(inputs: &[&str], expression: &str) -> Result<Self, GpuError>{
+        if inputs.is_empty(){
+            return Err(GpuError::InvalidKernel("kernel needs at least one input".to_string()));
+        }
+        for (i, name) in inputs.iter().enumerate(){
+            if !is_identifier(name){
+                return Err(GpuError::InvalidKernel(format!("input name {:?} is not WGSL identifier", name)));
+            }
+            if inputs[..i].contains(name){
+                return Err(GpuError::InvalidKernel(format!("input name {:?} is repeated", name)));
+            }
+        }
+        if expression.trim().is_empty(){
+            return Err(GpuError::InvalidKernel("expression is empty".to_string()));
+        }
+
+        Ok(Self{
+            inputs: inputs.iter().map(|name| name.to_string()).collect(),
+            expression: expression.to_string(),
+        })
+    }
+    /// Create kernel with inputs count of inputs, named x0, x1...
+    /// Error if inputs is 0 or expression reads input out of inputs
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let expression = (Expr::input(0) - Expr::input(1)).abs();
+    /// let kernel = ElementwiseKernel::from_expr(2, &expression).unwrap();
+    ///
+    /// assert_eq!(kernel.get_expression(), "abs((x0 - x1))");
+    /// assert!(ElementwiseKernel::from_expr(1, &expression).is_err());
+    /// ```
+    pub fn from_expr(inputs: usize, expression: &Expr) -> Result<Self, GpuError>{
+        if let Some(index) = expression.max_input().filter(|index| *index >= inputs){
+            return Err(GpuError::InvalidKernel(format!("expression reads input {} of {} inputs", index, inputs)));
+        }
+
+        let names: Vec<String> = (0..inputs).map(|i| format!("x{}", i)).collect();
+        let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+
+        Self::from_wgsl(&names, &expression.to_wgsl(&names))
+    }
+    /// Returns count of inputs of kernel
+    pub fn inputs_len(&self) -> usize{
+        self.inputs.len()
+    }
+    /// Returns names of inputs
+    pub fn get_inputs(&self) -> &Vec<String>{
+        &self.inputs
+    }
+    /// Returns WGSL expression of kernel
+    pub fn get_expression(&self) -> &str{
+        &self.expression
+    }
+    /// Returns WGSL function named name, that computes kernel from its inputs
+    pub fn wgsl_function(&self, name: &str) -> String{
+        let params: Vec<String> = self.inputs.iter().map(|input| format!("{}: f32", input)).collect();
+
+        format!("fn {}({}) -> f32 {{\n\treturn {};\n}}\n", name, params.join(", "), self.expression)
+    }
+    /// Run kernel on inputs, broadcast to shape of output
+    /// Error if count of inputs does not match kernel, shapes can not be broadcast, expression
+    /// does not compile, or wgpu reports an error
+    pub async fn run(&self, context: Arc<GpuContext>, inputs: &[&Tensor<f32>]) -> Result<Tensor<f32>, GpuError>{
+        let mut graph = GpuGraph::new(context);
+        let inputs: Vec<_> = inputs.iter().map(|input| graph.input(input)).collect();
+        let output = graph.elementwise(self, &inputs)?;
+
+        Ok(graph.run(&[output]).await?.remove(0))
+    }
+}
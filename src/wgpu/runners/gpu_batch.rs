@@ -0,0 +1,229 @@
+use std::sync::Arc;
+
+use crate::{prelude::{capture_errors, GpuContext, GpuError, GpuTensor}, tensor::Tensor};
+
+/// Writer of samples straight into one gpu buffer, that is mapped while samples are written
+/// Inputs are copied once, from tensors into mapped memory, without Sample or GpuData in
+/// between. Inputs shared by every sample, like weights, are uploaded once and copied into
+/// every sample on gpu
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main(){
+///     if std::env::var("CI").is_ok() {
+///         eprintln!("Skipping GPU test in CI");
+///         return;
+///     }
+///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+///     let context = runner.get_context().await.unwrap();
+///
+///     // weights, inputs and bias of every sample
+///     let mut writer = GpuBatchWriter::new(context, &[vec!{2, 3}, vec!{3, 1}, vec!{2, 1}], 4).unwrap();
+///     writer.share(0, &Tensor::fill(1.0, &[2, 3])).unwrap();
+///     writer.share(2, &Tensor::fill(0.5, &[2, 1])).unwrap();
+///
+///     for i in 0..4{
+///         writer.write(&[&Tensor::fill(i as f32, &[3, 1])]).unwrap();
+///     }
+///
+///     runner.append_batch(writer.finish().unwrap(), vec!{}).unwrap();
+///     let output: Vec<Tensor<f32>> = runner.forward_no_activ().await.unwrap();
+///
+///     assert_eq!(output[2].get_data(), &vec!{6.5, 6.5});
+/// }
+/// ```
+pub struct GpuBatchWriter{
+    buffer: wgpu::Buffer,
+    input_shapes: Vec<Vec<u32>>,
+    /// Offset of every input in sample, followed by length of sample
+    input_offsets: Vec<usize>,
+    shared: Vec<Option<GpuTensor>>,
+
+    capacity: usize,
+    samples: usize,
+
+    context: Arc<GpuContext>,
+}
+
+impl GpuBatchWriter{
+    /// Create writer for capacity samples with inputs of input_shapes, buffer of all samples is
+    /// created mapped
+    /// Error if buffer is bigger than context allows to bind, or could not be created
+    pub fn new(context: Arc<GpuContext>, input_shapes: &[Vec<u32>], capacity: usize) -> Result<Self, GpuError>{
+        let mut input_offsets = vec!{0};
+        for shape in input_shapes{
+            input_offsets.push(input_offsets.last().unwrap() + shape.iter().product::<u32>() as usize);
+        }
+
+        let size = (capacity * input_offsets.last().unwrap() * size_of::<f32>()).max(size_of::<f32>()) as u64;
+        if size > context.max_buffer_size(){
+            return Err(GpuError::BufferTooBig(size));
+        }
+
+        let buffer = capture_errors(&context.device, || {
+            context.device.create_buffer(&wgpu::BufferDescriptor{
+                label: Some("Batch Buffer"),
+                size,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: true,
+            })
+        })?;
+
+        Ok(Self{
+            buffer,
+            input_shapes: input_shapes.to_vec(),
+            input_offsets,
+            shared: vec!{None; input_shapes.len()},
+
+            capacity,
+            samples: 0,
+
+            context,
+        })
+    }
+    /// Upload input shared by every sample once, so write skips it
+    /// Error if shape does not match, or samples were already written
+    pub fn share(&mut self, input: usize, tensor: &Tensor<f32>) -> Result<(), GpuError>{
+        if self.samples != 0{
+            return Err(GpuError::InvalidConfig("inputs must be shared before samples are written".to_string()));
+        }
+        if self.input_shapes.get(input) != Some(tensor.get_shape()){
+            return Err(GpuError::InvalidShapes(format!("shared input {} with shape {:?} does not match {:?}", input, tensor.get_shape(), self.input_shapes.get(input))));
+        }
+
+        self.shared[input] = Some(GpuTensor::from_tensor(tensor, self.context.clone())?);
+
+        Ok(())
+    }
+    /// Write inputs of next sample into mapped buffer, without shared inputs
+    /// Error if batch is full, or inputs do not match shapes of writer
+    pub fn write(&mut self, inputs: &[&Tensor<f32>]) -> Result<(), GpuError>{
+        if self.samples == self.capacity{
+            return Err(GpuError::InvalidShapes(format!("batch is full, capacity {}", self.capacity)));
+        }
+
+        let slots: Vec<usize> = (0..self.input_shapes.len()).filter(|&i| self.shared[i].is_none()).collect();
+        if slots.len() != inputs.len(){
+            return Err(GpuError::InvalidShapes(format!("sample needs {} inputs, but got {}", slots.len(), inputs.len())));
+        }
+        for (&slot, tensor) in slots.iter().zip(inputs.iter()){
+            if tensor.get_shape() != &self.input_shapes[slot]{
+                return Err(GpuError::InvalidShapes(format!("input {} with shape {:?} does not match {:?}", slot, tensor.get_shape(), self.input_shapes[slot])));
+            }
+        }
+
+        let sample_start = self.samples * self.sample_len();
+        for (&slot, tensor) in slots.iter().zip(inputs.iter()){
+            let start = ((sample_start + self.input_offsets[slot]) * size_of::<f32>()) as u64;
+            let end = start + (tensor.get_data().len() * size_of::<f32>()) as u64;
+
+            if start == end{
+                continue;
+            }
+
+            // mapped ranges start at MAP_ALIGNMENT, so range can begin before input
+            let mapped_start = start - start % wgpu::MAP_ALIGNMENT;
+            let mut view = self.buffer.slice(mapped_start..end).get_mapped_range_mut();
+            view[(start - mapped_start) as usize..].copy_from_slice(bytemuck::cast_slice(tensor.get_data()));
+        }
+
+        self.samples += 1;
+
+        Ok(())
+    }
+    /// Returns count of values of one sample
+    pub fn sample_len(&self) -> usize{
+        *self.input_offsets.last().unwrap()
+    }
+    /// Returns count of written samples
+    pub fn len(&self) -> usize{
+        self.samples
+    }
+    /// Returns true if no samples were written
+    pub fn is_empty(&self) -> bool{
+        self.samples == 0
+    }
+    /// Unmap buffer and copy shared inputs into every written sample on gpu
+    /// Error if copies could not be submitted
+    pub fn finish(self) -> Result<GpuBatch, GpuError>{
+        self.buffer.unmap();
+
+        let sample_len = self.sample_len();
+        let device = &self.context.device;
+        capture_errors(device, || {
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Batch Encoder"),
+            });
+
+            for (slot, shared) in self.shared.iter().enumerate(){
+                let Some(shared) = shared else { continue };
+                let size = (shared.count_data() * size_of::<f32>()) as u64;
+                if size == 0{
+                    continue;
+                }
+
+                for sample in 0..self.samples{
+                    let offset = ((sample * sample_len + self.input_offsets[slot]) * size_of::<f32>()) as u64;
+                    encoder.copy_buffer_to_buffer(shared.get_buffer(), 0, &self.buffer, offset, size);
+                }
+            }
+
+            self.context.queue.submit(Some(encoder.finish()));
+        })?;
+
+        Ok(GpuBatch{
+            buffer: self.buffer,
+            input_shapes: self.input_shapes,
+            sample_len,
+            samples: self.samples,
+
+            context: self.context,
+        })
+    }
+}
+
+/// Samples with inputs in one gpu buffer, written by GpuBatchWriter
+/// Can only be used with GpuRunners, that share its GpuContext
+#[derive(Clone)]
+pub struct GpuBatch{
+    buffer: wgpu::Buffer,
+    input_shapes: Vec<Vec<u32>>,
+    sample_len: usize,
+    samples: usize,
+
+    context: Arc<GpuContext>,
+}
+
+impl GpuBatch{
+    /// Returns buffer with inputs of all samples
+    pub fn get_buffer(&self) -> &wgpu::Buffer{
+        &self.buffer
+    }
+    /// Returns shape of every input of sample
+    pub fn get_input_shapes(&self) -> &Vec<Vec<u32>>{
+        &self.input_shapes
+    }
+    /// Returns context, which device holds the buffer
+    pub fn get_context(&self) -> &Arc<GpuContext>{
+        &self.context
+    }
+    /// Returns count of values of one sample
+    pub fn sample_len(&self) -> usize{
+        self.sample_len
+    }
+    /// Returns count of values of all samples
+    pub fn input_len(&self) -> usize{
+        self.sample_len * self.samples
+    }
+    /// Returns count of samples
+    pub fn len(&self) -> usize{
+        self.samples
+    }
+    /// Returns true if batch has no samples
+    pub fn is_empty(&self) -> bool{
+        self.samples == 0
+    }
+}
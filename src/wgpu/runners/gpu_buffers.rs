@@ -1,8 +1,10 @@
+use std::{sync::Arc, time::Duration};
+
 use wgpu::util::DeviceExt;
 
-use crate::{prelude::GpuData, tensor::Tensor};
+use crate::{prelude::{capture_errors, GpuConfig, GpuContext, GpuData, GpuError, GpuPipeline, GpuTensor, Readback}, tensor::Tensor};
 
-use super::{helpers::{dispatch_and_receive, get_bind_group, get_bind_group_layout, get_pipeline, get_pipeline_layout, get_size_using_metric, gpu_init, MemoryMetric}, shaders::{get_shader, GpuOperations}};
+use super::{helpers::{dispatch, encode_dispatch_timed, get_bind_group, get_bind_group_layout, get_pipeline, get_pipeline_layout, MemoryMetric}, precision::GpuPrecision, profiling::timestamps_duration, shaders::{get_shader, GpuOperations}};
 
 /// Buffers needed to perform a gpu operation
 /// Chunking not supported yet, so it has a max limit of data
@@ -10,42 +12,168 @@ pub struct GpuBuffers{
     pub inputs_buffer: wgpu::Buffer,
     pub shapes_buffer: Option<wgpu::Buffer>,
     pub params_buffer: Option<wgpu::Buffer>,
+    /// Metadata of shapes, for shaders using shared prelude
+    pub metadata_buffer: Option<wgpu::Buffer>,
+    /// Inputs shared by every sample, uploaded once for all chunks
+    pub shared_buffer: Option<wgpu::Buffer>,
     pub output_buffer: wgpu::Buffer,
 
     pub input_len: usize,
     pub output_len: usize,
     pub output_shape: Vec<u32>,
+    /// Output shape of every sample of ragged batch, empty if all samples have output_shape
+    pub output_shapes: Vec<Vec<u32>>,
 
-    pub device: wgpu::Device,
-    pub queue: wgpu::Queue,
+    pub context: Arc<GpuContext>,
     pub shader: Option<wgpu::ShaderModule>,
 
     pub bind_group_layout: Option<wgpu::BindGroupLayout>,
     pub pipeline_layout: Option<wgpu::PipelineLayout>,
+    /// Cached pipeline of operation, set by set_shader
+    pub pipeline: Option<Arc<GpuPipeline>>,
+    pub bind_group: Option<wgpu::BindGroup>,
 
     pub samples_count: u32,
+    /// Precision of values in inputs, shared inputs and output, taken from GpuData when buffers
+    /// are created
+    pub precision: GpuPrecision,
 
     max_buffer_size: u64,
 }
 
+/// Bytes of two u64 timestamps of compute pass
+const TIMESTAMPS_SIZE: u64 = 2 * size_of::<u64>() as u64;
+
+/// Error if buffer of size bytes is bigger than context allows to bind
+fn check_size(context: &GpuContext, size: u64) -> Result<(), GpuError>{
+    if size > context.max_buffer_size(){
+        return Err(GpuError::BufferTooBig(size));
+    }
+
+    Ok(())
+}
+
+/// Error if buffer of len values of precision is bigger than context allows to bind
+fn check_buffer_size(context: &GpuContext, len: usize, precision: GpuPrecision) -> Result<(), GpuError>{
+    check_size(context, precision.buffer_size(len))
+}
+
+/// Error if buffers of metadata, params or shared inputs of chunk are bigger than context
+/// allows to bind, so they are not created invalid
+fn check_data_buffers(context: &GpuContext, data: &GpuData, chunk_id: usize, precision: GpuPrecision) -> Result<(), GpuError>{
+    if !data.flat_shapes.is_empty() && data.use_shapes{
+        check_size(context, (data.chunk_metadata_len(chunk_id) * size_of::<u32>()) as u64)?;
+        check_buffer_size(context, data.shared_inputs.len().max(1), precision)?;
+    }
+    if data.use_params{
+        check_size(context, (data.params.len() * size_of::<f32>()) as u64)?;
+    }
+
+    Ok(())
+}
+
+/// Returns output shape of every sample, from shapes of ragged chunk, or output_shape repeated
+/// for every sample, that fits in output_len
+fn sample_output_shapes(output_shape: &[u32], output_shapes: &[Vec<u32>], output_len: usize, samples_count: usize) -> Vec<Vec<u32>>{
+    if !output_shapes.is_empty(){
+        return output_shapes.to_vec();
+    }
+
+    let sample_size: usize = output_shape.iter().product::<u32>() as usize;
+
+    vec!{output_shape.to_vec(); (output_len / sample_size.max(1)).min(samples_count)}
+}
+
+/// Create buffer with params of data, None if data does not use params
+fn create_params_buffer(device: &wgpu::Device, data: &GpuData) -> Option<wgpu::Buffer>{
+    if data.params.is_empty() || !data.use_params{
+        return None;
+    }
+
+    Some(device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
+        label: Some("Param Buffer"),
+        contents: bytemuck::cast_slice(&data.params),
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+    }))
+}
+
+/// Create buffer with metadata of shapes of chunk of data, None if data does not use shapes
+fn create_metadata_buffer(device: &wgpu::Device, data: &GpuData, chunk_id: usize) -> Option<wgpu::Buffer>{
+    if data.flat_shapes.is_empty() || !data.use_shapes{
+        return None;
+    }
+
+    Some(device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
+        label: Some("Metadata Buffer"),
+        contents: bytemuck::cast_slice(&data.chunk_metadata(chunk_id)),
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+    }))
+}
+
+/// Create buffer with inputs shared by every sample in precision, None if data does not use shapes
+/// Data without shared inputs gets buffer with one value, so shaders reading them can be bound
+fn create_shared_buffer(device: &wgpu::Device, data: &GpuData, precision: GpuPrecision) -> Option<wgpu::Buffer>{
+    if data.flat_shapes.is_empty() || !data.use_shapes{
+        return None;
+    }
+
+    Some(device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
+        label: Some("Shared Input Buffer"),
+        contents: &data.shared_bytes(precision),
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+    }))
+}
+
 impl GpuBuffers{
     
 }
 
 impl GpuBuffers{
     /// Initlize GpuBuffers with data from GpuData and max buffer size set by max_buffer_size
-    /// Max buffer size is 1GB because of the WGPU limitations
-    pub async fn init(max_buffer_size: u64, metric: MemoryMetric, data: &mut GpuData, chunk_id: usize) -> Self{
-        let (device, queue) = gpu_init(max_buffer_size, &metric).await;
-        let buffers: Option<GpuBuffers> = None;
+    /// Creates new GpuContext, use with_context to share one between buffers
+    /// Max buffer size is clamped to limits of adapter
+    /// Error if device could not be created
+    pub async fn init(max_buffer_size: u64, metric: MemoryMetric, data: &mut GpuData, chunk_id: usize) -> Result<Self, GpuError>{
+        Self::init_with_config(max_buffer_size, metric, &GpuConfig::default(), data, chunk_id).await
+    }
+    /// Initlize GpuBuffers with data from GpuData, on new GpuContext with adapter chosen by config
+    /// Error if there is no adapter matching config
+    pub async fn init_with_config(max_buffer_size: u64, metric: MemoryMetric, config: &GpuConfig, data: &mut GpuData, chunk_id: usize) -> Result<Self, GpuError>{
+        let context = GpuContext::init_with_config(max_buffer_size, &metric, config).await?;
 
-        let shader = None;
+        Self::with_context(context, data, chunk_id)
+    }
+    /// Initlize GpuBuffers with data from GpuData and max buffer size set by max_buffer_size and
+    /// shader
+    /// Max buffer size is clamped to limits of adapter
+    pub async fn with_shader(operation: GpuOperations, max_buffer_size: u64, metric: MemoryMetric, data: &mut GpuData, chunk_id: usize) -> Result<Self, GpuError>{
+        let mut buffers = Self::init(max_buffer_size, metric, data, chunk_id).await?;
+        buffers.set_shader(&operation)?;
+
+        Ok(buffers)
+    }
+    /// Initlize GpuBuffers with data from GpuData on existing GpuContext
+    /// Max buffer size is the binding limit of context
+    /// Error if inputs, outputs, metadata, params or shared inputs of chunk do not fit in max
+    /// buffer size, or buffers could not be created
+    pub fn with_context(context: Arc<GpuContext>, data: &GpuData, chunk_id: usize) -> Result<Self, GpuError>{
+        let (flat_inputs, _, output_len) = data.get_chunk(chunk_id).unwrap();
+        check_buffer_size(&context, flat_inputs.len().max(output_len), data.precision)?;
+        check_data_buffers(&context, data, chunk_id, data.precision)?;
+
+        let device = context.device.clone();
+
+        capture_errors(&device, || Self::create(context, data, chunk_id))
+    }
+    /// Create buffers of chunk on context
+    fn create(context: Arc<GpuContext>, data: &GpuData, chunk_id: usize) -> Self{
+        let device = &context.device;
 
         let (flat_inputs, samples_in_chunk, output_len) = &data.get_chunk(chunk_id).unwrap();
 
         let inputs_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
             label: Some("Input Buffer"),
-            contents: bytemuck::cast_slice(flat_inputs),
+            contents: &data.chunk_bytes(chunk_id, data.precision),
             usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
         });
 
@@ -63,143 +191,154 @@ impl GpuBuffers{
         else{
             shapes_buffer = None;
         }
-        let params_buffer;
+        let params_buffer = create_params_buffer(device, data);
 
-        if data.params.len()!=0 && data.use_params{
-            params_buffer = Some(device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
-                label: Some("Param Buffer"),
-                contents: bytemuck::cast_slice(&data.params),
-                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            }));
-        }
-        else{
-            params_buffer = None;
-        }
+        let metadata_buffer = create_metadata_buffer(device, data, chunk_id);
+        let shared_buffer = create_shared_buffer(device, data, data.precision);
 
         let output_buffer = device.create_buffer(&wgpu::BufferDescriptor{
             label: Some("Output Buffer"),
-            size: (output_len * std::mem::size_of::<f32>()) as u64,
+            size: data.precision.buffer_size(*output_len),
             usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
             mapped_at_creation: false,
         });
 
+        let max_buffer_size = context.max_buffer_size();
+
         Self{
             inputs_buffer,
             shapes_buffer,
             params_buffer,
+            metadata_buffer,
+            shared_buffer,
             output_buffer,
 
             input_len: flat_inputs.len(),
             output_len: *output_len,
             output_shape: data.output_shape.clone(),
+            output_shapes: data.chunk_output_shapes(chunk_id),
 
-            device,
-            queue,
-            shader,
+            context,
+            shader: None,
 
             bind_group_layout: None,
             pipeline_layout: None,
+            pipeline: None,
+            bind_group: None,
 
             samples_count: *samples_in_chunk as u32,
+            precision: data.precision,
 
-            max_buffer_size: get_size_using_metric(max_buffer_size, &metric),
+            max_buffer_size,
         }
     }
-    /// Initlize GpuBuffers with data from GpuData and max buffer size set by max_buffer_size and
-    /// shader
-    /// Max buffer size is 2GB because of the WGPU limitations
-    pub async fn with_shader(operation: GpuOperations, max_buffer_size: u64, metric: MemoryMetric, data: &mut GpuData, chunk_id: usize) -> Self{
-        let (device, queue) = gpu_init(max_buffer_size, &metric).await;
-        let buffers: Option<GpuBuffers> = None;
-
-        let shader = Some(get_shader(&device, &operation));
+    /// Initlize GpuBuffers on existing GpuContext, with inputs already on gpu
+    /// Output buffer can be copied from, so outputs can stay on gpu
+    /// Error if data is not in f32 precision of gpu inputs, buffers do not fit in max buffer
+    /// size, or could not be created
+    pub fn with_gpu_inputs(context: Arc<GpuContext>, data: &GpuData, inputs_buffer: wgpu::Buffer, input_len: usize) -> Result<Self, GpuError>{
+        if data.precision != GpuPrecision::F32{
+            return Err(GpuError::InvalidConfig(format!("inputs on gpu are f32, but data has {:?} precision", data.precision)));
+        }
+        check_buffer_size(&context, data.output_len, data.precision)?;
+        check_data_buffers(&context, data, usize::MAX, data.precision)?;
 
-        let (flat_inputs, samples_in_chunk, output_len) = &data.get_chunk(chunk_id).unwrap();
+        let device = context.device.clone();
 
-        let inputs_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
-            label: Some("Input Buffer"),
-            contents: bytemuck::cast_slice(flat_inputs),
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-        });
+        capture_errors(&device, || Self::create_with_gpu_inputs(context, data, inputs_buffer, input_len))
+    }
+    /// Create buffers for inputs already on gpu
+    fn create_with_gpu_inputs(context: Arc<GpuContext>, data: &GpuData, inputs_buffer: wgpu::Buffer, input_len: usize) -> Self{
+        let device = &context.device;
 
-        let input_shapes_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
-            label: Some("Shapes Buffer"),
-            contents: bytemuck::cast_slice(&data.flat_shapes),
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-        });
+        let mut data_shapes = data.flat_shapes.clone();
+        data_shapes.extend_from_slice(&data.output_shape);
 
-        let shapes_buffer;
-        if data.flat_shapes.len()!=0 && data.use_shapes{
+        let mut shapes_buffer = None;
+        if !data.flat_shapes.is_empty() && data.use_shapes{
             shapes_buffer = Some(device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
                 label: Some("Shapes Buffer"),
-                contents: bytemuck::cast_slice(&data.flat_shapes),
+                contents: bytemuck::cast_slice(&data_shapes),
                 usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
             }));
         }
-        else{
-            shapes_buffer = None;
-        }
 
-        let params_buffer;
+        let params_buffer = create_params_buffer(device, data);
 
-        if data.params.len()!=0 && data.use_params{
-            params_buffer = Some(device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
-                label: Some("Param Buffer"),
-                contents: bytemuck::cast_slice(&data.params),
-                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            }));
-        }
-        else{
-            params_buffer = None;
-        }
+        let metadata_buffer = create_metadata_buffer(device, data, usize::MAX);
+        let shared_buffer = create_shared_buffer(device, data, data.precision);
 
         let output_buffer = device.create_buffer(&wgpu::BufferDescriptor{
             label: Some("Output Buffer"),
-            size: (output_len * std::mem::size_of::<f32>()) as u64,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            size: data.precision.buffer_size(data.output_len),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
         });
 
+        let max_buffer_size = context.max_buffer_size();
+
         Self{
             inputs_buffer,
             shapes_buffer,
             params_buffer,
+            metadata_buffer,
+            shared_buffer,
             output_buffer,
 
-            input_len: flat_inputs.len(),
-            output_len: *output_len,
+            input_len,
+            output_len: data.output_len,
             output_shape: data.output_shape.clone(),
+            output_shapes: data.chunk_output_shapes(usize::MAX),
 
-            device,
-            queue,
-            shader,
+            context,
+            shader: None,
 
             bind_group_layout: None,
             pipeline_layout: None,
+            pipeline: None,
+            bind_group: None,
 
-            samples_count: *samples_in_chunk as u32,
-            max_buffer_size: get_size_using_metric(max_buffer_size, &metric),
+            samples_count: data.samples_count,
+            precision: data.precision,
+
+            max_buffer_size,
         }
     }
-    /// Set shader as operation
-    pub fn set_shader(&mut self, operation: &GpuOperations){
-        self.shader = Some(get_shader(&self.device, operation));
+    /// Set shader as operation, that reads and writes values of precision of buffers
+    /// Pipeline is taken from cache of context, so every operation is compiled once
+    /// Error if shader does not compile, or operation or device does not support precision
+    pub fn set_shader(&mut self, operation: &GpuOperations) -> Result<(), GpuError>{
+        let pipeline = self.context.get_pipeline_with_precision(operation, self.precision)?;
+
+        self.shader = Some(pipeline.shader.clone());
+        self.bind_group_layout = Some(pipeline.bind_group_layout.clone());
+        self.pipeline_layout = Some(pipeline.pipeline_layout.clone());
+        self.pipeline = Some(pipeline);
+        self.bind_group = None;
+
+        Ok(())
     }
 
     /// Update the buffers without rewriting them. More efficient if doing multiple operations in
     /// sequence
+    /// Shared inputs are not written again, they are the same for every chunk
     /// If you know that the size of the updated data is same as data inside
-    pub fn update(&mut self, data: &mut GpuData, chunk_id: usize){
+    /// Error if data does not fit in buffers
+    pub fn update(&mut self, data: &mut GpuData, chunk_id: usize) -> Result<(), GpuError>{
         let (flat_inputs, samples_in_chunk, output_len) = &data.get_chunk(chunk_id).unwrap();
 
-        self.queue.write_buffer(
+        if flat_inputs.len() > self.input_len{
+            return Err(GpuError::InvalidShapes(format!("{} inputs do not fit in buffer of {} inputs", flat_inputs.len(), self.input_len)));
+        }
+
+        self.context.queue.write_buffer(
             &self.inputs_buffer,
             0,
-            bytemuck::cast_slice(flat_inputs)
+            &data.chunk_bytes(chunk_id, self.precision)
         );
 
         if(self.shapes_buffer.is_some()){
-            self.queue.write_buffer(
+            self.context.queue.write_buffer(
                 &self.shapes_buffer.as_ref().unwrap(),
                 0,
                 bytemuck::cast_slice(&data.flat_shapes)
@@ -207,29 +346,51 @@ impl GpuBuffers{
         }
         
         if(self.params_buffer.is_some()){
-            self.queue.write_buffer(
+            self.context.queue.write_buffer(
                 &self.params_buffer.as_ref().unwrap(),
                 0,
                 bytemuck::cast_slice(&data.params)
             );
         }
 
+        if let Some(metadata_buffer) = &self.metadata_buffer && !data.flat_shapes.is_empty(){
+            self.context.queue.write_buffer(
+                metadata_buffer,
+                0,
+                bytemuck::cast_slice(&data.chunk_metadata(chunk_id))
+            );
+        }
+
+        self.output_shapes = data.chunk_output_shapes(chunk_id);
         self.samples_count = *samples_in_chunk as u32;
+
+        Ok(())
     }
     /// Update the buffers by rewriting them. Less efficient if doing multiple operations in
     /// sequence
-    pub fn rewrite(&mut self, data: &GpuData, chunk_id: usize){
+    /// Error if buffers of chunk do not fit in max buffer size, or could not be created
+    pub fn rewrite(&mut self, data: &GpuData, chunk_id: usize) -> Result<(), GpuError>{
+        let (flat_inputs, _, output_len) = data.get_chunk(chunk_id).unwrap();
+        check_buffer_size(&self.context, flat_inputs.len().max(output_len), self.precision)?;
+        check_data_buffers(&self.context, data, chunk_id, self.precision)?;
+
+        let device = self.context.device.clone();
+
+        capture_errors(&device, || self.create_rewrite(data, chunk_id))
+    }
+    /// Replace buffers with new ones, created for chunk
+    fn create_rewrite(&mut self, data: &GpuData, chunk_id: usize){
         let (flat_inputs, samples_in_chunk, output_len) = &data.get_chunk(chunk_id).unwrap();
 
-        let inputs_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
+        let inputs_buffer = self.context.device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
             label: Some("Input Buffer"),
-            contents: bytemuck::cast_slice(flat_inputs),
+            contents: &data.chunk_bytes(chunk_id, self.precision),
             usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
         });
 
         let shapes_buffer;
         if data.flat_shapes.len()!=0 && data.use_shapes{
-            shapes_buffer = Some(self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
+            shapes_buffer = Some(self.context.device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
                 label: Some("Shapes Buffer"),
                 contents: bytemuck::cast_slice(&data.flat_shapes),
                 usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
@@ -239,22 +400,14 @@ impl GpuBuffers{
             shapes_buffer = None;
         }
 
-        let params_buffer;
+        let params_buffer = create_params_buffer(&self.context.device, data);
 
-        if data.params.len()!=0 && data.use_params{
-            params_buffer = Some(self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
-                label: Some("Param Buffer"),
-                contents: bytemuck::cast_slice(&data.params),
-                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            }));
-        }
-        else{
-            params_buffer = None;
-        }
+        let metadata_buffer = create_metadata_buffer(&self.context.device, data, chunk_id);
+        let shared_buffer = create_shared_buffer(&self.context.device, data, self.precision);
 
-        let output_buffer = self.device.create_buffer(&wgpu::BufferDescriptor{
+        let output_buffer = self.context.device.create_buffer(&wgpu::BufferDescriptor{
             label: Some("Output Buffer"),
-            size: (output_len * std::mem::size_of::<f32>()) as u64,
+            size: self.precision.buffer_size(*output_len),
             usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
             mapped_at_creation: false,
         });
@@ -262,41 +415,295 @@ impl GpuBuffers{
         self.inputs_buffer = inputs_buffer;
         self.shapes_buffer = shapes_buffer;
         self.params_buffer = params_buffer;
+        self.metadata_buffer = metadata_buffer;
+        self.shared_buffer = shared_buffer;
         self.output_buffer = output_buffer;
+        self.bind_group = None;
 
+        self.output_shapes = data.chunk_output_shapes(chunk_id);
         self.samples_count = *samples_in_chunk as u32;
     }
     
     /// Prepare bind_group_layout and pipeline_layout before running operations
     /// Use it only after rewriting buffers. Updating buffers does not require preparations
-    pub fn prepare(&mut self){
+    /// Params buffer shorter than Params struct of shader is padded with 0.0
+    /// Error if shader is not set or buffers do not match it
+    pub fn prepare(&mut self) -> Result<(), GpuError>{
         if self.shader.is_none(){
-            panic!("Set shader before running preparation");
+            return Err(GpuError::NoShader);
         }
 
-        self.bind_group_layout = Some(get_bind_group_layout(&self));
-        self.pipeline_layout = Some(get_pipeline_layout(&self.device, self.bind_group_layout.as_ref().unwrap()));
+        self.check_bindings()?;
+
+        let device = self.context.device.clone();
+
+        capture_errors(&device, || {
+            self.pad_params();
+
+            if self.pipeline.is_none(){
+                self.bind_group_layout = Some(get_bind_group_layout(self));
+                self.pipeline_layout = Some(get_pipeline_layout(&self.context.device, self.bind_group_layout.as_ref().unwrap()));
+            }
+
+            self.bind_group = Some(get_bind_group(self));
+        })
     }
     
-    /// Run operation and return data
-    pub async fn run(&self) -> Vec<Tensor<f32>>{
-        if(self.shader.is_none()){
-            panic!("Set shader before running operation");
+    /// Pad params buffer with 0.0 to values of Params struct of shader, so optional params of
+    /// shader, like weight decay of backward_weight, can be left out of data
+    fn pad_params(&mut self){
+        let (Some(pipeline), Some(params_buffer)) = (&self.pipeline, &self.params_buffer) else {
+            return;
+        };
+        let size = pipeline.params_size;
+        if params_buffer.size() >= size{
+            return;
         }
 
-        let bind_group = get_bind_group(&self);
-        let compute_pipeline = get_pipeline(&self.device, &self.shader.as_ref().unwrap(), self.pipeline_layout.as_ref().unwrap());
+        let padded = self.context.device.create_buffer(&wgpu::BufferDescriptor{
+            label: Some("Param Buffer"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
 
-        let output_data: Vec<f32> = dispatch_and_receive(&self.device, &compute_pipeline, &bind_group, &self.queue, self.input_len, &self.output_buffer, self.output_len).await;
+        let mut encoder = self.context.device.create_command_encoder(&wgpu::CommandEncoderDescriptor{
+            label: Some("Encoder"),
+        });
+        encoder.copy_buffer_to_buffer(params_buffer, 0, &padded, 0, params_buffer.size());
+        self.context.queue.submit(Some(encoder.finish()));
 
-        let sample_size: usize = self.output_shape.iter().product::<u32>() as usize;
+        self.params_buffer = Some(padded);
+    }
+    /// Check that buffers needed by shader of cached pipeline exist
+    fn check_bindings(&self) -> Result<(), GpuError>{
+        if let Some(pipeline) = &self.pipeline{
+            if pipeline.uses_shapes && self.shapes_buffer.is_none(){
+                return Err(GpuError::InvalidShapes("operation requires shapes".to_string()));
+            }
+            if pipeline.uses_params && self.params_buffer.is_none(){
+                return Err(GpuError::InvalidShapes("operation requires params".to_string()));
+            }
+            if pipeline.uses_metadata && self.metadata_buffer.is_none(){
+                return Err(GpuError::InvalidShapes("operation requires shapes".to_string()));
+            }
+            if pipeline.uses_shared && self.shared_buffer.is_none(){
+                return Err(GpuError::InvalidShapes("operation requires shared inputs".to_string()));
+            }
+        }
 
-        let mut output_vec: Vec<Tensor<f32>> = Vec::with_capacity(output_data.len()/sample_size);
-        
-        for i in 0..(output_data.len()/sample_size).min(self.samples_count as usize){
-            output_vec.push( Tensor::from_data( &output_data[i*sample_size..(i+1)*sample_size], &self.output_shape ).unwrap());
+        Ok(())
+    }
+    /// Returns bind group and pipeline prepared for run, taking them from cache if they are
+    /// already created
+    fn get_bind_group_and_pipeline(&self) -> Result<(wgpu::BindGroup, wgpu::ComputePipeline), GpuError>{
+        if self.shader.is_none() || self.pipeline_layout.is_none(){
+            return Err(GpuError::NoShader);
+        }
+        self.check_bindings()?;
+
+        capture_errors(&self.context.device, || {
+            let bind_group = match &self.bind_group{
+                Some(bind_group) => bind_group.clone(),
+                None => get_bind_group(self),
+            };
+
+            let compute_pipeline = match &self.pipeline{
+                Some(pipeline) => pipeline.pipeline.clone(),
+                None => get_pipeline(&self.context.device, self.shader.as_ref().unwrap(), self.pipeline_layout.as_ref().unwrap(), &[]),
+            };
+
+            (bind_group, compute_pipeline)
+        })
+    }
+    /// Returns count of invocations, that compute output
+    /// Tiled shaders compute more outputs per workgroup, reductions use whole workgroup per output
+    fn dispatch_len(&self) -> usize{
+        match &self.pipeline{
+            Some(pipeline) => pipeline.dispatch_len(self.output_len),
+            None => self.output_len,
+        }
+    }
+    /// Run operation and keep output on gpu, as one GpuTensor for every sample
+    /// Error if buffers do not hold f32 values, as GpuTensors do
+    pub fn run_on_gpu(&self) -> Result<Vec<GpuTensor>, GpuError>{
+        if self.precision != GpuPrecision::F32{
+            return Err(GpuError::InvalidConfig(format!("GpuTensors can not hold outputs of {:?} precision", self.precision)));
+        }
+
+        let (bind_group, compute_pipeline) = self.get_bind_group_and_pipeline()?;
+
+        dispatch(&self.context.device, &compute_pipeline, &bind_group, &self.context.queue, self.dispatch_len())?;
+
+        let output_shapes = sample_output_shapes(&self.output_shape, &self.output_shapes, self.output_len, self.samples_count as usize);
+
+        if output_shapes.len() == 1 && self.output_shapes.is_empty(){
+            return Ok(vec!{GpuTensor::from_buffer(self.output_buffer.clone(), &self.output_shape, self.context.clone())});
+        }
+
+        capture_errors(&self.context.device, || self.split_output(&output_shapes))
+    }
+    /// Copy output of every sample into its own GpuTensor
+    fn split_output(&self, output_shapes: &[Vec<u32>]) -> Vec<GpuTensor>{
+        let mut encoder = self.context.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Encoder"),
+        });
+
+        let mut offset: u64 = 0;
+        let mut output_vec: Vec<GpuTensor> = Vec::with_capacity(output_shapes.len());
+
+        for shape in output_shapes{
+            let sample_bytes = (shape.iter().product::<u32>() as usize * size_of::<f32>()) as u64;
+
+            let buffer = self.context.device.create_buffer(&wgpu::BufferDescriptor{
+                label: Some("Gpu Tensor Buffer"),
+                size: sample_bytes,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+            encoder.copy_buffer_to_buffer(&self.output_buffer, offset, &buffer, 0, sample_bytes);
+            offset += sample_bytes;
+
+            output_vec.push(GpuTensor::from_buffer(buffer, shape, self.context.clone()));
         }
 
+        self.context.queue.submit(Some(encoder.finish()));
+
         output_vec
     }
+
+    /// Submit operation with copy of output into pooled staging buffer, without waiting for gpu
+    /// Buffers can be updated for next chunk and submitted again, before outputs are awaited
+    /// Error if shader is not set, or wgpu reports an error
+    pub fn submit(&self) -> Result<PendingOutputs, GpuError>{
+        self.submit_timed(false)
+    }
+    /// Same as submit, but if timestamps is set and context supports timestamps, compute pass
+    /// writes gpu timestamps, that are read back with outputs by PendingOutputs::outputs_timed
+    /// Error if shader is not set, or wgpu reports an error
+    pub fn submit_timed(&self, timestamps: bool) -> Result<PendingOutputs, GpuError>{
+        let (bind_group, compute_pipeline) = self.get_bind_group_and_pipeline()?;
+
+        let device = &self.context.device;
+        let size = self.output_buffer.size();
+        let timestamps = timestamps && self.context.supports_timestamps();
+
+        let (staging, timestamps_staging) = capture_errors(device, || {
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Encoder"),
+            });
+
+            let query_set = timestamps.then(|| device.create_query_set(&wgpu::QuerySetDescriptor{
+                label: Some("Timestamps"),
+                ty: wgpu::QueryType::Timestamp,
+                count: 2,
+            }));
+            let timestamp_writes = query_set.as_ref().map(|query_set| wgpu::ComputePassTimestampWrites{
+                query_set,
+                beginning_of_pass_write_index: Some(0),
+                end_of_pass_write_index: Some(1),
+            });
+
+            encode_dispatch_timed(&mut encoder, &compute_pipeline, &bind_group, self.dispatch_len(), device.limits().max_compute_workgroups_per_dimension, timestamp_writes);
+
+            let staging = self.context.staging.acquire(device, size);
+            encoder.copy_buffer_to_buffer(&self.output_buffer, 0, &staging, 0, size);
+
+            let timestamps_staging = query_set.map(|query_set| {
+                let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor{
+                    label: Some("Timestamps Resolve Buffer"),
+                    size: TIMESTAMPS_SIZE,
+                    usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+                    mapped_at_creation: false,
+                });
+                encoder.resolve_query_set(&query_set, 0..2, &resolve_buffer, 0);
+
+                let timestamps_staging = self.context.staging.acquire(device, TIMESTAMPS_SIZE);
+                encoder.copy_buffer_to_buffer(&resolve_buffer, 0, &timestamps_staging, 0, TIMESTAMPS_SIZE);
+
+                timestamps_staging
+            });
+
+            self.context.queue.submit(Some(encoder.finish()));
+
+            (staging, timestamps_staging)
+        })?;
+
+        Ok(PendingOutputs{
+            readback: Readback::pooled(self.context.clone(), staging, size),
+            timestamps: timestamps_staging.map(|staging| Readback::pooled(self.context.clone(), staging, TIMESTAMPS_SIZE)),
+            timestamp_period: self.context.queue.get_timestamp_period(),
+            precision: self.precision,
+            output_shapes: sample_output_shapes(&self.output_shape, &self.output_shapes, self.output_len, self.samples_count as usize),
+        })
+    }
+    /// Run operation and return data
+    /// Error if shader is not set, or wgpu reports an error
+    pub async fn run(&self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.submit()?.outputs().await
+    }
+}
+
+/// Outputs of submitted operation, that are read back when awaited
+pub struct PendingOutputs{
+    readback: Readback,
+    /// Readback of timestamps at beginning and end of compute pass
+    timestamps: Option<Readback>,
+    timestamp_period: f32,
+    /// Precision of values in read back output
+    precision: GpuPrecision,
+    /// Output shape of every sample, in order of outputs
+    output_shapes: Vec<Vec<u32>>,
+}
+
+impl PendingOutputs{
+    /// Returns true if outputs are already on cpu side
+    pub fn is_ready(&self) -> bool{
+        self.readback.is_ready()
+    }
+    /// Wait for readback and split it into output of every sample
+    /// Error if output could not be read back
+    pub async fn outputs(self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        Ok(self.outputs_timed().await?.0)
+    }
+    /// Wait for readback and split it into output of every sample, with gpu time of compute pass
+    /// Gpu time is None if operation was not submitted with timestamps
+    /// Error if output or timestamps could not be read back
+    pub async fn outputs_timed(self) -> Result<(Vec<Tensor<f32>>, Option<Duration>), GpuError>{
+        let output_data: Vec<f32> = self.precision.decode(self.readback.await?);
+        let gpu_time = read_gpu_time(self.timestamps, self.timestamp_period).await?;
+
+        Ok((split_outputs(&output_data, &self.output_shapes), gpu_time))
+    }
+    /// Same as outputs_timed, but outputs are f64 tensors, with exact values of f64 buffers
+    /// Error if output or timestamps could not be read back
+    pub async fn outputs_timed_f64(self) -> Result<(Vec<Tensor<f64>>, Option<Duration>), GpuError>{
+        let output_data: Vec<f64> = self.precision.decode_f64(self.readback.await?);
+        let gpu_time = read_gpu_time(self.timestamps, self.timestamp_period).await?;
+
+        Ok((split_outputs(&output_data, &self.output_shapes), gpu_time))
+    }
+}
+
+/// Returns gpu time between timestamps, None if there are no timestamps
+async fn read_gpu_time(timestamps: Option<Readback>, timestamp_period: f32) -> Result<Option<Duration>, GpuError>{
+    match timestamps{
+        Some(timestamps) => Ok(Some(timestamps_duration(&timestamps.await?, timestamp_period))),
+        None => Ok(None),
+    }
+}
+
+/// Split output data into tensor of every output shape
+fn split_outputs<T: Default + Clone>(output_data: &[T], output_shapes: &[Vec<u32>]) -> Vec<Tensor<T>>{
+    let mut offset = 0;
+    let mut output_vec: Vec<Tensor<T>> = Vec::with_capacity(output_shapes.len());
+
+    for shape in output_shapes{
+        let sample_size: usize = shape.iter().product::<u32>() as usize;
+        output_vec.push( Tensor::from_data( &output_data[offset..offset + sample_size], shape ).unwrap());
+        offset += sample_size;
+    }
+
+    output_vec
 }
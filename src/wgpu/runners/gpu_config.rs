@@ -0,0 +1,130 @@
+use wgpu::{BackendOptions, Backends, InstanceFlags, PowerPreference};
+
+use super::{gpu_error::GpuError, helpers::{DEFAULT_TILE_SIZE, TILE_SIZES}};
+
+/// Configuration of adapter, used to create GpuContext
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// let config = GpuConfig{
+///     power_preference: wgpu::PowerPreference::LowPower,
+///     ..Default::default()
+/// };
+///
+/// let runner = GpuRunner::with_config(config, 256, MemoryMetric::MB);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuConfig{
+    /// Backends, that adapter is searched on
+    pub backends: Backends,
+    pub power_preference: PowerPreference,
+    /// Use fallback (software) adapter, like llvmpipe or WARP, that works without gpu
+    pub force_fallback_adapter: bool,
+    /// Use adapter, that name contains this text, ignoring case
+    /// Names of available adapters are returned by enumerate_adapters
+    pub adapter_name: Option<String>,
+    /// Size of output tile, computed by one workgroup of matmul, forward and backward
+    /// propagation, one of TILE_SIZES
+    pub tile_size: u32,
+}
+
+impl Default for GpuConfig{
+    fn default() -> Self{
+        Self{
+            backends: Backends::PRIMARY,
+            power_preference: PowerPreference::default(),
+            force_fallback_adapter: false,
+            adapter_name: None,
+            tile_size: DEFAULT_TILE_SIZE,
+        }
+    }
+}
+
+impl GpuConfig{
+    /// Config of fallback (software) adapter on every backend
+    pub fn fallback() -> Self{
+        Self{
+            backends: Backends::all(),
+            force_fallback_adapter: true,
+            ..Default::default()
+        }
+    }
+    /// Config of adapter with name containing name
+    pub fn named(name: &str) -> Self{
+        Self{
+            adapter_name: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+    /// Check that config can be used to create device
+    /// Error if tile size is not one of TILE_SIZES
+    pub fn validate(&self) -> Result<(), GpuError>{
+        if !TILE_SIZES.contains(&self.tile_size){
+            return Err(GpuError::InvalidConfig(format!("tile size {} is not one of {:?}", self.tile_size, TILE_SIZES)));
+        }
+
+        Ok(())
+    }
+    /// Create wgpu instance on backends of config
+    pub fn create_instance(&self) -> wgpu::Instance{
+        wgpu::Instance::new(&wgpu::InstanceDescriptor{
+            backends: self.backends,
+            flags: InstanceFlags::default(),
+            backend_options: BackendOptions::default(),
+        })
+    }
+    /// Request adapter matching config from instance
+    /// Error if there is no matching adapter
+    pub async fn request_adapter(&self, instance: &wgpu::Instance) -> Result<wgpu::Adapter, GpuError>{
+        if let Some(name) = &self.adapter_name{
+            let adapters = instance.enumerate_adapters(self.backends);
+            let names: Vec<String> = adapters.iter().map(|adapter| adapter.get_info().name).collect();
+
+            return adapters.into_iter()
+                .find(|adapter| adapter.get_info().name.to_lowercase().contains(&name.to_lowercase()))
+                .ok_or(GpuError::NoAdapter(format!("no adapter named {}, available adapters: {:?}", name, names)));
+        }
+
+        let options = wgpu::RequestAdapterOptions{
+            power_preference: self.power_preference,
+            force_fallback_adapter: self.force_fallback_adapter,
+            compatible_surface: None,
+        };
+
+        instance.request_adapter(&options)
+            .await.map_err(|err| GpuError::NoAdapter(err.to_string()))
+    }
+}
+
+/// Adapter available on machine, with limits it supports
+#[derive(Debug, Clone)]
+pub struct AdapterDescription{
+    pub info: wgpu::AdapterInfo,
+    pub limits: wgpu::Limits,
+}
+
+/// Returns every adapter available on backends, used to choose name for GpuConfig
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// for adapter in enumerate_adapters(wgpu::Backends::all()){
+///     println!("{} ({:?}), max buffer size: {}", adapter.info.name, adapter.info.backend, adapter.limits.max_buffer_size);
+/// }
+/// ```
+pub fn enumerate_adapters(backends: Backends) -> Vec<AdapterDescription>{
+    let config = GpuConfig{
+        backends,
+        ..Default::default()
+    };
+
+    config.create_instance().enumerate_adapters(backends).iter()
+        .map(|adapter| AdapterDescription{
+            info: adapter.get_info(),
+            limits: adapter.limits(),
+        })
+        .collect()
+}
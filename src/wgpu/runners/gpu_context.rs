@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use super::{gpu_error::GpuError, gpu_config::GpuConfig, helpers::{gpu_init, gpu_init_with_config, MemoryMetric, WORKGROUP_SIZE}, pipeline_cache::{GpuPipeline, PipelineCache}, precision::GpuPrecision, readback::StagingPool, shaders::GpuOperations};
+
+/// Device and queue of one gpu, created once and shared by Arc between GpuRunners, GpuBuffers
+/// and GpuTensors
+/// Buffers can be exchanged only between objects using the same GpuContext
+pub struct GpuContext{
+    pub device: wgpu::Device,
+    pub queue: wgpu::Queue,
+    /// Limits the device was created with
+    pub limits: wgpu::Limits,
+    /// Features the device was created with, optional features are enabled when adapter
+    /// supports them
+    pub features: wgpu::Features,
+    /// Pipelines compiled on device, shared by everything using this context
+    pub pipelines: PipelineCache,
+    /// Staging buffers reused by readbacks of outputs
+    pub staging: StagingPool,
+}
+
+impl GpuContext{
+    /// Initialize device with max buffer size and return it as shared context
+    /// Max size is clamped to limits of adapter, 0 uses the biggest buffers adapter allows
+    /// Error if there is no adapter or device could not be created
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let context = GpuContext::init(256, &MemoryMetric::MB).await.unwrap();
+    ///
+    ///     let mut first_runner = GpuRunner::from_context(context.clone(), 256, MemoryMetric::MB);
+    ///     let mut second_runner = GpuRunner::from_context(context.clone(), 256, MemoryMetric::MB);
+    ///
+    ///     let tensor = Tensor::fill(1.0, &[2, 2]).to_gpu(&mut first_runner).await.unwrap();
+    ///
+    ///     second_runner.append_gpu(GpuSample::from_data(vec!{tensor}, vec!{1.0}, &[])).unwrap();
+    ///     let output: Vec<Tensor<f32>> = second_runner.add().await.unwrap();
+    ///
+    ///     assert_eq!(output[0].get_data(), &vec!{2.0; 4});
+    /// }
+    /// ```
+    pub async fn init(max_buffer_size: u64, metric: &MemoryMetric) -> Result<Arc<Self>, GpuError>{
+        let (device, queue) = gpu_init(max_buffer_size, metric).await?;
+
+        Ok(Arc::new(Self::from_device(device, queue)))
+    }
+    /// Initialize device on adapter chosen by config
+    /// Error if there is no adapter matching config
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let adapters = enumerate_adapters(wgpu::Backends::PRIMARY);
+    ///     if adapters.is_empty(){
+    ///         return;
+    ///     }
+    ///
+    ///     let config = GpuConfig::named(&adapters[0].info.name);
+    ///     let context = GpuContext::init_with_config(256, &MemoryMetric::MB, &config).await.unwrap();
+    ///
+    ///     let mut runner = GpuRunner::from_context(context, 256, MemoryMetric::MB);
+    ///     runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[])).unwrap();
+    ///
+    ///     assert_eq!(runner.add().await.unwrap()[0].get_data(), &vec!{2.0; 4});
+    /// }
+    /// ```
+    pub async fn init_with_config(max_buffer_size: u64, metric: &MemoryMetric, config: &GpuConfig) -> Result<Arc<Self>, GpuError>{
+        let (device, queue) = gpu_init_with_config(max_buffer_size, metric, config).await?;
+
+        Ok(Arc::new(Self{
+            pipelines: PipelineCache::with_tile_size(config.tile_size),
+            ..Self::from_device(device, queue)
+        }))
+    }
+    /// Initialize device on fallback (software) adapter, that gives the same results on every
+    /// machine it exists on
+    /// Error if there is no fallback adapter
+    pub async fn init_fallback(max_buffer_size: u64, metric: &MemoryMetric) -> Result<Arc<Self>, GpuError>{
+        Self::init_with_config(max_buffer_size, metric, &GpuConfig::fallback()).await
+    }
+    /// Create context from already created device and queue
+    pub fn from_device(device: wgpu::Device, queue: wgpu::Queue) -> Self{
+        let limits = device.limits();
+        let features = device.features();
+
+        Self{
+            device,
+            queue,
+            limits,
+            features,
+            pipelines: PipelineCache::new(),
+            staging: StagingPool::new(),
+        }
+    }
+    /// Returns compiled pipeline of operation, compiling it on first use
+    /// Error if shader does not compile
+    pub fn get_pipeline(&self, operation: &GpuOperations) -> Result<Arc<GpuPipeline>, GpuError>{
+        self.pipelines.get(&self.device, operation)
+    }
+    /// Returns compiled pipeline of operation, that reads and writes values of precision,
+    /// compiling it on first use
+    /// Error if device does not support precision, operation has no shader for it, or shader
+    /// does not compile
+    pub fn get_pipeline_with_precision(&self, operation: &GpuOperations, precision: GpuPrecision) -> Result<Arc<GpuPipeline>, GpuError>{
+        if !self.supports_precision(precision){
+            return Err(GpuError::InvalidConfig(format!("device does not support {:?} shaders", precision)));
+        }
+
+        self.pipelines.get_with_precision(&self.device, operation, precision)
+    }
+    /// Compile pipelines of all operations, so first run of every operation does not have to
+    /// wait for compilation
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let context = GpuContext::init(256, &MemoryMetric::MB).await.unwrap();
+    ///     context.prewarm().unwrap();
+    ///
+    ///     assert_eq!(context.pipelines.len(), GpuOperations::all().len());
+    /// }
+    /// ```
+    pub fn prewarm(&self) -> Result<(), GpuError>{
+        for operation in GpuOperations::all(){
+            self.get_pipeline(&operation)?;
+        }
+
+        Ok(())
+    }
+    /// Returns true if compute passes can write gpu timestamps, used by profiling
+    pub fn supports_timestamps(&self) -> bool{
+        self.features.contains(wgpu::Features::TIMESTAMP_QUERY)
+    }
+    /// Returns true if shaders can use half precision values, f16 buffers need it
+    pub fn supports_f16(&self) -> bool{
+        self.features.contains(wgpu::Features::SHADER_F16)
+    }
+    /// Returns true if shaders can use double precision values, f64 buffers need it
+    pub fn supports_f64(&self) -> bool{
+        self.features.contains(wgpu::Features::SHADER_F64)
+    }
+    /// Returns true if buffers of operations can hold values of precision
+    pub fn supports_precision(&self, precision: GpuPrecision) -> bool{
+        match precision{
+            GpuPrecision::F32 => true,
+            #[cfg(feature = "half")]
+            GpuPrecision::F16 => self.supports_f16(),
+            GpuPrecision::F64 => self.supports_f64(),
+        }
+    }
+    /// Returns max size of buffer, that can be bound to operation
+    pub fn max_buffer_size(&self) -> u64{
+        self.limits.max_storage_buffer_binding_size as u64
+    }
+    /// Returns max number of outputs, that one dispatch covers without splitting workgroups into
+    /// second dimension
+    pub fn max_dispatch_len(&self) -> usize{
+        self.limits.max_compute_workgroups_per_dimension as usize * WORKGROUP_SIZE as usize
+    }
+}
@@ -1,11 +1,33 @@
-use crate::prelude::Sample;
+use std::{borrow::Cow, ops::Range};
 
-use super::helpers::{get_size_using_metric, MemoryMetric};
+use crate::{prelude::{GpuBatch, GpuError, GpuSample, Sample}, tensor::Tensor};
+
+use super::{helpers::{get_size_using_metric, MemoryMetric}, precision::GpuPrecision};
+
+/// Bit set in metadata offset of input shared by every sample, rest of offset is index in
+/// buffer of shared inputs
+pub(crate) const SHARED_TENSOR: u32 = 1 << 31;
+/// Words in front of table of samples in metadata of ragged chunk
+const RAGGED_HEADER_LEN: usize = 4;
+/// Words of every sample in table of samples of ragged chunk, offsets of inputs, outputs and metadata
+const RAGGED_SAMPLE_LEN: usize = 3;
+
+/// Input shared by every sample, stored once in shared_inputs of GpuData
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedInput{
+    /// Index of input among inputs of sample
+    pub input: usize,
+    pub shape: Vec<u32>,
+    /// Index of the first element in shared_inputs
+    pub offset: usize,
+}
 
 /// Data with all gpu operations that will happen at the same time
 pub struct GpuData{
     pub flat_inputs: Vec<f32>,
     pub flat_shapes: Vec<u32>,
+    /// Rank of every input of sample, set with flat_shapes
+    pub ranks: Vec<u32>,
     pub params: Vec<f32>,
     pub output_len: usize,
     pub output_shape: Vec<u32>,
@@ -21,6 +43,35 @@ pub struct GpuData{
     max_chunk_len: usize,
     pub chunks: usize,
     samples_per_chunk: usize,
+
+    /// Samples can have other shapes, with the same count and ranks of inputs
+    /// flat_shapes and ranks are shapes of the first sample
+    pub ragged: bool,
+    /// Flat shapes of every sample of ragged batch
+    pub sample_shapes: Vec<Vec<u32>>,
+    /// Offset of inputs of every sample of ragged batch in flat_inputs, followed by length of
+    /// flat_inputs
+    pub sample_offsets: Vec<usize>,
+    /// Output shape of every sample of ragged batch, set before operation runs
+    pub sample_output_shapes: Vec<Vec<u32>>,
+    /// First sample of every chunk of ragged batch, followed by count of samples
+    chunk_starts: Vec<usize>,
+
+    /// Data of inputs shared by every sample, uploaded once and bound apart from flat_inputs
+    pub shared_inputs: Vec<f32>,
+    /// Inputs shared by every sample, sorted by index of input
+    /// flat_shapes and ranks include shapes of shared inputs
+    pub shared: Vec<SharedInput>,
+
+    /// Precision of values in gpu buffers, inputs are converted from f32 when they are uploaded
+    /// Chunks are sized by bytes of values in this precision
+    pub precision: GpuPrecision,
+
+    /// Exact values of inputs of f64 samples, flat_inputs holds them rounded to f32
+    /// Empty if samples are f32
+    pub flat_inputs_f64: Vec<f64>,
+    /// Exact values of f64 inputs shared by every sample, shared_inputs holds them rounded to f32
+    pub shared_inputs_f64: Vec<f64>,
 }
 
 impl GpuData{
@@ -29,6 +80,7 @@ impl GpuData{
         Self{
             flat_inputs: Vec::new(),
             flat_shapes: Vec::new(),
+            ranks: Vec::new(),
             params: Vec::new(),
             output_len: 0,
             output_shape: Vec::new(),
@@ -44,6 +96,20 @@ impl GpuData{
             max_chunk_len: 0,
             chunks: 0,
             samples_per_chunk: 0,
+
+            ragged: false,
+            sample_shapes: Vec::new(),
+            sample_offsets: vec!{0},
+            sample_output_shapes: Vec::new(),
+            chunk_starts: Vec::new(),
+
+            shared_inputs: Vec::new(),
+            shared: Vec::new(),
+
+            precision: GpuPrecision::F32,
+
+            flat_inputs_f64: Vec::new(),
+            shared_inputs_f64: Vec::new(),
         }
     }
     /// Create new empty GpuData with input.capacity = capacity
@@ -51,6 +117,7 @@ impl GpuData{
         Self{
             flat_inputs: Vec::with_capacity(capacity),
             flat_shapes: Vec::new(),
+            ranks: Vec::new(),
             params: Vec::new(),
             output_len: 0,
             output_shape: Vec::new(),
@@ -66,6 +133,20 @@ impl GpuData{
             max_chunk_len: 0,
             chunks: 0,
             samples_per_chunk: 0,
+
+            ragged: false,
+            sample_shapes: Vec::new(),
+            sample_offsets: vec!{0},
+            sample_output_shapes: Vec::new(),
+            chunk_starts: Vec::new(),
+
+            shared_inputs: Vec::new(),
+            shared: Vec::new(),
+
+            precision: GpuPrecision::F32,
+
+            flat_inputs_f64: Vec::new(),
+            shared_inputs_f64: Vec::new(),
         }
     }
     /// Disable params for GpuData
@@ -102,32 +183,189 @@ impl GpuData{
         self.single_output = false;
         self.output_len = self.output_len * self.samples_count.max(1) as usize;
     }
+    /// Enable ragged batch, so samples can have other shapes, with the same count and ranks of
+    /// inputs
+    /// Samples appended before keep their shapes
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut data = GpuData::new();
+    /// data.enable_ragged();
+    ///
+    /// data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3])}, vec!{}, &[])).unwrap();
+    /// data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[4, 3])}, vec!{}, &[])).unwrap();
+    ///
+    /// assert_eq!(data.sample_input_shapes(1), vec!{vec!{4, 3}});
+    /// assert_eq!(data.sample_offsets, vec!{0, 6, 18});
+    /// ```
+    pub fn enable_ragged(&mut self){
+        if self.ragged{
+            return;
+        }
+
+        self.ragged = true;
+        self.sample_shapes = vec!{self.flat_shapes.clone(); self.samples_count as usize};
+        self.sample_offsets = (0..=self.samples_count as usize).map(|i| i * self.input_per_sample).collect();
+    }
+    /// Returns shape of every input of sample of ragged batch, or of every sample if batch is
+    /// not ragged
+    pub fn sample_input_shapes(&self, sample: usize) -> Vec<Vec<u32>>{
+        if !self.ragged{
+            return self.input_shapes();
+        }
+
+        split_shapes(&self.sample_shapes[sample], &self.ranks)
+    }
+    /// Set output shape of every sample of ragged batch, output_shape is set to shape of the
+    /// first sample
+    pub fn set_sample_outputs(&mut self, output_shapes: Vec<Vec<u32>>){
+        let lens: Vec<usize> = output_shapes.iter().map(|shape| shape.iter().product::<u32>() as usize).collect();
+
+        if let Some(first) = output_shapes.first(){
+            self.output_shape = first.clone();
+        }
+        self.output_per_sample = lens.iter().copied().max().unwrap_or(0);
+        self.output_len = lens.iter().sum();
+
+        self.sample_output_shapes = output_shapes;
+    }
+    /// Split ragged batch into chunks of whole samples, so inputs and outputs of every chunk fit
+    /// in max_len values, outputs of chunk fit in max_dispatch_len invocations, and metadata
+    /// of chunk fits in max_metadata_len words
+    fn prepare_ragged_chunking(&mut self, max_len: usize, max_dispatch_len: usize, max_metadata_len: usize){
+        let max_outputs = max_len.min(max_dispatch_len);
+
+        self.chunk_starts = vec!{0};
+        let (mut inputs, mut outputs, mut metadata) = (0, 0, RAGGED_HEADER_LEN);
+        for sample in 0..self.samples_count as usize{
+            let input_len = self.sample_offsets[sample + 1] - self.sample_offsets[sample];
+            let output_len = self.sample_output_len(sample);
+            let metadata_len = self.sample_metadata_len(sample);
+
+            // sample bigger than buffer gets its own chunk
+            let start = *self.chunk_starts.last().unwrap();
+            if sample > start && (inputs + input_len > max_len || outputs + output_len > max_outputs || metadata + metadata_len > max_metadata_len){
+                self.chunk_starts.push(sample);
+                (inputs, outputs, metadata) = (0, 0, RAGGED_HEADER_LEN);
+            }
+
+            inputs += input_len;
+            outputs += output_len;
+            metadata += metadata_len;
+        }
+        self.chunk_starts.push(self.samples_count as usize);
+
+        self.chunks = self.chunk_starts.len() - 1;
+    }
+    /// Returns length of metadata of sample of ragged batch, its row in table of samples and its
+    /// block of shapes
+    fn sample_metadata_len(&self, sample: usize) -> usize{
+        let tensors = self.ranks.len() + 1;
+        let output_rank = self.sample_output_shapes.get(sample).map_or(self.output_shape.len(), |shape| shape.len());
+        let rank = self.ranks.iter().map(|rank| *rank as usize).chain([output_rank]).max().unwrap_or(0).max(1);
+
+        RAGGED_SAMPLE_LEN + 3 + tensors + 2 * tensors * rank
+    }
+    /// Returns output length of sample of ragged batch
+    fn sample_output_len(&self, sample: usize) -> usize{
+        match self.sample_output_shapes.get(sample){
+            Some(shape) => shape.iter().product::<u32>() as usize,
+            None => self.output_per_sample,
+        }
+    }
+    /// Returns range of samples in chunk of ragged batch, all samples if chunk_id is out of
+    /// chunks
+    pub fn chunk_samples(&self, chunk_id: usize) -> std::ops::Range<usize>{
+        if !self.ragged || chunk_id >= self.chunks{
+            return 0..self.samples_count as usize;
+        }
+
+        self.chunk_starts[chunk_id]..self.chunk_starts[chunk_id + 1]
+    }
+    /// Returns output shape of every sample of chunk of ragged batch, empty if batch is not
+    /// ragged or output shapes were not set
+    pub fn chunk_output_shapes(&self, chunk_id: usize) -> Vec<Vec<u32>>{
+        if !self.ragged{
+            return Vec::new();
+        }
+
+        self.sample_output_shapes.get(self.chunk_samples(chunk_id)).map(|shapes| shapes.to_vec()).unwrap_or_default()
+    }
     
     pub fn prepare_chunking(&mut self, max_buffer_size: u64, metric: &MemoryMetric){
-        let max_chunk_len = (get_size_using_metric(max_buffer_size, metric) / size_of::<f32>() as u64) as usize;
+        let max_chunk_len = (get_size_using_metric(max_buffer_size, metric) / self.precision.size() as u64) as usize;
 
         if self.input_per_sample == 0{
             println!("Insert data before enabling chunking");
             return
         }
+        if self.ragged{
+            let max_metadata_len = get_size_using_metric(max_buffer_size, metric) as usize / size_of::<u32>();
+            return self.prepare_ragged_chunking(max_chunk_len, usize::MAX, max_metadata_len);
+        }
         self.max_chunk_len = max_chunk_len - (max_chunk_len % self.input_per_sample);
         self.chunks = (self.flat_inputs.len() + self.max_chunk_len-1)/self.max_chunk_len;
     }
     pub fn prepare_chunking_alt(&mut self, max_buffer_size: u64){
-        let max_chunk_len = max_buffer_size as usize / size_of::<f32>();
+        let max_chunk_len = max_buffer_size as usize / self.precision.size();
 
         if self.input_per_sample == 0{
             println!("Insert data before enabling chunking");
             return
         }
-        self.max_chunk_len = max_chunk_len - (max_chunk_len % self.input_per_sample);
+        if self.ragged{
+            return self.prepare_ragged_chunking(max_chunk_len, usize::MAX, max_buffer_size as usize / size_of::<u32>());
+        }
+        // Sample bigger than buffer gets its own chunk
+        self.max_chunk_len = (max_chunk_len - (max_chunk_len % self.input_per_sample)).max(self.input_per_sample);
+        self.chunks = self.flat_inputs.len().div_ceil(self.max_chunk_len);
+    }
+    /// Prepare chunking, so inputs and outputs of every chunk fit in max_buffer_size bytes, and
+    /// outputs of chunk fit in max_dispatch_len invocations
+    /// Error if there are no samples
+    pub fn prepare_chunking_auto(&mut self, max_buffer_size: u64, max_dispatch_len: usize) -> Result<(), GpuError>{
+        if self.input_per_sample == 0{
+            return Err(GpuError::NoData);
+        }
+
+        let max_len = max_buffer_size as usize / self.precision.size();
+        if self.ragged{
+            self.prepare_ragged_chunking(max_len, max_dispatch_len, max_buffer_size as usize / size_of::<u32>());
+            return Ok(());
+        }
+
+        let mut samples_per_chunk = max_len / self.input_per_sample;
+        if !self.single_output && self.output_per_sample != 0{
+            samples_per_chunk = samples_per_chunk
+                .min(max_len / self.output_per_sample)
+                .min(max_dispatch_len / self.output_per_sample);
+        }
+
+        self.max_chunk_len = samples_per_chunk.max(1) * self.input_per_sample;
         self.chunks = (self.flat_inputs.len() + self.max_chunk_len-1)/self.max_chunk_len;
+
+        Ok(())
     }
 
     // Flat input, samples in chunk, output_in_chunk
     pub fn get_chunk(&self, chunk_id: usize) -> Option<(&[f32], usize, usize)>{
+        let (inputs, samples_in_chunk, output_in_chunk) = self.chunk_range(chunk_id);
+
+        Some((&self.flat_inputs[inputs], samples_in_chunk, output_in_chunk))
+    }
+    /// Returns range of chunk in flat_inputs, samples in chunk and output_in_chunk
+    fn chunk_range(&self, chunk_id: usize) -> (std::ops::Range<usize>, usize, usize){
         if chunk_id>=self.chunks{
-            return Some((&self.flat_inputs[..], self.samples_count as usize, self.output_len))
+            return (0..self.flat_inputs.len(), self.samples_count as usize, self.output_len)
+        }
+        if self.ragged{
+            let samples = self.chunk_samples(chunk_id);
+            let output_in_chunk = samples.clone().map(|sample| self.sample_output_len(sample)).sum();
+            let inputs = self.sample_offsets[samples.start]..self.sample_offsets[samples.end];
+
+            return (inputs, samples.len(), output_in_chunk);
         }
 
         let samples_in_chunk = (((chunk_id+1) * self.max_chunk_len).min(self.flat_inputs.len()) - chunk_id * self.max_chunk_len) / self.input_per_sample;
@@ -140,54 +378,312 @@ impl GpuData{
             output_in_chunk = samples_in_chunk * self.output_per_sample;
         }
 
-        return Some((&self.flat_inputs[chunk_id * self.max_chunk_len .. (((chunk_id+1) * self.max_chunk_len)).min(self.flat_inputs.len())], samples_in_chunk, output_in_chunk));
+        (chunk_id * self.max_chunk_len .. ((chunk_id+1) * self.max_chunk_len).min(self.flat_inputs.len()), samples_in_chunk, output_in_chunk)
     }
+    /// Returns bytes of inputs of chunk in precision of gpu buffers
+    /// f64 buffers get exact values of f64 samples, other values are converted from flat_inputs
+    pub fn chunk_bytes(&self, chunk_id: usize, precision: GpuPrecision) -> Cow<'_, [u8]>{
+        let (inputs, _, _) = self.chunk_range(chunk_id);
 
-    /// Append Sample to GpuData and set GpuData shapes and params to sample shapes and params
-    /// Is you want to skip later part, disable shapes or params
-    pub fn append(&mut self, sample: Sample) -> bool{
-        /*if !(self.output_shape.len() == 0 || self.output_shape == sample.output_shape){
-            return false;
-        }*/
+        if self.is_f64(){
+            return precision.encode_f64(&self.flat_inputs_f64[inputs]);
+        }
+        precision.encode(&self.flat_inputs[inputs])
+    }
+    /// Returns bytes of shared inputs in precision of gpu buffers, one zero value if there are
+    /// no shared inputs, so shaders reading them can be bound
+    pub fn shared_bytes(&self, precision: GpuPrecision) -> Cow<'_, [u8]>{
+        if self.shared_inputs.is_empty(){
+            return Cow::Owned(precision.encode(&[0.0]).into_owned());
+        }
+        if !self.shared_inputs_f64.is_empty(){
+            return precision.encode_f64(&self.shared_inputs_f64);
+        }
+        precision.encode(&self.shared_inputs)
+    }
+    /// Returns true if samples were appended with append_f64
+    pub fn is_f64(&self) -> bool{
+        !self.flat_inputs_f64.is_empty()
+    }
+
+    /// Share tensor as input at index of every sample, so it is stored once and samples are
+    /// appended without it
+    /// Inputs must be shared before samples are appended, error otherwise, or if input is already
+    /// shared
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut data = GpuData::new();
+    /// data.share(0, &Tensor::fill(1.0, &[2, 3])).unwrap();
+    /// data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[3, 1])}, vec!{}, &[2, 1])).unwrap();
+    /// data.append(Sample::from_data(vec!{Tensor::fill(2.0, &[3, 1])}, vec!{}, &[2, 1])).unwrap();
+    ///
+    /// assert_eq!(data.input_shapes(), vec!{vec!{2, 3}, vec!{3, 1}});
+    /// assert_eq!(data.flat_inputs.len(), 6);
+    /// assert_eq!(data.shared_inputs.len(), 6);
+    /// ```
+    pub fn share(&mut self, input: usize, tensor: &Tensor<f32>) -> Result<(), GpuError>{
+        if !self.shared_inputs_f64.is_empty(){
+            return Err(GpuError::InvalidConfig("f32 inputs can not be shared with f64 inputs".to_string()));
+        }
+
+        self.share_input(input, tensor)
+    }
+    /// Share f64 tensor as input at index of every sample, the same as share, but f64 buffers
+    /// get its values without rounding
+    /// f64 inputs can not be shared with f32 inputs
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut data = GpuData::new();
+    /// data.share_f64(0, &Tensor::fill(0.1, &[2, 3])).unwrap();
+    ///
+    /// assert_eq!(data.shared_inputs_f64, vec!{0.1; 6});
+    /// assert_eq!(data.shared_inputs, vec!{0.1; 6});
+    /// ```
+    pub fn share_f64(&mut self, input: usize, tensor: &Tensor<f64>) -> Result<(), GpuError>{
+        if self.shared_inputs.len() != self.shared_inputs_f64.len(){
+            return Err(GpuError::InvalidConfig("f64 inputs can not be shared with f32 inputs".to_string()));
+        }
+
+        self.share_input(input, &tensor.to_f32())?;
+        self.shared_inputs_f64.extend_from_slice(tensor.get_data());
+
+        Ok(())
+    }
+    /// Share tensor as input at index of every sample
+    fn share_input(&mut self, input: usize, tensor: &Tensor<f32>) -> Result<(), GpuError>{
+        if self.samples_count != 0{
+            return Err(GpuError::InvalidConfig("inputs must be shared before samples are appended".to_string()));
+        }
+        if self.shared.iter().any(|shared| shared.input == input){
+            return Err(GpuError::InvalidConfig(format!("input {} is already shared", input)));
+        }
+
+        let position = self.shared.partition_point(|shared| shared.input < input);
+        self.shared.insert(position, SharedInput{
+            input,
+            shape: tensor.get_shape().to_vec(),
+            offset: self.shared_inputs.len(),
+        });
+        self.shared_inputs.extend_from_slice(tensor.get_data());
+
+        Ok(())
+    }
+    /// Returns true if input at index is shared by every sample
+    pub fn is_shared(&self, input: usize) -> bool{
+        self.shared.iter().any(|shared| shared.input == input)
+    }
+    /// Returns flat inputs of sample, without shared inputs
+    pub fn sample_inputs(&self, sample: usize) -> &[f32]{
+        &self.flat_inputs[self.sample_range(sample)]
+    }
+    /// Returns range of sample in flat inputs
+    pub fn sample_range(&self, sample: usize) -> Range<usize>{
+        if self.ragged{
+            return self.sample_offsets[sample]..self.sample_offsets[sample + 1];
+        }
+
+        sample * self.input_per_sample..(sample + 1) * self.input_per_sample
+    }
+    /// Insert shapes of shared inputs between shapes of inputs of sample
+    /// None if shared inputs have index past inputs of sample
+    fn with_shared(&self, shapes: &[u32], ranks: &[u32]) -> Option<(Vec<u32>, Vec<u32>)>{
+        if self.shared.is_empty(){
+            return Some((shapes.to_vec(), ranks.to_vec()));
+        }
+
+        let inputs = ranks.len() + self.shared.len();
+        if self.shared.iter().any(|shared| shared.input >= inputs){
+            return None;
+        }
+
+        let mut sample_shapes = split_shapes(shapes, ranks).into_iter();
+        let merged: Vec<Vec<u32>> = (0..inputs).map(|input| {
+            match self.shared.iter().find(|shared| shared.input == input){
+                Some(shared) => shared.shape.clone(),
+                None => sample_shapes.next().unwrap(),
+            }
+        }).collect();
+
+        Some((merged.concat(), merged.iter().map(|shape| shape.len() as u32).collect()))
+    }
+    /// Returns offset in shared_inputs of every input, None for inputs of sample
+    fn shared_offsets(&self) -> Vec<Option<u32>>{
+        let mut offsets = vec!{None; self.ranks.len()};
+        for shared in &self.shared{
+            offsets[shared.input] = Some(shared.offset as u32);
+        }
+
+        offsets
+    }
+
+    /// Set shapes, params and output of sample, shared by cpu and gpu samples
+    /// Error if sample does not match samples already appended
+    fn append_metadata(&mut self, shapes: &[u32], ranks: &[u32], params: &[f32], output_shape: &[u32], output_len: u32, input_len: usize) -> Result<(), GpuError>{
+        let Some((shapes, ranks)) = self.with_shared(shapes, ranks) else {
+            return Err(GpuError::InvalidShapes("shared inputs do not fit inputs of sample".to_string()));
+        };
+        let (shapes, ranks) = (shapes.as_slice(), ranks.as_slice());
+        if !self.shared.is_empty() && input_len == 0{
+            return Err(GpuError::InvalidShapes("sample needs inputs, that are not shared".to_string()));
+        }
 
-        if self.flat_shapes.len() != 0 && self.flat_shapes != sample.shapes{
-            println!("Shapes does not match");
-            return false;
+        if self.ragged && self.samples_count != 0 && self.ranks != ranks{
+            return Err(GpuError::InvalidShapes(format!("ranks {:?} of sample do not match ranks {:?} of samples", ranks, self.ranks)));
         }
-        if self.params.len() != 0 && self.params != sample.params{
-            println!("Params does not match");
-            return false;
+        if !self.ragged && !self.flat_shapes.is_empty() && (self.flat_shapes != shapes || self.ranks != ranks){
+            return Err(GpuError::InvalidShapes(format!("shapes {:?} of sample do not match shapes {:?} of samples", shapes, self.flat_shapes)));
+        }
+        if self.params.len() != 0 && self.params != params{
+            return Err(GpuError::InvalidConfig(format!("params {:?} of sample do not match params {:?} of samples", params, self.params)));
         }
-        /*if self.output_shape.len() != 0 && self.output_shape != sample.output_shape{
-            println!("Params does not match");
-            return false;
-        }*/
 
-        if self.use_shapes && self.flat_shapes.len() == 0{
-            self.flat_shapes = sample.shapes;
+        if (self.use_shapes || self.ragged) && self.flat_shapes.is_empty(){
+            self.flat_shapes = shapes.to_vec();
+            self.ranks = ranks.to_vec();
         }
         if self.use_params && self.params.len() == 0{
-            self.params = sample.params;
+            self.params = params.to_vec();
         }
         if self.output_shape.len() == 0{
-            self.output_shape = sample.output_shape;
+            self.output_shape = output_shape.to_vec();
         }
 
-        self.flat_inputs.extend(sample.inputs);
         if self.single_output{
-            self.output_len = sample.output_len as usize;
+            self.output_len = output_len as usize;
         }
         else{
-            self.output_len += sample.output_len as usize;
+            self.output_len += output_len as usize;
         }
 
-        self.input_per_sample = sample.input_len;
+        if self.ragged{
+            self.sample_shapes.push(shapes.to_vec());
+            self.sample_offsets.push(self.sample_offsets.last().unwrap() + input_len);
+        }
+
+        self.input_per_sample = input_len;
 
         self.samples_count += 1;
 
-        self.output_per_sample = sample.output_len as usize;
+        self.output_per_sample = output_len as usize;
+
+        Ok(())
+    }
+
+    /// Append Sample to GpuData and set GpuData shapes and params to sample shapes and params
+    /// Is you want to skip later part, disable shapes or params
+    /// Error if sample does not match samples already appended, or they are f64
+    pub fn append(&mut self, sample: Sample) -> Result<(), GpuError>{
+        if self.is_f64(){
+            return Err(GpuError::InvalidConfig("f32 samples can not be mixed with f64 samples".to_string()));
+        }
+        self.append_metadata(&sample.shapes, &sample.ranks, &sample.params, &sample.output_shape, sample.output_len, sample.input_len)?;
+
+        self.flat_inputs.extend(sample.inputs);
+
+        Ok(())
+    }
+
+    /// Append inputs of sample straight from tensors, without building Sample, so data is copied
+    /// once, into flat_inputs
+    /// With GpuData created by with_capacity, no memory is reallocated while appending
+    /// Error if sample does not match samples already appended, or they are f64
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let weights: Tensor<f32> = Tensor::fill(1.0, &[2, 3]);
+    /// let inputs: Tensor<f32> = Tensor::fill(2.0, &[3, 1]);
+    ///
+    /// let mut gpu_data = GpuData::with_capacity(2 * 9);
+    /// gpu_data.append_tensors(&[&weights, &inputs], &[], &[2, 1]).unwrap();
+    /// gpu_data.append_tensors(&[&weights, &inputs], &[], &[2, 1]).unwrap();
+    ///
+    /// assert_eq!(gpu_data.samples_count, 2);
+    /// assert_eq!(gpu_data.flat_inputs.len(), 18);
+    /// ```
+    pub fn append_tensors(&mut self, inputs: &[&Tensor<f32>], params: &[f32], output_shape: &[u32]) -> Result<(), GpuError>{
+        if self.is_f64(){
+            return Err(GpuError::InvalidConfig("f32 samples can not be mixed with f64 samples".to_string()));
+        }
+
+        self.append_inputs(inputs, params, output_shape)
+    }
+    /// Append inputs of sample from tensors into flat_inputs
+    fn append_inputs(&mut self, inputs: &[&Tensor<f32>], params: &[f32], output_shape: &[u32]) -> Result<(), GpuError>{
+        let shapes: Vec<u32> = inputs.iter()
+            .flat_map(|tensor| tensor.get_shape().iter().copied())
+            .collect();
+        let ranks: Vec<u32> = inputs.iter().map(|tensor| tensor.get_shape().len() as u32).collect();
+
+        let input_len = inputs.iter().map(|tensor| tensor.get_data().len()).sum();
+        let output_len: u32 = output_shape.iter().product();
+
+        self.append_metadata(&shapes, &ranks, params, output_shape, output_len, input_len)?;
+
+        for tensor in inputs{
+            self.flat_inputs.extend_from_slice(tensor.get_data());
+        }
+
+        Ok(())
+    }
+    /// Append sample of f64 tensors, that f64 buffers get without rounding
+    /// flat_inputs holds values rounded to f32, so every operation can run on them in f32
+    /// f64 samples can not be mixed with f32 samples
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let weights: Tensor<f64> = Tensor::fill(0.1, &[2, 3]);
+    /// let inputs: Tensor<f64> = Tensor::fill(2.0, &[3, 1]);
+    ///
+    /// let mut gpu_data = GpuData::new();
+    /// gpu_data.append_f64(&[&weights, &inputs], &[], &[2, 1]).unwrap();
+    ///
+    /// assert!(gpu_data.is_f64());
+    /// assert_eq!(gpu_data.flat_inputs_f64.len(), 9);
+    /// assert_eq!(gpu_data.flat_inputs[0], 0.1f32);
+    /// ```
+    pub fn append_f64(&mut self, inputs: &[&Tensor<f64>], params: &[f32], output_shape: &[u32]) -> Result<(), GpuError>{
+        if self.flat_inputs.len() != self.flat_inputs_f64.len(){
+            return Err(GpuError::InvalidConfig("f64 samples can not be mixed with f32 samples".to_string()));
+        }
+
+        let rounded: Vec<Tensor<f32>> = inputs.iter().map(|tensor| tensor.to_f32()).collect();
+        let rounded: Vec<&Tensor<f32>> = rounded.iter().collect();
+        self.append_inputs(&rounded, params, output_shape)?;
+
+        for tensor in inputs{
+            self.flat_inputs_f64.extend_from_slice(tensor.get_data());
+        }
+
+        Ok(())
+    }
+    /// Append every sample of GpuBatch, inputs are not copied, because they already are on gpu
+    /// Error if batch does not match samples already appended
+    pub fn append_batch(&mut self, batch: &GpuBatch, params: &[f32], output_shape: &[u32]) -> Result<(), GpuError>{
+        let shapes: Vec<u32> = batch.get_input_shapes().iter().flatten().copied().collect();
+        let ranks: Vec<u32> = batch.get_input_shapes().iter().map(|shape| shape.len() as u32).collect();
+        let output_len: u32 = output_shape.iter().product();
+
+        for _ in 0..batch.len(){
+            self.append_metadata(&shapes, &ranks, params, output_shape, output_len, batch.sample_len())?;
+        }
 
-        true
+        Ok(())
+    }
+    /// Append shapes and params of GpuSample, inputs are not copied, because they already are on
+    /// gpu
+    /// Error if sample does not match samples already appended
+    pub fn append_gpu(&mut self, sample: &GpuSample) -> Result<(), GpuError>{
+        self.append_metadata(&sample.shapes, &sample.ranks, &sample.params, &sample.output_shape, sample.output_len, sample.input_len)
     }
     /// Manually set params for GpuData
     /// Most of the time you wont need to do it, because appending by default changes them for
@@ -196,7 +692,149 @@ impl GpuData{
         self.params = params;
     }
 
+    /// Returns shape of every input of sample
+    pub fn input_shapes(&self) -> Vec<Vec<u32>>{
+        split_shapes(&self.flat_shapes, &self.ranks)
+    }
+    /// Returns metadata of shapes, read by shaders from binding 4
+    /// [rank, tensors, sample_size, offset of every tensor..., shape and strides of every tensor...]
+    /// Output is the last tensor, tensors with lower rank are padded with leading dims of 1, and
+    /// dims of 1 have stride 0, so shaders can broadcast them
+    /// Offsets of shared inputs have the highest bit set, and point into buffer of shared inputs
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut data = GpuData::new();
+    /// data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3]), Tensor::fill(1.0, &[3])}, vec!{}, &[2, 3])).unwrap();
+    ///
+    /// assert_eq!(data.metadata(), vec!{
+    ///     2, 3, 9,
+    ///     0, 6, 0,
+    ///     2, 3, 3, 1,
+    ///     1, 3, 0, 1,
+    ///     2, 3, 3, 1,
+    /// });
+    /// ```
+    pub fn metadata(&self) -> Vec<u32>{
+        let mut shapes = self.input_shapes();
+        shapes.push(self.output_shape.clone());
+
+        shapes_metadata(&shapes, self.input_per_sample, &self.shared_offsets())
+    }
+
+    /// Returns metadata of chunk, the same as metadata if batch is not ragged
+    /// Ragged batch has tables of samples in chunk, in front of metadata of every sample:
+    /// [0, tensors, 0, samples, input offset, output offset and metadata offset of every sample..., metadata of every sample...]
+    /// Offsets of inputs and outputs are counted from start of chunk
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let mut data = GpuData::new();
+    /// data.enable_ragged();
+    /// data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2])}, vec!{}, &[])).unwrap();
+    /// data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[3])}, vec!{}, &[])).unwrap();
+    /// data.set_sample_outputs(vec!{vec!{2}, vec!{3}});
+    ///
+    /// assert_eq!(data.chunk_metadata(0), vec!{
+    ///     0, 2, 0, 2,
+    ///     0, 0, 10,
+    ///     2, 2, 19,
+    ///     1, 2, 0, 0, 0, 2, 1, 2, 1,
+    ///     1, 2, 0, 0, 0, 3, 1, 3, 1,
+    /// });
+    /// ```
+    pub fn chunk_metadata(&self, chunk_id: usize) -> Vec<u32>{
+        if !self.ragged{
+            return self.metadata();
+        }
+
+        let samples = self.chunk_samples(chunk_id);
+        let tensors = self.ranks.len() as u32 + 1;
+
+        let header_len = RAGGED_HEADER_LEN + RAGGED_SAMPLE_LEN * samples.len();
+        let mut header: Vec<u32> = vec!{0, tensors, 0, samples.len() as u32};
+        let mut blocks: Vec<u32> = Vec::new();
+
+        let shared_offsets = self.shared_offsets();
+        let (mut output_offset, input_start) = (0, self.sample_offsets[samples.start]);
+        for sample in samples{
+            let mut shapes = split_shapes(&self.sample_shapes[sample], &self.ranks);
+            shapes.push(self.sample_output_shapes.get(sample).cloned().unwrap_or_else(|| self.output_shape.clone()));
+
+            header.extend([(self.sample_offsets[sample] - input_start) as u32, output_offset as u32, (header_len + blocks.len()) as u32]);
+            blocks.extend(shapes_metadata(&shapes, 0, &shared_offsets));
+
+            output_offset += self.sample_output_len(sample);
+        }
+
+        header.extend(blocks);
+        header
+    }
+    /// Returns length of chunk_metadata, without building it
+    pub fn chunk_metadata_len(&self, chunk_id: usize) -> usize{
+        if !self.ragged{
+            return self.metadata().len();
+        }
+
+        RAGGED_HEADER_LEN + self.chunk_samples(chunk_id).map(|sample| self.sample_metadata_len(sample)).sum::<usize>()
+    }
+
     pub fn get_input_size(&self) -> u32{
-        return self.flat_shapes.iter().product();
+        self.flat_shapes.iter().product()
+    }
+}
+
+/// Split flat shapes into shape of every input by ranks
+fn split_shapes(flat_shapes: &[u32], ranks: &[u32]) -> Vec<Vec<u32>>{
+    let mut offset = 0;
+
+    ranks.iter().map(|rank| {
+        let shape = flat_shapes[offset..offset + *rank as usize].to_vec();
+        offset += *rank as usize;
+        shape
+    }).collect()
+}
+
+/// Returns metadata of input shapes followed by output shape, in layout of GpuData::metadata
+/// Inputs of one sample are packed one after another, samples are sample_size apart
+/// Inputs with offset in shared_offsets are shared by every sample, and are not packed
+pub(crate) fn shapes_metadata(shapes: &[Vec<u32>], sample_size: usize, shared_offsets: &[Option<u32>]) -> Vec<u32>{
+    let rank = shapes.iter().map(|shape| shape.len()).max().unwrap_or(0).max(1);
+
+    let mut metadata: Vec<u32> = vec!{rank as u32, shapes.len() as u32, sample_size as u32};
+
+    let mut offset = 0;
+    for (input, shape) in shapes[..shapes.len() - 1].iter().enumerate(){
+        match shared_offsets.get(input).copied().flatten(){
+            Some(shared) => metadata.push(SHARED_TENSOR | shared),
+            None => {
+                metadata.push(offset);
+                offset += shape.iter().product::<u32>();
+            }
+        }
+    }
+    metadata.push(0);
+
+    for shape in shapes{
+        let mut padded = vec!{1; rank - shape.len()};
+        padded.extend_from_slice(shape);
+
+        let mut strides = vec!{0; rank};
+        let mut stride = 1;
+        for dim in (0..rank).rev(){
+            if padded[dim] != 1{
+                strides[dim] = stride;
+            }
+            stride *= padded[dim];
+        }
+
+        metadata.extend(padded);
+        metadata.extend(strides);
     }
+
+    metadata
 }
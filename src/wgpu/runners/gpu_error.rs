@@ -0,0 +1,95 @@
+use std::fmt;
+
+/// Error of gpu operation
+/// Errors of device creation let application fall back to cpu
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main(){
+///     if std::env::var("CI").is_ok() {
+///         eprintln!("Skipping GPU test in CI");
+///         return;
+///     }
+///
+///     let tensor: Tensor<f32> = Tensor::fill(1.0, &[2, 2]);
+///
+///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+///     runner.append(Sample::from_data(vec!{tensor.clone()}, vec!{1.0}, &[])).unwrap();
+///
+///     let output: Tensor<f32> = match runner.add().await{
+///         Ok(mut output) => output.remove(0),
+///         Err(_) => tensor.add(1.0),
+///     };
+///
+///     assert_eq!(output.get_data(), &vec!{2.0; 4});
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum GpuError{
+    /// Buffer is bigger than device allows to bind
+    BufferTooBig(u64),
+    /// No adapter matching request was found
+    NoAdapter(String),
+    /// Adapter could not create device
+    NoDevice(String),
+    /// GpuConfig has value, that device can not use
+    InvalidConfig(String),
+    /// Custom kernel is not valid
+    InvalidKernel(String),
+    /// Operation was run before setting shader
+    NoShader,
+    /// Operation was run without any samples
+    NoData,
+    /// Shapes of samples do not fit operation
+    InvalidShapes(String),
+    /// Wgpu validation error, captured by error scope
+    Validation(String),
+    /// Device ran out of memory
+    OutOfMemory(String),
+    /// Output could not be read back from gpu
+    Readback(String),
+}
+
+impl fmt::Display for GpuError{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        match self{
+            GpuError::BufferTooBig(size) => write!(f, "Buffer size too big: {} bytes", size),
+            GpuError::NoAdapter(err) => write!(f, "No adapter found: {}", err),
+            GpuError::NoDevice(err) => write!(f, "No device: {}", err),
+            GpuError::InvalidConfig(err) => write!(f, "Invalid config: {}", err),
+            GpuError::InvalidKernel(err) => write!(f, "Invalid kernel: {}", err),
+            GpuError::NoShader => write!(f, "Set shader before running operation"),
+            GpuError::NoData => write!(f, "Insert data before running operation"),
+            GpuError::InvalidShapes(err) => write!(f, "Invalid shapes: {}", err),
+            GpuError::Validation(err) => write!(f, "Validation error: {}", err),
+            GpuError::OutOfMemory(err) => write!(f, "Out of memory: {}", err),
+            GpuError::Readback(err) => write!(f, "Readback failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for GpuError{}
+
+/// Run f inside validation and out of memory error scopes of device, and return captured
+/// error instead of panicking in wgpu error handler
+pub fn capture_errors<T>(device: &wgpu::Device, f: impl FnOnce() -> T) -> Result<T, GpuError>{
+    device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+    let output = f();
+
+    let validation = pollster::block_on(device.pop_error_scope());
+    let out_of_memory = pollster::block_on(device.pop_error_scope());
+
+    if let Some(err) = validation{
+        return Err(GpuError::Validation(err.to_string()));
+    }
+    if let Some(err) = out_of_memory{
+        return Err(GpuError::OutOfMemory(err.to_string()));
+    }
+
+    Ok(output)
+}
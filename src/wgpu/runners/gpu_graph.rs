@@ -0,0 +1,622 @@
+use std::{collections::HashMap, fmt::Write, sync::Arc};
+
+use wgpu::util::DeviceExt;
+
+use crate::{cpu::broadcasting::helpers::get_broadcast_shape, tensor::Tensor};
+
+use super::{elementwise::ElementwiseKernel, gpu_context::GpuContext, gpu_data::shapes_metadata, gpu_error::{capture_errors, GpuError}, gpu_tensor::GpuTensor, helpers::encode_dispatch, pipeline_cache::GpuPipeline, readback::Readback, shaders::{GpuOperations, PRELUDE_SRC}};
+
+/// Handle of tensor in GpuGraph
+/// It is valid only in graph, that created it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GraphTensor{
+    id: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnaryOp{
+    Relu,
+    Sigmoid,
+    Tanh,
+    Exp,
+    NLog,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp{
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+enum GraphOp{
+    Input(Tensor<f32>),
+    GpuInput(wgpu::Buffer),
+    Unary(UnaryOp, usize),
+    Binary(BinaryOp, usize, usize),
+    Custom(ElementwiseKernel, Vec<usize>),
+    Matmul(usize, usize),
+    Reduce(GpuOperations, usize),
+}
+
+struct GraphNode{
+    op: GraphOp,
+    shape: Vec<u32>,
+}
+
+/// Compute dispatch of graph, with nodes read from its input and node written to its output
+struct Kernel{
+    node: usize,
+    leaves: Vec<usize>,
+    pipeline: Arc<GpuPipeline>,
+}
+
+/// Generated source of fused kernel, while its nodes are emitted
+#[derive(Default)]
+struct FusedSource<'a>{
+    /// Nodes read from input, in order of their offsets
+    leaves: Vec<usize>,
+    /// Names of values of emitted nodes
+    values: HashMap<usize, String>,
+    /// Custom kernels, that are declared as functions before main
+    kernels: Vec<&'a ElementwiseKernel>,
+    body: String,
+}
+
+/// Returns broadcast shape of shapes with different ranks, lower rank is padded with leading 1s
+fn broadcast_shape(shape_a: &[u32], shape_b: &[u32]) -> Option<Vec<u32>>{
+    let rank = shape_a.len().max(shape_b.len());
+    let pad = |shape: &[u32]| -> Vec<u32>{
+        let mut padded = vec!{1; rank - shape.len()};
+        padded.extend_from_slice(shape);
+        padded
+    };
+
+    get_broadcast_shape(&pad(shape_a), &pad(shape_b))
+}
+
+/// Lazy graph of gpu operations
+/// Operations are only recorded, until outputs are requested with run. Chains of element-wise
+/// operations are fused into one generated shader, all dispatches are recorded into one command
+/// encoder, and only requested outputs are read back
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main(){
+///     if std::env::var("CI").is_ok() {
+///         eprintln!("Skipping GPU test in CI");
+///         return;
+///     }
+///     let context = GpuContext::init(1, &MemoryMetric::GB).await.unwrap();
+///     let mut graph = GpuGraph::new(context);
+///
+///     let input = graph.input(&Tensor::fill(1.0, &[2, 3]));
+///     let weights = graph.input(&Tensor::fill(0.5, &[4, 2]));
+///     let bias = graph.input(&Tensor::fill(-1.0, &[4, 1]));
+///
+///     let linear = graph.matmul(weights, input).unwrap();
+///     let linear = graph.add(linear, bias).unwrap();
+///     let activated = graph.relu(linear).unwrap();
+///     let sum = graph.sum(activated).unwrap();
+///
+///     // matmul, fused add and relu, sum
+///     assert_eq!(graph.dispatch_count(&[sum]).unwrap(), 3);
+///
+///     let output: Vec<Tensor<f32>> = graph.run(&[sum]).await.unwrap();
+///
+///     assert_eq!(output[0].get_data(), &vec!{0.0});
+/// }
+/// ```
+pub struct GpuGraph{
+    context: Arc<GpuContext>,
+    nodes: Vec<GraphNode>,
+}
+
+// Private functions
+impl GpuGraph{
+    fn push(&mut self, op: GraphOp, shape: Vec<u32>) -> GraphTensor{
+        self.nodes.push(GraphNode{op, shape});
+
+        GraphTensor{id: self.nodes.len() - 1}
+    }
+    fn node(&self, tensor: GraphTensor) -> Result<&GraphNode, GpuError>{
+        self.nodes.get(tensor.id).ok_or(GpuError::InvalidShapes(format!("tensor {} is not in graph", tensor.id)))
+    }
+    fn unary(&mut self, op: UnaryOp, input: GraphTensor) -> Result<GraphTensor, GpuError>{
+        let shape = self.node(input)?.shape.clone();
+
+        Ok(self.push(GraphOp::Unary(op, input.id), shape))
+    }
+    fn binary(&mut self, op: BinaryOp, a: GraphTensor, b: GraphTensor) -> Result<GraphTensor, GpuError>{
+        let shape = broadcast_shape(&self.node(a)?.shape, &self.node(b)?.shape)
+            .ok_or(GpuError::InvalidShapes(format!("{:?} can not be broadcast with {:?}", self.nodes[a.id].shape, self.nodes[b.id].shape)))?;
+
+        Ok(self.push(GraphOp::Binary(op, a.id, b.id), shape))
+    }
+    fn scalar(&mut self, op: BinaryOp, input: GraphTensor, value: f32) -> Result<GraphTensor, GpuError>{
+        // scalar is read as tensor of one element, so shaders do not depend on its value
+        let scalar = self.input(&Tensor::from_data(&[value], &[1]).unwrap());
+
+        self.binary(op, input, scalar)
+    }
+    fn is_elementwise(&self, id: usize) -> bool{
+        matches!(self.nodes[id].op, GraphOp::Unary(..) | GraphOp::Binary(..) | GraphOp::Custom(..))
+    }
+    fn operands(&self, id: usize) -> Vec<usize>{
+        match &self.nodes[id].op{
+            GraphOp::Input(_) | GraphOp::GpuInput(_) => vec!{},
+            GraphOp::Unary(_, a) | GraphOp::Reduce(_, a) => vec!{*a},
+            GraphOp::Binary(_, a, b) | GraphOp::Matmul(a, b) => vec!{*a, *b},
+            GraphOp::Custom(_, inputs) => inputs.clone(),
+        }
+    }
+    /// Returns kernels, that compute requested outputs, in order of dispatch
+    /// Element-wise node is fused into its consumer, when it is its only consumer, consumer is
+    /// element-wise too, and node is not requested
+    fn plan(&self, outputs: &[GraphTensor]) -> Result<Vec<Kernel>, GpuError>{
+        let mut needed = vec!{false; self.nodes.len()};
+        let mut stack: Vec<usize> = Vec::new();
+        for output in outputs{
+            self.node(*output)?;
+            stack.push(output.id);
+        }
+        while let Some(id) = stack.pop(){
+            if !needed[id]{
+                needed[id] = true;
+                stack.extend(self.operands(id));
+            }
+        }
+
+        let mut consumers: Vec<Vec<usize>> = vec!{Vec::new(); self.nodes.len()};
+        for id in (0..self.nodes.len()).filter(|id| needed[*id]){
+            for operand in self.operands(id){
+                consumers[operand].push(id);
+            }
+        }
+
+        let fused: Vec<bool> = (0..self.nodes.len()).map(|id| {
+            needed[id] && self.is_elementwise(id) &&
+            consumers[id].len() == 1 && self.is_elementwise(consumers[id][0]) &&
+            !outputs.iter().any(|output| output.id == id)
+        }).collect();
+
+        let mut kernels: Vec<Kernel> = Vec::new();
+        // operands are always created before node, so order of ids is order of dispatch
+        for id in (0..self.nodes.len()).filter(|id| needed[*id] && !fused[*id]){
+            let kernel = match &self.nodes[id].op{
+                GraphOp::Input(_) | GraphOp::GpuInput(_) => continue,
+                GraphOp::Matmul(a, b) => Kernel{node: id, leaves: vec!{*a, *b}, pipeline: self.context.get_pipeline(&GpuOperations::Matmul)?},
+                GraphOp::Reduce(operation, a) => Kernel{node: id, leaves: vec!{*a}, pipeline: self.context.get_pipeline(operation)?},
+                GraphOp::Unary(..) | GraphOp::Binary(..) | GraphOp::Custom(..) => {
+                    let (source, leaves) = self.fused_source(id, &fused);
+
+                    Kernel{node: id, leaves, pipeline: self.context.pipelines.get_generated(&self.context.device, &source)?}
+                },
+            };
+
+            let input_len: usize = kernel.leaves.iter().map(|leaf| self.node_len(*leaf)).sum();
+            let input_size = (input_len * size_of::<f32>()) as u64;
+            if input_size > self.context.max_buffer_size(){
+                return Err(GpuError::BufferTooBig(input_size));
+            }
+
+            kernels.push(kernel);
+        }
+
+        Ok(kernels)
+    }
+    fn node_len(&self, id: usize) -> usize{
+        self.nodes[id].shape.iter().product::<u32>() as usize
+    }
+    /// Returns source of shader, that computes element-wise node with fused operands in one
+    /// invocation per output element, and leaves it reads
+    /// Leaves are read from input with shared prelude, so the same chain of operations on other
+    /// shapes reuses cached pipeline
+    fn fused_source(&self, id: usize, fused: &[bool]) -> (String, Vec<usize>){
+        let mut fused_source = FusedSource::default();
+        let result = self.emit(id, id, fused, &mut fused_source);
+
+        let mut source = String::from(PRELUDE_SRC);
+        for (i, kernel) in fused_source.kernels.iter().enumerate(){
+            source.push('\n');
+            source.push_str(&kernel.wgsl_function(&format!("custom_{}", i)));
+        }
+        source.push_str("
+@group(0) @binding(0)
+var<storage, read> input: array<f32>;
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>, @builtin(num_workgroups) num_workgroups: vec3<u32>){
+	let idx = invocation_index(global_id, num_workgroups);
+	if (idx >= arrayLength(&output)) {
+		return;
+	}
+
+");
+        source.push_str(&fused_source.body);
+        writeln!(source, "\toutput[idx] = {};\n}}", result).unwrap();
+
+        (source, fused_source.leaves)
+    }
+    /// Write statement computing node into body and return name of its value
+    fn emit<'a>(&'a self, id: usize, root: usize, fused: &[bool], source: &mut FusedSource<'a>) -> String{
+        if let Some(value) = source.values.get(&id){
+            return value.clone();
+        }
+
+        let expression = if id != root && !fused[id]{
+            source.leaves.push(id);
+            format!("input[broadcast_index({}u, 0u, idx)]", source.leaves.len() - 1)
+        }
+        else{
+            match &self.nodes[id].op{
+                GraphOp::Unary(op, a) => {
+                    let a = self.emit(*a, root, fused, source);
+                    match op{
+                        UnaryOp::Relu => format!("max({}, 0.0)", a),
+                        UnaryOp::Sigmoid => format!("1.0 / (1.0 + exp(-{}))", a),
+                        UnaryOp::Tanh => format!("tanh({})", a),
+                        UnaryOp::Exp => format!("exp({})", a),
+                        UnaryOp::NLog => format!("log({}) * 0.4342945", a),
+                    }
+                },
+                GraphOp::Binary(op, a, b) => {
+                    let a = self.emit(*a, root, fused, source);
+                    let b = self.emit(*b, root, fused, source);
+                    match op{
+                        BinaryOp::Add => format!("{} + {}", a, b),
+                        BinaryOp::Sub => format!("{} - {}", a, b),
+                        BinaryOp::Mul => format!("{} * {}", a, b),
+                        BinaryOp::Div => format!("{} / {}", a, b),
+                    }
+                },
+                GraphOp::Custom(kernel, inputs) => {
+                    let args: Vec<String> = inputs.iter().map(|input| self.emit(*input, root, fused, source)).collect();
+
+                    // the same kernel used more times is declared once
+                    let function = match source.kernels.iter().position(|declared| *declared == kernel){
+                        Some(function) => function,
+                        None => {
+                            source.kernels.push(kernel);
+                            source.kernels.len() - 1
+                        },
+                    };
+
+                    format!("custom_{}({})", function, args.join(", "))
+                },
+                _ => unreachable!("only element-wise nodes are fused"),
+            }
+        };
+
+        let name = format!("v{}", source.values.len());
+        writeln!(source.body, "\tlet {} = {};", name, expression).unwrap();
+        source.values.insert(id, name.clone());
+
+        name
+    }
+    /// Record kernels into encoder and return buffers of all computed nodes
+    /// Leaves of every kernel are copied one after another into its input buffer, so kernels read
+    /// them with the same metadata layout as samples of GpuRunner
+    fn record(&self, kernels: &[Kernel], outputs: &[GraphTensor], encoder: &mut wgpu::CommandEncoder) -> HashMap<usize, wgpu::Buffer>{
+        let device = &self.context.device;
+        let mut buffers: HashMap<usize, wgpu::Buffer> = HashMap::new();
+
+        let mut inputs: Vec<usize> = kernels.iter().flat_map(|kernel| kernel.leaves.clone()).collect();
+        inputs.extend(outputs.iter().map(|output| output.id));
+        for id in inputs{
+            let buffer = match &self.nodes[id].op{
+                GraphOp::Input(tensor) if !buffers.contains_key(&id) => device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
+                    label: Some("Graph Input Buffer"),
+                    contents: bytemuck::cast_slice(tensor.get_data()),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+                }),
+                GraphOp::GpuInput(buffer) => buffer.clone(),
+                _ => continue,
+            };
+            buffers.insert(id, buffer);
+        }
+
+        let shared_buffer = device.create_buffer(&wgpu::BufferDescriptor{
+            label: Some("Graph Shared Input Buffer"),
+            size: size_of::<f32>() as u64,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        for kernel in kernels{
+            let output_len = self.node_len(kernel.node);
+
+            let mut shapes: Vec<Vec<u32>> = kernel.leaves.iter().map(|leaf| self.nodes[*leaf].shape.clone()).collect();
+            shapes.push(self.nodes[kernel.node].shape.clone());
+            let input_len: usize = kernel.leaves.iter().map(|leaf| self.node_len(*leaf)).sum();
+
+            let input_buffer = device.create_buffer(&wgpu::BufferDescriptor{
+                label: Some("Graph Kernel Input Buffer"),
+                size: (input_len.max(1) * size_of::<f32>()) as u64,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            let metadata_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
+                label: Some("Graph Metadata Buffer"),
+                contents: bytemuck::cast_slice(&shapes_metadata(&shapes, input_len, &[])),
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+            let output_buffer = device.create_buffer(&wgpu::BufferDescriptor{
+                label: Some("Graph Output Buffer"),
+                size: (output_len.max(1) * size_of::<f32>()) as u64,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+            let mut offset = 0;
+            for leaf in &kernel.leaves{
+                let size = (self.node_len(*leaf) * size_of::<f32>()) as u64;
+                encoder.copy_buffer_to_buffer(&buffers[leaf], 0, &input_buffer, offset, size);
+                offset += size;
+            }
+
+            let mut entries = vec!{
+                wgpu::BindGroupEntry{
+                    binding: 0,
+                    resource: input_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry{
+                    binding: 3,
+                    resource: output_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry{
+                    binding: 4,
+                    resource: metadata_buffer.as_entire_binding(),
+                },
+            };
+            // graph has no shared inputs, shaders of operations reading them get empty one
+            if kernel.pipeline.uses_shared{
+                entries.push(wgpu::BindGroupEntry{
+                    binding: 5,
+                    resource: shared_buffer.as_entire_binding(),
+                });
+            }
+
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor{
+                label: Some("Graph Bind group"),
+                layout: &kernel.pipeline.bind_group_layout,
+                entries: &entries,
+            });
+
+            encode_dispatch(encoder, &kernel.pipeline.pipeline, &bind_group, kernel.pipeline.dispatch_len(output_len), self.context.limits.max_compute_workgroups_per_dimension);
+
+            buffers.insert(kernel.node, output_buffer);
+        }
+
+        buffers
+    }
+}
+
+// Public functions
+impl GpuGraph{
+    /// Create empty graph on context
+    pub fn new(context: Arc<GpuContext>) -> Self{
+        Self{
+            context,
+            nodes: Vec::new(),
+        }
+    }
+    /// Returns context of graph
+    pub fn get_context(&self) -> &Arc<GpuContext>{
+        &self.context
+    }
+    /// Returns shape of tensor, None if tensor is not in graph
+    pub fn get_shape(&self, tensor: GraphTensor) -> Option<&Vec<u32>>{
+        self.nodes.get(tensor.id).map(|node| &node.shape)
+    }
+    /// Returns count of nodes in graph
+    pub fn len(&self) -> usize{
+        self.nodes.len()
+    }
+    /// Returns true if graph has no nodes
+    pub fn is_empty(&self) -> bool{
+        self.nodes.is_empty()
+    }
+    /// Add tensor, that is uploaded when graph runs
+    pub fn input(&mut self, tensor: &Tensor<f32>) -> GraphTensor{
+        self.push(GraphOp::Input(tensor.clone()), tensor.get_shape().clone())
+    }
+    /// Add tensor, that already is on gpu
+    /// Error if tensor is not on context of graph
+    pub fn input_gpu(&mut self, tensor: &GpuTensor) -> Result<GraphTensor, GpuError>{
+        if !Arc::ptr_eq(tensor.get_context(), &self.context){
+            return Err(GpuError::InvalidShapes("GpuTensor is not on context of graph".to_string()));
+        }
+
+        Ok(self.push(GraphOp::GpuInput(tensor.get_buffer().clone()), tensor.get_shape().clone()))
+    }
+    /// Element-wise add with broadcasting, lower rank is padded with leading 1s
+    /// Error if shapes can not be broadcast
+    pub fn add(&mut self, a: GraphTensor, b: GraphTensor) -> Result<GraphTensor, GpuError>{
+        self.binary(BinaryOp::Add, a, b)
+    }
+    /// Element-wise sub with broadcasting, lower rank is padded with leading 1s
+    /// Error if shapes can not be broadcast
+    pub fn sub(&mut self, a: GraphTensor, b: GraphTensor) -> Result<GraphTensor, GpuError>{
+        self.binary(BinaryOp::Sub, a, b)
+    }
+    /// Element-wise mul with broadcasting, lower rank is padded with leading 1s
+    /// Error if shapes can not be broadcast
+    pub fn mul(&mut self, a: GraphTensor, b: GraphTensor) -> Result<GraphTensor, GpuError>{
+        self.binary(BinaryOp::Mul, a, b)
+    }
+    /// Element-wise div with broadcasting, lower rank is padded with leading 1s
+    /// Error if shapes can not be broadcast
+    pub fn div(&mut self, a: GraphTensor, b: GraphTensor) -> Result<GraphTensor, GpuError>{
+        self.binary(BinaryOp::Div, a, b)
+    }
+    /// Add value to every element
+    /// Error if tensor is not in graph
+    pub fn add_scalar(&mut self, input: GraphTensor, value: f32) -> Result<GraphTensor, GpuError>{
+        self.scalar(BinaryOp::Add, input, value)
+    }
+    /// Sub value from every element
+    /// Error if tensor is not in graph
+    pub fn sub_scalar(&mut self, input: GraphTensor, value: f32) -> Result<GraphTensor, GpuError>{
+        self.scalar(BinaryOp::Sub, input, value)
+    }
+    /// Mul every element by value
+    /// Error if tensor is not in graph
+    pub fn mul_scalar(&mut self, input: GraphTensor, value: f32) -> Result<GraphTensor, GpuError>{
+        self.scalar(BinaryOp::Mul, input, value)
+    }
+    /// Div every element by value
+    /// Error if tensor is not in graph
+    pub fn div_scalar(&mut self, input: GraphTensor, value: f32) -> Result<GraphTensor, GpuError>{
+        self.scalar(BinaryOp::Div, input, value)
+    }
+    /// Error if tensor is not in graph
+    pub fn relu(&mut self, input: GraphTensor) -> Result<GraphTensor, GpuError>{
+        self.unary(UnaryOp::Relu, input)
+    }
+    /// Error if tensor is not in graph
+    pub fn sigmoid(&mut self, input: GraphTensor) -> Result<GraphTensor, GpuError>{
+        self.unary(UnaryOp::Sigmoid, input)
+    }
+    /// Error if tensor is not in graph
+    pub fn tanh(&mut self, input: GraphTensor) -> Result<GraphTensor, GpuError>{
+        self.unary(UnaryOp::Tanh, input)
+    }
+    /// Error if tensor is not in graph
+    pub fn exp(&mut self, input: GraphTensor) -> Result<GraphTensor, GpuError>{
+        self.unary(UnaryOp::Exp, input)
+    }
+    /// Log10 of every element, the same as Tensor::nlog
+    /// Error if tensor is not in graph
+    pub fn nlog(&mut self, input: GraphTensor) -> Result<GraphTensor, GpuError>{
+        self.unary(UnaryOp::NLog, input)
+    }
+    /// Apply custom element-wise kernel, inputs are broadcast to shape of output and passed to
+    /// kernel in order of its inputs
+    /// Kernel is fused with other element-wise operations
+    /// Error if count of inputs does not match kernel or shapes can not be broadcast
+    pub fn elementwise(&mut self, kernel: &ElementwiseKernel, inputs: &[GraphTensor]) -> Result<GraphTensor, GpuError>{
+        if inputs.len() != kernel.inputs_len(){
+            return Err(GpuError::InvalidShapes(format!("kernel has {} inputs, got {}", kernel.inputs_len(), inputs.len())));
+        }
+
+        let mut shape: Vec<u32> = Vec::new();
+        for input in inputs{
+            let input_shape = &self.node(*input)?.shape;
+            shape = broadcast_shape(&shape, input_shape)
+                .ok_or(GpuError::InvalidShapes(format!("{:?} can not be broadcast with {:?}", shape, input_shape)))?;
+        }
+
+        Ok(self.push(GraphOp::Custom(kernel.clone(), inputs.iter().map(|input| input.id).collect()), shape))
+    }
+    /// Matrix multiplication of [m, k] and [k, n] tensors, with tiled matmul shader
+    /// Error if tensors are not matrices with matching inner dims
+    pub fn matmul(&mut self, a: GraphTensor, b: GraphTensor) -> Result<GraphTensor, GpuError>{
+        let (shape_a, shape_b) = (&self.node(a)?.shape, &self.node(b)?.shape);
+        if shape_a.len() != 2 || shape_b.len() != 2 || shape_a[1] != shape_b[0]{
+            return Err(GpuError::InvalidShapes(format!("{:?} can not be multiplied with {:?}", shape_a, shape_b)));
+        }
+
+        let shape = vec!{shape_a[0], shape_b[1]};
+        Ok(self.push(GraphOp::Matmul(a.id, b.id), shape))
+    }
+    /// Reduce tensor with one of GpuOperations::Reduce* operations, over all elements, or along
+    /// axis, that is kept with size 1
+    /// Error if operation is not reduction or axis is out of rank
+    pub fn reduce(&mut self, input: GraphTensor, operation: GpuOperations, axis: Option<u32>) -> Result<GraphTensor, GpuError>{
+        if !matches!(operation, GpuOperations::ReduceSum | GpuOperations::ReduceMean | GpuOperations::ReduceMax |
+            GpuOperations::ReduceMin | GpuOperations::ReduceArgmax | GpuOperations::ReduceVariance){
+            return Err(GpuError::InvalidShapes(format!("{:?} is not reduction", operation)));
+        }
+
+        let shape = match axis{
+            Some(axis) => {
+                let mut shape = self.node(input)?.shape.clone();
+                if axis as usize >= shape.len(){
+                    return Err(GpuError::InvalidShapes(format!("axis {} out of rank {}", axis, shape.len())));
+                }
+                shape[axis as usize] = 1;
+                shape
+            },
+            None => {
+                self.node(input)?;
+                vec!{1}
+            },
+        };
+
+        Ok(self.push(GraphOp::Reduce(operation, input.id), shape))
+    }
+    /// Sum of all elements, with output shape [1]
+    /// Error if tensor is not in graph
+    pub fn sum(&mut self, input: GraphTensor) -> Result<GraphTensor, GpuError>{
+        self.reduce(input, GpuOperations::ReduceSum, None)
+    }
+    /// Returns count of compute dispatches, that run records for outputs
+    /// Error if output is not in graph, or pipeline fails to compile
+    pub fn dispatch_count(&self, outputs: &[GraphTensor]) -> Result<usize, GpuError>{
+        Ok(self.plan(outputs)?.len())
+    }
+    /// Run graph and keep outputs on gpu
+    /// Error if output is not in graph, or wgpu reports an error
+    pub fn run_gpu(&self, outputs: &[GraphTensor]) -> Result<Vec<GpuTensor>, GpuError>{
+        let kernels = self.plan(outputs)?;
+        let device = &self.context.device;
+
+        capture_errors(device, || {
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor{
+                label: Some("Graph Encoder"),
+            });
+            let buffers = self.record(&kernels, outputs, &mut encoder);
+            self.context.queue.submit(Some(encoder.finish()));
+
+            outputs.iter()
+                .map(|output| GpuTensor::from_buffer(buffers[&output.id].clone(), &self.nodes[output.id].shape, self.context.clone()))
+                .collect()
+        })
+    }
+    /// Run graph and read back outputs
+    /// All dispatches and copies into staging buffers are submitted in one command encoder
+    /// Error if output is not in graph, or wgpu reports an error
+    pub async fn run(&self, outputs: &[GraphTensor]) -> Result<Vec<Tensor<f32>>, GpuError>{
+        let kernels = self.plan(outputs)?;
+        let device = &self.context.device;
+
+        let stagings = capture_errors(device, || {
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor{
+                label: Some("Graph Encoder"),
+            });
+            let buffers = self.record(&kernels, outputs, &mut encoder);
+
+            let stagings: Vec<(wgpu::Buffer, u64)> = outputs.iter().map(|output| {
+                let size = (self.node_len(output.id).max(1) * size_of::<f32>()) as u64;
+                let staging = self.context.staging.acquire(device, size);
+                encoder.copy_buffer_to_buffer(&buffers[&output.id], 0, &staging, 0, size);
+                (staging, size)
+            }).collect();
+
+            self.context.queue.submit(Some(encoder.finish()));
+
+            stagings
+        })?;
+
+        // all outputs are mapped at once, and read in order
+        let readbacks: Vec<Readback> = stagings.into_iter()
+            .map(|(staging, size)| Readback::pooled(self.context.clone(), staging, size))
+            .collect();
+
+        let mut return_vec: Vec<Tensor<f32>> = Vec::with_capacity(outputs.len());
+        for (output, readback) in outputs.iter().zip(readbacks){
+            let data = readback.await?;
+            return_vec.push(Tensor::from_data(&data[..self.node_len(output.id)], &self.nodes[output.id].shape).unwrap());
+        }
+
+        Ok(return_vec)
+    }
+}
@@ -1,8 +1,10 @@
 pub mod runner_ops;
 
-use crate::{prelude::{GpuBuffers, GpuData}, tensor::Tensor};
+use std::{sync::Arc, time::{Duration, Instant}};
 
-use super::{gpu_buffers, gpu_data, helpers::{get_size_using_metric, MemoryMetric}, sample::Sample, shaders::GpuOperations};
+use crate::{prelude::{capture_errors, GpuBatch, GpuBuffers, GpuConfig, GpuContext, GpuData, GpuError, GpuPrecision, GpuSample, GpuTensor, PendingOutputs}, tensor::Tensor};
+
+use super::{backend::{run_cpu, run_cpu_f64, Backend}, gpu_buffers, gpu_data, helpers::{get_size_using_metric, MemoryMetric}, profiling::{ChunkProfile, OperationProfile}, sample::Sample, shaders::GpuOperations};
 
 
 /// Returns a max buffer size, allowed for that operation
@@ -14,6 +16,33 @@ pub fn split_to_correct_size(buffer_size: u64, data_len: usize, sample_len: usiz
     data_len
 }
 
+/// Wait for outputs of chunk and append them to return_vec, recording readback and gpu time into
+/// profile of chunk
+/// Outputs of f64 buffers are also appended without rounding to f64_outputs
+async fn read_outputs(pending: PendingOutputs, return_vec: &mut Vec<Tensor<f32>>, f64_outputs: Option<&mut Vec<Tensor<f64>>>, chunk: Option<&mut ChunkProfile>) -> Result<(), GpuError>{
+    let started = Instant::now();
+    let gpu = match f64_outputs{
+        Some(f64_outputs) => {
+            let (mut outputs, gpu) = pending.outputs_timed_f64().await?;
+            return_vec.extend(outputs.iter().map(|output| output.to_f32()));
+            f64_outputs.append(&mut outputs);
+            gpu
+        },
+        None => {
+            let (mut outputs, gpu) = pending.outputs_timed().await?;
+            return_vec.append(&mut outputs);
+            gpu
+        },
+    };
+
+    if let Some(chunk) = chunk{
+        chunk.readback = started.elapsed();
+        chunk.gpu = gpu;
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum OverflowOperation{
     Add,
@@ -25,152 +54,857 @@ pub struct GpuRunner{
     pub gpu_data: GpuData,
     sample_len: u64,
     gpu_buffers: Option<GpuBuffers>,
+    /// Buffers of previous chunk, that is read back while next chunk runs in gpu_buffers
+    spare_buffers: Option<GpuBuffers>,
 
     cache_data_size: usize,
     buffer_size: u64,
     last_ops: Option<GpuOperations>,
 
-    single_output: bool,
     overflow_ops: OverflowOperation,
 
-    overflow_buffer: Option<GpuBuffers>,
-
     prepared_flag: bool,
+
+    context: Option<Arc<GpuContext>>,
+    gpu_samples: Vec<GpuSample>,
+    /// Samples written into one gpu buffer, used instead of gpu_samples
+    gpu_batch: Option<GpuBatch>,
+    keep_on_gpu: bool,
+    gpu_outputs: Vec<GpuTensor>,
+
+    backend: Backend,
+    config: GpuConfig,
+
+    /// Samples can have other shapes, kept after clear
+    ragged: bool,
+    /// Precision of values in buffers of operations, that support it, kept after clear
+    precision: GpuPrecision,
+    /// Outputs of last operation run in f64 buffers, without rounding to f32
+    f64_outputs: Vec<Tensor<f64>>,
+
+    profiling: bool,
+    /// Profiles of operations run since profiling was enabled or profiles were taken
+    profiles: Vec<OperationProfile>,
 }
 
 // Private functions
 impl GpuRunner{
 
     /// Prepare buffers for operation
-    async fn prepare_buffers(&mut self, gpu_ops: &GpuOperations, chunk_id: usize){
-        let mut buffers = GpuBuffers::init(self.buffer_size, MemoryMetric::B, &mut self.gpu_data, chunk_id).await;
-        buffers.set_shader(gpu_ops);
-        buffers.prepare();
+    async fn prepare_buffers(&mut self, gpu_ops: &GpuOperations, chunk_id: usize) -> Result<(), GpuError>{
+        let mut buffers = GpuBuffers::with_context(self.get_context().await?, &self.gpu_data, chunk_id)?;
+        buffers.set_shader(gpu_ops)?;
+        buffers.prepare()?;
 
         self.prepared_flag = true;
+        self.last_ops = Some(gpu_ops.clone());
         self.gpu_buffers = Some(buffers);
+
+        Ok(())
     }
     /// Update buffers for operation
-    async fn update_buffers(&mut self, chunk_id: usize){
-        let mut buffers = self.gpu_buffers.as_mut().unwrap();
-        buffers.update(&mut self.gpu_data, chunk_id);
+    async fn update_buffers(&mut self, chunk_id: usize) -> Result<(), GpuError>{
+        let buffers = self.gpu_buffers.as_mut().unwrap();
+        buffers.update(&mut self.gpu_data, chunk_id)?;
 
         self.prepared_flag = true;
+
+        Ok(())
     }
-    /// Run operation
-    async fn run_ops(&mut self, gpu_ops: &GpuOperations) -> Vec<Tensor<f32>>{
-        let mut return_vec: Vec<Tensor<f32>> = Vec::new();
-        for i in 0..self.gpu_data.chunks{
-            if !self.prepared_flag || self.gpu_buffers.is_none(){
-                self.prepare_buffers(gpu_ops, i).await;
+    /// Check that runner has samples with at least len flat shapes, before operation indexes them
+    fn check_shapes(&self, len: usize) -> Result<(), GpuError>{
+        if self.gpu_data.samples_count == 0{
+            return Err(GpuError::NoData);
+        }
+        if self.gpu_data.flat_shapes.len() < len{
+            return Err(GpuError::InvalidShapes(format!("operation needs {} flat shapes, but samples have {}", len, self.gpu_data.flat_shapes.len())));
+        }
+
+        Ok(())
+    }
+    /// Check that columns of the first matrix of samples match rows of the second, before
+    /// matmul and forward operations, so gpu returns the same error as cpu backend
+    fn check_inner_dims(&self, gpu_ops: &GpuOperations) -> Result<(), GpuError>{
+        if self.gpu_data.flat_shapes[1] != self.gpu_data.flat_shapes[2]{
+            return Err(GpuError::InvalidShapes(format!("inputs do not fit {:?}", gpu_ops)));
+        }
+
+        Ok(())
+    }
+    /// Check that samples of runner are on gpu, before operation keeps its outputs on gpu
+    fn check_gpu_samples(&self) -> Result<(), GpuError>{
+        if self.gpu_samples.is_empty() && self.gpu_batch.is_none(){
+            return Err(GpuError::InvalidConfig("outputs stay on gpu only for samples appended with append_gpu or append_batch".to_string()));
+        }
+
+        Ok(())
+    }
+    /// Set output shape of every sample of ragged batch
+    /// Error if operation does not support ragged batches, or inputs of sample do not fit it
+    fn prepare_ragged(&mut self, gpu_ops: &GpuOperations) -> Result<(), GpuError>{
+        if !gpu_ops.supports_ragged() || self.gpu_data.single_output{
+            return Err(GpuError::InvalidShapes(format!("{:?} does not support ragged samples", gpu_ops)));
+        }
+
+        let output_shapes = (0..self.gpu_data.samples_count as usize).map(|sample| {
+            let input_shapes = self.gpu_data.sample_input_shapes(sample);
+            gpu_ops.ragged_output_shape(&input_shapes)
+                .ok_or_else(|| GpuError::InvalidShapes(format!("inputs {:?} of sample {} do not fit {:?}", input_shapes, sample, gpu_ops)))
+        }).collect::<Result<Vec<Vec<u32>>, GpuError>>()?;
+
+        self.gpu_data.set_sample_outputs(output_shapes);
+
+        Ok(())
+    }
+    /// Copy inputs of gpu samples on gpu into one buffer
+    fn copy_gpu_inputs(&self, context: &GpuContext) -> Result<(wgpu::Buffer, usize), GpuError>{
+        let device = &context.device;
+        let input_len: usize = self.gpu_samples.iter().map(|sample| sample.input_len).sum();
+
+        let inputs_buffer = capture_errors(device, || {
+            let inputs_buffer = device.create_buffer(&wgpu::BufferDescriptor{
+                label: Some("Input Buffer"),
+                size: (input_len.max(1) * size_of::<f32>()) as u64,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Encoder"),
+            });
+
+            let mut offset: u64 = 0;
+            for sample in &self.gpu_samples{
+                for tensor in &sample.inputs{
+                    let size = (tensor.count_data() * size_of::<f32>()) as u64;
+                    encoder.copy_buffer_to_buffer(tensor.get_buffer(), 0, &inputs_buffer, offset, size);
+                    offset += size;
+                }
             }
-            else if self.gpu_buffers.is_some(){
-                self.update_buffers(i).await;
+
+            context.queue.submit(Some(encoder.finish()));
+
+            inputs_buffer
+        })?;
+
+        Ok((inputs_buffer, input_len))
+    }
+    /// Run operation on samples, which inputs already are on gpu
+    /// Inputs are copied on gpu into one buffer, so all samples run in one dispatch
+    /// Inputs of GpuBatch already are in one buffer, so they are used without copy
+    async fn run_gpu_ops(&mut self, gpu_ops: &GpuOperations) -> Result<Vec<Tensor<f32>>, GpuError>{
+        let started = Instant::now();
+        let context = self.get_context().await?;
+
+        let (inputs_buffer, input_len) = match &self.gpu_batch{
+            Some(batch) => (batch.get_buffer().clone(), batch.input_len()),
+            None => self.copy_gpu_inputs(&context)?,
+        };
+
+        self.gpu_data.precision = GpuPrecision::F32;
+        let mut buffers = GpuBuffers::with_gpu_inputs(context.clone(), &self.gpu_data, inputs_buffer, input_len)?;
+        buffers.set_shader(gpu_ops)?;
+        buffers.prepare()?;
+        let upload = started.elapsed();
+
+        let dispatch_started = Instant::now();
+        self.gpu_outputs = buffers.run_on_gpu()?;
+        let dispatch = dispatch_started.elapsed();
+
+        let readback_started = Instant::now();
+        let mut return_vec: Vec<Tensor<f32>> = Vec::with_capacity(self.gpu_outputs.len());
+        if !self.keep_on_gpu{
+            for tensor in &self.gpu_outputs{
+                return_vec.push(tensor.to_cpu().await?);
+            }
+        }
+
+        // inputs on gpu run in one dispatch, that has no timestamps
+        if self.profiling{
+            let mut profile = OperationProfile::new(gpu_ops.clone());
+            profile.chunks.push(ChunkProfile{
+                chunk: 0,
+                samples: self.gpu_data.samples_count as usize,
+                upload,
+                dispatch,
+                readback: readback_started.elapsed(),
+                gpu: None,
+            });
+            profile.total = started.elapsed();
+
+            self.profiles.push(profile);
+        }
+
+        Ok(return_vec)
+    }
+    /// Returns precision of buffers of operation, precision of runner if operation and device
+    /// support it, f32 otherwise
+    /// Error for f64 precision, that operation or device does not support, as results in f32
+    /// would not have the precision asked for
+    fn buffer_precision(&self, gpu_ops: &GpuOperations, context: &GpuContext) -> Result<GpuPrecision, GpuError>{
+        if gpu_ops.supports_precision(self.precision) && context.supports_precision(self.precision){
+            return Ok(self.precision);
+        }
+
+        if self.precision == GpuPrecision::F64{
+            if !context.supports_f64(){
+                return Err(GpuError::InvalidConfig("device does not support f64 shaders, adapter has no SHADER_F64 feature".to_string()));
             }
+            return Err(GpuError::InvalidConfig(format!("{:?} has no shader for F64 precision", gpu_ops)));
+        }
 
-            let mut chunk_output = self.gpu_buffers.as_ref().unwrap().run().await;
+        Ok(GpuPrecision::F32)
+    }
+    /// Returns true if operation runs on cpu
+    /// Auto backend is resolved on first operation, cpu is selected if there is no adapter
+    async fn use_cpu(&mut self) -> Result<bool, GpuError>{
+        if self.backend == Backend::Auto{
+            self.backend = match self.get_context().await{
+                Ok(_) => Backend::Gpu,
+                Err(GpuError::NoAdapter(_)) | Err(GpuError::NoDevice(_)) => Backend::Cpu,
+                Err(err) => return Err(err),
+            };
+        }
 
-            return_vec.append(&mut chunk_output);
+        Ok(self.backend == Backend::Cpu)
+    }
+    /// Returns max size of chunk in bytes, buffer size of runner limited by context
+    /// Buffer size of 0 uses the biggest buffers context allows
+    fn chunk_buffer_size(&self, context: &GpuContext) -> u64{
+        if self.buffer_size == 0{
+            return context.max_buffer_size();
         }
 
-        return_vec
+        self.buffer_size.min(context.max_buffer_size())
     }
+    /// Run operation
+    async fn run_ops(&mut self, gpu_ops: &GpuOperations) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.f64_outputs.clear();
+        self.gpu_outputs.clear();
 
-    /// Merge all output tensors, for operations that returns one tensor
-    async fn fix_for_single_output(&mut self, return_vec: &Vec<Tensor<f32>>) -> (bool, Vec<Tensor<f32>>){
-        if self.single_output && return_vec.len() > 1{
-            let mut return_tensor = Tensor::from_data(return_vec[0].get_data(), return_vec[0].get_shape()).unwrap();
-            for i in 1..return_vec.len(){
-                let tensor = Tensor::from_data(return_vec[i].get_data(), return_vec[i].get_shape()).unwrap();
-                return_tensor.append(&tensor).unwrap();
+        if !self.gpu_data.shared.is_empty() && !gpu_ops.supports_shared(){
+            return Err(GpuError::InvalidShapes(format!("{:?} does not support shared inputs", gpu_ops)));
+        }
+        if self.gpu_data.ragged{
+            self.prepare_ragged(gpu_ops)?;
+        }
+        // inputs already on gpu are f32, and inputs on gpu always run in f32
+        if !self.gpu_samples.is_empty() || self.gpu_batch.is_some(){
+            if self.precision == GpuPrecision::F64{
+                return Err(GpuError::InvalidConfig("samples on gpu are f32, and can not run in F64 precision".to_string()));
             }
-            let mut overflow_data = GpuData::with_capacity(return_tensor.get_data().len());
-            overflow_data.disable_params();
-            overflow_data.enable_single_output();
+            return self.run_gpu_ops(gpu_ops).await;
+        }
+        if self.use_cpu().await?{
+            if self.precision == GpuPrecision::F64{
+                self.f64_outputs = run_cpu_f64(gpu_ops, &self.gpu_data)?;
+                return Ok(self.f64_outputs.iter().map(|output| output.to_f32()).collect());
+            }
+            return run_cpu(gpu_ops, &self.gpu_data);
+        }
 
-            let sample: Sample = Sample::from_data(vec!{return_tensor}, vec!{}, return_vec[0].get_shape());
+        let started = Instant::now();
+        let mut profile = self.profiling.then(|| OperationProfile::new(gpu_ops.clone()));
 
-            overflow_data.append(sample);
+        let context = self.get_context().await?;
+        self.gpu_data.precision = self.buffer_precision(gpu_ops, &context)?;
+        let f64_buffers = self.gpu_data.precision == GpuPrecision::F64;
+        self.gpu_data.prepare_chunking_auto(self.chunk_buffer_size(&context), context.max_dispatch_len())?;
 
-            if self.overflow_buffer.is_none(){
-                let mut overflow_buffer = GpuBuffers::init(self.buffer_size, MemoryMetric::B, &mut overflow_data, 0).await;
+        // spare buffers of other operation or data have other shader and sizes
+        if !self.prepared_flag || self.last_ops.as_ref() != Some(gpu_ops){
+            self.spare_buffers = None;
+        }
 
-                overflow_buffer.set_shader(&GpuOperations::MatrixColSum);
-                self.overflow_buffer = Some(overflow_buffer);
+        // chunks alternate between two sets of buffers, so next chunk is uploaded and dispatched
+        // while output of previous one is read back
+        let mut pending: Option<PendingOutputs> = None;
+        let mut return_vec: Vec<Tensor<f32>> = Vec::new();
+        for i in 0..self.gpu_data.chunks{
+            // single output shaders count samples by length of inputs, so chunk with other
+            // count of samples needs buffers of its own size
+            let (chunk_inputs, chunk_samples, _) = self.gpu_data.get_chunk(i).unwrap();
+            let chunk_len = chunk_inputs.len();
+            let resized = self.gpu_buffers.as_ref().is_some_and(|buffers| {
+                buffers.input_len < chunk_len || (self.gpu_data.single_output && buffers.input_len != chunk_len) || buffers.precision != self.gpu_data.precision
+            });
+
+            let upload_started = Instant::now();
+            // buffers of other operation or data have other shader and sizes, chunks of ragged
+            // batch have their own sizes and metadata
+            if !self.prepared_flag || self.gpu_buffers.is_none() || self.last_ops.as_ref() != Some(gpu_ops) || resized || self.gpu_data.ragged{
+                self.prepare_buffers(gpu_ops, i).await?;
+            }
+            else if self.gpu_buffers.is_some(){
+                self.update_buffers(i).await?;
+            }
+            let upload = upload_started.elapsed();
+
+            let dispatch_started = Instant::now();
+            let submitted = self.gpu_buffers.as_ref().unwrap().submit_timed(self.profiling)?;
+            std::mem::swap(&mut self.gpu_buffers, &mut self.spare_buffers);
+
+            if let Some(profile) = &mut profile{
+                profile.chunks.push(ChunkProfile{
+                    chunk: i,
+                    samples: chunk_samples,
+                    upload,
+                    dispatch: dispatch_started.elapsed(),
+                    readback: Duration::ZERO,
+                    gpu: None,
+                });
             }
-            else{
-                let mut overflow_buffer = self.overflow_buffer.as_mut().unwrap();
 
-                overflow_buffer.update(&mut overflow_data, 0);
+            if let Some(previous) = pending.replace(submitted){
+                let chunk = profile.as_mut().map(|profile| &mut profile.chunks[i - 1]);
+                read_outputs(previous, &mut return_vec, f64_buffers.then_some(&mut self.f64_outputs), chunk).await?;
             }
+        }
+        if let Some(last) = pending{
+            let chunk = profile.as_mut().and_then(|profile| profile.chunks.last_mut());
+            read_outputs(last, &mut return_vec, f64_buffers.then_some(&mut self.f64_outputs), chunk).await?;
+        }
 
-            let mut overflow_buffer = self.overflow_buffer.as_mut().unwrap();
-            
-            let new_return_vec = overflow_buffer.run().await;
+        if let Some(mut profile) = profile{
+            profile.total = started.elapsed();
+            self.profiles.push(profile);
+        }
+
+        Ok(return_vec)
+    }
+
+    /// Merge outputs of chunks, for operations that returns one tensor
+    /// Every chunk output is computed with mean gradient of its samples, so outputs are averaged
+    /// with weights of their sample counts by parallel sum reduction on gpu, or on cpu if outputs
+    /// of all chunks do not fit one buffer
+    async fn fix_for_single_output(&mut self, return_vec: &[Tensor<f32>]) -> Result<(bool, Vec<Tensor<f32>>), GpuError>{
+        if !self.gpu_data.single_output || return_vec.len() <= 1{
+            return Ok((false, Vec::new()));
+        }
+
+        let output_shape = return_vec[0].get_shape().clone();
+        let output_len = return_vec[0].get_data().len();
+        let samples_count = self.gpu_data.samples_count as f32;
+
+        let mut weighted_data: Vec<f32> = Vec::with_capacity(return_vec.len() * output_len);
+        for (i, output) in return_vec.iter().enumerate(){
+            let (_, chunk_samples, _) = self.gpu_data.get_chunk(i).unwrap();
+            let weight = chunk_samples as f32 / samples_count;
 
-            return (true, new_return_vec);
+            weighted_data.extend(output.get_data().iter().map(|value| value * weight));
         }
-        else{
-            return (false, Vec::new());
+
+        // stacked outputs of chunks may not fit buffers of context, then they are summed on cpu
+        let context = self.get_context().await?;
+        if (weighted_data.len() * size_of::<f32>()) as u64 > context.max_buffer_size(){
+            let merged: Vec<f32> = (0..output_len)
+                .map(|i| weighted_data.iter().skip(i).step_by(output_len).sum())
+                .collect();
+
+            return Ok((true, vec!{Tensor::from_data(&merged, &output_shape).unwrap()}));
         }
+
+        let mut merge_data = GpuData::with_capacity(weighted_data.len());
+        merge_data.disable_params();
+
+        let outputs = Tensor::from_data(&weighted_data, &[return_vec.len() as u32, output_len as u32]).unwrap();
+        merge_data.append(Sample::from_data(vec!{outputs}, vec!{}, &[1, output_len as u32]))?;
+
+        let mut merge_buffers = GpuBuffers::with_context(context, &merge_data, 0)?;
+        merge_buffers.set_shader(&GpuOperations::ReduceSum)?;
+        merge_buffers.prepare()?;
+
+        let merged = merge_buffers.run().await?;
+
+        Ok((true, vec!{Tensor::from_data(merged[0].get_data(), &output_shape).unwrap()}))
     }
 }
 
 // Public functions
 impl GpuRunner{
-    /// Initialize GpuRunner with memory size. memory limit is 2GB because of the wgpu limitations
+    /// Initialize GpuRunner with memory size. memory is limited by max buffer size of device
+    /// Buffer size of 0 uses the biggest buffers device allows, chunks are always sized to fit
+    /// device limits
     pub fn init(buffer_size: u64, metric: MemoryMetric) -> Self{
         Self { 
             gpu_data: GpuData::new(),
             sample_len: 0,
             gpu_buffers: None,
+            spare_buffers: None,
             cache_data_size: 0,
             buffer_size: get_size_using_metric(buffer_size, &metric),
             last_ops: None,
 
-            single_output: false,
             overflow_ops: OverflowOperation::Add,
 
-            overflow_buffer: None,
-
             prepared_flag: false,
+
+            context: None,
+            gpu_samples: Vec::new(),
+            gpu_batch: None,
+            keep_on_gpu: false,
+            gpu_outputs: Vec::new(),
+
+            backend: Backend::Auto,
+            config: GpuConfig::default(),
+
+            ragged: false,
+            precision: GpuPrecision::F32,
+            f64_outputs: Vec::new(),
+
+            profiling: false,
+            profiles: Vec::new(),
         }
     }
-    /// Initialize GpuRunner with memory size with gpu_data.input_capacity = capacity. memory limit is 2GB because of the wgpu limitations
+    /// Initialize GpuRunner with memory size with gpu_data.input_capacity = capacity. memory is limited by max buffer size of device
     pub fn with_capacity(capacity: usize, buffer_size: u64, metric: MemoryMetric) -> Self{
         Self { 
             gpu_data: GpuData::with_capacity(capacity),
             sample_len: 0,
             gpu_buffers: None,
+            spare_buffers: None,
             cache_data_size: 0,
             buffer_size: get_size_using_metric(buffer_size, &metric),
             last_ops: None,
 
-            single_output: false,
             overflow_ops: OverflowOperation::Add,
-
-            overflow_buffer: None,
             
             prepared_flag: false,
+
+            context: None,
+            gpu_samples: Vec::new(),
+            gpu_batch: None,
+            keep_on_gpu: false,
+            gpu_outputs: Vec::new(),
+
+            backend: Backend::Auto,
+            config: GpuConfig::default(),
+
+            ragged: false,
+            precision: GpuPrecision::F32,
+            f64_outputs: Vec::new(),
+
+            profiling: false,
+            profiles: Vec::new(),
         }
     }
+    /// Initialize GpuRunner on shared GpuContext, so GpuTensors can be exchanged with other
+    /// runners on the same context
+    /// Buffer size is limited by max buffer size of context
+    pub fn from_context(context: Arc<GpuContext>, buffer_size: u64, metric: MemoryMetric) -> Self{
+        let mut runner = Self::init(buffer_size, metric);
+
+        runner.buffer_size = runner.buffer_size.min(context.max_buffer_size());
+        runner.context = Some(context);
+
+        runner
+    }
+    /// Initialize GpuRunner with memory size, that runs operations on chosen backend
+    /// Backend::Cpu runs every operation with cpu module, with the same output shapes and
+    /// batching as gpu
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let weights: Tensor<f32> = Tensor::fill(1.0, &[2, 3]);
+    ///     let inputs: Tensor<f32> = Tensor::fill(1.0, &[3, 4]);
+    ///     let bias: Tensor<f32> = Tensor::fill(-1.0, &[2, 1]);
+    ///
+    ///     let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+    ///     runner.append(Sample::from_data(vec!{weights, inputs, bias}, vec!{}, &[])).unwrap();
+    ///
+    ///     let output: Vec<Tensor<f32>> = runner.forward_relu().await.unwrap();
+    ///
+    ///     assert_eq!(output[0].get_data(), &vec!{2.0; 8});
+    ///     assert_eq!(output[0].get_shape(), &vec!{2, 4});
+    /// }
+    /// ```
+    pub fn with_backend(backend: Backend, buffer_size: u64, metric: MemoryMetric) -> Self{
+        let mut runner = Self::init(buffer_size, metric);
+        runner.backend = backend;
+
+        runner
+    }
+    /// Initialize GpuRunner with memory size, that creates its context on adapter chosen by
+    /// config
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let mut runner = GpuRunner::with_config(GpuConfig::fallback(), 256, MemoryMetric::MB);
+    ///     runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[])).unwrap();
+    ///
+    ///     // Auto backend runs on cpu, if machine has no fallback adapter
+    ///     let output: Vec<Tensor<f32>> = runner.add().await.unwrap();
+    ///
+    ///     assert_eq!(output[0].get_data(), &vec!{2.0; 4});
+    /// }
+    /// ```
+    pub fn with_config(config: GpuConfig, buffer_size: u64, metric: MemoryMetric) -> Self{
+        let mut runner = Self::init(buffer_size, metric);
+        runner.config = config;
+
+        runner
+    }
+    /// Set config of adapter, used when context of GpuRunner is created
+    /// Does nothing to already created context
+    pub fn set_config(&mut self, config: GpuConfig){
+        self.config = config;
+    }
+    /// Returns config of adapter of GpuRunner
+    pub fn get_config(&self) -> &GpuConfig{
+        &self.config
+    }
+    /// Set backend of GpuRunner
+    /// Samples with inputs on gpu always run on gpu
+    pub fn set_backend(&mut self, backend: Backend){
+        self.backend = backend;
+    }
+    /// Returns backend of GpuRunner
+    /// Auto backend is replaced by selected one after first operation
+    pub fn get_backend(&self) -> Backend{
+        self.backend
+    }
     /// append sample to GpuRunner
-    pub fn append(&mut self, sample: Sample){
+    /// Error if sample does not match samples already appended, or GpuBatch was appended
+    pub fn append(&mut self, sample: Sample) -> Result<(), GpuError>{
+        if self.gpu_batch.is_some(){
+            return Err(GpuError::InvalidConfig("GpuBatch can not be mixed with other samples".to_string()));
+        }
+
         let sample_len = sample.inputs.len();
 
-        let flag = self.gpu_data.append(sample);
+        self.gpu_data.append(sample)?;
+
+        self.sample_len = sample_len as u64;
+        self.prepared_flag = false;
+
+        Ok(())
+    }
+    /// Share tensor as input at index of every sample, so it is uploaded once and bound apart
+    /// from inputs of samples, that are appended without it
+    /// Inputs must be shared before samples are appended, error otherwise, and are dropped by clear
+    /// Element-wise, broadcast, matmul, forward and backward gradient and weight operations
+    /// support shared inputs, other operations return error
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     // weights and biases are uploaded once, samples contain only inputs
+    ///     runner.share(0, &Tensor::fill(1.0, &[2, 3])).unwrap();
+    ///     runner.share(2, &Tensor::fill(0.5, &[2, 1])).unwrap();
+    ///     for i in 0..4{
+    ///         runner.append(Sample::from_data(vec!{Tensor::fill(i as f32, &[3, 1])}, vec!{}, &[])).unwrap();
+    ///     }
+    ///
+    ///     let output: Vec<Tensor<f32>> = runner.forward_no_activ().await.unwrap();
+    ///
+    ///     assert_eq!(output[2].get_data(), &vec!{6.5, 6.5});
+    /// }
+    /// ```
+    pub fn share(&mut self, input: usize, tensor: &Tensor<f32>) -> Result<(), GpuError>{
+        self.gpu_data.share(input, tensor)?;
+        self.prepared_flag = false;
+
+        Ok(())
+    }
+    /// append sample to GpuRunner straight from tensors, so inputs are copied once, without
+    /// building Sample
+    /// Use GpuRunner::with_capacity, so inputs of all samples fit without reallocation
+    /// Error if sample does not match samples already appended, or GpuBatch was appended
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     let mut runner = GpuRunner::with_capacity(3 * 8, 1, MemoryMetric::GB);
+    ///     runner.set_backend(Backend::Cpu);
+    ///
+    ///     let weights: Tensor<f32> = Tensor::fill(2.0, &[2, 2]);
+    ///     for i in 0..3{
+    ///         runner.append_tensors(&[&weights, &Tensor::fill(i as f32, &[2, 2])], &[], &[]).unwrap();
+    ///     }
+    ///
+    ///     let output: Vec<Tensor<f32>> = runner.matmul().await.unwrap();
+    ///
+    ///     assert_eq!(output[2].get_data(), &vec!{8.0; 4});
+    /// }
+    /// ```
+    pub fn append_tensors(&mut self, inputs: &[&Tensor<f32>], params: &[f32], output_shape: &[u32]) -> Result<(), GpuError>{
+        if self.gpu_batch.is_some(){
+            return Err(GpuError::InvalidConfig("GpuBatch can not be mixed with other samples".to_string()));
+        }
+
+        let sample_len = inputs.iter().map(|tensor| tensor.get_data().len()).sum::<usize>();
+
+        self.gpu_data.append_tensors(inputs, params, output_shape)?;
+
+        self.sample_len = sample_len as u64;
+        self.prepared_flag = false;
 
-        if flag{
-            self.sample_len = sample_len as u64;
+        Ok(())
+    }
+    /// append sample of f64 tensors to GpuRunner, that f64 operations upload without rounding
+    /// Other operations run on values rounded to f32
+    /// f64 samples can not be mixed with f32 samples before clear
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let a: Tensor<f64> = Tensor::fill(0.1, &[2, 3]);
+    ///     let b: Tensor<f64> = Tensor::fill(3.0, &[3, 2]);
+    ///     runner.append_f64(&[&a, &b], &[], &[]).unwrap();
+    ///
+    ///     // devices without SHADER_F64 feature return error, instead of computing in f32
+    ///     match runner.matmul_f64().await{
+    ///         Ok(output) => assert!((output[0].get_data()[0] - 0.9).abs() < 1e-12),
+    ///         Err(err) => assert!(matches!(err, GpuError::InvalidConfig(_))),
+    ///     }
+    /// }
+    /// ```
+    pub fn append_f64(&mut self, inputs: &[&Tensor<f64>], params: &[f32], output_shape: &[u32]) -> Result<(), GpuError>{
+        if self.gpu_batch.is_some(){
+            return Err(GpuError::InvalidConfig("GpuBatch can not be mixed with other samples".to_string()));
         }
+
+        let sample_len = inputs.iter().map(|tensor| tensor.get_data().len()).sum::<usize>();
+
+        self.gpu_data.append_f64(inputs, params, output_shape)?;
+        self.sample_len = sample_len as u64;
+        self.prepared_flag = false;
+
+        Ok(())
+    }
+    /// Share f64 tensor as input at index of every sample, the same as share, but f64
+    /// operations upload it without rounding
+    /// Error if samples were already appended, input is already shared or f32 inputs are shared
+    pub fn share_f64(&mut self, input: usize, tensor: &Tensor<f64>) -> Result<(), GpuError>{
+        self.gpu_data.share_f64(input, tensor)?;
+        self.prepared_flag = false;
+
+        Ok(())
+    }
+    /// append all samples of GpuBatch to GpuRunner, with the same params for every sample
+    /// Inputs of batch are used by operations without copy
+    /// GpuBatch can not be mixed with other samples before clear
+    /// Error if runner has other samples, or batch is not on context of runner
+    pub fn append_batch(&mut self, batch: GpuBatch, params: Vec<f32>) -> Result<(), GpuError>{
+        if batch.is_empty(){
+            return Ok(());
+        }
+        if self.gpu_batch.is_some() || self.gpu_data.samples_count != 0{
+            return Err(GpuError::InvalidConfig("GpuBatch can not be mixed with other samples".to_string()));
+        }
+        if self.context.is_none(){
+            self.context = Some(batch.get_context().clone());
+        }
+
+        if !Arc::ptr_eq(batch.get_context(), self.context.as_ref().unwrap()){
+            return Err(GpuError::InvalidConfig("GpuBatch is not on context of runner".to_string()));
+        }
+
+        self.gpu_data.append_batch(&batch, &params, &[])?;
+
+        self.sample_len = batch.sample_len() as u64;
+        self.gpu_batch = Some(batch);
+
+        Ok(())
+    }
+    /// append sample with inputs already on gpu to GpuRunner
+    /// Samples from Sample and GpuSample can not be mixed before clear
+    /// Error if inputs are not on context of runner, or sample does not match other samples
+    pub fn append_gpu(&mut self, sample: GpuSample) -> Result<(), GpuError>{
+        let sample_len = sample.inputs.len();
+
+        if sample.inputs.is_empty(){
+            return Ok(());
+        }
+        if self.context.is_none(){
+            self.context = Some(sample.inputs[0].get_context().clone());
+        }
+
+        if self.gpu_batch.is_some(){
+            return Err(GpuError::InvalidConfig("GpuBatch can not be mixed with other samples".to_string()));
+        }
+
+        let context = self.context.as_ref().unwrap();
+        if sample.inputs.iter().any(|tensor| !Arc::ptr_eq(tensor.get_context(), context)){
+            return Err(GpuError::InvalidConfig("GpuTensors are not on context of runner".to_string()));
+        }
+
+        self.gpu_data.append_gpu(&sample)?;
+
+        self.sample_len = sample_len as u64;
+        self.gpu_samples.push(sample);
+
+        Ok(())
+    }
+    /// Returns GpuContext of GpuRunner, that can be shared with other runners and is used to
+    /// create GpuTensors
+    /// Context is created on first call, if runner was not created from one
+    /// Error if there is no adapter, so application can fall back to cpu
+    pub async fn get_context(&mut self) -> Result<Arc<GpuContext>, GpuError>{
+        if self.context.is_none(){
+            self.context = Some(GpuContext::init_with_config(self.buffer_size, &MemoryMetric::B, &self.config).await?);
+        }
+
+        Ok(self.context.clone().unwrap())
+    }
+    /// Returns limits of device of GpuRunner, creating context if needed
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let mut runner = GpuRunner::init(0, MemoryMetric::B);
+    ///
+    ///     let limits = runner.get_limits().await.unwrap();
+    ///     println!("max buffer size: {}", limits.max_storage_buffer_binding_size);
+    ///     println!("max workgroups: {}", limits.max_compute_workgroups_per_dimension);
+    /// }
+    /// ```
+    pub async fn get_limits(&mut self) -> Result<wgpu::Limits, GpuError>{
+        Ok(self.get_context().await?.limits.clone())
+    }
+    /// Compile pipelines of all operations on context of runner, so first run of every
+    /// operation does not have to wait for compilation
+    pub async fn prewarm(&mut self) -> Result<(), GpuError>{
+        self.get_context().await?.prewarm()
+    }
+    /// Enable profiling, so every operation run on gpu records wall-clock time of upload, dispatch
+    /// and readback of its chunks, and gpu time of compute passes if adapter supports
+    /// TIMESTAMP_QUERY
+    /// Operations run on cpu backend are not profiled
+    pub fn enable_profiling(&mut self){
+        self.profiling = true;
+    }
+    /// Disable profiling, recorded profiles are kept until taken
+    pub fn disable_profiling(&mut self){
+        self.profiling = false;
+    }
+    /// Returns true if profiling is enabled
+    pub fn is_profiling(&self) -> bool{
+        self.profiling
+    }
+    /// Returns profile of last profiled operation
+    pub fn last_profile(&self) -> Option<&OperationProfile>{
+        self.profiles.last()
+    }
+    /// Returns recorded profiles of operations in order they were run, and clears them
+    pub fn take_profiles(&mut self) -> Vec<OperationProfile>{
+        std::mem::take(&mut self.profiles)
+    }
+    /// Enable ragged batches, so samples can have other shapes, with the same count and ranks of
+    /// inputs. Every sample gets output with its own shape
+    /// Element-wise, broadcast, matmul, forward and backward gradient operations support ragged
+    /// batches, other operations return error
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.enable_ragged();
+    ///
+    ///     // sequences of other lengths, multiplied by the same weights
+    ///     let weights: Tensor<f32> = Tensor::fill(1.0, &[2, 3]);
+    ///     for len in [1, 4, 2]{
+    ///         runner.append(Sample::from_data(vec!{weights.clone(), Tensor::fill(1.0, &[3, len])}, vec!{}, &[])).unwrap();
+    ///     }
+    ///
+    ///     let output: Vec<Tensor<f32>> = runner.matmul().await.unwrap();
+    ///
+    ///     assert_eq!(output[1].get_shape(), &vec!{2, 4});
+    ///     assert_eq!(output[2].get_data(), &vec!{3.0; 4});
+    /// }
+    /// ```
+    pub fn enable_ragged(&mut self){
+        self.ragged = true;
+        self.gpu_data.enable_ragged();
+    }
+    /// Returns true if ragged batches are enabled
+    pub fn is_ragged(&self) -> bool{
+        self.ragged
+    }
+    /// Set precision of values in gpu buffers, kept after clear
+    /// Samples and outputs stay f32 tensors, values are converted when they are uploaded and
+    /// read back. Operations without shader for precision, devices without support for it,
+    /// and samples already on gpu run in f32. Cpu backend computes F16 in f32
+    ///
+    /// With GpuPrecision::F16, matmul and forward operations keep inputs and outputs in half
+    /// precision, so chunks hold twice as many samples, and accumulate products in f32
+    ///
+    /// With GpuPrecision::F64, operations do not fall back to f32, but return error, if device
+    /// has no SHADER_F64 feature, operation has no f64 shader or samples are on gpu. Cpu backend
+    /// runs them in f64 on cpu. Outputs are rounded to f32, `_f64` operations, like matmul_f64,
+    /// return them without rounding
+    pub fn set_precision(&mut self, precision: GpuPrecision){
+        self.precision = precision;
+        self.prepared_flag = false;
+    }
+    /// Returns precision of values in gpu buffers, set by set_precision
+    pub fn get_precision(&self) -> GpuPrecision{
+        self.precision
     }
     /// clear gpu_data
     pub fn clear(&mut self){
         self.gpu_data = GpuData::new();
+        if self.ragged{
+            self.gpu_data.enable_ragged();
+        }
+        self.gpu_samples.clear();
+        self.gpu_batch = None;
+        self.gpu_outputs.clear();
+        self.prepared_flag = false;
     }
     /// set new gpu_data
     pub fn set_data(&mut self, gpu_data: GpuData){
         self.gpu_data = gpu_data;
+        self.prepared_flag = false;
     }
 }
 
@@ -1,4 +1,4 @@
-use crate::{prelude::{get_broadcast_shape, GpuOperations, GpuRunner}, tensor::Tensor};
+use crate::{prelude::{get_broadcast_shape, GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
 
 impl GpuRunner{
 
@@ -20,17 +20,22 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1]), Tensor::fill(1.0, &[1, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);
+    ///     runner.append(sample).unwrap();
     ///     
     ///     //output shape: [2, 2]
-    ///     let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_add().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_add().await.unwrap();
     /// }
     /// ```
-    pub async fn tens_broadcast_add(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn tens_broadcast_add(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
-        let flat_shapes_len = self.gpu_data.flat_shapes.len();
+        let shapes = self.gpu_data.input_shapes();
+        if shapes.len() != 2{
+            return Err(GpuError::InvalidShapes(format!("operation needs 2 tensors in sample, but samples have {}", shapes.len())));
+        }
 
-        self.gpu_data.output_shape = get_broadcast_shape(&self.gpu_data.flat_shapes[0..flat_shapes_len/2].to_vec(), &self.gpu_data.flat_shapes[flat_shapes_len/2..].to_vec()).unwrap();
+        self.gpu_data.output_shape = get_broadcast_shape(&shapes[0], &shapes[1])
+            .ok_or(GpuError::InvalidShapes(format!("shapes {:?} and {:?} can not be broadcast", shapes[0], shapes[1])))?;
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
         self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
 
@@ -40,14 +45,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BroadcastAdd).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BroadcastAdd).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a broadcast subtraction operation on tensor using GpuRunner
@@ -68,16 +73,22 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1]), Tensor::fill(1.0, &[1, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);
+    ///     runner.append(sample).unwrap();
     ///     
     ///     //output shape: [2, 2]
-    ///     let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_sub().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_sub().await.unwrap();
     /// }
     /// ```
-    pub async fn tens_broadcast_sub(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn tens_broadcast_sub(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
         
-        let flat_shapes_len = self.gpu_data.flat_shapes.len();
-        self.gpu_data.output_shape = get_broadcast_shape(&self.gpu_data.flat_shapes[0..flat_shapes_len/2].to_vec(), &self.gpu_data.flat_shapes[flat_shapes_len/2..].to_vec()).unwrap();
+        let shapes = self.gpu_data.input_shapes();
+        if shapes.len() != 2{
+            return Err(GpuError::InvalidShapes(format!("operation needs 2 tensors in sample, but samples have {}", shapes.len())));
+        }
+
+        self.gpu_data.output_shape = get_broadcast_shape(&shapes[0], &shapes[1])
+            .ok_or(GpuError::InvalidShapes(format!("shapes {:?} and {:?} can not be broadcast", shapes[0], shapes[1])))?;
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
         self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
 
@@ -87,14 +98,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BroadcastSub).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BroadcastSub).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a broadcast multiplication operation on tensor using GpuRunner
@@ -115,16 +126,22 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1]), Tensor::fill(1.0, &[1, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);
+    ///     runner.append(sample).unwrap();
     ///     
     ///     //output shape: [2, 2]
-    ///     let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_mul().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_mul().await.unwrap();
     /// }
     /// ```
-    pub async fn tens_broadcast_mul(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn tens_broadcast_mul(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
-        let flat_shapes_len = self.gpu_data.flat_shapes.len();
-        self.gpu_data.output_shape = get_broadcast_shape(&self.gpu_data.flat_shapes[0..flat_shapes_len/2].to_vec(), &self.gpu_data.flat_shapes[flat_shapes_len/2..].to_vec()).unwrap();
+        let shapes = self.gpu_data.input_shapes();
+        if shapes.len() != 2{
+            return Err(GpuError::InvalidShapes(format!("operation needs 2 tensors in sample, but samples have {}", shapes.len())));
+        }
+
+        self.gpu_data.output_shape = get_broadcast_shape(&shapes[0], &shapes[1])
+            .ok_or(GpuError::InvalidShapes(format!("shapes {:?} and {:?} can not be broadcast", shapes[0], shapes[1])))?;
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
         self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
         
@@ -134,14 +151,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BroadcastMul).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BroadcastMul).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a broadcast division operation on tensor using GpuRunner
@@ -162,16 +179,22 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1]), Tensor::fill(1.0, &[1, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);
+    ///     runner.append(sample).unwrap();
     ///     
     ///     //output shape: [2, 2]
-    ///     let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_div().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_div().await.unwrap();
     /// }
     /// ```
-    pub async fn tens_broadcast_div(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn tens_broadcast_div(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
+
+        let shapes = self.gpu_data.input_shapes();
+        if shapes.len() != 2{
+            return Err(GpuError::InvalidShapes(format!("operation needs 2 tensors in sample, but samples have {}", shapes.len())));
+        }
 
-        let flat_shapes_len = self.gpu_data.flat_shapes.len();
-        self.gpu_data.output_shape = get_broadcast_shape(&self.gpu_data.flat_shapes[0..flat_shapes_len/2].to_vec(), &self.gpu_data.flat_shapes[flat_shapes_len/2..].to_vec()).unwrap();
+        self.gpu_data.output_shape = get_broadcast_shape(&shapes[0], &shapes[1])
+            .ok_or(GpuError::InvalidShapes(format!("shapes {:?} and {:?} can not be broadcast", shapes[0], shapes[1])))?;
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
         self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
         
@@ -181,13 +204,13 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BroadcastDiv).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BroadcastDiv).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 }
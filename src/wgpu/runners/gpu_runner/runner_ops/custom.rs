@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use crate::{prelude::{CustomOperation, GpuError, GpuOperations, GpuRunner, GpuTensor}, tensor::Tensor};
+
+impl GpuRunner{
+
+    /// Perform custom operation on samples using GpuRunner
+    /// Output shape is computed by operation from input shapes and params of first sample, so
+    /// all samples have to share shapes and params, like in built-in operations
+    /// Custom operations run only on gpu
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let source = "
+    /// @group(0) @binding(0)
+    /// var<storage, read> input: array<f32>;
+    ///
+    /// struct Params {
+    ///     scale: f32,
+    /// }
+    /// @group(0) @binding(2)
+    /// var<uniform> params: Params;
+    ///
+    /// @group(0) @binding(3)
+    /// var<storage, read_write> output: array<f32>;
+    ///
+    /// @compute @workgroup_size(64)
+    /// fn main(@builtin(global_invocation_id) global_id: vec3<u32>, @builtin(num_workgroups) num_workgroups: vec3<u32>){
+    ///     let idx = invocation_index(global_id, num_workgroups);
+    ///     if (idx >= arrayLength(&output)) {
+    ///         return;
+    ///     }
+    ///
+    ///     let output_len = tensor_len(output_tensor());
+    ///     let sample = idx / output_len;
+    ///     let inner_idx = idx % output_len;
+    ///
+    ///     let a = input[broadcast_index(0u, sample, inner_idx)];
+    ///     let b = input[broadcast_index(1u, sample, inner_idx)];
+    ///     output[idx] = (a + b) * params.scale;
+    /// }
+    /// ";
+    ///
+    ///     let layout = CustomLayout{params: true, metadata: true, ..Default::default()};
+    ///     let scaled_add = CustomOperation::new("scaled_add", source, layout, |shapes, _| {
+    ///         get_broadcast_shape(&shapes[0], &shapes[1])
+    ///     }).unwrap();
+    ///     let scaled_add = Arc::new(scaled_add);
+    ///
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let a: Tensor<f32> = Tensor::fill(1.0, &[2, 3]);
+    ///     let b: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0], &[1, 3]).unwrap();
+    ///     runner.append(Sample::from_data(vec!{a, b}, vec!{2.0}, &[])).unwrap();
+    ///
+    ///     let output: Vec<Tensor<f32>> = runner.custom(&scaled_add).await.unwrap();
+    ///
+    ///     assert_eq!(output[0].get_data(), &vec!{4.0, 6.0, 8.0, 4.0, 6.0, 8.0});
+    ///     assert_eq!(output[0].get_shape(), &vec!{2, 3});
+    /// }
+    /// ```
+    pub async fn custom(&mut self, operation: &Arc<CustomOperation>) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
+
+        let input_shapes = self.gpu_data.input_shapes();
+        let output_shape = operation.output_shape(&input_shapes, &self.gpu_data.params)
+            .ok_or(GpuError::InvalidShapes(format!("inputs {:?} do not fit {}", input_shapes, operation.get_name())))?;
+
+        self.gpu_data.output_shape = output_shape;
+        self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
+        self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
+
+        let layout = operation.get_layout();
+        match layout.shapes || layout.metadata{
+            true => self.gpu_data.enable_shapes(),
+            false => self.gpu_data.disable_shapes(),
+        }
+        match layout.params{
+            true => self.gpu_data.enable_params(),
+            false => self.gpu_data.disable_params(),
+        }
+        match layout.single_output{
+            true => self.gpu_data.enable_single_output(),
+            false => self.gpu_data.disable_single_output(),
+        }
+
+        self.gpu_data.prepare_chunking_alt(self.buffer_size);
+
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::Custom(operation.clone())).await?;
+
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
+
+        if fix_needed{
+            return Ok(new_return_vec);
+        }
+        Ok(return_vec)
+    }
+    /// Same as [`GpuRunner::custom`], but outputs are not read back and stay on gpu.
+    /// Inputs have to be appended with append_gpu or append_batch, error otherwise
+    pub async fn custom_gpu(&mut self, operation: &Arc<CustomOperation>) -> Result<Vec<GpuTensor>, GpuError>{
+        self.check_gpu_samples()?;
+        self.gpu_outputs.clear();
+
+        self.keep_on_gpu = true;
+        let result = self.custom(operation).await;
+        self.keep_on_gpu = false;
+
+        result?;
+
+        Ok(std::mem::take(&mut self.gpu_outputs))
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{prelude::{GpuError, GpuPrecision, GpuRunner}, tensor::Tensor};
+
+/// Create variants of operations, that run in f64 buffers and return outputs as f64 tensors
+macro_rules! f64_output_ops{
+    ($($op:ident => $f64_op:ident),* $(,)?) => {
+        impl GpuRunner{
+            $(
+                #[doc = concat!("Same as [`GpuRunner::", stringify!($op), "`], but inputs, outputs and computation are f64, and outputs are not rounded.")]
+                #[doc = "Samples of f64 tensors are appended with append_f64, f32 samples are converted"]
+                #[doc = "Cpu backend computes them in f64 on cpu. Error if device has no SHADER_F64 feature or samples are on gpu"]
+                pub async fn $f64_op(&mut self) -> Result<Vec<Tensor<f64>>, GpuError>{
+                    let precision = self.precision;
+                    self.precision = GpuPrecision::F64;
+                    let result = self.$op().await;
+                    self.precision = precision;
+
+                    result?;
+
+                    Ok(std::mem::take(&mut self.f64_outputs))
+                }
+            )*
+        }
+    };
+}
+
+f64_output_ops!{
+    tens_add => tens_add_f64,
+    tens_sub => tens_sub_f64,
+    tens_mul => tens_mul_f64,
+    tens_div => tens_div_f64,
+
+    tens_broadcast_add => tens_broadcast_add_f64,
+    tens_broadcast_sub => tens_broadcast_sub_f64,
+    tens_broadcast_mul => tens_broadcast_mul_f64,
+    tens_broadcast_div => tens_broadcast_div_f64,
+
+    matmul => matmul_f64,
+
+    forward_no_activ => forward_no_activ_f64,
+    forward_relu => forward_relu_f64,
+}
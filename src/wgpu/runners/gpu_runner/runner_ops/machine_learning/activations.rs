@@ -1,4 +1,4 @@
-use crate::{prelude::{GpuOperations, GpuRunner}, tensor::Tensor};
+use crate::{prelude::{GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
 
 impl GpuRunner{
 
@@ -20,12 +20,13 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///
-    ///     let output_data: Vec<Tensor<f32>> = runner.relu().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.relu().await.unwrap();
     /// }
     /// ```
-    pub async fn relu(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn relu(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
         let flat_shapes_len = self.gpu_data.flat_shapes.len();
         self.gpu_data.output_shape = self.gpu_data.flat_shapes[0..flat_shapes_len].to_vec();
@@ -38,14 +39,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::ReLU).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::ReLU).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a relu derivative operation on tensor using GpuRunner
@@ -66,12 +67,13 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///
-    ///     let output_data: Vec<Tensor<f32>> = runner.relu_der().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.relu_der().await.unwrap();
     /// }
     /// ```
-    pub async fn relu_der(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn relu_der(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
         let flat_shapes_len = self.gpu_data.flat_shapes.len();
         self.gpu_data.output_shape = self.gpu_data.flat_shapes[0..flat_shapes_len].to_vec();
@@ -84,9 +86,9 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::ReLUDer).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::ReLUDer).await?;
 
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a sigmoid operation on tensor using GpuRunner
@@ -107,12 +109,13 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///
-    ///     let output_data: Vec<Tensor<f32>> = runner.sigmoid().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.sigmoid().await.unwrap();
     /// }
     /// ```
-    pub async fn sigmoid(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn sigmoid(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
         let flat_shapes_len = self.gpu_data.flat_shapes.len();
         self.gpu_data.output_shape = self.gpu_data.flat_shapes[0..flat_shapes_len].to_vec();
@@ -125,14 +128,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::Sigmoid).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::Sigmoid).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a sigmoid derivative operation on tensor using GpuRunner
@@ -153,12 +156,13 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///
-    ///     let output_data: Vec<Tensor<f32>> = runner.sigmoid().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.sigmoid().await.unwrap();
     /// }
     /// ```
-    pub async fn sigmoid_der(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn sigmoid_der(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
         let flat_shapes_len = self.gpu_data.flat_shapes.len();
         self.gpu_data.output_shape = self.gpu_data.flat_shapes[0..flat_shapes_len].to_vec();
@@ -171,8 +175,8 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::SigmoidDer).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::SigmoidDer).await?;
 
-        return_vec
+        Ok(return_vec)
     }
 }
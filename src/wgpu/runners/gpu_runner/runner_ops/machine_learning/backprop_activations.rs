@@ -1,4 +1,4 @@
-use crate::{prelude::{get_broadcast_shape, GpuOperations, GpuRunner}, tensor::Tensor};
+use crate::{prelude::{get_broadcast_shape, GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
 
 impl GpuRunner{
 
@@ -20,12 +20,13 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1]), Tensor::fill(1.0, &[1, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///
-    ///     let output_data: Vec<Tensor<f32>> = runner.backward_relu().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.backward_relu().await.unwrap();
     /// }
     /// ```
-    pub async fn backward_relu(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn backward_relu(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
         let flat_shapes_len = self.gpu_data.flat_shapes.len();
         self.gpu_data.output_shape = get_broadcast_shape(&self.gpu_data.flat_shapes[0..flat_shapes_len/2].to_vec(), &self.gpu_data.flat_shapes[flat_shapes_len/2..].to_vec()).unwrap();
@@ -38,14 +39,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BackwardRelu).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BackwardRelu).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a relu operation on tensor using GpuRunner
@@ -66,12 +67,13 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1]), Tensor::fill(1.0, &[1, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///
-    ///     let output_data: Vec<Tensor<f32>> = runner.backward_sigmoid().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.backward_sigmoid().await.unwrap();
     /// }
     /// ```
-    pub async fn backward_sigmoid(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn backward_sigmoid(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
         let flat_shapes_len = self.gpu_data.flat_shapes.len();
         self.gpu_data.output_shape = get_broadcast_shape(&self.gpu_data.flat_shapes[0..flat_shapes_len/2].to_vec(), &self.gpu_data.flat_shapes[flat_shapes_len/2..].to_vec()).unwrap();
@@ -84,13 +86,13 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BackwardSigmoid).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BackwardSigmoid).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 }   
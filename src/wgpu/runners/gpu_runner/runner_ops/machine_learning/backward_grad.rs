@@ -1,4 +1,4 @@
-use crate::{prelude::{GpuOperations, GpuRunner}, tensor::Tensor};
+use crate::{prelude::{GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
 
 impl GpuRunner{
 
@@ -22,12 +22,13 @@ impl GpuRunner{
     ///     let sample = Sample::from_data(vec!{weights, grad_output}, vec!{}, &[]);
     ///
     ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
-    ///     runner.append(sample);
+    ///     runner.append(sample).unwrap();
     ///
-    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.backward_grad().await;
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.backward_grad().await.unwrap();
     /// }
     /// ```
-    pub async fn backward_grad(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn backward_grad(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(4)?;
 
         self.gpu_data.output_shape = vec!{self.gpu_data.flat_shapes[1], self.gpu_data.flat_shapes[3]};
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
@@ -39,13 +40,13 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BackwardGradient).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BackwardGradient).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 }
@@ -1,8 +1,10 @@
-use crate::{prelude::{GpuOperations, GpuRunner}, tensor::Tensor};
+use crate::{prelude::{GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
 
 impl GpuRunner{
 
     /// Backpropagation for weights without activation
+    /// Sample: {weights, grad_output, linear_cache}, params: {learning_rate, weight_decay, l1_decay}
+    /// weight_decay (L2) and l1_decay are optional and default to 0.0
     /// No need to care about output shape while creating sample
     /// It is managed by GpuRunner
     ///
@@ -27,30 +29,31 @@ impl GpuRunner{
     ///
     ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
     ///
-    ///     runner.append(sample);
+    ///     runner.append(sample).unwrap();
     ///
-    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.backward_weight().await;
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.backward_weight().await.unwrap();
     /// }
     /// ```
-    pub async fn backward_weight(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn backward_weight(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(2)?;
 
         self.gpu_data.output_shape = vec!{self.gpu_data.flat_shapes[0], self.gpu_data.flat_shapes[1]};
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
         self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
-        
+
         self.gpu_data.enable_shapes();
         self.gpu_data.enable_params();
         self.gpu_data.enable_single_output();
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BackwardWeight).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BackwardWeight).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 }
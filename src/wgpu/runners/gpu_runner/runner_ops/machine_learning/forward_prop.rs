@@ -1,4 +1,4 @@
-use crate::{prelude::{GpuOperations, GpuRunner}, tensor::Tensor};
+use crate::{prelude::{GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
 
 impl GpuRunner{
     /// Forward propagation without activation
@@ -24,12 +24,14 @@ impl GpuRunner{
     ///
     ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
     ///
-    ///     runner.append(sample);
+    ///     runner.append(sample).unwrap();
     ///
-    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.forward_no_activ().await;
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.forward_no_activ().await.unwrap();
     /// }
     /// ```
-    pub async fn forward_no_activ(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn forward_no_activ(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(4)?;
+        self.check_inner_dims(&GpuOperations::ForwardNoActiv)?;
 
         self.gpu_data.output_shape = vec!{self.gpu_data.flat_shapes[0], self.gpu_data.flat_shapes[3]};
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
@@ -41,14 +43,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::ForwardNoActiv).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::ForwardNoActiv).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Forward propagation with relu activation
@@ -74,12 +76,14 @@ impl GpuRunner{
     ///
     ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
     ///
-    ///     runner.append(sample);
+    ///     runner.append(sample).unwrap();
     ///
-    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.forward_relu().await;
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.forward_relu().await.unwrap();
     /// }
     /// ```
-    pub async fn forward_relu(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn forward_relu(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(4)?;
+        self.check_inner_dims(&GpuOperations::ForwardRelu)?;
             
         self.gpu_data.output_shape = vec!{self.gpu_data.flat_shapes[0], self.gpu_data.flat_shapes[3]};
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
@@ -91,14 +95,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::ForwardRelu).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::ForwardRelu).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Forward propagation with sigmoid activation
@@ -124,12 +128,14 @@ impl GpuRunner{
     ///
     ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
     ///
-    ///     runner.append(sample);
+    ///     runner.append(sample).unwrap();
     ///
-    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.forward_sigmoid().await;
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.forward_sigmoid().await.unwrap();
     /// }
     /// ```
-    pub async fn forward_sigmoid(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn forward_sigmoid(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(4)?;
+        self.check_inner_dims(&GpuOperations::ForwardSigmoid)?;
 
         self.gpu_data.output_shape = vec!{self.gpu_data.flat_shapes[0], self.gpu_data.flat_shapes[3]};
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
@@ -141,13 +147,13 @@ impl GpuRunner{
     
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::ForwardSigmoid).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::ForwardSigmoid).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
     
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     } 
 }
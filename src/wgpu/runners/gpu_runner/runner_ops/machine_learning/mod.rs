@@ -4,3 +4,5 @@ pub mod backward_weight;
 pub mod forward_prop;
 pub mod activations;
 pub mod backprop_activations;
+pub mod normalization;
+pub mod recurrent;
@@ -0,0 +1,417 @@
+use crate::{prelude::{GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
+
+const BATCH_NORM: f32 = 0.0;
+const LAYER_NORM: f32 = 1.0;
+const GROUP_NORM: f32 = 2.0;
+
+/// Returns params in shader layout {eps, groups, learning_rate, mode}
+fn norm_params(params: &[f32], mode: f32) -> Vec<f32>{
+    vec!{
+        *params.first().unwrap_or(&1e-5),
+        *params.get(1).unwrap_or(&1.0),
+        *params.get(2).unwrap_or(&0.0),
+        mode,
+    }
+}
+
+// Private functions
+impl GpuRunner{
+    /// Run normalization op, where output has shape of first input
+    async fn run_norm(&mut self, gpu_ops: &GpuOperations, mode: f32) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(2)?;
+
+        self.gpu_data.output_shape = self.gpu_data.flat_shapes[0..2].to_vec();
+        self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
+        self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
+
+        let params = norm_params(&self.gpu_data.params, mode);
+        self.gpu_data.set_params(params);
+
+        self.gpu_data.enable_shapes();
+        self.gpu_data.enable_params();
+        self.gpu_data.disable_single_output();
+
+        self.gpu_data.prepare_chunking_alt(self.buffer_size);
+
+        let return_vec: Vec<Tensor<f32>> = self.run_ops(gpu_ops).await?;
+
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
+
+        if fix_needed{
+            return Ok(new_return_vec);
+        }
+        Ok(return_vec)
+    }
+
+    /// Run normalization params update, where output is gamma appended with beta
+    async fn run_norm_params(&mut self, mode: f32) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(2)?;
+
+        self.gpu_data.output_shape = vec!{self.gpu_data.flat_shapes[0] * 2, self.gpu_data.flat_shapes[1]};
+        self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
+        self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
+
+        let params = norm_params(&self.gpu_data.params, mode);
+        self.gpu_data.set_params(params);
+
+        self.gpu_data.enable_shapes();
+        self.gpu_data.enable_params();
+        self.gpu_data.enable_single_output();
+
+        self.gpu_data.prepare_chunking_alt(self.buffer_size);
+
+        let return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::NormBackwardParams).await?;
+
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
+
+        if fix_needed{
+            return Ok(new_return_vec);
+        }
+        Ok(return_vec)
+    }
+}
+
+impl GpuRunner{
+    /// Batch normalization of [features, batch] matrix using statistics of the batch
+    /// Sample: {input, gamma[features, 1], beta[features, 1]}, params: {eps}
+    /// No need to care about output shape while creating sample
+    /// It is managed by GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let inputs: Tensor<f32> = Tensor::from_data(&[1.0, 3.0, 2.0, 6.0], &[2, 2]).unwrap();
+    ///     let gamma: Tensor<f32> = Tensor::fill(1.0, &[2, 1]);
+    ///     let beta: Tensor<f32> = Tensor::fill(0.0, &[2, 1]);
+    ///
+    ///     let sample = Sample::from_data(vec!{inputs, gamma, beta}, vec!{1e-5}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.batch_norm().await.unwrap();
+    /// }
+    /// ```
+    pub async fn batch_norm(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_norm(&GpuOperations::NormForward, BATCH_NORM).await
+    }
+
+    /// Batch normalization of [features, batch] matrix using running statistics (eval mode)
+    /// Sample: {input, running_mean, running_var, gamma, beta}, all params with shape
+    /// [features, 1], params: {eps}
+    /// No need to care about output shape while creating sample
+    /// It is managed by GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let inputs: Tensor<f32> = Tensor::from_data(&[1.0, 3.0, 2.0, 6.0], &[2, 2]).unwrap();
+    ///     let mean: Tensor<f32> = Tensor::fill(0.0, &[2, 1]);
+    ///     let var: Tensor<f32> = Tensor::fill(1.0, &[2, 1]);
+    ///     let gamma: Tensor<f32> = Tensor::fill(1.0, &[2, 1]);
+    ///     let beta: Tensor<f32> = Tensor::fill(0.0, &[2, 1]);
+    ///
+    ///     let sample = Sample::from_data(vec!{inputs, mean, var, gamma, beta}, vec!{1e-5}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.batch_norm_eval().await.unwrap();
+    /// }
+    /// ```
+    pub async fn batch_norm_eval(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(2)?;
+
+        self.gpu_data.output_shape = self.gpu_data.flat_shapes[0..2].to_vec();
+        self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
+        self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
+
+        self.gpu_data.enable_shapes();
+        self.gpu_data.enable_params();
+        self.gpu_data.disable_single_output();
+
+        self.gpu_data.prepare_chunking_alt(self.buffer_size);
+
+        let return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::BatchNormEval).await?;
+
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
+
+        if fix_needed{
+            return Ok(new_return_vec);
+        }
+        Ok(return_vec)
+    }
+
+    /// Layer normalization of matrix over trailing dims with shape of gamma,
+    /// gamma [1, cols] normalizes each row, gamma [rows, cols] normalizes whole matrix
+    /// Sample: {input, gamma, beta}, params: {eps}
+    /// No need to care about output shape while creating sample
+    /// It is managed by GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let inputs: Tensor<f32> = Tensor::from_data(&[1.0, 3.0, 2.0, 6.0], &[2, 2]).unwrap();
+    ///     let gamma: Tensor<f32> = Tensor::fill(1.0, &[1, 2]);
+    ///     let beta: Tensor<f32> = Tensor::fill(0.0, &[1, 2]);
+    ///
+    ///     let sample = Sample::from_data(vec!{inputs, gamma, beta}, vec!{1e-5}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.layer_norm().await.unwrap();
+    /// }
+    /// ```
+    pub async fn layer_norm(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_norm(&GpuOperations::NormForward, LAYER_NORM).await
+    }
+
+    /// Group normalization of [channels, batch] matrix
+    /// Sample: {input, gamma[channels, 1], beta[channels, 1]}, params: {eps, groups}
+    /// No need to care about output shape while creating sample
+    /// It is managed by GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let inputs: Tensor<f32> = Tensor::rand(1.0, &[4, 2]);
+    ///     let gamma: Tensor<f32> = Tensor::fill(1.0, &[4, 1]);
+    ///     let beta: Tensor<f32> = Tensor::fill(0.0, &[4, 1]);
+    ///
+    ///     let sample = Sample::from_data(vec!{inputs, gamma, beta}, vec!{1e-5, 2.0}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.group_norm().await.unwrap();
+    /// }
+    /// ```
+    pub async fn group_norm(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_norm(&GpuOperations::NormForward, GROUP_NORM).await
+    }
+
+    /// Gradient of input of batch normalization
+    /// Sample: {input, gamma, grad_output}, params: {eps}
+    /// No need to care about output shape while creating sample
+    /// It is managed by GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let inputs: Tensor<f32> = Tensor::rand(1.0, &[2, 3]);
+    ///     let gamma: Tensor<f32> = Tensor::fill(1.0, &[2, 1]);
+    ///     let grad_output: Tensor<f32> = Tensor::rand(1.0, &[2, 3]);
+    ///
+    ///     let sample = Sample::from_data(vec!{inputs, gamma, grad_output}, vec!{1e-5}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.backward_batch_norm().await.unwrap();
+    /// }
+    /// ```
+    pub async fn backward_batch_norm(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_norm(&GpuOperations::NormBackward, BATCH_NORM).await
+    }
+
+    /// Gradient of input of layer normalization
+    /// Sample: {input, gamma, grad_output}, params: {eps}
+    /// No need to care about output shape while creating sample
+    /// It is managed by GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let inputs: Tensor<f32> = Tensor::rand(1.0, &[2, 3]);
+    ///     let gamma: Tensor<f32> = Tensor::fill(1.0, &[1, 3]);
+    ///     let grad_output: Tensor<f32> = Tensor::rand(1.0, &[2, 3]);
+    ///
+    ///     let sample = Sample::from_data(vec!{inputs, gamma, grad_output}, vec!{1e-5}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.backward_layer_norm().await.unwrap();
+    /// }
+    /// ```
+    pub async fn backward_layer_norm(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_norm(&GpuOperations::NormBackward, LAYER_NORM).await
+    }
+
+    /// Gradient of input of group normalization
+    /// Sample: {input, gamma, grad_output}, params: {eps, groups}
+    /// No need to care about output shape while creating sample
+    /// It is managed by GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let inputs: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+    ///     let gamma: Tensor<f32> = Tensor::fill(1.0, &[4, 1]);
+    ///     let grad_output: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+    ///
+    ///     let sample = Sample::from_data(vec!{inputs, gamma, grad_output}, vec!{1e-5, 2.0}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.backward_group_norm().await.unwrap();
+    /// }
+    /// ```
+    pub async fn backward_group_norm(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_norm(&GpuOperations::NormBackward, GROUP_NORM).await
+    }
+
+    /// Update gamma and beta of batch normalization, averaged over all samples
+    /// Sample: {gamma, beta, input, grad_output}, params: {eps, groups(unused), learning_rate}
+    /// Returns gamma appended with beta, shape [features * 2, 1]
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let gamma: Tensor<f32> = Tensor::fill(1.0, &[2, 1]);
+    ///     let beta: Tensor<f32> = Tensor::fill(0.0, &[2, 1]);
+    ///     let inputs: Tensor<f32> = Tensor::rand(1.0, &[2, 3]);
+    ///     let grad_output: Tensor<f32> = Tensor::rand(1.0, &[2, 3]);
+    ///
+    ///     let sample = Sample::from_data(vec!{gamma, beta, inputs, grad_output}, vec!{1e-5, 1.0, 0.01}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.backward_batch_norm_params().await.unwrap();
+    /// }
+    /// ```
+    pub async fn backward_batch_norm_params(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_norm_params(BATCH_NORM).await
+    }
+
+    /// Update gamma and beta of layer normalization, averaged over all samples
+    /// Sample: {gamma, beta, input, grad_output}, params: {eps, groups(unused), learning_rate}
+    /// Returns gamma appended with beta
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let gamma: Tensor<f32> = Tensor::fill(1.0, &[1, 3]);
+    ///     let beta: Tensor<f32> = Tensor::fill(0.0, &[1, 3]);
+    ///     let inputs: Tensor<f32> = Tensor::rand(1.0, &[2, 3]);
+    ///     let grad_output: Tensor<f32> = Tensor::rand(1.0, &[2, 3]);
+    ///
+    ///     let sample = Sample::from_data(vec!{gamma, beta, inputs, grad_output}, vec!{1e-5, 1.0, 0.01}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.backward_layer_norm_params().await.unwrap();
+    /// }
+    /// ```
+    pub async fn backward_layer_norm_params(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_norm_params(LAYER_NORM).await
+    }
+
+    /// Update gamma and beta of group normalization, averaged over all samples
+    /// Sample: {gamma, beta, input, grad_output}, params: {eps, groups, learning_rate}
+    /// Returns gamma appended with beta, shape [channels * 2, 1]
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let gamma: Tensor<f32> = Tensor::fill(1.0, &[4, 1]);
+    ///     let beta: Tensor<f32> = Tensor::fill(0.0, &[4, 1]);
+    ///     let inputs: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+    ///     let grad_output: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+    ///
+    ///     let sample = Sample::from_data(vec!{gamma, beta, inputs, grad_output}, vec!{1e-5, 2.0, 0.01}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.backward_group_norm_params().await.unwrap();
+    /// }
+    /// ```
+    pub async fn backward_group_norm_params(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_norm_params(GROUP_NORM).await
+    }
+}
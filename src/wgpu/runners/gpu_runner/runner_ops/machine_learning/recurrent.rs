@@ -0,0 +1,296 @@
+use crate::{prelude::{GRU, GpuError, GpuOperations, GpuRunner, LSTM, MemoryMetric, RNN, Sample}, tensor::Tensor};
+
+// Private functions
+impl GpuRunner{
+    /// Run recurrent cell op, where output has state_count hidden states stacked along rows
+    async fn run_cell(&mut self, gpu_ops: &GpuOperations, state_count: u32) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(10)?;
+
+        self.gpu_data.output_shape = vec!{self.gpu_data.flat_shapes[3] * state_count, self.gpu_data.flat_shapes[9]};
+        self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
+        self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
+
+        self.gpu_data.enable_shapes();
+        self.gpu_data.enable_params();
+        self.gpu_data.disable_single_output();
+
+        self.gpu_data.prepare_chunking_alt(self.buffer_size);
+
+        self.run_ops(gpu_ops).await
+    }
+}
+
+impl GpuRunner{
+    /// One step of vanilla recurrent cell
+    /// Sample: {weight_ih, weight_hh, bias_ih, bias_hh, input[input_size, batch], hidden[hidden_size, batch]}
+    /// Returns next hidden state of each sample
+    /// No need to care about output shape while creating sample
+    /// It is managed by GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let cell = RNNCell::new(3, 2);
+    ///
+    ///     let input: Tensor<f32> = Tensor::fill(1.0, &[3, 4]);
+    ///     let hidden: Tensor<f32> = Tensor::fill(0.0, &[2, 4]);
+    ///
+    ///     let sample = Sample::from_data(vec!{cell.weight_ih, cell.weight_hh, cell.bias_ih, cell.bias_hh, input, hidden}, vec!{}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.rnn_cell().await.unwrap();
+    /// }
+    /// ```
+    pub async fn rnn_cell(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_cell(&GpuOperations::RnnCell, 1).await
+    }
+
+    /// One step of GRU cell
+    /// Sample: {weight_ih, weight_hh, bias_ih, bias_hh, input[input_size, batch], hidden[hidden_size, batch]}
+    /// Returns next hidden state of each sample
+    /// No need to care about output shape while creating sample
+    /// It is managed by GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let cell = GRUCell::new(3, 2);
+    ///
+    ///     let input: Tensor<f32> = Tensor::fill(1.0, &[3, 4]);
+    ///     let hidden: Tensor<f32> = Tensor::fill(0.0, &[2, 4]);
+    ///
+    ///     let sample = Sample::from_data(vec!{cell.weight_ih, cell.weight_hh, cell.bias_ih, cell.bias_hh, input, hidden}, vec!{}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.gru_cell().await.unwrap();
+    /// }
+    /// ```
+    pub async fn gru_cell(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_cell(&GpuOperations::GruCell, 1).await
+    }
+
+    /// One step of LSTM cell
+    /// Sample: {weight_ih, weight_hh, bias_ih, bias_hh, input[input_size, batch], hidden[hidden_size, batch], cell[hidden_size, batch]}
+    /// Returns next hidden state appended with next cell state, [2 * hidden_size, batch], of each sample
+    /// No need to care about output shape while creating sample
+    /// It is managed by GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let cell = LSTMCell::new(3, 2);
+    ///
+    ///     let input: Tensor<f32> = Tensor::fill(1.0, &[3, 4]);
+    ///     let hidden: Tensor<f32> = Tensor::fill(0.0, &[2, 4]);
+    ///     let cell_state: Tensor<f32> = Tensor::fill(0.0, &[2, 4]);
+    ///
+    ///     let sample = Sample::from_data(vec!{cell.weight_ih, cell.weight_hh, cell.bias_ih, cell.bias_hh, input, hidden, cell_state}, vec!{}, &[]);
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let full_gpu_output: Vec<Tensor<f32>> = runner.lstm_cell().await.unwrap();
+    /// }
+    /// ```
+    pub async fn lstm_cell(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_cell(&GpuOperations::LstmCell, 2).await
+    }
+}
+
+/// Returns zero state for every sequence, error if any sequence is empty or lengths differ
+fn zero_states(sequences: &[Vec<Tensor<f32>>], hidden_size: u32) -> Result<Vec<Tensor<f32>>, GpuError>{
+    let steps = sequences.first().ok_or(GpuError::NoData)?.len();
+    if steps == 0 || sequences.iter().any(|sequence| sequence.len() != steps){
+        return Err(GpuError::InvalidShapes("sequences are empty or have different lengths".to_string()));
+    }
+
+    Ok(sequences.iter()
+        .map(|sequence| Tensor::fill(0.0, &[hidden_size, sequence[0].get_shape()[1]]))
+        .collect())
+}
+
+/// Returns rows [start, end) of matrix
+fn split_rows(tensor: &Tensor<f32>, start: u32, end: u32) -> Tensor<f32>{
+    let cols = tensor.get_shape()[1];
+    let data = &tensor.get_data()[(start * cols) as usize..(end * cols) as usize];
+
+    Tensor::from_data(data, &[end - start, cols]).unwrap()
+}
+
+impl RNN{
+    /// Returns hidden state of every time step of every sequence, starting from zero state
+    /// Every sequence is a separate sample, so all of them are computed in one dispatch per time step
+    /// Caches are not stored, so backward has to use forward on cpu
+    /// Error if sequences are empty or have different lengths
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let rnn = RNN::new(3, 2);
+    ///
+    ///     let sequences: Vec<Vec<Tensor<f32>>> = vec!{vec!{Tensor::fill(1.0, &[3, 4]); 5}; 2};
+    ///
+    ///     let outputs = rnn.forward_gpu(&sequences, 1, MemoryMetric::GB).await.unwrap();
+    /// }
+    /// ```
+    pub async fn forward_gpu(&self, sequences: &[Vec<Tensor<f32>>], buffer_size: u64, metric: MemoryMetric) -> Result<Vec<Vec<Tensor<f32>>>, GpuError>{
+        let mut hidden = zero_states(sequences, self.cell.hidden_size())?;
+        let mut outputs: Vec<Vec<Tensor<f32>>> = vec!{Vec::with_capacity(sequences[0].len()); sequences.len()};
+
+        let mut runner = GpuRunner::init(buffer_size, metric);
+
+        for t in 0..sequences[0].len(){
+            runner.clear();
+            for (sequence, state) in sequences.iter().zip(hidden.iter()){
+                let sample = Sample::from_data(vec!{self.cell.weight_ih.clone(), self.cell.weight_hh.clone(), self.cell.bias_ih.clone(), self.cell.bias_hh.clone(), sequence[t].clone(), state.clone()}, vec!{}, &[]);
+                runner.append(sample)?;
+            }
+
+            hidden = runner.rnn_cell().await?;
+
+            for (output, state) in outputs.iter_mut().zip(hidden.iter()){
+                output.push(state.clone());
+            }
+        }
+
+        Ok(outputs)
+    }
+}
+
+impl GRU{
+    /// Returns hidden state of every time step of every sequence, starting from zero state
+    /// Every sequence is a separate sample, so all of them are computed in one dispatch per time step
+    /// Caches are not stored, so backward has to use forward on cpu
+    /// Error if sequences are empty or have different lengths
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let gru = GRU::new(3, 2);
+    ///
+    ///     let sequences: Vec<Vec<Tensor<f32>>> = vec!{vec!{Tensor::fill(1.0, &[3, 4]); 5}; 2};
+    ///
+    ///     let outputs = gru.forward_gpu(&sequences, 1, MemoryMetric::GB).await.unwrap();
+    /// }
+    /// ```
+    pub async fn forward_gpu(&self, sequences: &[Vec<Tensor<f32>>], buffer_size: u64, metric: MemoryMetric) -> Result<Vec<Vec<Tensor<f32>>>, GpuError>{
+        let mut hidden = zero_states(sequences, self.cell.hidden_size())?;
+        let mut outputs: Vec<Vec<Tensor<f32>>> = vec!{Vec::with_capacity(sequences[0].len()); sequences.len()};
+
+        let mut runner = GpuRunner::init(buffer_size, metric);
+
+        for t in 0..sequences[0].len(){
+            runner.clear();
+            for (sequence, state) in sequences.iter().zip(hidden.iter()){
+                let sample = Sample::from_data(vec!{self.cell.weight_ih.clone(), self.cell.weight_hh.clone(), self.cell.bias_ih.clone(), self.cell.bias_hh.clone(), sequence[t].clone(), state.clone()}, vec!{}, &[]);
+                runner.append(sample)?;
+            }
+
+            hidden = runner.gru_cell().await?;
+
+            for (output, state) in outputs.iter_mut().zip(hidden.iter()){
+                output.push(state.clone());
+            }
+        }
+
+        Ok(outputs)
+    }
+}
+
+impl LSTM{
+    /// Returns hidden state of every time step of every sequence, starting from zero states
+    /// Every sequence is a separate sample, so all of them are computed in one dispatch per time step
+    /// Caches are not stored, so backward has to use forward on cpu
+    /// Error if sequences are empty or have different lengths
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let lstm = LSTM::new(3, 2);
+    ///
+    ///     let sequences: Vec<Vec<Tensor<f32>>> = vec!{vec!{Tensor::fill(1.0, &[3, 4]); 5}; 2};
+    ///
+    ///     let outputs = lstm.forward_gpu(&sequences, 1, MemoryMetric::GB).await.unwrap();
+    /// }
+    /// ```
+    pub async fn forward_gpu(&self, sequences: &[Vec<Tensor<f32>>], buffer_size: u64, metric: MemoryMetric) -> Result<Vec<Vec<Tensor<f32>>>, GpuError>{
+        let hidden_size = self.cell.hidden_size();
+
+        let mut hidden = zero_states(sequences, hidden_size)?;
+        let mut cell = hidden.clone();
+        let mut outputs: Vec<Vec<Tensor<f32>>> = vec!{Vec::with_capacity(sequences[0].len()); sequences.len()};
+
+        let mut runner = GpuRunner::init(buffer_size, metric);
+
+        for t in 0..sequences[0].len(){
+            runner.clear();
+            for ((sequence, state), cell_state) in sequences.iter().zip(hidden.iter()).zip(cell.iter()){
+                let sample = Sample::from_data(vec!{self.cell.weight_ih.clone(), self.cell.weight_hh.clone(), self.cell.bias_ih.clone(), self.cell.bias_hh.clone(), sequence[t].clone(), state.clone(), cell_state.clone()}, vec!{}, &[]);
+                runner.append(sample)?;
+            }
+
+            let states = runner.lstm_cell().await?;
+
+            hidden = states.iter().map(|state| split_rows(state, 0, hidden_size)).collect();
+            cell = states.iter().map(|state| split_rows(state, hidden_size, 2 * hidden_size)).collect();
+
+            for (output, state) in outputs.iter_mut().zip(hidden.iter()){
+                output.push(state.clone());
+            }
+        }
+
+        Ok(outputs)
+    }
+}
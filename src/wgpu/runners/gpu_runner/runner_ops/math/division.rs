@@ -1,4 +1,4 @@
-use crate::{prelude::{GpuOperations, GpuRunner}, tensor::Tensor};
+use crate::{prelude::{GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
 
 impl GpuRunner{
     /// Perform a division operation on tensor using GpuRunner
@@ -19,12 +19,13 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///
-    ///     let output_data: Vec<Tensor<f32>> = runner.div().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.div().await.unwrap();
     /// }
     /// ```
-    pub async fn div(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn div(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
         let flat_shapes_len = self.gpu_data.flat_shapes.len();
         self.gpu_data.output_shape = self.gpu_data.flat_shapes[0..flat_shapes_len].to_vec();
@@ -37,14 +38,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::Div).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::Div).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
     /// Perform a tensor division operation on tensors using GpuRunner
     /// No need to care about output shape while creating sample
@@ -64,15 +65,15 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2]), Tensor::fill(1.0, &[2, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///
-    ///     let output_data: Vec<Tensor<f32>> = runner.tens_div().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.tens_div().await.unwrap();
     /// }
     /// ```
-    pub async fn tens_div(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn tens_div(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
-        let flat_shapes_len = self.gpu_data.flat_shapes.len();
-        self.gpu_data.output_shape = self.gpu_data.flat_shapes[0..flat_shapes_len/2].to_vec();
+        self.gpu_data.output_shape = self.gpu_data.input_shapes()[0].clone();
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
         self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
 
@@ -82,13 +83,13 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::TensDiv).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::TensDiv).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 }
@@ -1,4 +1,4 @@
-use crate::{prelude::{GpuOperations, GpuRunner}, tensor::Tensor};
+use crate::{prelude::{GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
 
 impl GpuRunner{
 
@@ -20,12 +20,13 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(2.0, &[2, 2])}, vec!{2.0}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///
-    ///     let output_data: Vec<Tensor<f32>> = runner.log().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.log().await.unwrap();
     /// }
     /// ```
-    pub async fn log(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn log(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
         let flat_shapes_len = self.gpu_data.flat_shapes.len();
         self.gpu_data.output_shape = self.gpu_data.flat_shapes[0..flat_shapes_len].to_vec();
@@ -38,14 +39,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::Log).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::Log).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a natural log (log10) operation on tensors using GpuRunner
@@ -66,12 +67,13 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(10.0, &[2, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///
-    ///     let output_data: Vec<Tensor<f32>> = runner.nlog().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.nlog().await.unwrap();
     /// }
     /// ```
-    pub async fn nlog(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn nlog(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
         let flat_shapes_len = self.gpu_data.flat_shapes.len();
         self.gpu_data.output_shape = self.gpu_data.flat_shapes[0..flat_shapes_len].to_vec();
@@ -84,13 +86,13 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::NLog).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::NLog).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 }
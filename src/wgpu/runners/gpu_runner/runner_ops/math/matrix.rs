@@ -1,4 +1,4 @@
-use crate::{prelude::{GpuOperations, GpuRunner}, tensor::Tensor};
+use crate::{prelude::{GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
 
 impl GpuRunner{
 
@@ -20,16 +20,19 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2]), Tensor::fill(1.0, &[2, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///     
     ///     //return shape [tens1[0], tens2[1]]
-    ///     let output_data: Vec<Tensor<f32>> = runner.matmul().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.matmul().await.unwrap();
     /// }
     /// ```
-    pub async fn matmul(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn matmul(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(4)?;
+        self.check_inner_dims(&GpuOperations::Matmul)?;
         
         self.gpu_data.output_shape = vec!{self.gpu_data.flat_shapes[0], self.gpu_data.flat_shapes[3]};
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
+        self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
 
         self.gpu_data.enable_shapes();
         self.gpu_data.disable_params();
@@ -37,14 +40,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
         
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::Matmul).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::Matmul).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a matrix column product operation on tensors using GpuRunner
@@ -65,16 +68,18 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///     
     ///     //return shape [tens[0], 1]
-    ///     let output_data: Vec<Tensor<f32>> = runner.matrix_col_prod().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.matrix_col_prod().await.unwrap();
     /// }
     /// ```
-    pub async fn matrix_col_prod(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn matrix_col_prod(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
         self.gpu_data.output_shape = vec!{self.gpu_data.flat_shapes[0], 1};
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
+        self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
 
         self.gpu_data.enable_shapes();
         self.gpu_data.disable_params();
@@ -82,14 +87,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::MatrixColProd).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::MatrixColProd).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a matrix column sum operation on tensors using GpuRunner
@@ -110,13 +115,14 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///     
     ///     //return shape [tens[0], 1]
-    ///     let output_data: Vec<Tensor<f32>> = runner.matrix_col_sum().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.matrix_col_sum().await.unwrap();
     /// }
     /// ```
-    pub async fn matrix_col_sum(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn matrix_col_sum(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
 
         self.gpu_data.output_shape = vec!{self.gpu_data.flat_shapes[0], 1};
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
@@ -128,14 +134,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::MatrixColSum).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::MatrixColSum).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a matrix row product operation on tensors using GpuRunner
@@ -156,13 +162,14 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///     
     ///     //return shape [1, tens[1]]
-    ///     let output_data: Vec<Tensor<f32>> = runner.matrix_row_prod().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.matrix_row_prod().await.unwrap();
     /// }
     /// ```
-    pub async fn matrix_row_prod(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn matrix_row_prod(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(2)?;
 
         self.gpu_data.output_shape = vec!{1, self.gpu_data.flat_shapes[1]};
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
@@ -174,14 +181,14 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::MatrixRowProd).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::MatrixRowProd).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 
     /// Perform a matrix row sum operation on tensors using GpuRunner
@@ -202,13 +209,14 @@ impl GpuRunner{
     /// 
     ///     let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{}, &[]);
     ///
-    ///     runner.append(sample);        
+    ///     runner.append(sample).unwrap();        
     ///     
     ///     //return shape [1, tens[1]]
-    ///     let output_data: Vec<Tensor<f32>> = runner.matrix_row_sum().await;
+    ///     let output_data: Vec<Tensor<f32>> = runner.matrix_row_sum().await.unwrap();
     /// }
     /// ```
-    pub async fn matrix_row_sum(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn matrix_row_sum(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(2)?;
 
         self.gpu_data.output_shape = vec!{1, self.gpu_data.flat_shapes[1]};
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
@@ -219,13 +227,13 @@ impl GpuRunner{
         self.gpu_data.disable_single_output();
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::MatrixRowSum).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::MatrixRowSum).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 }
@@ -4,3 +4,4 @@ pub mod functions;
 pub mod matrix;
 pub mod multiplication;
 pub mod subtraction;
+pub mod reduction;
@@ -0,0 +1,379 @@
+use crate::{prelude::{GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
+
+impl GpuRunner{
+    /// Set output shape of reduction and run it
+    /// Reduction along axis takes axis from params[0] and keeps it with size 1, reduction of all
+    /// elements has output shape [1]
+    async fn run_reduction(&mut self, operation: GpuOperations, along_axis: bool) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(1)?;
+
+        let input_shape = self.gpu_data.input_shapes()[0].clone();
+
+        self.gpu_data.output_shape = if along_axis{
+            let axis = match self.gpu_data.params.first(){
+                Some(axis) if *axis >= 0.0 && (*axis as usize) < input_shape.len() => *axis as usize,
+                _ => return Err(GpuError::InvalidShapes(format!("reduction needs axis in params[0], lower than rank {}", input_shape.len()))),
+            };
+
+            let mut output_shape = input_shape;
+            output_shape[axis] = 1;
+            output_shape
+        }
+        else{
+            vec!{1}
+        };
+        self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
+        self.gpu_data.output_per_sample = self.gpu_data.output_shape.iter().product::<u32>() as usize;
+
+        self.gpu_data.enable_shapes();
+        self.gpu_data.disable_params();
+        self.gpu_data.disable_single_output();
+
+        self.gpu_data.prepare_chunking_alt(self.buffer_size);
+
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&operation).await?;
+
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
+
+        if fix_needed{
+            return Ok(new_return_vec);
+        }
+        Ok(return_vec)
+    }
+
+    /// Sum all elements of every sample using GpuRunner
+    /// Elements are reduced in parallel by whole workgroup
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::fill(2.0, &[2, 3])}, vec!{}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     //return shape [1]
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_sum().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{12.0});
+    /// }
+    /// ```
+    pub async fn reduce_sum(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceSum, false).await
+    }
+
+    /// Sum elements of every sample along axis using GpuRunner
+    /// Axis is taken from params[0] of sample, and is kept in output with size 1
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::fill(2.0, &[2, 3])}, vec!{1.0}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     //return shape [2, 1]
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_sum_axis().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{6.0, 6.0});
+    /// }
+    /// ```
+    pub async fn reduce_sum_axis(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceSum, true).await
+    }
+
+    /// Mean of all elements of every sample using GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::from_data(&[1.0, 2.0, 3.0, 6.0], &[2, 2]).unwrap()}, vec!{}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_mean().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{3.0});
+    /// }
+    /// ```
+    pub async fn reduce_mean(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceMean, false).await
+    }
+
+    /// Mean of elements of every sample along axis using GpuRunner
+    /// Axis is taken from params[0] of sample, and is kept in output with size 1
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::from_data(&[1.0, 2.0, 3.0, 6.0], &[2, 2]).unwrap()}, vec!{0.0}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     //return shape [1, 2]
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_mean_axis().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{2.0, 4.0});
+    /// }
+    /// ```
+    pub async fn reduce_mean_axis(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceMean, true).await
+    }
+
+    /// Biggest element of every sample using GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::from_data(&[1.0, 7.0, -3.0, 6.0], &[2, 2]).unwrap()}, vec!{}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_max().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{7.0});
+    /// }
+    /// ```
+    pub async fn reduce_max(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceMax, false).await
+    }
+
+    /// Biggest elements of every sample along axis using GpuRunner
+    /// Axis is taken from params[0] of sample, and is kept in output with size 1
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::from_data(&[1.0, 7.0, -3.0, 6.0], &[2, 2]).unwrap()}, vec!{0.0}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_max_axis().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{1.0, 7.0});
+    /// }
+    /// ```
+    pub async fn reduce_max_axis(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceMax, true).await
+    }
+
+    /// Smallest element of every sample using GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::from_data(&[1.0, 7.0, -3.0, 6.0], &[2, 2]).unwrap()}, vec!{}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_min().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{-3.0});
+    /// }
+    /// ```
+    pub async fn reduce_min(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceMin, false).await
+    }
+
+    /// Smallest elements of every sample along axis using GpuRunner
+    /// Axis is taken from params[0] of sample, and is kept in output with size 1
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::from_data(&[1.0, 7.0, -3.0, 6.0], &[2, 2]).unwrap()}, vec!{1.0}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_min_axis().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{1.0, -3.0});
+    /// }
+    /// ```
+    pub async fn reduce_min_axis(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceMin, true).await
+    }
+
+    /// Flat index of first biggest element of every sample using GpuRunner
+    /// Index is returned as f32
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::from_data(&[1.0, 7.0, -3.0, 7.0], &[2, 2]).unwrap()}, vec!{}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_argmax().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{1.0});
+    /// }
+    /// ```
+    pub async fn reduce_argmax(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceArgmax, false).await
+    }
+
+    /// Indexes of first biggest elements of every sample along axis using GpuRunner
+    /// Axis is taken from params[0] of sample, and is kept in output with size 1
+    /// Indexes are returned as f32
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::from_data(&[1.0, 7.0, -3.0, 6.0], &[2, 2]).unwrap()}, vec!{1.0}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_argmax_axis().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{1.0, 1.0});
+    /// }
+    /// ```
+    pub async fn reduce_argmax_axis(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceArgmax, true).await
+    }
+
+    /// Population variance of all elements of every sample using GpuRunner
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::from_data(&[1.0, 3.0, 1.0, 3.0], &[2, 2]).unwrap()}, vec!{}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_variance().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{1.0});
+    /// }
+    /// ```
+    pub async fn reduce_variance(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceVariance, false).await
+    }
+
+    /// Population variances of elements of every sample along axis using GpuRunner
+    /// Axis is taken from params[0] of sample, and is kept in output with size 1
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///     let mut runner: GpuRunner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let sample = Sample::from_data(vec!{Tensor::from_data(&[1.0, 3.0, 2.0, 2.0], &[2, 2]).unwrap()}, vec!{1.0}, &[]);
+    ///
+    ///     runner.append(sample).unwrap();
+    ///
+    ///     let output_data: Vec<Tensor<f32>> = runner.reduce_variance_axis().await.unwrap();
+    ///
+    ///     assert_eq!(output_data[0].get_data(), &vec!{1.0, 0.0});
+    /// }
+    /// ```
+    pub async fn reduce_variance_axis(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.run_reduction(GpuOperations::ReduceVariance, true).await
+    }
+}
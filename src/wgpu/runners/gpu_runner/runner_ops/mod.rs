@@ -2,3 +2,6 @@ pub mod machine_learning;
 pub mod math;
 pub mod subtypes;
 pub mod broadcasting;
+pub mod custom;
+pub mod resident;
+pub mod double;
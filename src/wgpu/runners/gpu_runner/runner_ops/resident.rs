@@ -0,0 +1,92 @@
+use crate::prelude::{GpuError, GpuRunner, GpuTensor};
+
+/// Create variants of operations, that keep outputs on gpu as GpuTensors
+macro_rules! gpu_output_ops{
+    ($($op:ident => $gpu_op:ident),* $(,)?) => {
+        impl GpuRunner{
+            $(
+                #[doc = concat!("Same as [`GpuRunner::", stringify!($op), "`], but outputs are not read back and stay on gpu.")]
+                #[doc = "Inputs have to be appended with append_gpu or append_batch, error otherwise"]
+                pub async fn $gpu_op(&mut self) -> Result<Vec<GpuTensor>, GpuError>{
+                    self.check_gpu_samples()?;
+                    self.gpu_outputs.clear();
+
+                    self.keep_on_gpu = true;
+                    let result = self.$op().await;
+                    self.keep_on_gpu = false;
+
+                    result?;
+
+                    Ok(std::mem::take(&mut self.gpu_outputs))
+                }
+            )*
+        }
+    };
+}
+
+gpu_output_ops!{
+    add => add_gpu,
+    tens_add => tens_add_gpu,
+    sub => sub_gpu,
+    tens_sub => tens_sub_gpu,
+    mul => mul_gpu,
+    tens_mul => tens_mul_gpu,
+    div => div_gpu,
+    tens_div => tens_div_gpu,
+    log => log_gpu,
+    nlog => nlog_gpu,
+
+    tens_broadcast_add => tens_broadcast_add_gpu,
+    tens_broadcast_sub => tens_broadcast_sub_gpu,
+    tens_broadcast_mul => tens_broadcast_mul_gpu,
+    tens_broadcast_div => tens_broadcast_div_gpu,
+
+    matmul => matmul_gpu,
+    matrix_transpose => matrix_transpose_gpu,
+    matrix_col_sum => matrix_col_sum_gpu,
+    matrix_col_prod => matrix_col_prod_gpu,
+    matrix_row_sum => matrix_row_sum_gpu,
+    matrix_row_prod => matrix_row_prod_gpu,
+
+    reduce_sum => reduce_sum_gpu,
+    reduce_sum_axis => reduce_sum_axis_gpu,
+    reduce_mean => reduce_mean_gpu,
+    reduce_mean_axis => reduce_mean_axis_gpu,
+    reduce_max => reduce_max_gpu,
+    reduce_max_axis => reduce_max_axis_gpu,
+    reduce_min => reduce_min_gpu,
+    reduce_min_axis => reduce_min_axis_gpu,
+    reduce_argmax => reduce_argmax_gpu,
+    reduce_argmax_axis => reduce_argmax_axis_gpu,
+    reduce_variance => reduce_variance_gpu,
+    reduce_variance_axis => reduce_variance_axis_gpu,
+
+    relu => relu_gpu,
+    relu_der => relu_der_gpu,
+    sigmoid => sigmoid_gpu,
+    sigmoid_der => sigmoid_der_gpu,
+
+    forward_no_activ => forward_no_activ_gpu,
+    forward_relu => forward_relu_gpu,
+    forward_sigmoid => forward_sigmoid_gpu,
+    backward_weight => backward_weight_gpu,
+    backward_bias => backward_bias_gpu,
+    backward_grad => backward_grad_gpu,
+    backward_relu => backward_relu_gpu,
+    backward_sigmoid => backward_sigmoid_gpu,
+
+    batch_norm => batch_norm_gpu,
+    batch_norm_eval => batch_norm_eval_gpu,
+    layer_norm => layer_norm_gpu,
+    group_norm => group_norm_gpu,
+    backward_batch_norm => backward_batch_norm_gpu,
+    backward_layer_norm => backward_layer_norm_gpu,
+    backward_group_norm => backward_group_norm_gpu,
+    backward_batch_norm_params => backward_batch_norm_params_gpu,
+    backward_layer_norm_params => backward_layer_norm_params_gpu,
+    backward_group_norm_params => backward_group_norm_params_gpu,
+
+    rnn_cell => rnn_cell_gpu,
+    gru_cell => gru_cell_gpu,
+    lstm_cell => lstm_cell_gpu,
+}
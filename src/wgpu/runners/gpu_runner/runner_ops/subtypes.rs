@@ -1,7 +1,8 @@
-use crate::{prelude::{GpuOperations, GpuRunner}, tensor::Tensor};
+use crate::{prelude::{GpuError, GpuOperations, GpuRunner}, tensor::Tensor};
 
 impl GpuRunner{
-    pub async fn matrix_transpose(&mut self) -> Vec<Tensor<f32>>{
+    pub async fn matrix_transpose(&mut self) -> Result<Vec<Tensor<f32>>, GpuError>{
+        self.check_shapes(2)?;
 
         self.gpu_data.output_shape = vec!{self.gpu_data.flat_shapes[1], self.gpu_data.flat_shapes[0]};
         self.gpu_data.output_len = self.gpu_data.output_shape.iter().product::<u32>() as usize * self.gpu_data.samples_count as usize;
@@ -13,13 +14,13 @@ impl GpuRunner{
         
         self.gpu_data.prepare_chunking_alt(self.buffer_size);
 
-        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::MatrixTranspose).await;
+        let mut return_vec: Vec<Tensor<f32>> = self.run_ops(&GpuOperations::MatrixTranspose).await?;
 
-        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await;
+        let (fix_needed, new_return_vec) = self.fix_for_single_output(&return_vec).await?;
         
         if fix_needed{
-            return new_return_vec;
+            return Ok(new_return_vec);
         }
-        return_vec
+        Ok(return_vec)
     }
 }
@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use wgpu::util::DeviceExt;
+
+use crate::{prelude::{capture_errors, GpuContext, GpuError, GpuRunner}, tensor::Tensor};
+
+use super::readback::Readback;
+
+/// Tensor, which data stays in gpu buffer between operations
+/// Can only be used with GpuRunners, that share its GpuContext
+#[derive(Clone)]
+pub struct GpuTensor{
+    buffer: wgpu::Buffer,
+    shape: Vec<u32>,
+
+    context: Arc<GpuContext>,
+}
+
+impl GpuTensor{
+    /// Create GpuTensor from existing buffer with STORAGE and COPY_SRC usage
+    /// Most of the time, you wont need to use it
+    pub fn from_buffer(buffer: wgpu::Buffer, shape: &[u32], context: Arc<GpuContext>) -> Self{
+        Self{
+            buffer,
+            shape: shape.to_vec(),
+
+            context,
+        }
+    }
+    /// Upload tensor to new buffer on context
+    /// Error if buffer could not be created
+    pub fn from_tensor(tensor: &Tensor<f32>, context: Arc<GpuContext>) -> Result<Self, GpuError>{
+        let buffer = capture_errors(&context.device, || {
+            context.device.create_buffer_init(&wgpu::util::BufferInitDescriptor{
+                label: Some("Gpu Tensor Buffer"),
+                contents: bytemuck::cast_slice(tensor.get_data()),
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            })
+        })?;
+
+        Ok(Self::from_buffer(buffer, tensor.get_shape(), context))
+    }
+    /// Returns shape of tensor
+    pub fn get_shape(&self) -> &Vec<u32>{
+        &self.shape
+    }
+    /// Change shape of tensor if the product of new shape is equal to current element count
+    /// Data on gpu is not changed
+    pub fn set_shape(&mut self, new_shape: &[u32]){
+        if new_shape.iter().product::<u32>() as usize != self.count_data(){
+            return;
+        }
+
+        self.shape = new_shape.to_vec();
+    }
+    /// Returns count of elements in tensor
+    pub fn count_data(&self) -> usize{
+        self.shape.iter().product::<u32>() as usize
+    }
+    /// Returns buffer with data of tensor
+    pub fn get_buffer(&self) -> &wgpu::Buffer{
+        &self.buffer
+    }
+    /// Returns context, which device holds the buffer
+    pub fn get_context(&self) -> &Arc<GpuContext>{
+        &self.context
+    }
+    /// Read data back from gpu
+    /// Error if buffer could not be read
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let tensor: Tensor<f32> = Tensor::fill(1.0, &[2, 2]);
+    ///     let gpu_tensor: GpuTensor = tensor.to_gpu(&mut runner).await.unwrap();
+    ///
+    ///     let result: Tensor<f32> = gpu_tensor.to_cpu().await.unwrap();
+    ///
+    ///     assert_eq!(result.get_data(), tensor.get_data());
+    /// }
+    /// ```
+    pub async fn to_cpu(&self) -> Result<Tensor<f32>, GpuError>{
+        let data = Readback::copy(self.context.clone(), &self.buffer, self.buffer.size())?.await?;
+
+        Ok(Tensor::from_data(&data[..self.count_data()], &self.shape).unwrap())
+    }
+}
+
+impl Tensor<f32>{
+    /// Upload tensor to gpu of runner, so it can be used in operations without uploading
+    /// it again
+    /// Error if runner has no device
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let gpu_tensor: GpuTensor = Tensor::fill(1.0, &[2, 2]).to_gpu(&mut runner).await.unwrap();
+    ///
+    ///     assert_eq!(gpu_tensor.get_shape(), &vec!{2, 2});
+    /// }
+    /// ```
+    pub async fn to_gpu(&self, runner: &mut GpuRunner) -> Result<GpuTensor, GpuError>{
+        GpuTensor::from_tensor(self, runner.get_context().await?)
+    }
+}
+
+/// Sample for one gpu operation, with inputs that already are on gpu
+#[derive(Clone)]
+pub struct GpuSample{
+    pub inputs: Vec<GpuTensor>,
+    pub shapes: Vec<u32>,
+    /// Rank of every input, to split shapes into shape of every input
+    pub ranks: Vec<u32>,
+    pub params: Vec<f32>,
+    pub output_len: u32,
+    pub output_shape: Vec<u32>,
+
+    pub input_len: usize,
+}
+
+impl GpuSample{
+    /// Create sample from gpu inputs params and output shape
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main(){
+    ///     if std::env::var("CI").is_ok() {
+    ///         eprintln!("Skipping GPU test in CI");
+    ///         return;
+    ///     }
+    ///
+    ///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+    ///
+    ///     let tensor = Tensor::fill(1.0, &[3]).to_gpu(&mut runner).await.unwrap();
+    ///     let sample = GpuSample::from_data(vec!{tensor}, vec!{1.0}, &[3]);
+    ///
+    ///     assert_eq!(sample.input_len, 3);
+    /// }
+    /// ```
+    pub fn from_data(inputs: Vec<GpuTensor>, params: Vec<f32>, output_shape: &[u32]) -> Self{
+        let shapes: Vec<u32> = inputs.iter()
+            .flat_map(|tensor| tensor.get_shape().iter().copied())
+            .collect();
+        let ranks: Vec<u32> = inputs.iter().map(|tensor| tensor.get_shape().len() as u32).collect();
+
+        let input_len = inputs.iter().map(|tensor| tensor.count_data()).sum();
+        let output_len: u32 = output_shape.iter().product();
+
+        Self{
+            inputs,
+            shapes,
+            ranks,
+            params,
+            output_len,
+            output_shape: output_shape.to_vec(),
+
+            input_len,
+        }
+    }
+}
@@ -1,6 +1,6 @@
-use wgpu::{BackendOptions, Backends, InstanceFlags, Limits};
+use wgpu::Limits;
 
-use crate::prelude::GpuBuffers;
+use crate::prelude::{capture_errors, GpuBuffers, GpuConfig, GpuError, Readback};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum MemoryMetric{
@@ -27,44 +27,40 @@ pub fn get_size_using_metric(size: u64, metric: &MemoryMetric) -> u64{
     0
 }
 
-/// Initlize a device with size and queue
-/// Max size is 2 GB, because of the WGPU limitations
-///
-/// Most of the time, you wont need to use it
-pub async fn gpu_init(max_buffer_size: u64, metric: &MemoryMetric) -> (wgpu::Device, wgpu::Queue){
-    let mut real_buffer_size: u64;
+/// Features requested from adapter, when it supports them
+/// Timestamps are used by profiling, f16 and f64 shaders by half and double precision buffers
+pub const OPTIONAL_FEATURES: wgpu::Features = wgpu::Features::TIMESTAMP_QUERY.union(wgpu::Features::SHADER_F16).union(wgpu::Features::SHADER_F64);
 
-    real_buffer_size = get_size_using_metric(max_buffer_size, metric);
-    
-    if real_buffer_size > 2_147_483_648{
-        panic!("Buffer size too big");
-    }
+/// Invocations in one workgroup of every shader
+pub const WORKGROUP_SIZE: u32 = 64;
 
-    if real_buffer_size == 0{
-        real_buffer_size = 1024*1024*256;
-    }
+/// Tile sizes supported by tiled matrix multiplication, every invocation of workgroup computes
+/// tile_size^2 / WORKGROUP_SIZE outputs of tile
+pub const TILE_SIZES: [u32; 3] = [8, 16, 32];
+/// Tile size used, when GpuConfig does not set other
+pub const DEFAULT_TILE_SIZE: u32 = 16;
 
-    if real_buffer_size == 2_147_483_648{
-        real_buffer_size = 2_147_483_647;
-    }
+/// Initlize a device with size and queue
+/// Max size is clamped to limits of adapter, 0 uses the biggest buffers adapter allows
+/// Error if there is no adapter, so application can fall back to cpu
+///
+/// Most of the time, you wont need to use it
+pub async fn gpu_init(max_buffer_size: u64, metric: &MemoryMetric) -> Result<(wgpu::Device, wgpu::Queue), GpuError>{
+    gpu_init_with_config(max_buffer_size, metric, &GpuConfig::default()).await
+}
+/// Same as gpu_init, but adapter is chosen by config, for example fallback adapter or adapter
+/// with name
+pub async fn gpu_init_with_config(max_buffer_size: u64, metric: &MemoryMetric, config: &GpuConfig) -> Result<(wgpu::Device, wgpu::Queue), GpuError>{
+    config.validate()?;
 
-    let limits = Limits{
-        max_buffer_size: real_buffer_size,
-        max_storage_buffer_binding_size: real_buffer_size as u32,
-        ..Limits::downlevel_defaults()
-    };
+    let instance = config.create_instance();
+    let adapter = config.request_adapter(&instance).await?;
 
-    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor{
-        backends: Backends::PRIMARY,
-        flags: InstanceFlags::default(),
-        backend_options: BackendOptions::default(),
-    });
-    let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions::default())
-        .await.expect("No adapter found");
+    let limits = device_limits(get_size_using_metric(max_buffer_size, metric), &adapter.limits());
 
     let device_descriptor = wgpu::DeviceDescriptor{
         label: Some("New Device"),
-        required_features: wgpu::Features::empty(),
+        required_features: adapter.features() & OPTIONAL_FEATURES,
         required_limits: limits,
         memory_hints: wgpu::MemoryHints::Performance,
         trace: wgpu::Trace::Off,
@@ -72,11 +68,40 @@ pub async fn gpu_init(max_buffer_size: u64, metric: &MemoryMetric) -> (wgpu::Dev
     };
 
     adapter.request_device(&device_descriptor)
-        .await.expect("No device")
+        .await.map_err(|err| GpuError::NoDevice(err.to_string()))
 }
 
-/// Get bind_group_layout for buffers
-pub fn get_bind_group_layout(buffers: &GpuBuffers) -> wgpu::BindGroupLayout{
+/// Limits requested from adapter, with buffer sizes clamped to max_buffer_size in bytes
+/// Max buffer size of 0 uses the biggest buffers adapter allows
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// let adapter_limits = wgpu::Limits::default();
+///
+/// let limits = device_limits(64 * 1024 * 1024, &adapter_limits);
+/// assert_eq!(limits.max_storage_buffer_binding_size, 64 * 1024 * 1024);
+///
+/// let limits = device_limits(0, &adapter_limits);
+/// assert_eq!(limits.max_storage_buffer_binding_size, adapter_limits.max_storage_buffer_binding_size);
+/// ```
+pub fn device_limits(max_buffer_size: u64, adapter_limits: &Limits) -> Limits{
+    let mut buffer_size = adapter_limits.max_buffer_size;
+    if max_buffer_size != 0{
+        buffer_size = buffer_size.min(max_buffer_size);
+    }
+
+    Limits{
+        max_buffer_size: buffer_size,
+        max_storage_buffer_binding_size: buffer_size.min(adapter_limits.max_storage_buffer_binding_size as u64) as u32,
+        ..adapter_limits.clone()
+    }
+}
+
+/// Create bind_group_layout with input and output, and optionally shapes, params, metadata and
+/// shared inputs bindings
+pub fn create_bind_group_layout(device: &wgpu::Device, uses_shapes: bool, uses_params: bool, uses_metadata: bool, uses_shared: bool) -> wgpu::BindGroupLayout{
     let mut bind_group_layout_entries = vec!{
         wgpu::BindGroupLayoutEntry{
             binding: 0,
@@ -100,7 +125,7 @@ pub fn get_bind_group_layout(buffers: &GpuBuffers) -> wgpu::BindGroupLayout{
         }
     };
 
-    if buffers.shapes_buffer.is_some(){
+    if uses_shapes{
         bind_group_layout_entries.push(
             wgpu::BindGroupLayoutEntry{
                 binding: 1,
@@ -115,7 +140,7 @@ pub fn get_bind_group_layout(buffers: &GpuBuffers) -> wgpu::BindGroupLayout{
         );
     }
 
-    if buffers.params_buffer.is_some(){
+    if uses_params{
         bind_group_layout_entries.push(
             wgpu::BindGroupLayoutEntry{
                 binding: 2,
@@ -129,8 +154,38 @@ pub fn get_bind_group_layout(buffers: &GpuBuffers) -> wgpu::BindGroupLayout{
             },
         );
     }
+
+    if uses_metadata{
+        bind_group_layout_entries.push(
+            wgpu::BindGroupLayoutEntry{
+                binding: 4,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None
+                },
+                count: None,
+            },
+        );
+    }
+
+    if uses_shared{
+        bind_group_layout_entries.push(
+            wgpu::BindGroupLayoutEntry{
+                binding: 5,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None
+                },
+                count: None,
+            },
+        );
+    }
     
-    let bind_group_layout = buffers.device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor{
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor{
         label: Some("Bing group layout"),
         entries: &bind_group_layout_entries,
     });
@@ -138,37 +193,70 @@ pub fn get_bind_group_layout(buffers: &GpuBuffers) -> wgpu::BindGroupLayout{
     bind_group_layout
 }
 
+/// Get bind_group_layout for buffers
+pub fn get_bind_group_layout(buffers: &GpuBuffers) -> wgpu::BindGroupLayout{
+    create_bind_group_layout(&buffers.context.device, buffers.shapes_buffer.is_some(), buffers.params_buffer.is_some(), false, false)
+}
+
 /// Get bind_group for buffers if bind_group_layout present
+/// If buffers use cached pipeline, only bindings declared by its shader are bound
 pub fn get_bind_group(buffers: &GpuBuffers) -> wgpu::BindGroup{
-    
+    let (uses_shapes, uses_params, uses_metadata, uses_shared) = match &buffers.pipeline{
+        Some(pipeline) => (pipeline.uses_shapes, pipeline.uses_params, pipeline.uses_metadata, pipeline.uses_shared),
+        None => (buffers.shapes_buffer.is_some(), buffers.params_buffer.is_some(), false, false),
+    };
+
+    // only inputs of samples are bound, so arrayLength does not count unwritten capacity of buffer
+    let inputs_size = buffers.precision.buffer_size(buffers.input_len).min(buffers.inputs_buffer.size());
+
     let mut bind_group_entries = vec!{
         wgpu::BindGroupEntry{
             binding: 0,
-            resource: buffers.inputs_buffer.as_entire_binding(),
+            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding{
+                buffer: &buffers.inputs_buffer,
+                offset: 0,
+                size: wgpu::BufferSize::new(inputs_size),
+            }),
         },
         wgpu::BindGroupEntry{
             binding: 3,
             resource: buffers.output_buffer.as_entire_binding(),
         }
     };
-    if buffers.shapes_buffer.is_some(){
+    if uses_shapes{
         bind_group_entries.push(
             wgpu::BindGroupEntry{
                 binding: 1,
-                resource: buffers.shapes_buffer.as_ref().unwrap().as_entire_binding(),
+                resource: buffers.shapes_buffer.as_ref().expect("Operation requires shapes").as_entire_binding(),
             }
         );
     }
-    if buffers.params_buffer.is_some(){
+    if uses_params{
         bind_group_entries.push(
             wgpu::BindGroupEntry{
                 binding: 2,
-                resource: buffers.params_buffer.as_ref().unwrap().as_entire_binding(),
+                resource: buffers.params_buffer.as_ref().expect("Operation requires params").as_entire_binding(),
+            }
+        );
+    }
+    if uses_metadata{
+        bind_group_entries.push(
+            wgpu::BindGroupEntry{
+                binding: 4,
+                resource: buffers.metadata_buffer.as_ref().expect("Operation requires shapes").as_entire_binding(),
+            }
+        );
+    }
+    if uses_shared{
+        bind_group_entries.push(
+            wgpu::BindGroupEntry{
+                binding: 5,
+                resource: buffers.shared_buffer.as_ref().expect("Operation requires shared inputs").as_entire_binding(),
             }
         );
     }
 
-    let bind_group = buffers.device.create_bind_group(&wgpu::BindGroupDescriptor{
+    let bind_group = buffers.context.device.create_bind_group(&wgpu::BindGroupDescriptor{
         label: Some("Bind group"),
         layout: buffers.bind_group_layout.as_ref().unwrap(),
         entries: &bind_group_entries,
@@ -189,61 +277,110 @@ pub fn get_pipeline_layout(device: &wgpu::Device, bind_group_layout: &wgpu::Bind
 }
 
 /// Get pipeline for bind_group_layout
-pub fn get_pipeline(device: &wgpu::Device, shader: &wgpu::ShaderModule, pipeline_layout: &wgpu::PipelineLayout) -> wgpu::ComputePipeline{
+pub fn get_pipeline(device: &wgpu::Device, shader: &wgpu::ShaderModule, pipeline_layout: &wgpu::PipelineLayout, constants: &[(&str, f64)]) -> wgpu::ComputePipeline{
     let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor{
         label: Some("Compute pipeline"),
         layout: Some(&pipeline_layout),
         module: &shader,
         entry_point: Some("main"),
-        compilation_options: wgpu::PipelineCompilationOptions::default(),
+        // shaders write workgroup memory before reading it, zeroing it first only slows down
+        // dispatches with many workgroups
+        compilation_options: wgpu::PipelineCompilationOptions{
+            constants,
+            zero_initialize_workgroup_memory: false,
+        },
         cache: None,
     });
 
     pipeline
 }
 
-/// Dispatch and recive data
-///
-/// tbh I propably does not need to write this, because GpuBuffers are handlig it by default
-pub async fn dispatch_and_receive(device: &wgpu::Device, pipeline: &wgpu::ComputePipeline, bind_group: &wgpu::BindGroup, queue: &wgpu::Queue, input_data_len: usize, output_buffer: &wgpu::Buffer, output_len: usize) -> Vec<f32>{
-    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-        label: Some("Encoder"),
+/// Record compute pass with enough workgroups to cover output_len invocations
+/// Workgroups are split into second dimension after max_workgroups, limit of device
+pub fn encode_dispatch(encoder: &mut wgpu::CommandEncoder, pipeline: &wgpu::ComputePipeline, bind_group: &wgpu::BindGroup, output_len: usize, max_workgroups: u32){
+    encode_dispatch_timed(encoder, pipeline, bind_group, output_len, max_workgroups, None);
+}
+
+/// Same as encode_dispatch, but compute pass writes timestamps at its beginning and end, if
+/// timestamp_writes is set
+pub fn encode_dispatch_timed(encoder: &mut wgpu::CommandEncoder, pipeline: &wgpu::ComputePipeline, bind_group: &wgpu::BindGroup, output_len: usize, max_workgroups: u32, timestamp_writes: Option<wgpu::ComputePassTimestampWrites>){
+    let total_invocations = output_len as u32;
+    let total_workgroups = total_invocations.div_ceil(WORKGROUP_SIZE);
+
+    // 3D split
+    let x = total_workgroups.min(max_workgroups);
+    let y = total_workgroups.div_ceil(max_workgroups).min(max_workgroups);
+    let z = 1;       
+
+    let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor{
+        label: Some("Compute pass"),
+        timestamp_writes,
     });
-    {
-        let workgroup_size = 64;
-        let total_invocations = output_len as u32;
-        let total_workgroups = (total_invocations + workgroup_size - 1) / workgroup_size;
-
-        // 3D split
-        let x = total_workgroups.min(65535);
-        let y = ((total_workgroups / 65535) + 1).min(65535);
-        let z = 1;       
-
-        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor{
-            label: Some("Compute pass"),
-            timestamp_writes: None,
+    compute_pass.set_pipeline(pipeline);
+    compute_pass.set_bind_group(0, bind_group, &[]);
+    compute_pass.dispatch_workgroups(x, y.max(1), z);
+}
+
+/// Dispatch without reading output back, so it can stay on gpu
+pub fn dispatch(device: &wgpu::Device, pipeline: &wgpu::ComputePipeline, bind_group: &wgpu::BindGroup, queue: &wgpu::Queue, output_len: usize) -> Result<(), GpuError>{
+    capture_errors(device, || {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Encoder"),
         });
-        compute_pass.set_pipeline(pipeline);
-        compute_pass.set_bind_group(0, bind_group, &[]);
-        compute_pass.dispatch_workgroups(x, y.max(1), z);
-    }
+        encode_dispatch(&mut encoder, pipeline, bind_group, output_len, device.limits().max_compute_workgroups_per_dimension);
 
-    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        queue.submit(Some(encoder.finish()));
+    })
+}
+
+/// Create staging buffer with size of buffer
+fn create_staging(device: &wgpu::Device, size: u64) -> wgpu::Buffer{
+    device.create_buffer(&wgpu::BufferDescriptor {
         label: Some("Staging"),
-        size: output_buffer.size(),
+        size,
         usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
         mapped_at_creation: false,
-    });
+    })
+}
 
-    encoder.copy_buffer_to_buffer(output_buffer, 0, &staging, 0, staging.size());
-    queue.submit(Some(encoder.finish()));
+/// Read data of buffer with COPY_SRC usage
+pub async fn read_buffer(device: &wgpu::Device, queue: &wgpu::Queue, buffer: &wgpu::Buffer) -> Result<Vec<f32>, GpuError>{
+    let staging = capture_errors(device, || {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Encoder"),
+        });
 
-    let slice = staging.slice(..);
-    slice.map_async(wgpu::MapMode::Read, |_| {});
-    device.poll(wgpu::MaintainBase::Wait);
+        let staging = create_staging(device, buffer.size());
 
-    let data = slice.get_mapped_range();
-    let result: Vec<f32> = bytemuck::cast_slice(&data).to_vec();
-    
-    result
+        encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, staging.size());
+        queue.submit(Some(encoder.finish()));
+
+        staging
+    })?;
+
+    let size = staging.size();
+    Readback::new(device, staging, size).await
+}
+
+/// Dispatch and recive data
+/// Readback does not block executor, while gpu runs dispatch
+///
+/// tbh I propably does not need to write this, because GpuBuffers are handlig it by default
+pub async fn dispatch_and_receive(device: &wgpu::Device, pipeline: &wgpu::ComputePipeline, bind_group: &wgpu::BindGroup, queue: &wgpu::Queue, input_data_len: usize, output_buffer: &wgpu::Buffer, output_len: usize) -> Result<Vec<f32>, GpuError>{
+    let staging = capture_errors(device, || {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Encoder"),
+        });
+        encode_dispatch(&mut encoder, pipeline, bind_group, output_len, device.limits().max_compute_workgroups_per_dimension);
+
+        let staging = create_staging(device, output_buffer.size());
+
+        encoder.copy_buffer_to_buffer(output_buffer, 0, &staging, 0, staging.size());
+        queue.submit(Some(encoder.finish()));
+
+        staging
+    })?;
+
+    let size = staging.size();
+    Readback::new(device, staging, size).await
 }
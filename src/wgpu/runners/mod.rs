@@ -1,6 +1,20 @@
+pub mod gpu_error;
 pub mod helpers;
 pub mod shaders;
+pub mod custom_op;
 pub mod sample;
+pub mod precision;
 pub mod gpu_data;
+pub mod gpu_config;
+pub mod pipeline_cache;
+pub mod readback;
+pub mod profiling;
+pub mod gpu_context;
 pub mod gpu_buffers;
 pub mod gpu_runner;
+pub mod backend;
+pub mod conformance;
+pub mod gpu_tensor;
+pub mod gpu_batch;
+pub mod gpu_graph;
+pub mod elementwise;
@@ -0,0 +1,180 @@
+use std::{collections::HashMap, sync::{Arc, Mutex}};
+
+use super::{gpu_error::{capture_errors, GpuError}, helpers::{create_bind_group_layout, get_pipeline, get_pipeline_layout, DEFAULT_TILE_SIZE, WORKGROUP_SIZE}, precision::GpuPrecision, shaders::{get_shader_from_source, GpuOperations}};
+
+/// Compiled shader of operation with its layouts and compute pipeline
+pub struct GpuPipeline{
+    pub shader: wgpu::ShaderModule,
+    pub bind_group_layout: wgpu::BindGroupLayout,
+    pub pipeline_layout: wgpu::PipelineLayout,
+    pub pipeline: wgpu::ComputePipeline,
+
+    /// Shader reads shapes buffer at binding 1
+    pub uses_shapes: bool,
+    /// Shader reads params buffer at binding 2
+    pub uses_params: bool,
+    /// Size in bytes of Params struct of shader, params buffer is padded with 0.0 to it
+    pub params_size: u64,
+    /// Shader reads metadata of shapes at binding 4, declared by shared prelude
+    pub uses_metadata: bool,
+    /// Shader reads inputs shared by every sample at binding 5
+    pub uses_shared: bool,
+    /// Outputs computed by one workgroup, tiled shaders compute whole tiles and reductions
+    /// use whole workgroup for one output
+    pub outputs_per_workgroup: usize,
+}
+
+/// Returns size in bytes of params struct, that shader reads at binding 2, from reflection of
+/// parsed shader, 0 if shader has no params or does not parse
+fn params_struct_size(source: &str) -> u64{
+    let Ok(module) = wgpu::naga::front::wgsl::parse_str(source) else {
+        return 0;
+    };
+
+    module.global_variables.iter()
+        .find(|(_, variable)| variable.binding == Some(wgpu::naga::ResourceBinding{group: 0, binding: 2}))
+        .map(|(_, variable)| module.types[variable.ty].inner.size(module.to_ctx()) as u64)
+        .unwrap_or(0)
+}
+
+impl GpuPipeline{
+    /// Compile operation on device
+    /// Bindings of layout are taken from bindings declared in shader
+    /// Shaders using tiled matrix multiplication are compiled with tile_size
+    /// Error if shader does not pass validation
+    pub fn compile(device: &wgpu::Device, operation: &GpuOperations, tile_size: u32) -> Result<Self, GpuError>{
+        Self::compile_source(device, operation.shader_src(), tile_size)
+    }
+    /// Compile WGSL source on device, the same way as shaders of operations
+    /// Error if shader does not pass validation
+    pub fn compile_source(device: &wgpu::Device, source: &str, tile_size: u32) -> Result<Self, GpuError>{
+        let uses_shapes = source.contains("@binding(1)");
+        let uses_params = source.contains("@binding(2)");
+        let params_size = params_struct_size(source);
+        let uses_metadata = source.contains("@binding(4)");
+        let uses_shared = source.contains("@binding(5)");
+
+        let mut constants: Vec<(&str, f64)> = Vec::new();
+        let mut outputs_per_workgroup = WORKGROUP_SIZE as usize;
+        if source.contains("override TILE_SIZE"){
+            constants.push(("TILE_SIZE", tile_size as f64));
+            outputs_per_workgroup = (tile_size * tile_size) as usize;
+        }
+        if source.contains("var<workgroup> accumulators"){
+            outputs_per_workgroup = 1;
+        }
+
+        capture_errors(device, || {
+            let shader = get_shader_from_source(device, source);
+            let bind_group_layout = create_bind_group_layout(device, uses_shapes, uses_params, uses_metadata, uses_shared);
+            let pipeline_layout = get_pipeline_layout(device, &bind_group_layout);
+            let pipeline = get_pipeline(device, &shader, &pipeline_layout, &constants);
+
+            Self{
+                shader,
+                bind_group_layout,
+                pipeline_layout,
+                pipeline,
+
+                uses_shapes,
+                uses_params,
+                params_size,
+                uses_metadata,
+                uses_shared,
+                outputs_per_workgroup,
+            }
+        })
+    }
+    /// Returns count of invocations, that compute output_len outputs
+    pub fn dispatch_len(&self, output_len: usize) -> usize{
+        let workgroups = output_len.div_ceil(self.outputs_per_workgroup);
+        workgroups.min((u32::MAX / WORKGROUP_SIZE) as usize) * WORKGROUP_SIZE as usize
+    }
+}
+
+/// Pipelines compiled on one device, every operation is compiled once for every precision, on
+/// first use
+pub struct PipelineCache{
+    pipelines: Mutex<HashMap<(GpuOperations, GpuPrecision), Arc<GpuPipeline>>>,
+    generated: Mutex<HashMap<String, Arc<GpuPipeline>>>,
+    tile_size: u32,
+}
+
+impl Default for PipelineCache{
+    fn default() -> Self{
+        Self::with_tile_size(DEFAULT_TILE_SIZE)
+    }
+}
+
+impl PipelineCache{
+    /// Create empty cache
+    pub fn new() -> Self{
+        Self::default()
+    }
+    /// Create empty cache, that compiles tiled matrix multiplication with tile_size
+    pub fn with_tile_size(tile_size: u32) -> Self{
+        Self{
+            pipelines: Mutex::new(HashMap::new()),
+            generated: Mutex::new(HashMap::new()),
+            tile_size,
+        }
+    }
+    /// Returns tile size of tiled matrix multiplication
+    pub fn tile_size(&self) -> u32{
+        self.tile_size
+    }
+    /// Returns pipeline of operation, compiling it if it is not in cache yet
+    /// Pipelines that failed to compile are not cached
+    pub fn get(&self, device: &wgpu::Device, operation: &GpuOperations) -> Result<Arc<GpuPipeline>, GpuError>{
+        self.get_with_precision(device, operation, GpuPrecision::F32)
+    }
+    /// Returns pipeline of operation, that reads and writes values of precision, compiling it if
+    /// it is not in cache yet
+    /// Error if operation has no shader for precision, or shader does not compile
+    pub fn get_with_precision(&self, device: &wgpu::Device, operation: &GpuOperations, precision: GpuPrecision) -> Result<Arc<GpuPipeline>, GpuError>{
+        let mut pipelines = self.pipelines.lock().unwrap();
+
+        let key = (operation.clone(), precision);
+        if let Some(pipeline) = pipelines.get(&key){
+            return Ok(pipeline.clone());
+        }
+
+        let source = operation.precision_shader_src(precision)
+            .ok_or_else(|| GpuError::InvalidConfig(format!("{:?} has no shader for {:?} precision", operation, precision)))?;
+
+        let pipeline = Arc::new(GpuPipeline::compile_source(device, source, self.tile_size)?);
+        pipelines.insert(key, pipeline.clone());
+
+        Ok(pipeline)
+    }
+    /// Returns pipeline of shader generated at runtime, compiling it if the same source is not in
+    /// cache yet
+    pub fn get_generated(&self, device: &wgpu::Device, source: &str) -> Result<Arc<GpuPipeline>, GpuError>{
+        let mut generated = self.generated.lock().unwrap();
+
+        if let Some(pipeline) = generated.get(source){
+            return Ok(pipeline.clone());
+        }
+
+        let pipeline = Arc::new(GpuPipeline::compile_source(device, source, self.tile_size)?);
+        generated.insert(source.to_string(), pipeline.clone());
+
+        Ok(pipeline)
+    }
+    /// Returns count of compiled shaders generated at runtime
+    pub fn generated_len(&self) -> usize{
+        self.generated.lock().unwrap().len()
+    }
+    /// Returns true if operation is already compiled
+    pub fn contains(&self, operation: &GpuOperations) -> bool{
+        self.pipelines.lock().unwrap().contains_key(&(operation.clone(), GpuPrecision::F32))
+    }
+    /// Returns count of compiled operations, counting every precision of operation
+    pub fn len(&self) -> usize{
+        self.pipelines.lock().unwrap().len()
+    }
+    /// Returns true if no operation is compiled
+    pub fn is_empty(&self) -> bool{
+        self.len() == 0
+    }
+}
@@ -0,0 +1,122 @@
+use std::borrow::Cow;
+
+/// Precision of values in gpu buffers
+/// Values of f32 samples and outputs are converted when they are uploaded and read back, f64
+/// samples are uploaded without rounding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum GpuPrecision{
+    #[default]
+    F32,
+    /// Half precision values in buffers, that halve memory of inputs and outputs. Shaders
+    /// compute and accumulate in f32, and round once, when output is stored
+    /// Needs SHADER_F16 feature of adapter
+    #[cfg(feature = "half")]
+    F16,
+    /// Double precision values in buffers, shaders compute and accumulate in f64
+    /// Needs SHADER_F64 feature of adapter, operations error on devices without it
+    F64,
+}
+
+impl GpuPrecision{
+    /// Returns bytes of one value in gpu buffer
+    pub fn size(&self) -> usize{
+        match self{
+            GpuPrecision::F32 => size_of::<f32>(),
+            #[cfg(feature = "half")]
+            GpuPrecision::F16 => size_of::<half::f16>(),
+            GpuPrecision::F64 => size_of::<f64>(),
+        }
+    }
+    /// Returns bytes of buffer with len values, padded to 4 bytes, as buffers are written and
+    /// bound in 4 byte words
+    pub fn buffer_size(&self, len: usize) -> u64{
+        (len * self.size()).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT as usize) as u64
+    }
+    /// Returns bytes of values in precision, padded to 4 bytes
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// assert_eq!(GpuPrecision::F32.encode(&[1.0, 2.0]).len(), 8);
+    /// ```
+    pub fn encode<'a>(&self, values: &'a [f32]) -> Cow<'a, [u8]>{
+        match self{
+            GpuPrecision::F32 => Cow::Borrowed(bytemuck::cast_slice(values)),
+            #[cfg(feature = "half")]
+            GpuPrecision::F16 => {
+                let mut bytes: Vec<u8> = Vec::with_capacity(self.buffer_size(values.len()) as usize);
+                for value in values{
+                    bytes.extend_from_slice(&half::f16::from_f32(*value).to_le_bytes());
+                }
+                bytes.resize(self.buffer_size(values.len()) as usize, 0);
+
+                Cow::Owned(bytes)
+            },
+            GpuPrecision::F64 => {
+                let wide: Vec<f64> = values.iter().map(|value| *value as f64).collect();
+                Cow::Owned(bytemuck::cast_slice(&wide).to_vec())
+            },
+        }
+    }
+    /// Returns bytes of f64 values in precision, padded to 4 bytes
+    /// Values are rounded, unless precision is F64
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// assert_eq!(GpuPrecision::F64.encode_f64(&[0.1]), 0.1f64.to_le_bytes().as_slice());
+    /// assert_eq!(GpuPrecision::F32.encode_f64(&[0.1]), 0.1f32.to_le_bytes().as_slice());
+    /// ```
+    pub fn encode_f64<'a>(&self, values: &'a [f64]) -> Cow<'a, [u8]>{
+        match self{
+            GpuPrecision::F64 => Cow::Borrowed(bytemuck::cast_slice(values)),
+            _ => {
+                let narrow: Vec<f32> = values.iter().map(|value| *value as f32).collect();
+                Cow::Owned(self.encode(&narrow).into_owned())
+            },
+        }
+    }
+    /// Returns f32 values of buffer read back as 4 byte words
+    /// Half precision buffers padded to 4 bytes decode into one more value
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// assert_eq!(GpuPrecision::F32.decode(vec!{1.0, 2.0}), vec!{1.0, 2.0});
+    /// ```
+    pub fn decode(&self, words: Vec<f32>) -> Vec<f32>{
+        match self{
+            GpuPrecision::F32 => words,
+            #[cfg(feature = "half")]
+            GpuPrecision::F16 => bytemuck::cast_slice::<f32, u8>(&words)
+                .chunks_exact(size_of::<half::f16>())
+                .map(|bytes| half::f16::from_le_bytes([bytes[0], bytes[1]]).to_f32())
+                .collect(),
+            GpuPrecision::F64 => self.decode_f64(words).iter().map(|value| *value as f32).collect(),
+        }
+    }
+    /// Returns f64 values of buffer read back as 4 byte words, without rounding if precision is
+    /// F64
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// let words: Vec<f32> = bytemuck::cast_slice(&[0.1f64]).to_vec();
+    ///
+    /// assert_eq!(GpuPrecision::F64.decode_f64(words), vec!{0.1});
+    /// ```
+    pub fn decode_f64(&self, words: Vec<f32>) -> Vec<f64>{
+        match self{
+            GpuPrecision::F64 => bytemuck::cast_slice::<f32, u8>(&words)
+                .chunks_exact(size_of::<f64>())
+                .map(|bytes| f64::from_le_bytes(bytes.try_into().unwrap()))
+                .collect(),
+            _ => self.decode(words).iter().map(|value| *value as f64).collect(),
+        }
+    }
+}
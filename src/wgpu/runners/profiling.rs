@@ -0,0 +1,105 @@
+use std::{fmt, time::Duration};
+
+use super::shaders::GpuOperations;
+
+/// Times of one chunk of operation
+/// Chunks are pipelined, so upload and dispatch of chunk overlap with readback of previous one
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkProfile{
+    /// Index of chunk in operation
+    pub chunk: usize,
+    /// Samples processed by chunk
+    pub samples: usize,
+    /// Wall-clock time of creating or updating buffers with inputs of chunk
+    pub upload: Duration,
+    /// Wall-clock time of encoding and submitting dispatch with copy of outputs
+    pub dispatch: Duration,
+    /// Wall-clock time of waiting for outputs and reading them into tensors
+    pub readback: Duration,
+    /// Gpu time of compute pass, from timestamps written by gpu
+    /// None if adapter does not support TIMESTAMP_QUERY
+    pub gpu: Option<Duration>,
+}
+
+/// Profile of one operation of GpuRunner, with times of its chunks
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main(){
+///     if std::env::var("CI").is_ok() {
+///         eprintln!("Skipping GPU test in CI");
+///         return;
+///     }
+///     let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+///     runner.enable_profiling();
+///
+///     runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[])).unwrap();
+///     runner.add().await.unwrap();
+///
+///     let profiles: Vec<OperationProfile> = runner.take_profiles();
+///
+///     assert_eq!(profiles[0].operation, GpuOperations::Add);
+///     assert_eq!(profiles[0].chunks.len(), 1);
+///     println!("{}", profiles[0]);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationProfile{
+    pub operation: GpuOperations,
+    pub chunks: Vec<ChunkProfile>,
+    /// Wall-clock time of whole operation
+    pub total: Duration,
+}
+
+impl OperationProfile{
+    /// Create profile of operation without chunks
+    pub fn new(operation: GpuOperations) -> Self{
+        Self{
+            operation,
+            chunks: Vec::new(),
+            total: Duration::ZERO,
+        }
+    }
+    /// Returns upload time of all chunks
+    pub fn upload(&self) -> Duration{
+        self.chunks.iter().map(|chunk| chunk.upload).sum()
+    }
+    /// Returns dispatch time of all chunks
+    pub fn dispatch(&self) -> Duration{
+        self.chunks.iter().map(|chunk| chunk.dispatch).sum()
+    }
+    /// Returns readback time of all chunks
+    pub fn readback(&self) -> Duration{
+        self.chunks.iter().map(|chunk| chunk.readback).sum()
+    }
+    /// Returns gpu time of all chunks, None if any chunk has no timestamps
+    pub fn gpu(&self) -> Option<Duration>{
+        self.chunks.iter().map(|chunk| chunk.gpu).sum()
+    }
+}
+
+impl fmt::Display for OperationProfile{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        writeln!(f, "{:?}: {:?} in {} chunks", self.operation, self.total, self.chunks.len())?;
+        writeln!(f, "  upload: {:?}, dispatch: {:?}, readback: {:?}, gpu: {:?}", self.upload(), self.dispatch(), self.readback(), self.gpu())?;
+
+        for chunk in &self.chunks{
+            writeln!(f, "  chunk {} ({} samples): upload: {:?}, dispatch: {:?}, readback: {:?}, gpu: {:?}", chunk.chunk, chunk.samples, chunk.upload, chunk.dispatch, chunk.readback, chunk.gpu)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns time between two gpu timestamps, read back as f32 values
+/// Timestamps are u64 ticks, period is in nanoseconds per tick
+pub(crate) fn timestamps_duration(data: &[f32], period: f32) -> Duration{
+    let ticks: Vec<u64> = data.chunks(2)
+        .map(|pair| pair[0].to_bits() as u64 | ((pair[1].to_bits() as u64) << 32))
+        .collect();
+
+    Duration::from_nanos((ticks[1].saturating_sub(ticks[0]) as f64 * period as f64) as u64)
+}
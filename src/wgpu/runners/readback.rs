@@ -0,0 +1,276 @@
+use std::{collections::HashMap, future::Future, pin::Pin, sync::{Arc, LazyLock, Mutex}, task::{Context, Poll, Waker}};
+
+use super::{gpu_context::GpuContext, gpu_error::{capture_errors, GpuError}};
+
+/// Staging buffers kept by pool, the smallest ones are dropped first
+const MAX_POOLED_STAGING: usize = 4;
+
+/// Staging buffers of one device, reused between readbacks instead of allocating new buffer for
+/// every read
+pub struct StagingPool{
+    buffers: Mutex<Vec<wgpu::Buffer>>,
+}
+
+impl Default for StagingPool{
+    fn default() -> Self{
+        Self{
+            buffers: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl StagingPool{
+    /// Create empty pool
+    pub fn new() -> Self{
+        Self::default()
+    }
+    /// Returns the smallest pooled buffer with at least size bytes, or creates new one
+    pub fn acquire(&self, device: &wgpu::Device, size: u64) -> wgpu::Buffer{
+        let mut buffers = self.buffers.lock().unwrap();
+
+        let fitting = buffers.iter().enumerate()
+            .filter(|(_, buffer)| buffer.size() >= size)
+            .min_by_key(|(_, buffer)| buffer.size())
+            .map(|(i, _)| i);
+
+        match fitting{
+            Some(i) => buffers.swap_remove(i),
+            None => device.create_buffer(&wgpu::BufferDescriptor{
+                label: Some("Staging"),
+                size: size.max(size_of::<f32>() as u64),
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+        }
+    }
+    /// Return unmapped buffer to pool
+    pub fn release(&self, buffer: wgpu::Buffer){
+        let mut buffers = self.buffers.lock().unwrap();
+
+        buffers.push(buffer);
+        if buffers.len() > MAX_POOLED_STAGING{
+            let smallest = buffers.iter().enumerate().min_by_key(|(_, buffer)| buffer.size()).map(|(i, _)| i).unwrap();
+            buffers.swap_remove(smallest);
+        }
+    }
+    /// Returns count of pooled buffers
+    pub fn len(&self) -> usize{
+        self.buffers.lock().unwrap().len()
+    }
+    /// Returns true if pool has no buffers
+    pub fn is_empty(&self) -> bool{
+        self.len() == 0
+    }
+}
+
+/// Result of map_async callback and waker of task waiting for it
+#[derive(Default)]
+struct MapState{
+    result: Option<Result<(), wgpu::BufferAsyncError>>,
+    /// Error of device poll on helper thread, callback is never called after it
+    poll_error: Option<String>,
+    waker: Option<Waker>,
+}
+
+impl MapState{
+    fn is_pending(&self) -> bool{
+        self.result.is_none() && self.poll_error.is_none()
+    }
+}
+
+type SharedState = Arc<Mutex<MapState>>;
+
+/// Map states waited on by poll thread of every device
+/// Only one thread waits on a device, as waiting for it locks device on some backends
+static WATCHED: LazyLock<Mutex<HashMap<wgpu::Device, Vec<SharedState>>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Wait on device with helper thread, until mapping of state completes
+/// Thread is started if device has no thread yet, and stops when no mapping is pending
+fn watch(device: &wgpu::Device, state: SharedState){
+    let mut watched = WATCHED.lock().unwrap();
+
+    if let Some(states) = watched.get_mut(device){
+        states.push(state);
+        return;
+    }
+    watched.insert(device.clone(), vec!{state});
+
+    let device = device.clone();
+    std::thread::spawn(move || {
+        loop{
+            // callbacks run inside device poll, so no state is locked while polling
+            let poll_result = device.poll(wgpu::PollType::Wait);
+
+            let mut watched = WATCHED.lock().unwrap();
+            let states = watched.get_mut(&device).unwrap();
+
+            let mut wakers: Vec<Waker> = Vec::new();
+            states.retain(|state| {
+                let mut state = state.lock().unwrap();
+                if let Err(err) = &poll_result && state.result.is_none(){
+                    state.poll_error = Some(err.to_string());
+                }
+                if state.is_pending(){
+                    return true;
+                }
+                wakers.extend(state.waker.take());
+                false
+            });
+
+            let finished = states.is_empty();
+            if finished{
+                watched.remove(&device);
+            }
+            drop(watched);
+
+            // tasks are woken after thread stopped using device, so it is not used after they end
+            if finished{
+                drop(device);
+                wakers.into_iter().for_each(Waker::wake);
+                return;
+            }
+            wakers.into_iter().for_each(Waker::wake);
+        }
+    });
+}
+
+/// Future of data of staging buffer, that resolves when mapping of buffer completes
+/// Device is waited on by helper thread, so executor can run other tasks, while gpu finishes work
+/// submitted before readback, and task is woken only after mapping completes. Staging buffer is
+/// returned to pool of context after read
+///
+/// # Example
+/// ```
+/// use flashlight_tensor::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main(){
+///     if std::env::var("CI").is_ok() {
+///         eprintln!("Skipping GPU test in CI");
+///         return;
+///     }
+///     let context = GpuContext::init(1, &MemoryMetric::GB).await.unwrap();
+///
+///     let first = GpuTensor::from_tensor(&Tensor::fill(1.0, &[4]), context.clone()).unwrap();
+///     let second = GpuTensor::from_tensor(&Tensor::fill(2.0, &[8]), context.clone()).unwrap();
+///
+///     // both copies are submitted, before waiting for any of them
+///     let first_read = Readback::copy(context.clone(), first.get_buffer(), 16).unwrap();
+///     let second_read = Readback::copy(context.clone(), second.get_buffer(), 32).unwrap();
+///
+///     assert_eq!(second_read.await.unwrap(), vec!{2.0; 8});
+///     assert_eq!(first_read.await.unwrap(), vec!{1.0; 4});
+/// }
+/// ```
+pub struct Readback{
+    device: wgpu::Device,
+    staging: Option<wgpu::Buffer>,
+    size: u64,
+    state: Arc<Mutex<MapState>>,
+    context: Option<Arc<GpuContext>>,
+    polling: bool,
+}
+
+impl Readback{
+    /// Start mapping staging buffer, after encoder copying size bytes into it was submitted
+    /// Staging buffer is dropped after read
+    pub fn new(device: &wgpu::Device, staging: wgpu::Buffer, size: u64) -> Self{
+        Self::map(device.clone(), staging, size, None)
+    }
+    /// Start mapping staging buffer acquired from pool of context, after encoder copying size
+    /// bytes into it was submitted
+    pub fn pooled(context: Arc<GpuContext>, staging: wgpu::Buffer, size: u64) -> Self{
+        Self::map(context.device.clone(), staging, size, Some(context))
+    }
+    /// Copy size bytes of buffer with COPY_SRC usage into pooled staging buffer and start
+    /// mapping it
+    /// Error if wgpu reports an error
+    pub fn copy(context: Arc<GpuContext>, buffer: &wgpu::Buffer, size: u64) -> Result<Self, GpuError>{
+        let device = &context.device;
+
+        let staging = capture_errors(device, || {
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Encoder"),
+            });
+
+            let staging = context.staging.acquire(device, size);
+            encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, size);
+            context.queue.submit(Some(encoder.finish()));
+
+            staging
+        })?;
+
+        Ok(Self::pooled(context, staging, size))
+    }
+    fn map(device: wgpu::Device, staging: wgpu::Buffer, size: u64, context: Option<Arc<GpuContext>>) -> Self{
+        let state: Arc<Mutex<MapState>> = Arc::new(Mutex::new(MapState::default()));
+
+        let callback_state = state.clone();
+        staging.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+            // task is woken by poll thread, after device poll returns
+            callback_state.lock().unwrap().result = Some(result);
+        });
+
+        Self{
+            device,
+            staging: Some(staging),
+            size,
+            state,
+            context,
+            polling: false,
+        }
+    }
+
+    /// Returns true if mapping completed, so awaiting readback does not wait for gpu
+    pub fn is_ready(&self) -> bool{
+        let _ = self.device.poll(wgpu::PollType::Poll);
+
+        self.state.lock().unwrap().result.is_some()
+    }
+    /// Read mapped data and return staging buffer to pool
+    fn read(&mut self) -> Vec<f32>{
+        let staging = self.staging.take().unwrap();
+
+        let data: Vec<f32> = bytemuck::cast_slice(&staging.slice(..self.size).get_mapped_range()).to_vec();
+        staging.unmap();
+
+        if let Some(context) = &self.context{
+            context.staging.release(staging);
+        }
+
+        data
+    }
+}
+
+impl Future for Readback{
+    type Output = Result<Vec<f32>, GpuError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output>{
+        if self.staging.is_none(){
+            return Poll::Ready(Err(GpuError::Readback("readback was already read".to_string())));
+        }
+
+        let (result, poll_error) = {
+            let mut state = self.state.lock().unwrap();
+            if state.result.is_none(){
+                state.waker = Some(cx.waker().clone());
+            }
+            // result is kept in state, so poll thread sees that mapping completed
+            (state.result.clone(), state.poll_error.clone())
+        };
+
+        match (result, poll_error){
+            (Some(Ok(())), _) => Poll::Ready(Ok(self.read())),
+            (Some(Err(err)), _) => Poll::Ready(Err(GpuError::Readback(err.to_string()))),
+            (None, Some(err)) => Poll::Ready(Err(GpuError::Readback(err))),
+            (None, None) => {
+                // waker is stored before watching, so callback can not complete unnoticed
+                if !self.polling{
+                    self.polling = true;
+                    watch(&self.device, self.state.clone());
+                }
+                Poll::Pending
+            },
+        }
+    }
+}
@@ -4,6 +4,8 @@ use crate::tensor::*;
 pub struct Sample{
     pub inputs: Vec<f32>,
     pub shapes: Vec<u32>,
+    /// Rank of every input, to split shapes into shape of every input
+    pub ranks: Vec<u32>,
     pub params: Vec<f32>,
     pub output_len: u32,
     pub output_shape: Vec<u32>,
@@ -26,10 +28,12 @@ impl Sample{
     pub fn from_data(input_tensors: Vec<Tensor<f32>>, params: Vec<f32>, output_shape: &[u32]) -> Self{
         let mut inputs: Vec<f32> = Vec::new();
         let mut shapes: Vec<u32> = Vec::new();
+        let mut ranks: Vec<u32> = Vec::new();
 
         for i in 0..input_tensors.len(){
             inputs.extend_from_slice(input_tensors[i].get_data());
             shapes.extend_from_slice(input_tensors[i].get_shape());
+            ranks.push(input_tensors[i].get_shape().len() as u32);
         }
 
         let output_len: u32 = output_shape.iter().product();
@@ -38,6 +42,7 @@ impl Sample{
         Self{
             inputs,
             shapes,
+            ranks,
             params,
             output_len,
             output_shape: output_shape.to_vec(),
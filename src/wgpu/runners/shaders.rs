@@ -1,5 +1,59 @@
+/// Source of shader, with shared prelude of indexing helpers and binding of shared inputs in
+/// front
+macro_rules! with_prelude{
+    ($path:literal) => {
+        concat!(include_str!("../shaders/f32/prelude.wgsl"), include_str!("../shaders/f32/shared.wgsl"), include_str!($path))
+    };
+}
+
+/// Source of shader, with shared prelude, binding of shared inputs and tiled matrix
+/// multiplication in front
+macro_rules! with_gemm{
+    ($path:literal) => {
+        concat!(include_str!("../shaders/f32/prelude.wgsl"), include_str!("../shaders/f32/shared.wgsl"), include_str!("../shaders/f32/gemm.wgsl"), include_str!($path))
+    };
+}
+
+/// Source of half precision shader, with f16 extension enabled, shared prelude, binding of
+/// shared inputs in f16 and tiled matrix multiplication, that accumulates in f32, in front
+#[cfg(feature = "half")]
+macro_rules! with_gemm_f16{
+    ($path:literal) => {
+        concat!("enable f16;\n", include_str!("../shaders/f32/prelude.wgsl"), include_str!("../shaders/f16/shared.wgsl"), include_str!("../shaders/f32/gemm.wgsl"), include_str!($path))
+    };
+}
+
+/// Source of double precision shader, with shared prelude and binding of shared inputs in f64
+/// in front
+macro_rules! with_prelude_f64{
+    ($path:literal) => {
+        concat!(include_str!("../shaders/f32/prelude.wgsl"), include_str!("../shaders/f64/shared.wgsl"), include_str!($path))
+    };
+}
+
+/// Source of double precision shader, with shared prelude, binding of shared inputs in f64 and
+/// tiled matrix multiplication, that accumulates in f64, in front
+macro_rules! with_gemm_f64{
+    ($path:literal) => {
+        concat!(include_str!("../shaders/f32/prelude.wgsl"), include_str!("../shaders/f64/shared.wgsl"), include_str!("../shaders/f64/gemm.wgsl"), include_str!($path))
+    };
+}
+
+/// Source of shader, with shared prelude, accumulator and workgroup parallel reduction in front
+macro_rules! with_reduce{
+    ($accumulator:literal, $path:literal) => {
+        concat!(include_str!("../shaders/f32/prelude.wgsl"), include_str!($accumulator), include_str!("../shaders/f32/reduce.wgsl"), include_str!($path))
+    };
+}
+
+use std::sync::Arc;
+
+use crate::cpu::broadcasting::helpers::get_broadcast_shape;
+
+use super::{custom_op::CustomOperation, precision::GpuPrecision};
+
 /// Gpu tensor operations supported by this library
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum GpuOperations {
     None,
     Add,
@@ -34,45 +88,233 @@ pub enum GpuOperations {
     BackwardGradient,
     BackwardRelu,
     BackwardSigmoid,
+    NormForward,
+    BatchNormEval,
+    NormBackward,
+    NormBackwardParams,
+    RnnCell,
+    GruCell,
+    LstmCell,
+    ReduceSum,
+    ReduceMean,
+    ReduceMax,
+    ReduceMin,
+    ReduceArgmax,
+    ReduceVariance,
+    /// Operation with shader supplied by caller
+    Custom(Arc<CustomOperation>),
 }
 
 impl GpuOperations{
+    /// Returns every built-in operation with its own shader, used to pre-compile pipelines
+    pub fn all() -> Vec<GpuOperations>{
+        vec!{
+            GpuOperations::Add,
+            GpuOperations::TensAdd,
+            GpuOperations::Sub,
+            GpuOperations::TensSub,
+            GpuOperations::Mul,
+            GpuOperations::TensMul,
+            GpuOperations::Div,
+            GpuOperations::TensDiv,
+            GpuOperations::NLog,
+            GpuOperations::Log,
+            GpuOperations::Matmul,
+            GpuOperations::ReLU,
+            GpuOperations::ReLUDer,
+            GpuOperations::Sigmoid,
+            GpuOperations::SigmoidDer,
+            GpuOperations::BroadcastAdd,
+            GpuOperations::BroadcastSub,
+            GpuOperations::BroadcastMul,
+            GpuOperations::BroadcastDiv,
+            GpuOperations::MatrixTranspose,
+            GpuOperations::MatrixRowSum,
+            GpuOperations::MatrixRowProd,
+            GpuOperations::MatrixColSum,
+            GpuOperations::MatrixColProd,
+            GpuOperations::ForwardNoActiv,
+            GpuOperations::ForwardSigmoid,
+            GpuOperations::ForwardRelu,
+            GpuOperations::BackwardWeight,
+            GpuOperations::BackwardBias,
+            GpuOperations::BackwardGradient,
+            GpuOperations::BackwardRelu,
+            GpuOperations::BackwardSigmoid,
+            GpuOperations::NormForward,
+            GpuOperations::BatchNormEval,
+            GpuOperations::NormBackward,
+            GpuOperations::NormBackwardParams,
+            GpuOperations::RnnCell,
+            GpuOperations::GruCell,
+            GpuOperations::LstmCell,
+            GpuOperations::ReduceSum,
+            GpuOperations::ReduceMean,
+            GpuOperations::ReduceMax,
+            GpuOperations::ReduceMin,
+            GpuOperations::ReduceArgmax,
+            GpuOperations::ReduceVariance,
+        }
+    }
     /// Return correct path to shader
-    fn shader_src(&self) -> &'static str{
+    pub fn shader_src(&self) -> &str{
         match self{
             GpuOperations::None => include_str!("../shaders/f32/math/addition/add.wgsl"),
             GpuOperations::Add => include_str!("../shaders/f32/math/addition/add.wgsl"),
-            GpuOperations::TensAdd => include_str!("../shaders/f32/math/addition/tens_add.wgsl"),
+            GpuOperations::TensAdd => with_prelude!("../shaders/f32/math/addition/tens_add.wgsl"),
             GpuOperations::Sub => include_str!("../shaders/f32/math/subtraction/sub.wgsl"),
-            GpuOperations::TensSub => include_str!("../shaders/f32/math/subtraction/tens_sub.wgsl"),
+            GpuOperations::TensSub => with_prelude!("../shaders/f32/math/subtraction/tens_sub.wgsl"),
             GpuOperations::Mul => include_str!("../shaders/f32/math/multiplication/mul.wgsl"),
-            GpuOperations::TensMul => include_str!("../shaders/f32/math/multiplication/tens_mul.wgsl"),
+            GpuOperations::TensMul => with_prelude!("../shaders/f32/math/multiplication/tens_mul.wgsl"),
             GpuOperations::Div => include_str!("../shaders/f32/math/divistion/div.wgsl"),
-            GpuOperations::TensDiv => include_str!("../shaders/f32/math/divistion/tens_div.wgsl"),
-            GpuOperations::BroadcastAdd => include_str!("../shaders/f32/broadcasting/broadcast_add.wgsl"),
-            GpuOperations::BroadcastSub => include_str!("../shaders/f32/broadcasting/broadcast_sub.wgsl"),
-            GpuOperations::BroadcastMul => include_str!("../shaders/f32/broadcasting/broadcast_mul.wgsl"),
-            GpuOperations::BroadcastDiv => include_str!("../shaders/f32/broadcasting/broadcast_div.wgsl"),
+            GpuOperations::TensDiv => with_prelude!("../shaders/f32/math/divistion/tens_div.wgsl"),
+            GpuOperations::BroadcastAdd => with_prelude!("../shaders/f32/broadcasting/broadcast_add.wgsl"),
+            GpuOperations::BroadcastSub => with_prelude!("../shaders/f32/broadcasting/broadcast_sub.wgsl"),
+            GpuOperations::BroadcastMul => with_prelude!("../shaders/f32/broadcasting/broadcast_mul.wgsl"),
+            GpuOperations::BroadcastDiv => with_prelude!("../shaders/f32/broadcasting/broadcast_div.wgsl"),
             GpuOperations::NLog => include_str!("../shaders/f32/math/functions/nlog.wgsl"),
             GpuOperations::Log => include_str!("../shaders/f32/math/functions/log.wgsl"),
             GpuOperations::ReLU => include_str!("../shaders/f32/machine_learning/relu.wgsl"),
             GpuOperations::ReLUDer => include_str!("../shaders/f32/machine_learning/relu_der.wgsl"),
             GpuOperations::Sigmoid => include_str!("../shaders/f32/machine_learning/sigmoid.wgsl"),
             GpuOperations::SigmoidDer => include_str!("../shaders/f32/machine_learning/sigmoid_der.wgsl"),
-            GpuOperations::Matmul => include_str!("../shaders/f32/math/matrix/matmul.wgsl"),
-            GpuOperations::MatrixRowSum => include_str!("../shaders/f32/subtypes/matrix_row_sum.wgsl"),
-            GpuOperations::MatrixRowProd => include_str!("../shaders/f32/subtypes/matrix_row_prod.wgsl"),
-            GpuOperations::MatrixColSum => include_str!("../shaders/f32/subtypes/matrix_col_sum.wgsl"),
-            GpuOperations::MatrixColProd => include_str!("../shaders/f32/subtypes/matrix_col_prod.wgsl"),
+            GpuOperations::Matmul => with_gemm!("../shaders/f32/math/matrix/matmul.wgsl"),
+            GpuOperations::MatrixRowSum => with_reduce!("../shaders/f32/reduction/sum.wgsl", "../shaders/f32/reduction/tensor.wgsl"),
+            GpuOperations::MatrixRowProd => with_reduce!("../shaders/f32/reduction/prod.wgsl", "../shaders/f32/reduction/tensor.wgsl"),
+            GpuOperations::MatrixColSum => with_reduce!("../shaders/f32/reduction/sum.wgsl", "../shaders/f32/reduction/tensor.wgsl"),
+            GpuOperations::MatrixColProd => with_reduce!("../shaders/f32/reduction/prod.wgsl", "../shaders/f32/reduction/tensor.wgsl"),
             GpuOperations::MatrixTranspose => include_str!("../shaders/f32/subtypes/matrix_transpose.wgsl"),
-            GpuOperations::ForwardNoActiv => include_str!("../shaders/f32/machine_learning/forward_no_activ.wgsl"),
-            GpuOperations::ForwardRelu => include_str!("../shaders/f32/machine_learning/forward_relu.wgsl"),
-            GpuOperations::ForwardSigmoid => include_str!("../shaders/f32/machine_learning/forward_sigmoid.wgsl"),
-            GpuOperations::BackwardWeight => include_str!("../shaders/f32/machine_learning/backward_weight_grad.wgsl"),
-            GpuOperations::BackwardBias => include_str!("../shaders/f32/machine_learning/backward_bias_grad.wgsl"),
-            GpuOperations::BackwardGradient => include_str!("../shaders/f32/machine_learning/backward_input_grad.wgsl"),
-            GpuOperations::BackwardRelu => include_str!("../shaders/f32/machine_learning/backward_relu.wgsl"),
-            GpuOperations::BackwardSigmoid => include_str!("../shaders/f32/machine_learning/backward_sigmoid.wgsl"),
+            GpuOperations::ForwardNoActiv => with_gemm!("../shaders/f32/machine_learning/forward_no_activ.wgsl"),
+            GpuOperations::ForwardRelu => with_gemm!("../shaders/f32/machine_learning/forward_relu.wgsl"),
+            GpuOperations::ForwardSigmoid => with_gemm!("../shaders/f32/machine_learning/forward_sigmoid.wgsl"),
+            GpuOperations::BackwardWeight => with_gemm!("../shaders/f32/machine_learning/backward_weight_grad.wgsl"),
+            GpuOperations::BackwardBias => with_reduce!("../shaders/f32/reduction/sum.wgsl", "../shaders/f32/machine_learning/backward_bias_grad.wgsl"),
+            GpuOperations::BackwardGradient => with_gemm!("../shaders/f32/machine_learning/backward_input_grad.wgsl"),
+            GpuOperations::BackwardRelu => with_prelude!("../shaders/f32/machine_learning/backward_relu.wgsl"),
+            GpuOperations::BackwardSigmoid => with_prelude!("../shaders/f32/machine_learning/backward_sigmoid.wgsl"),
+            GpuOperations::NormForward => include_str!("../shaders/f32/machine_learning/norm_forward.wgsl"),
+            GpuOperations::BatchNormEval => include_str!("../shaders/f32/machine_learning/batch_norm_eval.wgsl"),
+            GpuOperations::NormBackward => include_str!("../shaders/f32/machine_learning/norm_backward.wgsl"),
+            GpuOperations::NormBackwardParams => include_str!("../shaders/f32/machine_learning/norm_backward_params.wgsl"),
+            GpuOperations::RnnCell => include_str!("../shaders/f32/machine_learning/rnn_cell.wgsl"),
+            GpuOperations::GruCell => include_str!("../shaders/f32/machine_learning/gru_cell.wgsl"),
+            GpuOperations::LstmCell => include_str!("../shaders/f32/machine_learning/lstm_cell.wgsl"),
+            GpuOperations::ReduceSum => with_reduce!("../shaders/f32/reduction/sum.wgsl", "../shaders/f32/reduction/tensor.wgsl"),
+            GpuOperations::ReduceMean => with_reduce!("../shaders/f32/reduction/mean.wgsl", "../shaders/f32/reduction/tensor.wgsl"),
+            GpuOperations::ReduceMax => with_reduce!("../shaders/f32/reduction/max.wgsl", "../shaders/f32/reduction/tensor.wgsl"),
+            GpuOperations::ReduceMin => with_reduce!("../shaders/f32/reduction/min.wgsl", "../shaders/f32/reduction/tensor.wgsl"),
+            GpuOperations::ReduceArgmax => with_reduce!("../shaders/f32/reduction/argmax.wgsl", "../shaders/f32/reduction/tensor.wgsl"),
+            GpuOperations::ReduceVariance => with_reduce!("../shaders/f32/reduction/variance.wgsl", "../shaders/f32/reduction/tensor.wgsl"),
+            GpuOperations::Custom(operation) => operation.get_source(),
+        }
+    }
+    /// Returns source of shader, that reads and writes values of precision in buffers
+    /// None if operation has no shader for precision
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// assert!(GpuOperations::Matmul.precision_shader_src(GpuPrecision::F32).is_some());
+    /// assert!(GpuOperations::Matmul.precision_shader_src(GpuPrecision::F64).is_some());
+    /// assert!(GpuOperations::ForwardSigmoid.precision_shader_src(GpuPrecision::F64).is_none());
+    /// ```
+    pub fn precision_shader_src(&self, precision: GpuPrecision) -> Option<&str>{
+        match precision{
+            GpuPrecision::F32 => Some(self.shader_src()),
+            #[cfg(feature = "half")]
+            GpuPrecision::F16 => match self{
+                GpuOperations::Matmul => Some(with_gemm_f16!("../shaders/f16/math/matrix/matmul.wgsl")),
+                GpuOperations::ForwardNoActiv => Some(with_gemm_f16!("../shaders/f16/machine_learning/forward_no_activ.wgsl")),
+                GpuOperations::ForwardRelu => Some(with_gemm_f16!("../shaders/f16/machine_learning/forward_relu.wgsl")),
+                GpuOperations::ForwardSigmoid => Some(with_gemm_f16!("../shaders/f16/machine_learning/forward_sigmoid.wgsl")),
+                _ => None,
+            },
+            // exp of f64 is not supported by shader backends, so there is no f64 sigmoid
+            GpuPrecision::F64 => match self{
+                GpuOperations::TensAdd => Some(with_prelude_f64!("../shaders/f64/math/addition/tens_add.wgsl")),
+                GpuOperations::TensSub => Some(with_prelude_f64!("../shaders/f64/math/subtraction/tens_sub.wgsl")),
+                GpuOperations::TensMul => Some(with_prelude_f64!("../shaders/f64/math/multiplication/tens_mul.wgsl")),
+                GpuOperations::TensDiv => Some(with_prelude_f64!("../shaders/f64/math/divistion/tens_div.wgsl")),
+                GpuOperations::BroadcastAdd => Some(with_prelude_f64!("../shaders/f64/broadcasting/broadcast_add.wgsl")),
+                GpuOperations::BroadcastSub => Some(with_prelude_f64!("../shaders/f64/broadcasting/broadcast_sub.wgsl")),
+                GpuOperations::BroadcastMul => Some(with_prelude_f64!("../shaders/f64/broadcasting/broadcast_mul.wgsl")),
+                GpuOperations::BroadcastDiv => Some(with_prelude_f64!("../shaders/f64/broadcasting/broadcast_div.wgsl")),
+                GpuOperations::Matmul => Some(with_gemm_f64!("../shaders/f64/math/matrix/matmul.wgsl")),
+                GpuOperations::ForwardNoActiv => Some(with_gemm_f64!("../shaders/f64/machine_learning/forward_no_activ.wgsl")),
+                GpuOperations::ForwardRelu => Some(with_gemm_f64!("../shaders/f64/machine_learning/forward_relu.wgsl")),
+                _ => None,
+            },
+        }
+    }
+    /// Returns true if operation has shader for values of precision in buffers
+    pub fn supports_precision(&self, precision: GpuPrecision) -> bool{
+        self.precision_shader_src(precision).is_some()
+    }
+    /// Returns true if operation can run on ragged batch, with other shapes in every sample
+    /// Shaders of these operations read shapes and offsets of every sample from metadata
+    pub fn supports_ragged(&self) -> bool{
+        matches!(self,
+            GpuOperations::Add | GpuOperations::Sub | GpuOperations::Mul | GpuOperations::Div |
+            GpuOperations::Log | GpuOperations::NLog |
+            GpuOperations::ReLU | GpuOperations::ReLUDer | GpuOperations::Sigmoid | GpuOperations::SigmoidDer |
+            GpuOperations::TensAdd | GpuOperations::TensSub | GpuOperations::TensMul | GpuOperations::TensDiv |
+            GpuOperations::BroadcastAdd | GpuOperations::BroadcastSub | GpuOperations::BroadcastMul | GpuOperations::BroadcastDiv |
+            GpuOperations::BackwardRelu | GpuOperations::BackwardSigmoid |
+            GpuOperations::Matmul | GpuOperations::ForwardNoActiv | GpuOperations::ForwardRelu | GpuOperations::ForwardSigmoid |
+            GpuOperations::BackwardGradient
+        )
+    }
+    /// Returns true if operation can read inputs shared by every sample
+    /// Shaders of these operations read inputs with load_input, from binding 5 if input is shared
+    pub fn supports_shared(&self) -> bool{
+        matches!(self,
+            GpuOperations::TensAdd | GpuOperations::TensSub | GpuOperations::TensMul | GpuOperations::TensDiv |
+            GpuOperations::BroadcastAdd | GpuOperations::BroadcastSub | GpuOperations::BroadcastMul | GpuOperations::BroadcastDiv |
+            GpuOperations::BackwardRelu | GpuOperations::BackwardSigmoid |
+            GpuOperations::Matmul | GpuOperations::ForwardNoActiv | GpuOperations::ForwardRelu | GpuOperations::ForwardSigmoid |
+            GpuOperations::BackwardGradient | GpuOperations::BackwardWeight
+        )
+    }
+    /// Returns output shape of one sample of ragged batch from shapes of its inputs
+    /// None if operation does not support ragged batches, or inputs do not fit operation
+    ///
+    /// # Example
+    /// ```
+    /// use flashlight_tensor::prelude::*;
+    ///
+    /// assert_eq!(GpuOperations::Matmul.ragged_output_shape(&[vec!{5, 3}, vec!{3, 2}]), Some(vec!{5, 2}));
+    /// assert_eq!(GpuOperations::Matmul.ragged_output_shape(&[vec!{5, 3}, vec!{4, 2}]), None);
+    /// assert_eq!(GpuOperations::MatrixTranspose.ragged_output_shape(&[vec!{5, 3}]), None);
+    /// ```
+    pub fn ragged_output_shape(&self, input_shapes: &[Vec<u32>]) -> Option<Vec<u32>>{
+        if !self.supports_ragged() || input_shapes.is_empty(){
+            return None;
+        }
+
+        let matrix = |i: usize| -> Option<(u32, u32)>{
+            match input_shapes.get(i)?.as_slice(){
+                [rows, cols] => Some((*rows, *cols)),
+                _ => None,
+            }
+        };
+
+        match self{
+            GpuOperations::Matmul | GpuOperations::ForwardNoActiv | GpuOperations::ForwardRelu | GpuOperations::ForwardSigmoid => {
+                let (rows, depth) = matrix(0)?;
+                let (inner, cols) = matrix(1)?;
+                (depth == inner).then(|| vec!{rows, cols})
+            },
+            GpuOperations::BackwardGradient => {
+                let (rows, cols) = matrix(0)?;
+                let (grad_rows, grad_cols) = matrix(1)?;
+                (rows == grad_rows).then(|| vec!{cols, grad_cols})
+            },
+            GpuOperations::BroadcastAdd | GpuOperations::BroadcastSub | GpuOperations::BroadcastMul | GpuOperations::BroadcastDiv |
+            GpuOperations::BackwardRelu | GpuOperations::BackwardSigmoid => {
+                get_broadcast_shape(input_shapes.first()?, input_shapes.get(1)?)
+            },
+            _ => Some(input_shapes[0].clone()),
         }
     }
 }
@@ -81,9 +323,17 @@ impl GpuOperations{
 ///
 /// Most of the time, you wont need to use it
 pub fn get_shader(device: &wgpu::Device, operation: &GpuOperations) -> wgpu::ShaderModule{
+    get_shader_from_source(device, operation.shader_src())
+}
+
+/// Returns a shader module of WGSL source, used for shaders generated at runtime
+pub fn get_shader_from_source(device: &wgpu::Device, source: &str) -> wgpu::ShaderModule{
     device.create_shader_module(wgpu::ShaderModuleDescriptor{
         label: Some("WGSL Shader"),
-        source: wgpu::ShaderSource::Wgsl(operation.shader_src().into()),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
     })
 }
 
+/// Shared prelude of indexing helpers, that runtime generated shaders are wrapped with
+pub const PRELUDE_SRC: &str = include_str!("../shaders/f32/prelude.wgsl");
+
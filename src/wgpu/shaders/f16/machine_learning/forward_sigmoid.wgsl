@@ -0,0 +1,43 @@
+@group(0) @binding(0)
+var<storage, read> input: array<f16>; //weights, inputs, biases
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f16>;
+
+fn sigmoid(x: f32) -> f32{
+	return 1.0 / (1.0 + exp(-x));
+}
+
+fn gemm_rows(sample: u32) -> u32 {
+	return sample_matrix_rows(sample, 0u);
+}
+
+fn gemm_cols(sample: u32) -> u32 {
+	return sample_matrix_cols(sample, 1u);
+}
+
+fn gemm_depth(sample: u32) -> u32 {
+	return sample_matrix_cols(sample, 0u);
+}
+
+// Output is padded to 4 bytes, so samples with output of one value can count one more
+// sample, that stores into padding
+fn gemm_samples() -> u32 {
+	return meta_samples(arrayLength(&output));
+}
+
+fn load_a(sample: u32, row: u32, k: u32) -> f32 {
+	return load_input(0u, matrix_index(0u, sample, row, k));
+}
+
+fn load_b(sample: u32, k: u32, col: u32) -> f32 {
+	return load_input(1u, matrix_index(1u, sample, k, col));
+}
+
+fn store(sample: u32, row: u32, col: u32, dot_sum: f32) {
+	let inner_idx = row * gemm_cols(sample) + col;
+
+	let bias = load_input(2u, broadcast_index(2u, sample, inner_idx));
+
+	output[sample_output_offset(sample) + inner_idx] = f16(sigmoid(dot_sum + bias));
+}
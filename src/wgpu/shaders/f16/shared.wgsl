@@ -0,0 +1,13 @@
+// Inputs shared by every sample in half precision, uploaded once and bound apart from inputs of
+// samples
+// Shaders read inputs with load_input, that converts them to f32
+
+@group(0) @binding(5)
+var<storage, read> shared_inputs: array<f16>;
+
+fn load_input(tensor: u32, idx: u32) -> f32 {
+	if (meta_shared(tensor)) {
+		return f32(shared_inputs[idx]);
+	}
+	return f32(input[idx]);
+}
@@ -0,0 +1,114 @@
+// Workgroup tiled matrix multiplication, shared by matmul, forward and backward shaders
+//
+// Every workgroup computes TILE_SIZE x TILE_SIZE tiles of output. For every step of depth, tiles
+// of A and B are loaded into workgroup memory once and read by all invocations of workgroup.
+// Shader using it defines size of product and how elements are loaded and stored:
+// gemm_rows, gemm_cols, gemm_depth of every sample, gemm_samples, load_a, load_b and store
+// Samples of ragged batch have their own sizes, so their tiles are numbered one after another
+
+// Set by pipeline from tile size of GpuConfig, must be 8, 16 or 32
+override TILE_SIZE: u32 = 16u;
+
+const MAX_TILE_LEN: u32 = 1024u;
+const MAX_OUTPUTS_PER_INVOCATION: u32 = 16u;
+
+var<workgroup> tile_a: array<f32, MAX_TILE_LEN>;
+var<workgroup> tile_b: array<f32, MAX_TILE_LEN>;
+
+// Compute tile of output of sample at tile_row and tile_col
+fn gemm_tile(sample: u32, tile_row: u32, tile_col: u32, local_index: u32) {
+	let rows = gemm_rows(sample);
+	let cols = gemm_cols(sample);
+	let depth = gemm_depth(sample);
+
+	let outputs_per_invocation = TILE_SIZE * TILE_SIZE / 64u;
+
+	var sums: array<f32, MAX_OUTPUTS_PER_INVOCATION>;
+	for (var i = 0u; i < outputs_per_invocation; i++) {
+		sums[i] = 0.0;
+	}
+
+	for (var step = 0u; step < depth; step += TILE_SIZE) {
+		for (var i = 0u; i < outputs_per_invocation; i++) {
+			let local = local_index + i * 64u;
+			let row = local / TILE_SIZE;
+			let col = local % TILE_SIZE;
+
+			var a = 0.0;
+			if (tile_row + row < rows && step + col < depth) {
+				a = load_a(sample, tile_row + row, step + col);
+			}
+			var b = 0.0;
+			if (step + row < depth && tile_col + col < cols) {
+				b = load_b(sample, step + row, tile_col + col);
+			}
+			tile_a[local] = a;
+			tile_b[local] = b;
+		}
+		workgroupBarrier();
+
+		for (var i = 0u; i < outputs_per_invocation; i++) {
+			let local = local_index + i * 64u;
+			let row = local / TILE_SIZE;
+			let col = local % TILE_SIZE;
+
+			var sum = sums[i];
+			for (var k = 0u; k < TILE_SIZE; k++) {
+				sum += tile_a[row * TILE_SIZE + k] * tile_b[k * TILE_SIZE + col];
+			}
+			sums[i] = sum;
+		}
+		workgroupBarrier();
+	}
+
+	for (var i = 0u; i < outputs_per_invocation; i++) {
+		let local = local_index + i * 64u;
+		let row = tile_row + local / TILE_SIZE;
+		let col = tile_col + local % TILE_SIZE;
+
+		if (row < rows && col < cols) {
+			store(sample, row, col, sums[i]);
+		}
+	}
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(workgroup_id) workgroup_id: vec3<u32>, @builtin(num_workgroups) num_workgroups: vec3<u32>, @builtin(local_invocation_index) local_index: u32){
+	// dispatch is sized by output length, so workgroups loop, when there are more tiles
+	let workgroup = workgroup_id.y * num_workgroups.x + workgroup_id.x;
+	let workgroups = num_workgroups.x * num_workgroups.y;
+
+	if (meta_ragged()) {
+		var first_tile = 0u;
+		for (var sample = 0u; sample < gemm_samples(); sample++) {
+			let tiles_cols = (gemm_cols(sample) + TILE_SIZE - 1u) / TILE_SIZE;
+			let tiles = (gemm_rows(sample) + TILE_SIZE - 1u) / TILE_SIZE * tiles_cols;
+
+			// the first tile of workgroup in sample
+			var tile = workgroup;
+			if (tile < first_tile) {
+				tile += (first_tile - tile + workgroups - 1u) / workgroups * workgroups;
+			}
+			for (; tile < first_tile + tiles; tile += workgroups) {
+				let sample_tile = tile - first_tile;
+				gemm_tile(sample, sample_tile / tiles_cols * TILE_SIZE, sample_tile % tiles_cols * TILE_SIZE, local_index);
+			}
+
+			first_tile += tiles;
+		}
+		return;
+	}
+
+	let tiles_rows = (gemm_rows(0u) + TILE_SIZE - 1u) / TILE_SIZE;
+	let tiles_cols = (gemm_cols(0u) + TILE_SIZE - 1u) / TILE_SIZE;
+	let tiles_per_sample = tiles_rows * tiles_cols;
+	let tiles = gemm_samples() * tiles_per_sample;
+
+	for (var tile = workgroup; tile < tiles; tile += workgroups) {
+		let sample = tile / tiles_per_sample;
+		let tile_row = (tile % tiles_per_sample) / tiles_cols * TILE_SIZE;
+		let tile_col = (tile % tiles_per_sample) % tiles_cols * TILE_SIZE;
+
+		gemm_tile(sample, tile_row, tile_col, local_index);
+	}
+}
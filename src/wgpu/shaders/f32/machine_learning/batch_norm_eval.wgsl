@@ -0,0 +1,43 @@
+@group(0) @binding(0)
+var<storage, read> input: array<f32>; //input, running_mean, running_var, gamma, beta
+
+@group(0) @binding(1)
+var<storage, read> shapes: array<u32>;
+
+struct Params {
+	eps: f32,
+}
+@group(0) @binding(2)
+var<uniform> params: Params;
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>){
+
+	let idx = global_id.y * 65535u + global_id.x;
+	if (idx >= arrayLength(&output)) {
+		return;
+	}
+
+	let rows = shapes[0];
+	let cols = shapes[1];
+
+	let mean_offset = rows * cols;
+	let var_offset = mean_offset + rows;
+	let gamma_offset = var_offset + rows;
+	let beta_offset = gamma_offset + rows;
+
+	let sample_size = beta_offset + rows;
+
+	let sample_idx = idx / mean_offset;
+	let inner_idx = idx % mean_offset;
+
+	let row = inner_idx / cols;
+	let sample_start = sample_idx * sample_size;
+
+	let normalized = (input[sample_start + inner_idx] - input[sample_start + mean_offset + row]) / sqrt(input[sample_start + var_offset + row] + params.eps);
+
+	output[idx] = normalized * input[sample_start + gamma_offset + row] + input[sample_start + beta_offset + row];
+}
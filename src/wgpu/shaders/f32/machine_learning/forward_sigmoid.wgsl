@@ -1,8 +1,5 @@
 @group(0) @binding(0)
-var<storage, read> input: array<f32>;
-
-@group(0) @binding(1)
-var<storage, read> shapes: array<u32>;
+var<storage, read> input: array<f32>; //weights, inputs, biases
 
 @group(0) @binding(3)
 var<storage, read_write> output: array<f32>;
@@ -11,86 +8,34 @@ fn sigmoid(x: f32) -> f32{
 	return 1.0 / (1.0 + exp(-x));
 }
 
-fn idx_to_global(idx: u32, shape: array<u32, 6>, rank: u32) -> array<u32, 6> {
-    var used = idx;
-    var out: array<u32, 6>;
-    var shape_prod = 1u;
-
-    for (var i = 0u; i < rank; i++) {
-        shape_prod *= shape[i];
-    }
-
-    for (var i = 0u; i < rank; i++) {
-        shape_prod = shape_prod / shape[i];
-        out[i] = used / shape_prod;
-        used = used % shape_prod;
-    }
-
-    return out;
+fn gemm_rows(sample: u32) -> u32 {
+	return sample_matrix_rows(sample, 0u);
 }
 
-fn global_to_idx(pos: array<u32, 6>, shape: array<u32, 6>, rank: u32) -> u32 {
-    var idx = 0u;
-    var stride = 1u;
-    for (var i = rank; i > 0u; i--) {
-        idx += pos[i - 1u] * stride;
-        stride *= shape[i - 1u];
-    }
-    return idx;
+fn gemm_cols(sample: u32) -> u32 {
+	return sample_matrix_cols(sample, 1u);
 }
 
-@compute @workgroup_size(64)
-fn main(@builtin(global_invocation_id) global_id: vec3<u32>){
-
-	let idx = global_id.y * 65535u + global_id.x;
-	if (idx >= arrayLength(&output)) {
-		return;
-	}
-
-	let input_offset = shapes[0] * shapes[1];
-	let bias_offset = input_offset + shapes[2] * shapes[3];
-
-    let sample_size = bias_offset + shapes[4] * shapes[5];
-	let output_size = shapes[6] * shapes[7];
-
-    let sample_idx = idx / output_size;
-    let inner_idx = idx % output_size;
-
-	var weight_shape: array<u32, 6>;
-	var input_shape: array<u32, 6>;
-	var bias_shape: array<u32, 6>;
-
-	var output_shape: array<u32, 6>;
-
-	for(var i = 0u; i<2; i++){
-		weight_shape[i] = shapes[i];
-		input_shape[i] = shapes[2+i];
-		bias_shape[i] = shapes[4+i];
-		output_shape[i] = shapes[6+i];
-	}
-
-	let output_pos = idx_to_global(inner_idx, output_shape, 2);
+fn gemm_depth(sample: u32) -> u32 {
+	return sample_matrix_cols(sample, 0u);
+}
 
-	var dot_sum = 0.0;
-	for(var i=0u; i<weight_shape[1]; i++){
-		let weight_id = sample_idx * sample_size + output_pos[0] * weight_shape[1] + i;
-		let input_id = sample_idx * sample_size + input_offset + output_pos[1] + input_shape[1] * i;
+fn gemm_samples() -> u32 {
+	return meta_samples(arrayLength(&output));
+}
 
-		dot_sum += input[weight_id] * input[input_id];
-	}
+fn load_a(sample: u32, row: u32, k: u32) -> f32 {
+	return load_input(0u, matrix_index(0u, sample, row, k));
+}
 
-	var bias_pos: array<u32, 6>;
+fn load_b(sample: u32, k: u32, col: u32) -> f32 {
+	return load_input(1u, matrix_index(1u, sample, k, col));
+}
 
-	for (var i = 0u; i < 2; i++) {
-		if (bias_shape[i] == 1u){
-			bias_pos[i] = 0u;
-		}
-		else{
-			bias_pos[i] = output_pos[i];
-		}
-    }
+fn store(sample: u32, row: u32, col: u32, dot_sum: f32) {
+	let inner_idx = row * gemm_cols(sample) + col;
 
-	let bias_idx = sample_idx * sample_size + bias_offset + global_to_idx(bias_pos, bias_shape, 2);
+	let bias = load_input(2u, broadcast_index(2u, sample, inner_idx));
 
-    output[idx] = sigmoid(dot_sum + input[bias_idx]);
+	output[sample_output_offset(sample) + inner_idx] = sigmoid(dot_sum + bias);
 }
@@ -0,0 +1,81 @@
+@group(0) @binding(0)
+var<storage, read> input: array<f32>; //weight_ih, weight_hh, bias_ih, bias_hh, input, hidden
+
+@group(0) @binding(1)
+var<storage, read> shapes: array<u32>;
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f32>;
+
+fn sigmoid(x: f32) -> f32{
+	return 1.0 / (1.0 + exp(-x));
+}
+
+// Returns W_ih @ x + b_ih for one row and batch column
+fn gate_ih(sample_offset: u32, row: u32, col: u32) -> f32{
+	let input_size = shapes[1];
+	let hidden_size = shapes[3];
+	let gate_rows = shapes[0];
+	let batch = shapes[9];
+
+	let bias_ih_offset = sample_offset + gate_rows * (input_size + hidden_size);
+	let input_offset = bias_ih_offset + 2u * gate_rows;
+
+	var sum = input[bias_ih_offset + row];
+	for(var i = 0u; i < input_size; i++){
+		sum += input[sample_offset + row * input_size + i] * input[input_offset + i * batch + col];
+	}
+
+	return sum;
+}
+
+// Returns W_hh @ h + b_hh for one row and batch column
+fn gate_hh(sample_offset: u32, row: u32, col: u32) -> f32{
+	let input_size = shapes[1];
+	let hidden_size = shapes[3];
+	let gate_rows = shapes[0];
+	let batch = shapes[9];
+
+	let weight_hh_offset = sample_offset + gate_rows * input_size;
+	let bias_hh_offset = weight_hh_offset + gate_rows * hidden_size + gate_rows;
+	let hidden_offset = bias_hh_offset + gate_rows + input_size * batch;
+
+	var sum = input[bias_hh_offset + row];
+	for(var i = 0u; i < hidden_size; i++){
+		sum += input[weight_hh_offset + row * hidden_size + i] * input[hidden_offset + i * batch + col];
+	}
+
+	return sum;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>){
+
+	let idx = global_id.y * 65535u + global_id.x;
+	if (idx >= arrayLength(&output)) {
+		return;
+	}
+
+	let gate_rows = shapes[0];
+	let input_size = shapes[1];
+	let hidden_size = shapes[3];
+	let batch = shapes[9];
+
+	let sample_size = gate_rows * (input_size + hidden_size + 2u) + (input_size + hidden_size) * batch;
+	let output_size = shapes[12] * shapes[13];
+
+	let sample_idx = idx / output_size;
+	let inner_idx = idx % output_size;
+
+	let row = inner_idx / batch;
+	let col = inner_idx % batch;
+
+	let sample_offset = sample_idx * sample_size;
+	let hidden_offset = sample_offset + gate_rows * (input_size + hidden_size + 2u) + input_size * batch;
+
+	let reset = sigmoid(gate_ih(sample_offset, row, col) + gate_hh(sample_offset, row, col));
+	let update = sigmoid(gate_ih(sample_offset, hidden_size + row, col) + gate_hh(sample_offset, hidden_size + row, col));
+	let new_gate = tanh(gate_ih(sample_offset, 2u * hidden_size + row, col) + reset * gate_hh(sample_offset, 2u * hidden_size + row, col));
+
+	output[idx] = (1.0 - update) * new_gate + update * input[hidden_offset + row * batch + col];
+}
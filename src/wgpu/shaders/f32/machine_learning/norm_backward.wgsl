@@ -0,0 +1,110 @@
+@group(0) @binding(0)
+var<storage, read> input: array<f32>; //input, gamma, grad_output
+
+@group(0) @binding(1)
+var<storage, read> shapes: array<u32>;
+
+struct Params {
+	eps: f32,
+	groups: f32,
+	learning_rate: f32,
+	mode: f32, // 0 - batch norm, 1 - layer norm, 2 - group norm
+}
+@group(0) @binding(2)
+var<uniform> params: Params;
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f32>;
+
+var<private> rows: u32;
+var<private> cols: u32;
+var<private> param_size: u32;
+var<private> mode: u32;
+var<private> groups: u32;
+
+// Returns local index of k-th element of the group normalized together with element (row, col)
+fn group_element(k: u32, row: u32, col: u32) -> u32{
+	if (mode == 2u){
+		let channels_per_group = rows / groups;
+		let group_row = (row / channels_per_group) * channels_per_group;
+		return (group_row + k) * cols + col;
+	}
+	if (mode == 1u){
+		return ((row * cols + col) / param_size) * param_size + k;
+	}
+	return row * cols + k;
+}
+
+fn group_len() -> u32{
+	if (mode == 2u){
+		return rows / groups;
+	}
+	if (mode == 1u){
+		return param_size;
+	}
+	return cols;
+}
+
+fn param_index(local_idx: u32) -> u32{
+	if (mode == 1u){
+		return local_idx % param_size;
+	}
+	return local_idx / cols;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>){
+
+	let idx = global_id.y * 65535u + global_id.x;
+	if (idx >= arrayLength(&output)) {
+		return;
+	}
+
+	rows = shapes[0];
+	cols = shapes[1];
+	param_size = shapes[2] * shapes[3];
+	mode = u32(params.mode);
+	groups = max(u32(params.groups), 1u);
+
+	let gamma_offset = rows * cols;
+	let grad_offset = gamma_offset + param_size;
+
+	let sample_size = grad_offset + shapes[4] * shapes[5];
+
+	let sample_idx = idx / gamma_offset;
+	let inner_idx = idx % gamma_offset;
+
+	let row = inner_idx / cols;
+	let col = inner_idx % cols;
+
+	let sample_start = sample_idx * sample_size;
+	let count = group_len();
+
+	var mean = 0.0;
+	for (var k = 0u; k < count; k++){
+		mean += input[sample_start + group_element(k, row, col)];
+	}
+	mean = mean / f32(count);
+
+	var variance = 0.0;
+	for (var k = 0u; k < count; k++){
+		let diff = input[sample_start + group_element(k, row, col)] - mean;
+		variance += diff * diff;
+	}
+	let inv_std = 1.0 / sqrt(variance / f32(count) + params.eps);
+
+	var grad_sum = 0.0;
+	var grad_dot = 0.0;
+	for (var k = 0u; k < count; k++){
+		let local_idx = group_element(k, row, col);
+		let grad_normalized = input[sample_start + grad_offset + local_idx] * input[sample_start + gamma_offset + param_index(local_idx)];
+
+		grad_sum += grad_normalized;
+		grad_dot += grad_normalized * (input[sample_start + local_idx] - mean) * inv_std;
+	}
+
+	let normalized = (input[sample_start + inner_idx] - mean) * inv_std;
+	let grad_normalized = input[sample_start + grad_offset + inner_idx] * input[sample_start + gamma_offset + param_index(inner_idx)];
+
+	output[idx] = inv_std / f32(count) * (f32(count) * grad_normalized - grad_sum - normalized * grad_dot);
+}
@@ -0,0 +1,121 @@
+@group(0) @binding(0)
+var<storage, read> input: array<f32>; //gamma, beta, input, grad_output
+
+@group(0) @binding(1)
+var<storage, read> shapes: array<u32>;
+
+struct Params {
+	eps: f32,
+	groups: f32,
+	learning_rate: f32,
+	mode: f32, // 0 - batch norm, 1 - layer norm, 2 - group norm
+}
+@group(0) @binding(2)
+var<uniform> params: Params;
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f32>; //updated gamma, updated beta
+
+var<private> rows: u32;
+var<private> cols: u32;
+var<private> param_size: u32;
+var<private> mode: u32;
+var<private> groups: u32;
+
+// Returns local index of k-th element of the group normalized together with element (row, col)
+fn group_element(k: u32, row: u32, col: u32) -> u32{
+	if (mode == 2u){
+		let channels_per_group = rows / groups;
+		let group_row = (row / channels_per_group) * channels_per_group;
+		return (group_row + k) * cols + col;
+	}
+	if (mode == 1u){
+		return ((row * cols + col) / param_size) * param_size + k;
+	}
+	return row * cols + k;
+}
+
+fn group_len() -> u32{
+	if (mode == 2u){
+		return rows / groups;
+	}
+	if (mode == 1u){
+		return param_size;
+	}
+	return cols;
+}
+
+// Returns local index of k-th element scaled by param
+fn param_element(k: u32, param_idx: u32) -> u32{
+	if (mode == 1u){
+		return k * param_size + param_idx;
+	}
+	return param_idx * cols + k;
+}
+
+fn param_len() -> u32{
+	if (mode == 1u){
+		return rows * cols / param_size;
+	}
+	return cols;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>){
+
+	let idx = global_id.y * 65535u + global_id.x;
+	if (idx >= arrayLength(&output)) {
+		return;
+	}
+
+	rows = shapes[4];
+	cols = shapes[5];
+	param_size = shapes[0] * shapes[1];
+	mode = u32(params.mode);
+	groups = max(u32(params.groups), 1u);
+
+	let input_offset = param_size * 2u;
+	let grad_offset = input_offset + rows * cols;
+
+	let sample_size = grad_offset + shapes[6] * shapes[7];
+	let sample_count: u32 = arrayLength(&input) / sample_size;
+
+	let is_beta = idx >= param_size;
+	let param_idx = idx % param_size;
+
+	var sum = 0.0;
+	for (var s = 0u; s < sample_count; s++){
+		let sample_start = s * sample_size;
+
+		for (var k = 0u; k < param_len(); k++){
+			let local_idx = param_element(k, param_idx);
+			let grad = input[sample_start + grad_offset + local_idx];
+
+			if (is_beta){
+				sum += grad;
+				continue;
+			}
+
+			let row = local_idx / cols;
+			let col = local_idx % cols;
+			let count = group_len();
+
+			var mean = 0.0;
+			for (var j = 0u; j < count; j++){
+				mean += input[sample_start + input_offset + group_element(j, row, col)];
+			}
+			mean = mean / f32(count);
+
+			var variance = 0.0;
+			for (var j = 0u; j < count; j++){
+				let diff = input[sample_start + input_offset + group_element(j, row, col)] - mean;
+				variance += diff * diff;
+			}
+
+			let normalized = (input[sample_start + input_offset + local_idx] - mean) / sqrt(variance / f32(count) + params.eps);
+			sum += grad * normalized;
+		}
+	}
+
+	output[idx] = input[idx] - (sum / f32(sample_count)) * params.learning_rate;
+}
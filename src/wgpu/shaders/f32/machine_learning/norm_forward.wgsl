@@ -0,0 +1,97 @@
+@group(0) @binding(0)
+var<storage, read> input: array<f32>; //input, gamma, beta
+
+@group(0) @binding(1)
+var<storage, read> shapes: array<u32>;
+
+struct Params {
+	eps: f32,
+	groups: f32,
+	learning_rate: f32,
+	mode: f32, // 0 - batch norm, 1 - layer norm, 2 - group norm
+}
+@group(0) @binding(2)
+var<uniform> params: Params;
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f32>;
+
+// Returns local index of k-th element of the group normalized together with element (row, col)
+fn group_element(k: u32, row: u32, col: u32, rows: u32, cols: u32, param_size: u32, mode: u32, groups: u32) -> u32{
+	if (mode == 2u){
+		let channels_per_group = rows / groups;
+		let group_row = (row / channels_per_group) * channels_per_group;
+		return (group_row + k) * cols + col;
+	}
+	if (mode == 1u){
+		let inner_idx = row * cols + col;
+		return (inner_idx / param_size) * param_size + k;
+	}
+	return row * cols + k;
+}
+
+fn group_len(rows: u32, cols: u32, param_size: u32, mode: u32, groups: u32) -> u32{
+	if (mode == 2u){
+		return rows / groups;
+	}
+	if (mode == 1u){
+		return param_size;
+	}
+	return cols;
+}
+
+fn param_index(row: u32, col: u32, cols: u32, param_size: u32, mode: u32) -> u32{
+	if (mode == 1u){
+		return (row * cols + col) % param_size;
+	}
+	return row;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>){
+
+	let idx = global_id.y * 65535u + global_id.x;
+	if (idx >= arrayLength(&output)) {
+		return;
+	}
+
+	let rows = shapes[0];
+	let cols = shapes[1];
+
+	let gamma_offset = rows * cols;
+	let param_size = shapes[2] * shapes[3];
+	let beta_offset = gamma_offset + param_size;
+
+	let sample_size = beta_offset + shapes[4] * shapes[5];
+
+	let sample_idx = idx / gamma_offset;
+	let inner_idx = idx % gamma_offset;
+
+	let row = inner_idx / cols;
+	let col = inner_idx % cols;
+
+	let mode = u32(params.mode);
+	let groups = max(u32(params.groups), 1u);
+
+	let sample_start = sample_idx * sample_size;
+	let count = group_len(rows, cols, param_size, mode, groups);
+
+	var mean = 0.0;
+	for (var k = 0u; k < count; k++){
+		mean += input[sample_start + group_element(k, row, col, rows, cols, param_size, mode, groups)];
+	}
+	mean = mean / f32(count);
+
+	var variance = 0.0;
+	for (var k = 0u; k < count; k++){
+		let diff = input[sample_start + group_element(k, row, col, rows, cols, param_size, mode, groups)] - mean;
+		variance += diff * diff;
+	}
+	variance = variance / f32(count);
+
+	let param_idx = param_index(row, col, cols, param_size, mode);
+
+	let normalized = (input[sample_start + inner_idx] - mean) / sqrt(variance + params.eps);
+
+	output[idx] = normalized * input[sample_start + gamma_offset + param_idx] + input[sample_start + beta_offset + param_idx];
+}
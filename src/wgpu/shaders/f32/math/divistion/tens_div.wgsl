@@ -1,20 +1,21 @@
 @group(0) @binding(0)
 var<storage, read> input: array<f32>;
 
-@group(0) @binding(1)
-var<storage, read> input_shape: array<u32>;
-
 @group(0) @binding(3)
 var<storage, read_write> output: array<f32>;
 
 @compute @workgroup_size(64)
-fn main(@builtin(global_invocation_id) global_id: vec3<u32>){
-	let idx = global_id.y * 65535u + global_id.x;
-	let tensor_size = u32(input_shape[0] * input_shape[1]);
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>, @builtin(num_workgroups) num_workgroups: vec3<u32>){
+	let idx = invocation_index(global_id, num_workgroups);
+	if (idx >= arrayLength(&output)) {
+		return;
+	}
 
-	let sample_size = tensor_size*2;
+	let sample = output_sample(idx);
+	let inner_idx = idx - sample_output_offset(sample);
 
-	let offset = idx/tensor_size;
+	let a = load_input(0u, broadcast_index(0u, sample, inner_idx));
+	let b = load_input(1u, broadcast_index(1u, sample, inner_idx));
 
-	output[idx] = input[offset*tensor_size + idx] / input[offset*tensor_size + idx + tensor_size];
+	output[idx] = a / b;
 }
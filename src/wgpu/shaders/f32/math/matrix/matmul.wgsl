@@ -1,46 +1,33 @@
 @group(0) @binding(0)
 var<storage, read> input: array<f32>;
 
-@group(0) @binding(1)
-var<storage, read> input_shape: array<u32>;
-
 @group(0) @binding(3)
 var<storage, read_write> output: array<f32>;
 
-@compute @workgroup_size(64)
-fn main(@builtin(global_invocation_id) global_id: vec3<u32>){
-
-	let idx = global_id.y * 65535u + global_id.x;
-	if (idx >= arrayLength(&input)) {
-		return;
-	}
-
-    let M = input_shape[0];
-    let K = input_shape[1];
-    let N = input_shape[3];
-
-    let sample_output_size = M * N;
-    let sample_input_size = M * K + K * N;
+fn gemm_rows(sample: u32) -> u32 {
+	return sample_matrix_rows(sample, 0u);
+}
 
-    if (idx >= arrayLength(&output)) {
-        return;
-    }
+fn gemm_cols(sample: u32) -> u32 {
+	return sample_matrix_cols(sample, 1u);
+}
 
-    let sample_idx = idx / sample_output_size;
-    let inner_idx = idx % sample_output_size;
+fn gemm_depth(sample: u32) -> u32 {
+	return sample_matrix_cols(sample, 0u);
+}
 
-    let row = inner_idx / N;
-    let col = inner_idx % N;
+fn gemm_samples() -> u32 {
+	return meta_samples(arrayLength(&output));
+}
 
-    let offset_inputA = sample_idx * sample_input_size;
-    let offset_inputB = offset_inputA + M * K;
+fn load_a(sample: u32, row: u32, k: u32) -> f32 {
+	return load_input(0u, matrix_index(0u, sample, row, k));
+}
 
-    var sum: f32 = 0.0;
-    for (var k: u32 = 0u; k < K; k = k + 1u) {
-        let a = input[offset_inputA + row * K + k];
-        let b = input[offset_inputB + k * N + col];
-        sum = sum + a * b;
-    }
+fn load_b(sample: u32, k: u32, col: u32) -> f32 {
+	return load_input(1u, matrix_index(1u, sample, k, col));
+}
 
-    output[idx] = sum;
+fn store(sample: u32, row: u32, col: u32, sum: f32) {
+	output[sample_output_offset(sample) + row * gemm_cols(sample) + col] = sum;
 }
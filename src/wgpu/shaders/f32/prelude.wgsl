@@ -0,0 +1,184 @@
+// Indexing helpers shared by shaders, that read shapes of tensors from metadata buffer
+//
+// Metadata is written by GpuData::metadata:
+// [rank, tensors, sample_size, offset of every tensor in sample..., shape and strides of every tensor...]
+// Output is the last tensor. Tensors with lower rank are padded with leading dims of 1, and
+// dims of 1 have stride 0, so they are broadcast over output
+
+@group(0) @binding(4)
+var<storage, read> metadata: array<u32>;
+
+// Index of invocation, workgroups are split into y after x dimension is full
+fn invocation_index(global_id: vec3<u32>, num_workgroups: vec3<u32>) -> u32 {
+	return global_id.y * num_workgroups.x * 64u + global_id.x;
+}
+
+fn meta_rank() -> u32 {
+	return sample_rank(0u);
+}
+
+fn meta_tensors() -> u32 {
+	return metadata[1];
+}
+
+// Number of inputs of one sample
+fn meta_sample_size() -> u32 {
+	return metadata[2];
+}
+
+fn meta_offset(tensor: u32) -> u32 {
+	return sample_offset(0u, tensor);
+}
+
+fn meta_shape(tensor: u32, dim: u32) -> u32 {
+	return sample_shape(0u, tensor, dim);
+}
+
+fn meta_stride(tensor: u32, dim: u32) -> u32 {
+	return sample_stride(0u, tensor, dim);
+}
+
+fn output_tensor() -> u32 {
+	return meta_tensors() - 1u;
+}
+
+fn tensor_len(tensor: u32) -> u32 {
+	return sample_tensor_len(0u, tensor);
+}
+
+// Ragged batch, with shapes of every sample, has 0 in place of sample_size and tables of samples
+// in front of metadata of every sample, written by GpuData::chunk_metadata:
+// [0, tensors, 0, samples, input offset, output offset and metadata offset of every sample..., metadata of every sample...]
+// Functions without sample read shapes of the first sample
+fn meta_ragged() -> bool {
+	return metadata[2] == 0u;
+}
+
+// Number of samples, output_len is length of outputs of all samples
+fn meta_samples(output_len: u32) -> u32 {
+	if (meta_ragged()) {
+		return metadata[3];
+	}
+	return output_len / tensor_len(output_tensor());
+}
+
+// Offset of metadata of sample
+fn sample_meta(sample: u32) -> u32 {
+	if (meta_ragged()) {
+		return metadata[4u + sample * 3u + 2u];
+	}
+	return 0u;
+}
+
+// Index of the first input of sample
+fn sample_input_offset(sample: u32) -> u32 {
+	if (meta_ragged()) {
+		return metadata[4u + sample * 3u];
+	}
+	return sample * meta_sample_size();
+}
+
+// Index of the first output of sample
+fn sample_output_offset(sample: u32) -> u32 {
+	if (meta_ragged()) {
+		return metadata[4u + sample * 3u + 1u];
+	}
+	return sample * tensor_len(output_tensor());
+}
+
+// Sample, that output element at idx belongs to
+fn output_sample(idx: u32) -> u32 {
+	if (!meta_ragged()) {
+		return idx / tensor_len(output_tensor());
+	}
+
+	var low = 0u;
+	var high = metadata[3];
+	while (high - low > 1u) {
+		let middle = (low + high) / 2u;
+		if (sample_output_offset(middle) <= idx) {
+			low = middle;
+		}
+		else {
+			high = middle;
+		}
+	}
+	return low;
+}
+
+fn sample_rank(sample: u32) -> u32 {
+	return metadata[sample_meta(sample)];
+}
+
+fn sample_offset(sample: u32, tensor: u32) -> u32 {
+	return metadata[sample_meta(sample) + 3u + tensor];
+}
+
+fn sample_shape(sample: u32, tensor: u32, dim: u32) -> u32 {
+	return metadata[sample_meta(sample) + 3u + meta_tensors() + tensor * 2u * sample_rank(sample) + dim];
+}
+
+fn sample_stride(sample: u32, tensor: u32, dim: u32) -> u32 {
+	return metadata[sample_meta(sample) + 3u + meta_tensors() + (tensor * 2u + 1u) * sample_rank(sample) + dim];
+}
+
+fn sample_tensor_len(sample: u32, tensor: u32) -> u32 {
+	var len = 1u;
+	for (var dim = 0u; dim < sample_rank(sample); dim++) {
+		len *= sample_shape(sample, tensor, dim);
+	}
+	return len;
+}
+
+// Inputs shared by every sample have SHARED_TENSOR bit set in offset, and the rest of offset is
+// index in buffer of shared inputs, the same for every sample
+const SHARED_TENSOR: u32 = 0x80000000u;
+
+fn meta_shared(tensor: u32) -> bool {
+	return (meta_offset(tensor) & SHARED_TENSOR) != 0u;
+}
+
+// Index of the first element of tensor in sample
+fn tensor_start(tensor: u32, sample: u32) -> u32 {
+	let offset = sample_offset(sample, tensor);
+	if ((offset & SHARED_TENSOR) != 0u) {
+		return offset & ~SHARED_TENSOR;
+	}
+	return sample_input_offset(sample) + offset;
+}
+
+// Index in input of element of tensor, at position of output element, in sample
+fn broadcast_index(tensor: u32, sample: u32, output_idx: u32) -> u32 {
+	let out = output_tensor();
+
+	var rest = output_idx;
+	var idx = tensor_start(tensor, sample);
+	for (var dim = sample_rank(sample); dim > 0u; dim--) {
+		let size = sample_shape(sample, out, dim - 1u);
+		idx += (rest % size) * sample_stride(sample, tensor, dim - 1u);
+		rest = rest / size;
+	}
+	return idx;
+}
+
+fn matrix_rows(tensor: u32) -> u32 {
+	return sample_matrix_rows(0u, tensor);
+}
+
+fn matrix_cols(tensor: u32) -> u32 {
+	return sample_matrix_cols(0u, tensor);
+}
+
+fn sample_matrix_rows(sample: u32, tensor: u32) -> u32 {
+	return sample_shape(sample, tensor, sample_rank(sample) - 2u);
+}
+
+fn sample_matrix_cols(sample: u32, tensor: u32) -> u32 {
+	return sample_shape(sample, tensor, sample_rank(sample) - 1u);
+}
+
+// Index in input of element at row and col of matrix tensor, in sample
+fn matrix_index(tensor: u32, sample: u32, row: u32, col: u32) -> u32 {
+	let rank = sample_rank(sample);
+	return tensor_start(tensor, sample) + row * sample_stride(sample, tensor, rank - 2u) + col * sample_stride(sample, tensor, rank - 1u);
+}
@@ -0,0 +1,38 @@
+// Workgroup parallel reduction, shared by reduction shaders
+//
+// Every workgroup computes one output at a time. Invocations accumulate strided elements of
+// reduced lane, then partial accumulators are merged in workgroup memory as a tree.
+// Shader using it defines accumulator and how elements are loaded and stored:
+// Accumulator, reduce_init, reduce_add, reduce_merge, reduce_finish,
+// reduce_outputs, reduce_len, reduce_load and reduce_store
+
+var<workgroup> accumulators: array<Accumulator, 64>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(workgroup_id) workgroup_id: vec3<u32>, @builtin(num_workgroups) num_workgroups: vec3<u32>, @builtin(local_invocation_index) local_index: u32){
+	let outputs = reduce_outputs();
+	let len = reduce_len();
+
+	// dispatch is capped by device limits, so workgroups loop, when there are more outputs
+	let workgroups = num_workgroups.x * num_workgroups.y;
+	for (var output_idx = workgroup_id.y * num_workgroups.x + workgroup_id.x; output_idx < outputs; output_idx += workgroups) {
+		var acc = reduce_init();
+		for (var i = local_index; i < len; i += 64u) {
+			acc = reduce_add(acc, reduce_load(output_idx, i), i);
+		}
+		accumulators[local_index] = acc;
+		workgroupBarrier();
+
+		for (var stride = 32u; stride > 0u; stride = stride / 2u) {
+			if (local_index < stride) {
+				accumulators[local_index] = reduce_merge(accumulators[local_index], accumulators[local_index + stride]);
+			}
+			workgroupBarrier();
+		}
+
+		if (local_index == 0u) {
+			reduce_store(output_idx, reduce_finish(accumulators[0], len));
+		}
+		workgroupBarrier();
+	}
+}
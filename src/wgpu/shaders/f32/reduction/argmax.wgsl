@@ -0,0 +1,31 @@
+// Index of first biggest element, stored as f32
+struct Accumulator {
+	max: f32,
+	index: u32,
+}
+
+fn reduce_init() -> Accumulator {
+	return Accumulator(-3.402823e38, 0xffffffffu);
+}
+
+fn reduce_add(acc: Accumulator, value: f32, i: u32) -> Accumulator {
+	// elements of one invocation come in increasing order, so equal value keeps first index
+	if (acc.index == 0xffffffffu || value > acc.max) {
+		return Accumulator(value, i);
+	}
+	return acc;
+}
+
+fn reduce_merge(a: Accumulator, b: Accumulator) -> Accumulator {
+	if (b.index == 0xffffffffu) {
+		return a;
+	}
+	if (a.index == 0xffffffffu || b.max > a.max || (b.max == a.max && b.index < a.index)) {
+		return b;
+	}
+	return a;
+}
+
+fn reduce_finish(acc: Accumulator, len: u32) -> f32 {
+	return f32(acc.index);
+}
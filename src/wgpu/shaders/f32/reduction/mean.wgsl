@@ -0,0 +1,19 @@
+struct Accumulator {
+	sum: f32,
+}
+
+fn reduce_init() -> Accumulator {
+	return Accumulator(0.0);
+}
+
+fn reduce_add(acc: Accumulator, value: f32, i: u32) -> Accumulator {
+	return Accumulator(acc.sum + value);
+}
+
+fn reduce_merge(a: Accumulator, b: Accumulator) -> Accumulator {
+	return Accumulator(a.sum + b.sum);
+}
+
+fn reduce_finish(acc: Accumulator, len: u32) -> f32 {
+	return acc.sum / f32(len);
+}
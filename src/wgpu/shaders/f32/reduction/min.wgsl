@@ -0,0 +1,19 @@
+struct Accumulator {
+	min: f32,
+}
+
+fn reduce_init() -> Accumulator {
+	return Accumulator(3.402823e38);
+}
+
+fn reduce_add(acc: Accumulator, value: f32, i: u32) -> Accumulator {
+	return Accumulator(min(acc.min, value));
+}
+
+fn reduce_merge(a: Accumulator, b: Accumulator) -> Accumulator {
+	return Accumulator(min(a.min, b.min));
+}
+
+fn reduce_finish(acc: Accumulator, len: u32) -> f32 {
+	return acc.min;
+}
@@ -0,0 +1,19 @@
+struct Accumulator {
+	prod: f32,
+}
+
+fn reduce_init() -> Accumulator {
+	return Accumulator(1.0);
+}
+
+fn reduce_add(acc: Accumulator, value: f32, i: u32) -> Accumulator {
+	return Accumulator(acc.prod * value);
+}
+
+fn reduce_merge(a: Accumulator, b: Accumulator) -> Accumulator {
+	return Accumulator(a.prod * b.prod);
+}
+
+fn reduce_finish(acc: Accumulator, len: u32) -> f32 {
+	return acc.prod;
+}
@@ -0,0 +1,43 @@
+// Reduction of first input into output, over every dim where output has size 1
+
+@group(0) @binding(0)
+var<storage, read> input: array<f32>;
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f32>;
+
+fn reduce_outputs() -> u32 {
+	return arrayLength(&output);
+}
+
+fn reduce_len() -> u32 {
+	return tensor_len(0u) / tensor_len(output_tensor());
+}
+
+// Element i of lane reduced into output, last reduced dim is the fastest
+fn reduce_load(output_idx: u32, i: u32) -> f32 {
+	let out = output_tensor();
+	let sample = output_idx / tensor_len(out);
+
+	var rest_out = output_idx % tensor_len(out);
+	var rest = i;
+	var idx = sample * meta_sample_size() + meta_offset(0u);
+	for (var dim = meta_rank(); dim > 0u; dim--) {
+		let out_size = meta_shape(out, dim - 1u);
+		let in_size = meta_shape(0u, dim - 1u);
+
+		var coord = rest_out % out_size;
+		if (out_size == 1u) {
+			coord = rest % in_size;
+			rest = rest / in_size;
+		}
+		rest_out = rest_out / out_size;
+
+		idx += coord * meta_stride(0u, dim - 1u);
+	}
+	return input[idx];
+}
+
+fn reduce_store(output_idx: u32, value: f32) {
+	output[output_idx] = value;
+}
@@ -0,0 +1,31 @@
+// Population variance, accumulated with Welford and merged with Chan update
+struct Accumulator {
+	count: f32,
+	mean: f32,
+	m2: f32,
+}
+
+fn reduce_init() -> Accumulator {
+	return Accumulator(0.0, 0.0, 0.0);
+}
+
+fn reduce_add(acc: Accumulator, value: f32, i: u32) -> Accumulator {
+	let count = acc.count + 1.0;
+	let delta = value - acc.mean;
+	let mean = acc.mean + delta / count;
+	return Accumulator(count, mean, acc.m2 + delta * (value - mean));
+}
+
+fn reduce_merge(a: Accumulator, b: Accumulator) -> Accumulator {
+	let count = a.count + b.count;
+	if (count == 0.0) {
+		return a;
+	}
+	let delta = b.mean - a.mean;
+	let mean = a.mean + delta * b.count / count;
+	return Accumulator(count, mean, a.m2 + b.m2 + delta * delta * a.count * b.count / count);
+}
+
+fn reduce_finish(acc: Accumulator, len: u32) -> f32 {
+	return acc.m2 / max(acc.count, 1.0);
+}
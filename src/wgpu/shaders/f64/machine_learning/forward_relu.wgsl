@@ -0,0 +1,44 @@
+@group(0) @binding(0)
+var<storage, read> input: array<f64>; //weights, inputs, biases
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f64>;
+
+fn relu(x: f64) -> f64{
+	if(x > 0.0){
+		return x;
+	}
+	return 0.0;
+}
+
+fn gemm_rows(sample: u32) -> u32 {
+	return sample_matrix_rows(sample, 0u);
+}
+
+fn gemm_cols(sample: u32) -> u32 {
+	return sample_matrix_cols(sample, 1u);
+}
+
+fn gemm_depth(sample: u32) -> u32 {
+	return sample_matrix_cols(sample, 0u);
+}
+
+fn gemm_samples() -> u32 {
+	return meta_samples(arrayLength(&output));
+}
+
+fn load_a(sample: u32, row: u32, k: u32) -> f64 {
+	return load_input(0u, matrix_index(0u, sample, row, k));
+}
+
+fn load_b(sample: u32, k: u32, col: u32) -> f64 {
+	return load_input(1u, matrix_index(1u, sample, k, col));
+}
+
+fn store(sample: u32, row: u32, col: u32, dot_sum: f64) {
+	let inner_idx = row * gemm_cols(sample) + col;
+
+	let bias = load_input(2u, broadcast_index(2u, sample, inner_idx));
+
+	output[sample_output_offset(sample) + inner_idx] = relu(dot_sum + bias);
+}
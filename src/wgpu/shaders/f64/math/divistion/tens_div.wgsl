@@ -0,0 +1,21 @@
+@group(0) @binding(0)
+var<storage, read> input: array<f64>;
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f64>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>, @builtin(num_workgroups) num_workgroups: vec3<u32>){
+	let idx = invocation_index(global_id, num_workgroups);
+	if (idx >= arrayLength(&output)) {
+		return;
+	}
+
+	let sample = output_sample(idx);
+	let inner_idx = idx - sample_output_offset(sample);
+
+	let a = load_input(0u, broadcast_index(0u, sample, inner_idx));
+	let b = load_input(1u, broadcast_index(1u, sample, inner_idx));
+
+	output[idx] = a / b;
+}
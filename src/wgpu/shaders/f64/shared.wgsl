@@ -0,0 +1,12 @@
+// Inputs shared by every sample in double precision, uploaded once and bound apart from inputs of samples
+// Shaders read inputs with load_input, at index from broadcast_index or matrix_index
+
+@group(0) @binding(5)
+var<storage, read> shared_inputs: array<f64>;
+
+fn load_input(tensor: u32, idx: u32) -> f64 {
+	if (meta_shared(tensor)) {
+		return shared_inputs[idx];
+	}
+	return input[idx];
+}
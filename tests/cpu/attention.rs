@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod attention{
+    use flashlight_tensor::prelude::*;
+
+    use crate::cpu::helpers::{assert_close, numerical_grad_of};
+
+    #[test]
+    fn softmax(){
+        let input: Tensor<f32> = Tensor::rand(3.0, &[2, 4, 3]);
+        let output = input.softmax();
+
+        for matrix in 0..2{
+            for col in 0..3{
+                let column_sum: f32 = (0..4).map(|row| output.value(&[matrix, row, col]).unwrap()).sum();
+                assert!((column_sum - 1.0).abs() < 1e-5);
+            }
+        }
+
+        let grad_output: Tensor<f32> = Tensor::rand(1.0, &[2, 4, 3]);
+        let grad_input = output.softmax_backward(&grad_output).unwrap();
+
+        assert_close(&grad_input, &numerical_grad_of(&input, &grad_output, |x| x.softmax()), 1e-2);
+    }
+
+    #[test]
+    fn causal_attention(){
+        let query: Tensor<f32> = Tensor::rand(1.0, &[4, 5]);
+        let key: Tensor<f32> = Tensor::rand(1.0, &[4, 5]);
+        let value: Tensor<f32> = Tensor::rand(1.0, &[3, 5]);
+
+        let (output, weights) = scaled_dot_product_attention(&query, &key, &value, true).unwrap();
+
+        assert_eq!(output.get_shape(), &vec!{3, 5});
+        for key_id in 0..5{
+            for query_id in 0..key_id{
+                assert_eq!(weights.value(&[key_id, query_id]).unwrap(), &0.0);
+            }
+        }
+
+        // first query can only attend to the first key
+        let expected = value.matrix_col(0).unwrap();
+        assert_close(&output.matrix_col(0).unwrap(), &expected, 1e-2);
+    }
+
+    #[test]
+    fn attention_backward(){
+        let query: Tensor<f32> = Tensor::rand(1.0, &[2, 4, 5]);
+        let key: Tensor<f32> = Tensor::rand(1.0, &[2, 4, 6]);
+        let value: Tensor<f32> = Tensor::rand(1.0, &[2, 3, 6]);
+        let grad_output: Tensor<f32> = Tensor::rand(1.0, &[2, 3, 5]);
+
+        for causal in [false, true]{
+            let (_, weights) = scaled_dot_product_attention(&query, &key, &value, causal).unwrap();
+            let (grad_query, grad_key, grad_value) = scaled_dot_product_attention_backward(&query, &key, &value, &weights, &grad_output).unwrap();
+
+            assert_close(&grad_query, &numerical_grad_of(&query, &grad_output, |x| scaled_dot_product_attention(x, &key, &value, causal).unwrap().0), 1e-2);
+            assert_close(&grad_key, &numerical_grad_of(&key, &grad_output, |x| scaled_dot_product_attention(&query, x, &value, causal).unwrap().0), 1e-2);
+            assert_close(&grad_value, &numerical_grad_of(&value, &grad_output, |x| scaled_dot_product_attention(&query, &key, x, causal).unwrap().0), 1e-2);
+        }
+    }
+
+    #[test]
+    fn multi_head_attention_backward(){
+        let mut attention = MultiHeadAttention::new(6, 3).unwrap();
+        attention.causal = true;
+
+        let input: Tensor<f32> = Tensor::rand(1.0, &[6, 4]);
+        let grad_output: Tensor<f32> = Tensor::rand(1.0, &[6, 4]);
+
+        attention.forward(&input, &input, &input).unwrap();
+        let (grad_query, grad_key, grad_value, grads) = attention.backward(&grad_output).unwrap();
+
+        let grad_input = grad_query.tens_add(&grad_key).unwrap().tens_add(&grad_value).unwrap();
+        let expected = numerical_grad_of(&input, &grad_output, |x| attention.clone().forward(x, x, x).unwrap());
+        assert_close(&grad_input, &expected, 1e-2);
+
+        let expected = numerical_grad_of(&attention.weight_k, &grad_output, |weight| {
+            let mut attention = attention.clone();
+            attention.weight_k = weight.clone();
+            attention.forward(&input, &input, &input).unwrap()
+        });
+        assert_close(&grads.weight_k, &expected, 1e-2);
+
+        let expected = numerical_grad_of(&attention.bias_v, &grad_output, |bias| {
+            let mut attention = attention.clone();
+            attention.bias_v = bias.clone();
+            attention.forward(&input, &input, &input).unwrap()
+        });
+        assert_close(&grads.bias_v, &expected, 1e-2);
+    }
+}
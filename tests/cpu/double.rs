@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod double_tests{
+    use flashlight_tensor::prelude::*;
+    use crate::cpu::helpers::assert_close;
+
+    #[test]
+    fn conversions(){
+        let a: Tensor<f64> = Tensor::from_data(&[0.1, -2.5, 1e-10], &[3]).unwrap();
+
+        let rounded: Tensor<f32> = a.to_f32();
+        assert_eq!(rounded.get_data(), &vec!{0.1, -2.5, 1e-10});
+        assert_eq!(rounded.to_f64().get_data()[1], -2.5);
+        assert_ne!(rounded.to_f64().get_data()[0], 0.1);
+    }
+
+    #[test]
+    fn float_literals_stay_f32(){
+        // functions of f32 tensors are inherent, so unannotated tensors are not inferred as f64
+        let a = Tensor::from_data(&[0.1, 0.2, 0.3], &[3]).unwrap();
+        let mean = a.relu().mean();
+
+        assert_eq!(size_of_val(&mean), size_of::<f32>());
+        assert_eq!(mean, 0.2f32);
+    }
+
+    #[test]
+    fn matrix_mul(){
+        let a: Tensor<f64> = Tensor::fill(0.1, &[2, 10000]);
+        let b: Tensor<f64> = Tensor::fill(1.0, &[10000, 3]);
+
+        let result: Tensor<f64> = a.matrix_mul(&b).unwrap();
+        assert_eq!(result.get_shape(), &vec!{2, 3});
+        for value in result.get_data(){
+            assert!((value - 1000.0).abs() < 1e-9, "{}", value);
+        }
+
+        let lhs: Tensor<f32> = Tensor::rand(1.0, &[4, 6, 5]);
+        let rhs: Tensor<f32> = Tensor::rand(1.0, &[4, 5, 3]);
+        let batched: Tensor<f64> = lhs.to_f64().batch_matrix_mul(&rhs.to_f64()).unwrap();
+        assert_close(&batched, &lhs.batch_matrix_mul(&rhs).unwrap().to_f64(), 1e-5);
+
+        let vector: Tensor<f64> = Tensor::from_data(&[1.0, 2.0, 3.0], &[3]).unwrap();
+        assert_eq!(vector.dot_product(&vector), Some(14.0));
+    }
+
+    #[test]
+    fn functions_and_activations(){
+        let a: Tensor<f64> = Tensor::from_data(&[-2.0, 0.0, 1e-9, 3.0], &[2, 2]).unwrap();
+
+        assert_eq!(a.relu().get_data(), &vec!{0.0, 0.0, 1e-9, 3.0});
+        assert_eq!(a.relu_der().get_data(), &vec!{0.0, 1.0, 1.0, 1.0});
+        assert_eq!(a.sigmoid().get_data()[1], 0.5);
+        assert!((a.sigmoid().get_data()[2] - (0.5 + 0.25e-9)).abs() < 1e-18);
+        assert_eq!(a.tanh().get_data()[3], 3.0f64.tanh());
+        assert_eq!(a.tanh_der().get_data()[1], 1.0);
+
+        let positive: Tensor<f64> = Tensor::from_data(&[1.0, 10.0, 1000.0], &[3]).unwrap();
+        assert_eq!(positive.nlog().get_data(), &vec!{0.0, 1.0, 3.0});
+        assert_eq!(positive.log(10.0).get_data()[1], 1.0);
+
+        let softmax = a.softmax();
+        for col in 0..2{
+            let sum = softmax.get_data()[col] + softmax.get_data()[2 + col];
+            assert!((sum - 1.0).abs() < 1e-15);
+        }
+        let grad = softmax.softmax_backward(&Tensor::fill(1.0, &[2, 2])).unwrap();
+        for value in grad.get_data(){
+            assert!(value.abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn reductions(){
+        let a: Tensor<f64> = Tensor::from_data(&[1.0, 2.0, 3.0, 4.0, 5.0, 7.0], &[2, 3]).unwrap();
+
+        assert_eq!(a.sum(), 22.0);
+        assert_eq!(a.mean(), 22.0 / 6.0);
+        assert_eq!(a.max(), 7.0);
+        assert_eq!(a.min(), 1.0);
+        assert_eq!(a.argmax(), 5);
+        assert_eq!(a.sum_axis(0).unwrap().get_data(), &vec!{5.0, 7.0, 10.0});
+        assert_eq!(a.argmax_axis(1).unwrap().get_data(), &vec!{2.0, 2.0});
+
+        // values too close for f32 keep their variance in f64
+        let close: Tensor<f64> = Tensor::from_data(&[1e8, 1e8 + 1.0, 1e8 + 2.0], &[3]).unwrap();
+        assert!((close.variance() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(close.to_f32().variance(), 0.0);
+    }
+
+    #[test]
+    fn normalization(){
+        let input: Tensor<f32> = Tensor::rand(1.0, &[4, 6]);
+        let gamma: Tensor<f32> = Tensor::rand(1.0, &[4, 1]);
+        let beta: Tensor<f32> = Tensor::rand(1.0, &[4, 1]);
+
+        let expected = input.batch_norm(&gamma, &beta, 1e-5).unwrap().to_f64();
+        let output: Tensor<f64> = input.to_f64().batch_norm(&gamma.to_f64(), &beta.to_f64(), 1e-5).unwrap();
+        assert_close(&output, &expected, 1e-4);
+
+        let grad: Tensor<f32> = Tensor::rand(1.0, &[4, 6]);
+        let (expected, _, _) = input.layer_norm_backward(&Tensor::fill(1.0, &[6]), &grad, 1e-5).unwrap();
+        let (output, _, _) = input.to_f64().layer_norm_backward(&Tensor::fill(1.0, &[6]), &grad.to_f64(), 1e-5).unwrap();
+        assert_close(&output, &expected.to_f64(), 1e-3);
+    }
+}
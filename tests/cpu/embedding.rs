@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod embedding{
+    use flashlight_tensor::prelude::*;
+
+    #[test]
+    fn forward(){
+        let embedding = Embedding::new(5, 3);
+
+        let output = embedding.forward(&[4, 1, 4]).unwrap();
+
+        assert_eq!(output.get_shape(), &vec!{3, 3});
+        assert_eq!(output.matrix_col(0).unwrap().get_data(), output.matrix_col(2).unwrap().get_data());
+        assert_eq!(output.matrix_col(1).unwrap().get_data(), embedding.weight.matrix_row(1).unwrap().get_data());
+
+        assert!(embedding.forward(&[5]).is_none());
+    }
+
+    #[test]
+    fn sparse_grad(){
+        let mut embedding = Embedding::new(6, 2);
+        let weight = embedding.weight.clone();
+
+        let grad_output: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]).unwrap();
+        let grad = embedding.backward(&[3, 0, 3], &grad_output).unwrap();
+
+        assert_eq!(grad.ids, vec!{0, 3});
+        assert_eq!(grad.values.get_data(), &vec!{2.0, 5.0, 4.0, 10.0});
+
+        let dense = grad.to_dense(6);
+        let expected = grad_output.matrix_transpose().unwrap();
+        assert_eq!(dense.matrix_row(3).unwrap().get_data(), expected.matrix_row(0).unwrap().tens_add(&expected.matrix_row(2).unwrap()).unwrap().get_data());
+
+        embedding.update(&grad, 1.0);
+
+        let expected_weight = weight.tens_sub(&dense).unwrap();
+        assert_eq!(embedding.weight.get_data(), expected_weight.get_data());
+        assert!(embedding.backward(&[0], &grad_output).is_none());
+    }
+}
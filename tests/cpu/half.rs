@@ -0,0 +1,64 @@
+#[cfg(all(test, feature = "half"))]
+mod half_tests{
+    use flashlight_tensor::prelude::*;
+
+    #[test]
+    fn conversions(){
+        let a: Tensor<f32> = Tensor::from_data(&[1.0, -0.5, 65504.0, 1e-3, 3.14159], &[5]).unwrap();
+
+        let f16s: Tensor<f16> = a.to_f16();
+        assert_eq!(f16s.get_shape(), &vec!{5});
+        for (x, y) in f16s.to_f32().get_data().iter().zip(a.get_data()){
+            assert!((x - y).abs() <= y.abs() * 1e-3, "{} {}", x, y);
+        }
+
+        let bf16s: Tensor<bf16> = a.to_bf16();
+        for (x, y) in bf16s.to_f32().get_data().iter().zip(a.get_data()){
+            assert!((x - y).abs() <= y.abs() * 1e-2, "{} {}", x, y);
+        }
+
+        // f16 overflows past 65504, bf16 keeps range of f32
+        let big: Tensor<f32> = Tensor::fill(1e6, &[1]);
+        assert!(big.to_f16().get_data()[0].is_infinite());
+        assert!((big.to_bf16().to_f32().get_data()[0] - 1e6).abs() < 1e4);
+    }
+
+    #[test]
+    fn generic_operations(){
+        let a: Tensor<f16> = Tensor::fill(f16::from_f32(1.5), &[2, 2]);
+        let b: Tensor<f16> = Tensor::fill(f16::from_f32(0.25), &[2, 2]);
+
+        assert_eq!(a.tens_add(&b).unwrap().to_f32().get_data(), &vec!{1.75; 4});
+        assert_eq!(a.tens_mul(&b).unwrap().to_f32().get_data(), &vec!{0.375; 4});
+        assert_eq!(a.matrix_transpose().unwrap().get_shape(), &vec!{2, 2});
+    }
+
+    #[test]
+    fn matrix_mul(){
+        let a: Tensor<f32> = Tensor::rand(1.0, &[7, 33]);
+        let b: Tensor<f32> = Tensor::rand(1.0, &[33, 5]);
+        let expected = a.matrix_mul(&b).unwrap();
+
+        let f16s = a.to_f16().matrix_mul(&b.to_f16()).unwrap();
+        assert_eq!(f16s.get_shape(), &vec!{7, 5});
+        for (x, y) in f16s.to_f32().get_data().iter().zip(expected.get_data()){
+            assert!((x - y).abs() < 2e-2, "{} {}", x, y);
+        }
+
+        let bf16s = a.to_bf16().matrix_mul(&b.to_bf16()).unwrap();
+        for (x, y) in bf16s.to_f32().get_data().iter().zip(expected.get_data()){
+            assert!((x - y).abs() < 1e-1, "{} {}", x, y);
+        }
+
+        assert!(a.to_f16().matrix_mul(&a.to_f16()).is_none());
+    }
+
+    #[test]
+    fn f32_accumulation(){
+        // 4096 ones sum past 2048, where f16 can not add one anymore
+        let a: Tensor<f16> = Tensor::fill(f16::ONE, &[1, 4096]);
+        let b: Tensor<f16> = Tensor::fill(f16::ONE, &[4096, 1]);
+
+        assert_eq!(a.matrix_mul(&b).unwrap().to_f32().get_data(), &vec!{4096.0});
+    }
+}
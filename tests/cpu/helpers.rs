@@ -0,0 +1,44 @@
+use flashlight_tensor::prelude::*;
+
+/// Asserts that tensors have the same shape, and every value differs by less than epsilon
+pub fn assert_close<T: Float>(a: &Tensor<T>, b: &Tensor<T>, epsilon: T){
+    for (x, y) in a.get_data().iter().zip(b.get_data()) {
+        assert!((*x - *y).abs() < epsilon, "Values differ: {:?} {:?}", x, y);
+    }
+    assert_eq!(a.get_shape(), b.get_shape());
+}
+
+/// Asserts that tensors have the same shape, and every value differs by at most epsilon
+/// relative to value of b, so big values, like sums, are compared with their own tolerance
+pub fn assert_relative_close<T: Float>(a: &Tensor<T>, b: &Tensor<T>, epsilon: T){
+    for (x, y) in a.get_data().iter().zip(b.get_data()) {
+        assert!((*x - *y).abs() <= epsilon * (T::ONE + y.abs()), "Values differ: {:?} {:?}", x, y);
+    }
+    assert_eq!(a.get_shape(), b.get_shape());
+}
+
+/// Returns numerical gradient of loss over every element of x, from central differences
+pub fn numerical_grad(x: &Tensor<f32>, mut loss: impl FnMut(&Tensor<f32>) -> f32) -> Tensor<f32>{
+    let h = 1e-2;
+    let mut grad: Vec<f32> = Vec::with_capacity(x.count_data());
+
+    for i in 0..x.count_data(){
+        let mut plus = x.clone();
+        let mut minus = x.clone();
+
+        let mut data = x.get_data().clone();
+        data[i] += h;
+        plus.set_data(&data);
+        data[i] -= 2.0 * h;
+        minus.set_data(&data);
+
+        grad.push((loss(&plus) - loss(&minus)) / (2.0 * h));
+    }
+
+    Tensor::from_data(&grad, x.get_shape()).unwrap()
+}
+
+/// Returns numerical gradient of sum(forward(x) * grad_output) over every element of x
+pub fn numerical_grad_of(x: &Tensor<f32>, grad_output: &Tensor<f32>, mut forward: impl FnMut(&Tensor<f32>) -> Tensor<f32>) -> Tensor<f32>{
+    numerical_grad(x, |x| forward(x).tens_mul(grad_output).unwrap().sum())
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod reduction{
+    use flashlight_tensor::prelude::*;
+
+    #[test]
+    fn axis_reductions(){
+        let tensor: Tensor<f32> = Tensor::from_data(&[1.0, 5.0, 2.0, 4.0, 3.0, 6.0, 0.0, 2.0, 9.0, 1.0, 1.0, 3.0], &[2, 2, 3]).unwrap();
+
+        let sum = tensor.sum_axis(1).unwrap();
+        assert_eq!(sum.get_shape(), &vec!{2, 1, 3});
+        assert_eq!(sum.get_data(), &vec!{5.0, 8.0, 8.0, 1.0, 3.0, 12.0});
+
+        assert_eq!(tensor.max_axis(2).unwrap().get_data(), &vec!{5.0, 6.0, 9.0, 3.0});
+        assert_eq!(tensor.min_axis(0).unwrap().get_data(), &vec!{0.0, 2.0, 2.0, 1.0, 1.0, 3.0});
+        assert_eq!(tensor.argmax_axis(2).unwrap().get_data(), &vec!{1.0, 2.0, 2.0, 2.0});
+        assert_eq!(tensor.mean_axis(0).unwrap().get_data(), &vec!{0.5, 3.5, 5.5, 2.5, 2.0, 4.5});
+        assert_eq!(tensor.variance_axis(1).unwrap().get_data(), &vec!{2.25, 1.0, 4.0, 0.25, 0.25, 9.0});
+    }
+
+    #[test]
+    fn full_reductions(){
+        let tensor: Tensor<f32> = Tensor::from_data(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0], &[2, 4]).unwrap();
+
+        assert_eq!(tensor.mean(), 5.0);
+        assert_eq!(tensor.max(), 9.0);
+        assert_eq!(tensor.min(), 2.0);
+        assert_eq!(tensor.argmax(), 7);
+        assert_eq!(tensor.variance(), 4.0);
+    }
+
+    #[test]
+    fn invalid_axis(){
+        let tensor: Tensor<f32> = Tensor::fill(1.0, &[2, 3]);
+
+        assert!(tensor.sum_axis(2).is_none());
+        assert!(tensor.argmax_axis(5).is_none());
+    }
+}
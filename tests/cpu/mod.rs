@@ -2,3 +2,11 @@ pub mod creation;
 pub mod additional;
 pub mod subtypes;
 pub mod math;
+pub mod normalization;
+pub mod regularization;
+pub mod recurrent;
+pub mod attention;
+pub mod embedding;
+pub mod half;
+pub mod double;
+pub mod helpers;
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod normalization_tests{
+    use flashlight_tensor::prelude::*;
+
+    use crate::cpu::helpers::{assert_close, numerical_grad_of};
+
+    fn spread(count: usize) -> Vec<f32>{
+        (0..count).map(|i| ((i * 7) % count) as f32 * 0.5).collect()
+    }
+
+    #[test]
+    fn batch_norm(){
+        let input: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0, 10.0, 20.0, 30.0], &[2, 3]).unwrap();
+        let gamma: Tensor<f32> = Tensor::from_data(&[2.0, 1.0], &[2, 1]).unwrap();
+        let beta: Tensor<f32> = Tensor::from_data(&[1.0, 0.0], &[2, 1]).unwrap();
+
+        let result = input.batch_norm(&gamma, &beta, 0.0).unwrap();
+
+        let s = (1.5f32).sqrt();
+        let expected: Tensor<f32> = Tensor::from_data(&[1.0 - 2.0 * s, 1.0, 1.0 + 2.0 * s, -s, 0.0, s], &[2, 3]).unwrap();
+
+        assert_close(&result, &expected, 1e-4);
+    }
+
+    #[test]
+    fn batch_norm_backward(){
+        // spread out inputs, so variance stays far from epsilon in numerical gradient
+        let input: Tensor<f32> = Tensor::from_data(&spread(12), &[3, 4]).unwrap();
+        let gamma: Tensor<f32> = Tensor::rand(1.0, &[3, 1]);
+        let beta: Tensor<f32> = Tensor::rand(1.0, &[3, 1]);
+        let grad_output: Tensor<f32> = Tensor::rand(1.0, &[3, 4]);
+
+        let (grad_input, _, grad_beta) = input.batch_norm_backward(&gamma, &grad_output, 1e-3).unwrap();
+
+        let expected = numerical_grad_of(&input, &grad_output, |x| x.batch_norm(&gamma, &beta, 1e-3).unwrap());
+
+        assert_close(&grad_input, &expected, 1e-2);
+        assert_close(&grad_beta, &grad_output.matrix_col_sum().unwrap(), 1e-4);
+    }
+
+    #[test]
+    fn batch_norm_running_stats(){
+        let mut norm = BatchNorm::new(2);
+        norm.momentum = 0.5;
+
+        let input: Tensor<f32> = Tensor::from_data(&[1.0, 3.0, 2.0, 6.0], &[2, 2]).unwrap();
+        norm.forward(&input).unwrap();
+
+        assert_eq!(norm.running_mean.get_data(), &vec!{1.0, 2.0});
+        assert_eq!(norm.running_var.get_data(), &vec!{1.5, 4.5});
+
+        norm.eval();
+        let result = norm.forward(&input).unwrap();
+        let expected = input.batch_norm_eval(&norm.running_mean, &norm.running_var, &norm.gamma, &norm.beta, norm.eps).unwrap();
+
+        assert_close(&result, &expected, 1e-4);
+    }
+
+    #[test]
+    fn layer_norm_backward(){
+        let input: Tensor<f32> = Tensor::from_data(&spread(24), &[2, 3, 4]).unwrap();
+        let gamma: Tensor<f32> = Tensor::rand(1.0, &[3, 4]);
+        let beta: Tensor<f32> = Tensor::rand(1.0, &[3, 4]);
+        let grad_output: Tensor<f32> = Tensor::rand(1.0, &[2, 3, 4]);
+
+        let (grad_input, _, _) = input.layer_norm_backward(&gamma, &grad_output, 1e-3).unwrap();
+
+        let expected = numerical_grad_of(&input, &grad_output, |x| x.layer_norm(&gamma, &beta, 1e-3).unwrap());
+
+        assert_close(&grad_input, &expected, 1e-2);
+    }
+
+    #[test]
+    fn group_norm(){
+        let input: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+        let gamma: Tensor<f32> = Tensor::fill(1.0, &[4, 1]);
+        let beta: Tensor<f32> = Tensor::fill(0.0, &[4, 1]);
+
+        // one channel per group leaves a single element in each group
+        let result = input.group_norm(4, &gamma, &beta, 1e-5).unwrap();
+        assert_close(&result, &Tensor::fill(0.0, &[4, 3]), 1e-4);
+
+        // single group over all channels is a layer norm of each column
+        let result = input.group_norm(1, &gamma, &beta, 1e-5).unwrap();
+        let expected = input.matrix_transpose().unwrap().layer_norm(&Tensor::fill(1.0, &[4]), &Tensor::fill(0.0, &[4]), 1e-5).unwrap().matrix_transpose().unwrap();
+        assert_close(&result, &expected, 1e-4);
+
+        assert!(input.group_norm(3, &gamma, &beta, 0.0).is_none());
+    }
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod recurrent{
+    use flashlight_tensor::prelude::*;
+
+    use crate::cpu::helpers::{assert_close, numerical_grad};
+
+    const STEPS: usize = 4;
+
+    /// Returns sum of outputs weighted by grad_outputs, so its gradient is grad_outputs
+    fn loss(outputs: &[Tensor<f32>], grad_outputs: &[Tensor<f32>]) -> f32{
+        outputs.iter().zip(grad_outputs)
+            .map(|(output, grad)| output.tens_mul(grad).unwrap().sum())
+            .sum()
+    }
+
+    fn sequence(size: u32, batch: u32) -> Vec<Tensor<f32>>{
+        (0..STEPS).map(|_| Tensor::rand(1.0, &[size, batch])).collect()
+    }
+
+    #[test]
+    fn rnn_cell(){
+        let cell = RNNCell::new(3, 2);
+
+        let input: Tensor<f32> = Tensor::rand(1.0, &[3, 4]);
+        let hidden: Tensor<f32> = Tensor::rand(1.0, &[2, 4]);
+
+        let (output, _) = cell.forward(&input, &hidden).unwrap();
+
+        let expected = cell.weight_ih.matrix_mul(&input).unwrap()
+            .tens_add(&cell.weight_hh.matrix_mul(&hidden).unwrap()).unwrap()
+            .tens_broadcast_add(&cell.bias_ih.tens_add(&cell.bias_hh).unwrap()).unwrap()
+            .tanh();
+
+        assert_close(&output, &expected, 1e-2);
+    }
+
+    #[test]
+    fn rnn_bptt(){
+        let mut rnn = RNN::new(3, 2);
+
+        let inputs = sequence(3, 4);
+        let grad_outputs = sequence(2, 4);
+
+        rnn.forward(&inputs, None).unwrap();
+        let (grad_inputs, grads) = rnn.backward(&grad_outputs).unwrap();
+
+        let expected = numerical_grad(&rnn.cell.weight_hh, |weight| {
+            let mut rnn = rnn.clone();
+            rnn.cell.weight_hh = weight.clone();
+            loss(&rnn.forward(&inputs, None).unwrap(), &grad_outputs)
+        });
+        assert_close(&grads.weight_hh, &expected, 1e-2);
+
+        let expected = numerical_grad(&rnn.cell.bias_ih, |bias| {
+            let mut rnn = rnn.clone();
+            rnn.cell.bias_ih = bias.clone();
+            loss(&rnn.forward(&inputs, None).unwrap(), &grad_outputs)
+        });
+        assert_close(&grads.bias_ih, &expected, 1e-2);
+
+        let expected = numerical_grad(&inputs[0], |input| {
+            let mut rnn = rnn.clone();
+            let mut inputs = inputs.clone();
+            inputs[0] = input.clone();
+            loss(&rnn.forward(&inputs, None).unwrap(), &grad_outputs)
+        });
+        assert_close(&grad_inputs[0], &expected, 1e-2);
+    }
+
+    #[test]
+    fn gru_bptt(){
+        let mut gru = GRU::new(3, 2);
+
+        let inputs = sequence(3, 4);
+        let grad_outputs = sequence(2, 4);
+
+        gru.forward(&inputs, None).unwrap();
+        let (grad_inputs, grads) = gru.backward(&grad_outputs).unwrap();
+
+        let expected = numerical_grad(&gru.cell.weight_hh, |weight| {
+            let mut gru = gru.clone();
+            gru.cell.weight_hh = weight.clone();
+            loss(&gru.forward(&inputs, None).unwrap(), &grad_outputs)
+        });
+        assert_close(&grads.weight_hh, &expected, 1e-2);
+
+        let expected = numerical_grad(&gru.cell.bias_hh, |bias| {
+            let mut gru = gru.clone();
+            gru.cell.bias_hh = bias.clone();
+            loss(&gru.forward(&inputs, None).unwrap(), &grad_outputs)
+        });
+        assert_close(&grads.bias_hh, &expected, 1e-2);
+
+        let expected = numerical_grad(&inputs[0], |input| {
+            let mut gru = gru.clone();
+            let mut inputs = inputs.clone();
+            inputs[0] = input.clone();
+            loss(&gru.forward(&inputs, None).unwrap(), &grad_outputs)
+        });
+        assert_close(&grad_inputs[0], &expected, 1e-2);
+    }
+
+    #[test]
+    fn lstm_bptt(){
+        let mut lstm = LSTM::new(3, 2);
+
+        let inputs = sequence(3, 4);
+        let grad_outputs = sequence(2, 4);
+
+        lstm.forward(&inputs, None, None).unwrap();
+        let (grad_inputs, grads) = lstm.backward(&grad_outputs).unwrap();
+
+        let expected = numerical_grad(&lstm.cell.weight_hh, |weight| {
+            let mut lstm = lstm.clone();
+            lstm.cell.weight_hh = weight.clone();
+            loss(&lstm.forward(&inputs, None, None).unwrap(), &grad_outputs)
+        });
+        assert_close(&grads.weight_hh, &expected, 1e-2);
+
+        let expected = numerical_grad(&lstm.cell.weight_ih, |weight| {
+            let mut lstm = lstm.clone();
+            lstm.cell.weight_ih = weight.clone();
+            loss(&lstm.forward(&inputs, None, None).unwrap(), &grad_outputs)
+        });
+        assert_close(&grads.weight_ih, &expected, 1e-2);
+
+        let expected = numerical_grad(&inputs[0], |input| {
+            let mut lstm = lstm.clone();
+            let mut inputs = inputs.clone();
+            inputs[0] = input.clone();
+            loss(&lstm.forward(&inputs, None, None).unwrap(), &grad_outputs)
+        });
+        assert_close(&grad_inputs[0], &expected, 1e-2);
+    }
+
+    #[test]
+    fn backward_without_forward(){
+        let rnn = RNN::new(3, 2);
+
+        assert!(rnn.backward(&sequence(2, 4)).is_none());
+        assert!(RNN::new(3, 2).forward(&[], None).is_none());
+    }
+}
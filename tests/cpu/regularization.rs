@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod regularization_tests{
+    use flashlight_tensor::prelude::*;
+
+    #[test]
+    fn dropout(){
+        let input: Tensor<f32> = Tensor::fill(1.0, &[100, 100]);
+
+        let (output, mask) = input.dropout(0.3, true);
+
+        let zeros = output.get_data().iter().filter(|x| **x == 0.0).count();
+        assert!(zeros > 2000 && zeros < 4000, "Zeroed {} of 10000", zeros);
+        assert!(output.get_data().iter().all(|x| *x == 0.0 || (x - 1.0 / 0.7).abs() < 1e-6));
+
+        let grad = Tensor::fill(2.0, &[100, 100]);
+        assert_eq!(grad.tens_mul(&mask).unwrap().get_data(), &output.mul(2.0).get_data().clone());
+
+        let (output, _) = input.dropout(0.3, false);
+        assert_eq!(output.get_data(), input.get_data());
+    }
+
+    #[test]
+    fn dropout_layer(){
+        let mut dropout = Dropout::new(0.5);
+        let input: Tensor<f32> = Tensor::rand(1.0, &[10, 10]);
+
+        assert!(dropout.backward(&input).is_none());
+
+        let output = dropout.forward(&input);
+        let grad = dropout.backward(&Tensor::fill(1.0, &[10, 10])).unwrap();
+
+        assert_eq!(output.get_data(), input.tens_mul(&grad).unwrap().get_data());
+
+        dropout.eval();
+        assert_eq!(dropout.forward(&input).get_data(), input.get_data());
+    }
+
+    #[test]
+    fn alpha_dropout(){
+        let input: Tensor<f32> = Tensor::rand(1.0, &[200, 200]);
+        let mean = input.sum() / input.count_data() as f32;
+
+        let (output, _) = input.alpha_dropout(0.2, true);
+        let output_mean = output.sum() / output.count_data() as f32;
+
+        assert!((output_mean - mean).abs() < 0.1, "Mean {} changed to {}", mean, output_mean);
+    }
+
+    #[test]
+    fn weight_penalty(){
+        let weights: Tensor<f32> = Tensor::from_data(&[-2.0, 0.0, 3.0], &[3]).unwrap();
+        let grad: Tensor<f32> = Tensor::fill(1.0, &[3]);
+
+        assert_eq!(weights.l1_penalty(1.0), 5.0);
+        assert_eq!(weights.l2_penalty(1.0), 6.5);
+
+        assert_eq!(weights.l1_grad(&grad, 1.0).unwrap().get_data(), &vec!{0.0, 1.0, 2.0});
+        assert_eq!(weights.l2_grad(&grad, 1.0).unwrap().get_data(), &vec!{-1.0, 1.0, 4.0});
+        assert!(weights.l2_grad(&Tensor::fill(1.0, &[2]), 1.0).is_none());
+    }
+
+    #[test]
+    fn clip_grad(){
+        let mut grads: Vec<Tensor<f32>> = vec!{Tensor::fill(2.0, &[2, 2]), Tensor::fill(-2.0, &[4])};
+
+        let norm = clip_grad_norm(&mut grads, 2.0);
+        assert!((norm - 8.0f32.sqrt() * 2.0).abs() < 1e-5);
+
+        let new_norm: f32 = grads.iter().map(|g| g.tens_mul(g).unwrap().sum()).sum::<f32>().sqrt();
+        assert!((new_norm - 2.0).abs() < 1e-5);
+
+        clip_grad_value(&mut grads, 0.5);
+        assert!(grads.iter().all(|g| g.get_data().iter().all(|x| x.abs() <= 0.5)));
+    }
+}
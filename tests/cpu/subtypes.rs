@@ -74,4 +74,30 @@ mod matrix_tests{
         assert_eq!(result.get_data(), &expected_data);
         assert_eq!(result.get_shape(), &expected_sizes);
     }
+    fn batch_item(tensor: &Tensor<f32>, id: usize) -> Tensor<f32>{
+        let shape = tensor.get_shape()[1..].to_vec();
+        let size = (shape[0] * shape[1]) as usize;
+
+        Tensor::from_data(&tensor.get_data()[id * size..(id + 1) * size], &shape).unwrap()
+    }
+    #[test]
+    fn batch_matrix_mul(){
+        let tensor1: Tensor<f32> = Tensor::rand(1.0, &[3, 2, 4]);
+        let tensor2: Tensor<f32> = Tensor::rand(1.0, &[3, 4, 5]);
+
+        let result = tensor1.batch_matrix_mul(&tensor2).unwrap();
+
+        assert_eq!(result.get_shape(), &vec!{3, 2, 5});
+        for i in 0..3{
+            let expected = batch_item(&tensor1, i).matrix_mul(&batch_item(&tensor2, i)).unwrap();
+            let result_matrix = batch_item(&result, i);
+
+            for (a, b) in result_matrix.get_data().iter().zip(expected.get_data()){
+                assert!((a - b).abs() < 1e-5, "Values differ: {} {}", a, b);
+            }
+            assert_eq!(batch_item(&result.batch_matrix_transpose().unwrap(), i).get_data(), result_matrix.matrix_transpose().unwrap().get_data());
+        }
+
+        assert!(tensor1.batch_matrix_mul(&tensor1).is_none());
+    }
 }
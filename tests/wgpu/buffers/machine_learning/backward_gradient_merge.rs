@@ -17,13 +17,13 @@ mod backward_gradient_merge{
 
         let sample = Sample::from_data(vec!{weights.clone(), grad_output.clone()}, vec!{}, grad_output.get_shape());
 
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
 
-        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await;
-        buffers.set_shader(&GpuOperations::BackwardGradient);
-        buffers.prepare();
+        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await.unwrap();
+        buffers.set_shader(&GpuOperations::BackwardGradient).unwrap();
+        buffers.prepare().unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await;
+        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
     
 
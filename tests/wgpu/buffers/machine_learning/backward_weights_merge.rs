@@ -21,13 +21,13 @@ mod backward_weights_merge{
 
         let sample = Sample::from_data(vec!{weights.clone(), grad_output.clone(), linear_cache.clone()}, vec!{learning_rate}, weights.get_shape());
 
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
 
-        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await;
-        buffers.set_shader(&GpuOperations::BackwardWeight);
-        buffers.prepare();
+        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await.unwrap();
+        buffers.set_shader(&GpuOperations::BackwardWeight).unwrap();
+        buffers.prepare().unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await;
+        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
     
 
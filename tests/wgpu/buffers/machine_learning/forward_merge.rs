@@ -17,13 +17,13 @@ mod forward_merge{
         let biases: Tensor<f32> = Tensor::from_data(&[3.0, 4.0], &[2,1]).unwrap();
 
         let sample = Sample::from_data(vec!{weights.clone(), inputs.clone(), biases.clone()}, vec!{}, &[weights.get_shape()[0], inputs.get_shape()[1]]);
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
 
-        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await;
-        buffers.set_shader(&GpuOperations::ForwardNoActiv);
-        buffers.prepare();
+        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await.unwrap();
+        buffers.set_shader(&GpuOperations::ForwardNoActiv).unwrap();
+        buffers.prepare().unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await;
+        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
 
         let cpu_output = weights.matrix_mul(&inputs).unwrap().tens_broadcast_add(&biases).unwrap();
@@ -50,13 +50,13 @@ mod forward_merge{
         let biases: Tensor<f32> = Tensor::from_data(&[3.0, 4.0], &[2,1]).unwrap();
 
         let sample = Sample::from_data(vec!{weights.clone(), inputs.clone(), biases.clone()}, vec!{}, &[weights.get_shape()[0], inputs.get_shape()[1]]);
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
 
-        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await;
-        buffers.set_shader(&GpuOperations::ForwardSigmoid);
-        buffers.prepare();
+        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await.unwrap();
+        buffers.set_shader(&GpuOperations::ForwardSigmoid).unwrap();
+        buffers.prepare().unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await;
+        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
 
         let cpu_output = weights.matrix_mul(&inputs).unwrap().tens_broadcast_add(&biases).unwrap().sigmoid();
@@ -83,13 +83,13 @@ mod forward_merge{
         let biases: Tensor<f32> = Tensor::from_data(&[3.0, -4.0], &[2,1]).unwrap();
 
         let sample = Sample::from_data(vec!{weights.clone(), inputs.clone(), biases.clone()}, vec!{}, &[weights.get_shape()[0], inputs.get_shape()[1]]);
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
 
-        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await;
-        buffers.set_shader(&GpuOperations::ForwardRelu);
-        buffers.prepare();
+        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await.unwrap();
+        buffers.set_shader(&GpuOperations::ForwardRelu).unwrap();
+        buffers.prepare().unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await;
+        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
 
         let cpu_output = weights.matrix_mul(&inputs).unwrap().tens_broadcast_add(&biases).unwrap().relu();
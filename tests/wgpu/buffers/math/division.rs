@@ -13,13 +13,13 @@ mod division{
 
         let tensor: Tensor<f32> = Tensor::fill(4.0, &[16, 16]);
         let sample = Sample::from_data(vec!{tensor.clone()}, vec!{2.0}, &[16, 16]);
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
 
-        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await;
-        buffers.set_shader(&GpuOperations::Div);
-        buffers.prepare();
+        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await.unwrap();
+        buffers.set_shader(&GpuOperations::Div).unwrap();
+        buffers.prepare().unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await;
+        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
 
         let cpu_output = tensor.div(2.0);
@@ -40,18 +40,18 @@ mod division{
         let tensor1: Tensor<f32> = Tensor::fill(2.0, &[16, 16]);
         let tensor2: Tensor<f32> = Tensor::fill(2.0, &[16, 16]);
         let sample = Sample::from_data(vec!{tensor1.clone(), tensor2.clone()}, vec!{}, &[16, 16]);
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
 
         let tensor1: Tensor<f32> = Tensor::fill(4.0, &[16, 16]);
         let tensor2: Tensor<f32> = Tensor::fill(2.0, &[16, 16]);
         let sample = Sample::from_data(vec!{tensor1.clone(), tensor2.clone()}, vec!{}, &[16, 16]);
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
 
-        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await;
-        buffers.set_shader(&GpuOperations::TensDiv);
-        buffers.prepare();
+        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await.unwrap();
+        buffers.set_shader(&GpuOperations::TensDiv).unwrap();
+        buffers.prepare().unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await;
+        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await.unwrap();
         let gpu_output = full_gpu_output[1].clone();
 
         let cpu_output = tensor1.tens_div(&tensor2).unwrap();
@@ -71,18 +71,18 @@ mod division{
         let tensor1: Tensor<f32> = Tensor::fill(2.0, &[3, 1]);
         let tensor2: Tensor<f32> = Tensor::fill(2.0, &[1, 5]);
         let sample = Sample::from_data(vec!{tensor1.clone(), tensor2.clone()}, vec!{}, &get_broadcast_shape(tensor1.get_shape(), tensor2.get_shape()).unwrap());
-        gpu_data.append(sample);
+        gpu_data.append(sample).unwrap();
 
         let tensor1: Tensor<f32> = Tensor::fill(10.0, &[3, 1]);
         let tensor2: Tensor<f32> = Tensor::fill(5.0, &[1, 5]);
         let sample = Sample::from_data(vec!{tensor1.clone(), tensor2.clone()}, vec!{}, &get_broadcast_shape(tensor1.get_shape(), tensor2.get_shape()).unwrap());
-       gpu_data.append(sample);
+       gpu_data.append(sample).unwrap();
 
-        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await;
-        buffers.set_shader(&GpuOperations::BroadcastDiv);
-        buffers.prepare();
+        let mut buffers = GpuBuffers::init(1, MemoryMetric::GB, &mut gpu_data, 0).await.unwrap();
+        buffers.set_shader(&GpuOperations::BroadcastDiv).unwrap();
+        buffers.prepare().unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await;
+        let full_gpu_output: Vec<Tensor<f32>> = buffers.run().await.unwrap();
         let gpu_output = full_gpu_output[1].clone();
 
         let cpu_output = tensor1.tens_broadcast_div(&tensor2).unwrap();
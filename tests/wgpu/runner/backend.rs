@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod backend{
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_all_close;
+
+    // runs operation on samples with the same inputs on gpu and cpu backend
+    macro_rules! assert_same_backends{
+        ($op:ident, $inputs:expr, $params:expr, $samples:expr) => {{
+            let mut outputs: Vec<Vec<Tensor<f32>>> = Vec::new();
+            for backend in [Backend::Gpu, Backend::Cpu]{
+                let mut runner = GpuRunner::with_backend(backend, 1, MemoryMetric::GB);
+                for _ in 0..$samples{
+                    runner.append(Sample::from_data($inputs.clone(), $params.clone(), &[])).unwrap();
+                }
+                outputs.push(runner.$op().await.unwrap());
+            }
+            assert_all_close(&outputs[0], &outputs[1], 1e-3);
+        }};
+    }
+
+    #[tokio::test]
+    async fn cpu_elementwise(){
+        let tensor: Tensor<f32> = Tensor::rand(10.0, &[4, 3]);
+        let other: Tensor<f32> = Tensor::rand(10.0, &[4, 1]);
+
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{tensor.clone()}, vec!{2.0}, &[])).unwrap();
+        runner.append(Sample::from_data(vec!{tensor.clone()}, vec!{2.0}, &[])).unwrap();
+
+        let output: Vec<Tensor<f32>> = runner.mul().await.unwrap();
+        assert_all_close(&output, &[tensor.mul(2.0), tensor.mul(2.0)], 1e-3);
+
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{tensor.clone(), other.clone()}, vec!{}, &[])).unwrap();
+
+        let output: Vec<Tensor<f32>> = runner.tens_broadcast_sub().await.unwrap();
+        assert_all_close(&output, &[tensor.tens_broadcast_sub(&other).unwrap()], 1e-3);
+    }
+
+    #[tokio::test]
+    async fn cpu_matrix(){
+        let first: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+        let second: Tensor<f32> = Tensor::rand(1.0, &[3, 5]);
+
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{first.clone(), second.clone()}, vec!{}, &[])).unwrap();
+
+        let output: Vec<Tensor<f32>> = runner.matmul().await.unwrap();
+        assert_all_close(&output, &[first.matrix_mul(&second).unwrap()], 1e-3);
+
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{first.clone()}, vec!{}, &[])).unwrap();
+
+        let output: Vec<Tensor<f32>> = runner.matrix_transpose().await.unwrap();
+        assert_all_close(&output, &[first.matrix_transpose().unwrap()], 1e-3);
+
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{first.clone()}, vec!{}, &[])).unwrap();
+
+        let output: Vec<Tensor<f32>> = runner.matrix_row_sum().await.unwrap();
+        assert_eq!(output[0].get_shape(), &vec!{1, 3});
+    }
+
+    #[tokio::test]
+    async fn cpu_backward_weight_merges_samples(){
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[4, 5]);
+        let first_grad: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+        let second_grad: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+        let linear_cache: Tensor<f32> = Tensor::rand(1.0, &[5, 3]);
+
+        let learning_rate = 0.5;
+
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{weights.clone(), first_grad.clone(), linear_cache.clone()}, vec!{learning_rate}, &[])).unwrap();
+        runner.append(Sample::from_data(vec!{weights.clone(), second_grad.clone(), linear_cache.clone()}, vec!{learning_rate}, &[])).unwrap();
+
+        let output: Vec<Tensor<f32>> = runner.backward_weight().await.unwrap();
+
+        let grad = first_grad.tens_add(&second_grad).unwrap().matrix_mul(&linear_cache.matrix_transpose().unwrap()).unwrap().div(2.0);
+        let expected = weights.tens_sub(&grad.mul(learning_rate)).unwrap();
+
+        assert_all_close(&output, &[expected], 1e-3);
+    }
+
+    #[tokio::test]
+    async fn cpu_lstm_cell(){
+        let cell = LSTMCell::new(3, 2);
+
+        let input: Tensor<f32> = Tensor::rand(1.0, &[3, 4]);
+        let hidden: Tensor<f32> = Tensor::rand(1.0, &[2, 4]);
+        let cell_state: Tensor<f32> = Tensor::rand(1.0, &[2, 4]);
+
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{cell.weight_ih.clone(), cell.weight_hh.clone(), cell.bias_ih.clone(), cell.bias_hh.clone(), input.clone(), hidden.clone(), cell_state.clone()}, vec!{}, &[])).unwrap();
+
+        let output: Vec<Tensor<f32>> = runner.lstm_cell().await.unwrap();
+
+        let (next_hidden, next_cell, _) = cell.forward(&input, &hidden, &cell_state).unwrap();
+
+        assert_all_close(&output, &[next_hidden.append(&next_cell).unwrap()], 1e-3);
+    }
+
+    #[tokio::test]
+    async fn cpu_invalid_shapes(){
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3]), Tensor::fill(1.0, &[2, 3])}, vec!{}, &[])).unwrap();
+
+        assert!(matches!(runner.matmul().await, Err(GpuError::InvalidShapes(_))));
+        assert_eq!(runner.get_backend(), Backend::Cpu);
+    }
+
+    #[tokio::test]
+    async fn auto_backend(){
+        let tensor: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{tensor.clone()}, vec!{1.0}, &[])).unwrap();
+
+        assert_eq!(runner.get_backend(), Backend::Auto);
+
+        let output: Vec<Tensor<f32>> = runner.add().await.unwrap();
+
+        assert_all_close(&output, &[tensor.add(1.0)], 1e-3);
+        assert_ne!(runner.get_backend(), Backend::Auto);
+    }
+
+    #[tokio::test]
+    async fn same_as_gpu(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let matrix: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+        let other: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+        let column: Tensor<f32> = Tensor::rand(1.0, &[4, 1]);
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[4, 5]);
+        let inputs: Tensor<f32> = Tensor::rand(1.0, &[5, 3]);
+
+        assert_same_backends!(add, vec!{matrix.clone()}, vec!{1.5}, 2);
+        assert_same_backends!(log, vec!{matrix.add(1.0)}, vec!{2.0}, 2);
+        assert_same_backends!(tens_mul, vec!{matrix.clone(), other.clone()}, vec!{}, 2);
+        assert_same_backends!(tens_broadcast_add, vec!{matrix.clone(), column.clone()}, vec!{}, 2);
+        assert_same_backends!(sigmoid, vec!{matrix.clone()}, vec!{}, 2);
+
+        assert_same_backends!(matrix_transpose, vec!{matrix.clone()}, vec!{}, 2);
+        assert_same_backends!(matrix_row_sum, vec!{matrix.clone()}, vec!{}, 2);
+        assert_same_backends!(matrix_col_sum, vec!{matrix.clone()}, vec!{}, 2);
+        assert_same_backends!(matmul, vec!{weights.clone(), inputs.clone()}, vec!{}, 2);
+
+        assert_same_backends!(forward_relu, vec!{weights.clone(), inputs.clone(), column.clone()}, vec!{}, 2);
+        assert_same_backends!(backward_grad, vec!{weights.clone(), matrix.clone()}, vec!{}, 1);
+        assert_same_backends!(backward_relu, vec!{matrix.clone(), other.clone()}, vec!{}, 1);
+        assert_same_backends!(backward_weight, vec!{weights.clone(), matrix.clone(), inputs.clone()}, vec!{0.1, 0.01, 0.01}, 2);
+        assert_same_backends!(backward_bias, vec!{column.clone(), matrix.clone(), other.clone()}, vec!{0.1}, 2);
+
+        assert_same_backends!(batch_norm, vec!{matrix.clone(), column.clone(), column.clone()}, vec!{1e-5}, 2);
+        assert_same_backends!(layer_norm, vec!{matrix.clone(), Tensor::fill(1.0, &[1, 3]), Tensor::fill(0.0, &[1, 3])}, vec!{1e-5}, 2);
+        assert_same_backends!(group_norm, vec!{matrix.clone(), column.clone(), column.clone()}, vec!{1e-5, 2.0}, 2);
+        assert_same_backends!(backward_layer_norm_params, vec!{Tensor::fill(1.0, &[1, 3]), Tensor::fill(0.0, &[1, 3]), matrix.clone(), other.clone()}, vec!{1e-5, 1.0, 0.1}, 2);
+
+        let cell = GRUCell::new(5, 4);
+        assert_same_backends!(gru_cell, vec!{cell.weight_ih.clone(), cell.weight_hh.clone(), cell.bias_ih.clone(), cell.bias_hh.clone(), inputs.clone(), matrix.clone()}, vec!{}, 2);
+    }
+
+    #[tokio::test]
+    async fn same_errors_as_gpu(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        // inner dims of [3, 3] x [4, 4] do not match
+        let mut errors: Vec<Vec<GpuError>> = Vec::new();
+        for backend in [Backend::Gpu, Backend::Cpu]{
+            let mut runner = GpuRunner::with_backend(backend, 1, MemoryMetric::GB);
+            runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[3, 3]), Tensor::fill(1.0, &[4, 4])}, vec!{}, &[])).unwrap();
+            let matmul = runner.matmul().await.err().unwrap();
+
+            let mut runner = GpuRunner::with_backend(backend, 1, MemoryMetric::GB);
+            runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[3, 3]), Tensor::fill(1.0, &[4, 4]), Tensor::fill(1.0, &[3, 1])}, vec!{}, &[])).unwrap();
+            let forward = runner.forward_relu().await.err().unwrap();
+
+            errors.push(vec!{matmul, forward});
+        }
+
+        assert!(matches!(errors[0][0], GpuError::InvalidShapes(_)));
+        assert_eq!(errors[0], errors[1]);
+    }
+}
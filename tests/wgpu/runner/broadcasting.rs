@@ -12,9 +12,9 @@ mod broadcasting{
         
         let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1]), Tensor::fill(1.0, &[1, 2])}, vec!{}, &[]);
         
-        runner.append(sample);
+        runner.append(sample).unwrap();
     
-        let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_add().await;
+        let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_add().await.unwrap();
         
         assert_eq!(output_data[0].get_data(), &vec!{2.0, 2.0, 2.0, 2.0});
     }
@@ -29,9 +29,9 @@ mod broadcasting{
         
         let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1]), Tensor::fill(1.0, &[1, 2])}, vec!{}, &[]);
         
-        runner.append(sample);
+        runner.append(sample).unwrap();
     
-        let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_sub().await;
+        let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_sub().await.unwrap();
         
         assert_eq!(output_data[0].get_data(), &vec!{0.0, 0.0, 0.0, 0.0});
     } 
@@ -46,9 +46,9 @@ mod broadcasting{
         
         let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1]), Tensor::fill(1.0, &[1, 2])}, vec!{}, &[]);
         
-        runner.append(sample);
+        runner.append(sample).unwrap();
     
-        let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_mul().await;
+        let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_mul().await.unwrap();
         
         assert_eq!(output_data[0].get_data(), &vec!{1.0, 1.0, 1.0, 1.0});
     }
@@ -63,9 +63,9 @@ mod broadcasting{
         
         let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1]), Tensor::fill(1.0, &[1, 2])}, vec!{}, &[]);
         
-        runner.append(sample);
+        runner.append(sample).unwrap();
     
-        let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_div().await;
+        let output_data: Vec<Tensor<f32>> = runner.tens_broadcast_div().await.unwrap();
         
         assert_eq!(output_data[0].get_data(), &vec!{1.0, 1.0, 1.0, 1.0});
     }
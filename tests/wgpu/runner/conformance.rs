@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod conformance{
+    use flashlight_tensor::prelude::*;
+
+    #[test]
+    fn covers_all_operations(){
+        for operation in GpuOperations::all(){
+            assert!(CONFORMANCE_OPERATIONS.iter().any(|(_, checked)| *checked == operation), "{:?} is not checked by conformance", operation);
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_operation(){
+        let context = match GpuContext::init(256, &MemoryMetric::MB).await{
+            Ok(context) => context,
+            Err(_) => return,
+        };
+
+        assert!(matches!(check_operation(context, "unknown", &ConformanceConfig::default()).await, Err(GpuError::InvalidShapes(_))));
+    }
+
+    #[tokio::test]
+    async fn gpu_matches_cpu(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let context = match GpuContext::init_fallback(256, &MemoryMetric::MB).await{
+            Ok(context) => context,
+            Err(_) => GpuContext::init(256, &MemoryMetric::MB).await.unwrap(),
+        };
+
+        let reports = run_conformance(context, &ConformanceConfig::default()).await.unwrap();
+
+        for report in &reports{
+            println!("{}", report);
+        }
+
+        assert!(reports.iter().all(|report| report.passed()));
+    }
+}
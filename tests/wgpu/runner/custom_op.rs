@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod custom_op{
+    use std::sync::Arc;
+
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_all_relative_close;
+
+    const SCALED_SUB: &str = "
+@group(0) @binding(0)
+var<storage, read> input: array<f32>;
+
+struct Params {
+    scale: f32,
+}
+@group(0) @binding(2)
+var<uniform> params: Params;
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>, @builtin(num_workgroups) num_workgroups: vec3<u32>){
+	let idx = invocation_index(global_id, num_workgroups);
+	if (idx >= arrayLength(&output)) {
+		return;
+	}
+
+	let output_len = tensor_len(output_tensor());
+	let sample = idx / output_len;
+	let inner_idx = idx % output_len;
+
+	output[idx] = (input[broadcast_index(0u, sample, inner_idx)] - input[broadcast_index(1u, sample, inner_idx)]) * params.scale;
+}
+";
+
+    // mean of every element over samples of chunk
+    const SAMPLE_MEAN: &str = "
+@group(0) @binding(0)
+var<storage, read> input: array<f32>;
+
+@group(0) @binding(3)
+var<storage, read_write> output: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>, @builtin(num_workgroups) num_workgroups: vec3<u32>){
+	let idx = invocation_index(global_id, num_workgroups);
+	if (idx >= arrayLength(&output)) {
+		return;
+	}
+
+	let samples = arrayLength(&input) / meta_sample_size();
+	var sum = 0.0;
+	for (var sample = 0u; sample < samples; sample++) {
+		sum += input[sample * meta_sample_size() + idx];
+	}
+	output[idx] = sum / f32(samples);
+}
+";
+
+    fn scaled_sub() -> Arc<CustomOperation>{
+        let layout = CustomLayout{params: true, metadata: true, ..Default::default()};
+
+        Arc::new(CustomOperation::new("scaled_sub", SCALED_SUB, layout, |shapes, params| {
+            if params.len() != 1{
+                return None;
+            }
+            get_broadcast_shape(&shapes[0], &shapes[1])
+        }).unwrap())
+    }
+
+    #[test]
+    fn layout_validation(){
+        let output_shape = |shapes: &[Vec<u32>], _: &[f32]| Some(shapes[0].clone());
+
+        // source declares params, layout does not
+        let layout = CustomLayout{metadata: true, ..Default::default()};
+        assert!(matches!(CustomOperation::new("op", SCALED_SUB, layout, output_shape), Err(GpuError::InvalidKernel(_))));
+
+        // layout uses shapes, source does not declare them
+        let layout = CustomLayout{shapes: true, params: true, metadata: true, ..Default::default()};
+        assert!(matches!(CustomOperation::new("op", SCALED_SUB, layout, output_shape), Err(GpuError::InvalidKernel(_))));
+
+        let source = SAMPLE_MEAN.replace("@binding(3)", "@binding(4)");
+        assert!(matches!(CustomOperation::new("op", &source, CustomLayout::default(), output_shape), Err(GpuError::InvalidKernel(_))));
+
+        let layout = CustomLayout{metadata: true, ..Default::default()};
+        let operation = CustomOperation::new("op", SAMPLE_MEAN, layout, output_shape).unwrap();
+        assert!(operation.get_source().starts_with(PRELUDE_SRC));
+        assert_eq!(operation.output_shape(&[vec!{2, 3}], &[]), Some(vec!{2, 3}));
+
+        // operations are equal by name, layout and source, not by output shape function
+        let same = CustomOperation::new("op", SAMPLE_MEAN, layout, |_, _| None).unwrap();
+        assert_eq!(operation, same);
+        let renamed = CustomOperation::new("other", SAMPLE_MEAN, layout, output_shape).unwrap();
+        assert_ne!(operation, renamed);
+    }
+
+    #[tokio::test]
+    async fn no_cpu_backend(){
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2]), Tensor::fill(1.0, &[2])}, vec!{1.0}, &[])).unwrap();
+
+        assert!(matches!(runner.custom(&scaled_sub()).await, Err(GpuError::InvalidKernel(_))));
+    }
+
+    #[tokio::test]
+    async fn chunked_samples(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let a: Vec<Tensor<f32>> = (0..7).map(|_| Tensor::rand(1.0, &[4, 9])).collect();
+        let b: Vec<Tensor<f32>> = (0..7).map(|_| Tensor::rand(1.0, &[1, 9])).collect();
+
+        let sample_bytes = (a[0].count_data() + b[0].count_data()) * size_of::<f32>();
+        let mut runner = GpuRunner::init(3 * sample_bytes as u64, MemoryMetric::B);
+        runner.set_backend(Backend::Gpu);
+        for (a, b) in a.iter().zip(b.iter()){
+            runner.append(Sample::from_data(vec!{a.clone(), b.clone()}, vec!{0.5}, &[])).unwrap();
+        }
+
+        let operation = scaled_sub();
+        let output = runner.custom(&operation).await.unwrap();
+
+        let expected: Vec<Tensor<f32>> = a.iter().zip(b.iter()).map(|(a, b)| a.tens_broadcast_sub(b).unwrap().mul(0.5)).collect();
+        assert_all_relative_close(&output, &expected, 1e-4);
+        assert!(runner.get_context().await.unwrap().pipelines.contains(&GpuOperations::Custom(operation)));
+
+        // output shape function rejects samples without scale
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{a[0].clone(), b[0].clone()}, vec!{}, &[])).unwrap();
+        assert!(matches!(runner.custom(&scaled_sub()).await, Err(GpuError::InvalidShapes(_))));
+    }
+
+    #[tokio::test]
+    async fn chunked_single_output(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let layout = CustomLayout{metadata: true, single_output: true, ..Default::default()};
+        let sample_mean = Arc::new(CustomOperation::new("sample_mean", SAMPLE_MEAN, layout, |shapes, _| Some(shapes[0].clone())).unwrap());
+
+        let samples: Vec<Tensor<f32>> = (0..5).map(|_| Tensor::rand(1.0, &[3, 70])).collect();
+
+        // chunks of 2, 2 and 1 samples
+        let mut runner = GpuRunner::init(2 * samples[0].count_data() as u64 * size_of::<f32>() as u64, MemoryMetric::B);
+        runner.set_backend(Backend::Gpu);
+        for sample in &samples{
+            runner.append(Sample::from_data(vec!{sample.clone()}, vec!{}, &[])).unwrap();
+        }
+
+        let output = runner.custom(&sample_mean).await.unwrap();
+
+        let mut expected = samples[0].clone();
+        for sample in &samples[1..]{
+            expected = expected.tens_add(sample).unwrap();
+        }
+        assert_all_relative_close(&output, &[expected.div(5.0)], 1e-4);
+    }
+
+    #[tokio::test]
+    async fn resident_samples(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        let context = runner.get_context().await.unwrap();
+
+        let a: Tensor<f32> = Tensor::rand(1.0, &[5, 5]);
+        let b: Tensor<f32> = Tensor::rand(1.0, &[5, 1]);
+        let inputs = vec!{GpuTensor::from_tensor(&a, context.clone()).unwrap(), GpuTensor::from_tensor(&b, context.clone()).unwrap()};
+        runner.append_gpu(GpuSample::from_data(inputs, vec!{2.0}, &[])).unwrap();
+
+        let output = runner.custom_gpu(&scaled_sub()).await.unwrap();
+
+        assert_eq!(output.len(), 1);
+        assert_all_relative_close(&[output[0].to_cpu().await.unwrap()], &[a.tens_broadcast_sub(&b).unwrap().mul(2.0)], 1e-4);
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod double{
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_all_close;
+
+    fn rand_f64(shape: &[u32]) -> Tensor<f64>{
+        Tensor::<f32>::rand(1.0, shape).to_f64().mul(std::f64::consts::PI)
+    }
+
+    #[test]
+    fn f64_encoding(){
+        assert_eq!(GpuPrecision::F64.size(), 8);
+        assert_eq!(GpuPrecision::F64.buffer_size(3), 24);
+
+        let encoded = GpuPrecision::F64.encode_f64(&[0.1, -1e300]);
+        let words: Vec<f32> = encoded.chunks(4).map(|word| f32::from_le_bytes(word.try_into().unwrap())).collect();
+        assert_eq!(GpuPrecision::F64.decode_f64(words.clone()), vec!{0.1, -1e300});
+        assert_eq!(GpuPrecision::F64.decode(words)[0], 0.1f32);
+
+        // f32 values are widened, f64 values are rounded for f32 buffers
+        assert_eq!(GpuPrecision::F64.encode(&[0.5]).as_ref(), 0.5f64.to_le_bytes().as_slice());
+        assert_eq!(GpuPrecision::F32.encode_f64(&[0.5]).as_ref(), 0.5f32.to_le_bytes().as_slice());
+
+        assert!(GpuOperations::Matmul.supports_precision(GpuPrecision::F64));
+        assert!(GpuOperations::BroadcastDiv.supports_precision(GpuPrecision::F64));
+        assert!(!GpuOperations::Sigmoid.supports_precision(GpuPrecision::F64));
+    }
+
+    #[test]
+    fn f64_data(){
+        let a: Tensor<f64> = Tensor::fill(0.1, &[2, 2]);
+
+        let mut data = GpuData::new();
+        data.append_f64(&[&a, &a], &[], &[2, 2]).unwrap();
+        data.append_f64(&[&a, &a], &[], &[2, 2]).unwrap();
+        assert!(data.append(Sample::from_data(vec!{Tensor::fill(0.1, &[2, 2]); 2}, vec!{}, &[2, 2])).is_err());
+        assert!(data.append_tensors(&[&Tensor::fill(0.1, &[2, 2]); 2], &[], &[2, 2]).is_err());
+
+        assert_eq!(data.samples_count, 2);
+        assert_eq!(data.flat_inputs.len(), data.flat_inputs_f64.len());
+        assert_eq!(data.flat_inputs, vec!{0.1f32; 16});
+
+        // chunks of f64 buffers hold exact values
+        data.precision = GpuPrecision::F64;
+        data.prepare_chunking_auto(8 * size_of::<f64>() as u64, usize::MAX).unwrap();
+        assert_eq!(data.chunks, 2);
+        assert_eq!(data.chunk_bytes(1, GpuPrecision::F64).as_ref(), GpuPrecision::F64.encode_f64(&[0.1; 8]).as_ref());
+        assert_eq!(data.chunk_bytes(1, GpuPrecision::F32).len(), 32);
+
+        // f32 samples can not follow f64 samples, and f64 inputs can not be shared with f32 ones
+        let mut data = GpuData::new();
+        data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2])}, vec!{}, &[])).unwrap();
+        assert!(matches!(data.append_f64(&[&Tensor::fill(1.0, &[2])], &[], &[]), Err(GpuError::InvalidConfig(_))));
+
+        let mut data = GpuData::new();
+        data.share_f64(0, &a).unwrap();
+        assert!(data.share(1, &Tensor::fill(1.0, &[2, 2])).is_err());
+        data.share_f64(1, &a).unwrap();
+        assert!(matches!(data.share_f64(1, &a), Err(GpuError::InvalidConfig(_))));
+        assert_eq!(data.shared_bytes(GpuPrecision::F64).len(), 8 * 8);
+    }
+
+    #[tokio::test]
+    async fn cpu_backend(){
+        let a: Tensor<f64> = rand_f64(&[3, 4]);
+        let b: Tensor<f64> = rand_f64(&[4, 2]);
+
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append_f64(&[&a, &b], &[], &[]).unwrap();
+
+        // f64 operations of cpu backend compute in f64, f32 operations round outputs
+        assert_all_close(&runner.matmul_f64().await.unwrap(), &[a.matrix_mul(&b).unwrap()], 1e-12);
+        assert_eq!(runner.get_precision(), GpuPrecision::F32);
+
+        let output = runner.matmul().await.unwrap();
+        assert_all_close(&[output[0].to_f64()], &[a.matrix_mul(&b).unwrap()], 1e-4);
+
+        // shared weights and converted f32 samples
+        let weights: Tensor<f64> = rand_f64(&[2, 3]);
+        let bias: Tensor<f64> = rand_f64(&[2, 1]);
+        let input = Tensor::<f32>::rand(1.0, &[3, 4]);
+
+        runner.clear();
+        runner.share_f64(0, &weights).unwrap();
+        runner.share_f64(2, &bias).unwrap();
+        runner.append_f64(&[&input.to_f64()], &[], &[]).unwrap();
+        let expected = weights.matrix_mul(&input.to_f64()).unwrap().tens_broadcast_add(&bias).unwrap().relu();
+        assert_all_close(&runner.forward_relu_f64().await.unwrap(), &[expected], 1e-12);
+
+        runner.clear();
+        runner.append(Sample::from_data(vec!{a.to_f32(), b.to_f32()}, vec!{}, &[])).unwrap();
+        assert_all_close(&runner.matmul_f64().await.unwrap(), &[a.to_f32().to_f64().matrix_mul(&b.to_f32().to_f64()).unwrap()], 1e-12);
+
+        // operations without f64 variant return error
+        runner.set_precision(GpuPrecision::F64);
+        assert!(matches!(runner.sigmoid().await, Err(GpuError::InvalidConfig(_))));
+    }
+
+    #[tokio::test]
+    async fn f64_runner(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let weights: Tensor<f64> = rand_f64(&[6, 5]);
+        let bias: Tensor<f64> = rand_f64(&[6, 1]);
+        let inputs: Vec<Tensor<f64>> = (0..9).map(|_| rand_f64(&[5, 3])).collect();
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.set_backend(Backend::Gpu);
+        let context = runner.get_context().await.unwrap();
+        runner.append_f64(&[&inputs[0], &inputs[1]], &[], &[]).unwrap();
+
+        if !context.supports_f64(){
+            // devices without f64 shaders return error instead of computing in f32
+            assert!(matches!(runner.tens_add_f64().await, Err(GpuError::InvalidConfig(_))));
+            runner.set_precision(GpuPrecision::F64);
+            assert!(matches!(runner.tens_mul().await, Err(GpuError::InvalidConfig(_))));
+
+            runner.set_precision(GpuPrecision::F32);
+            let output = runner.tens_add().await.unwrap();
+            assert_all_close(&[output[0].to_f64()], &[inputs[0].tens_add(&inputs[1]).unwrap()], 1e-5);
+            return;
+        }
+
+        assert_all_close(&runner.tens_mul_f64().await.unwrap(), &[inputs[0].tens_mul(&inputs[1]).unwrap()], 1e-12);
+
+        // the first buffer fits all samples, the second splits them into chunks
+        for buffer_size in [1 << 20, 200 * size_of::<f64>() as u64]{
+            let mut runner = GpuRunner::init(buffer_size, MemoryMetric::B);
+            runner.set_backend(Backend::Gpu);
+            for input in &inputs{
+                runner.append_f64(&[&weights, input, &bias], &[], &[]).unwrap();
+            }
+            let expected: Vec<Tensor<f64>> = inputs.iter().map(|input| weights.matrix_mul(input).unwrap().tens_broadcast_add(&bias).unwrap().relu()).collect();
+            assert_all_close(&runner.forward_relu_f64().await.unwrap(), &expected, 1e-12);
+
+            // shared weights are exact too
+            runner.clear();
+            runner.share_f64(0, &weights).unwrap();
+            for input in &inputs{
+                runner.append_f64(&[input], &[], &[]).unwrap();
+            }
+            let expected: Vec<Tensor<f64>> = inputs.iter().map(|input| weights.matrix_mul(input).unwrap()).collect();
+            assert_all_close(&runner.matmul_f64().await.unwrap(), &expected, 1e-12);
+        }
+
+        // operations without f64 shader return error
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.set_precision(GpuPrecision::F64);
+        runner.append_f64(&[&inputs[0]], &[], &[]).unwrap();
+        assert!(matches!(runner.sigmoid().await, Err(GpuError::InvalidConfig(_))));
+    }
+
+    #[tokio::test]
+    async fn gpu_samples(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        let context = runner.get_context().await.unwrap();
+        let tensor = GpuTensor::from_tensor(&Tensor::fill(1.0, &[2, 2]), context).unwrap();
+        runner.append_gpu(GpuSample::from_data(vec!{tensor.clone(), tensor}, vec!{}, &[])).unwrap();
+
+        // inputs on gpu are f32
+        assert!(matches!(runner.tens_add_f64().await, Err(GpuError::InvalidConfig(_))));
+        assert_eq!(runner.tens_add().await.unwrap()[0].get_data(), &vec!{2.0; 4});
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod elementwise{
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_relative_close;
+
+    #[test]
+    fn expressions(){
+        let x = Expr::input(0);
+        let y = Expr::input(1);
+
+        assert_eq!((2.0 * x.clone() - y.clone() / 4.0).to_wgsl(&["a", "b"]), "((2.0 * a) - (b / 4.0))");
+        assert_eq!((-x.clone()).sigmoid().to_wgsl(&["a"]), "(1.0 / (1.0 + exp((-(-a)))))");
+        assert_eq!(Expr::call("clamp", vec!{x.clone(), Expr::constant(0.0), Expr::constant(1.0)}).to_wgsl(&["a"]), "clamp(a, 0.0, 1.0)");
+        assert_eq!(x.clone().max(Expr::constant(f32::NEG_INFINITY)).to_wgsl(&["a"]), format!("max(a, bitcast<f32>({}u))", f32::NEG_INFINITY.to_bits()));
+
+        assert_eq!((x.clone() + y.clone()).max_input(), Some(1));
+        assert_eq!(Expr::constant(1.0).max_input(), None);
+
+        let kernel = ElementwiseKernel::from_expr(3, &(x.clone() * y)).unwrap();
+        assert_eq!(kernel.get_inputs(), &vec!{"x0".to_string(), "x1".to_string(), "x2".to_string()});
+        assert_eq!(kernel.wgsl_function("f"), "fn f(x0: f32, x1: f32, x2: f32) -> f32 {\n\treturn (x0 * x1);\n}\n");
+    }
+
+    #[test]
+    fn invalid_kernels(){
+        assert!(matches!(ElementwiseKernel::from_wgsl(&[], "1.0"), Err(GpuError::InvalidKernel(_))));
+        assert!(matches!(ElementwiseKernel::from_wgsl(&["1x"], "1.0"), Err(GpuError::InvalidKernel(_))));
+        assert!(matches!(ElementwiseKernel::from_wgsl(&["x", "x"], "x"), Err(GpuError::InvalidKernel(_))));
+        assert!(matches!(ElementwiseKernel::from_wgsl(&["__x"], "__x"), Err(GpuError::InvalidKernel(_))));
+        assert!(matches!(ElementwiseKernel::from_wgsl(&["x"], "  "), Err(GpuError::InvalidKernel(_))));
+        assert!(matches!(ElementwiseKernel::from_expr(1, &Expr::input(1)), Err(GpuError::InvalidKernel(_))));
+    }
+
+    #[tokio::test]
+    async fn wgsl_kernel(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let context = GpuContext::init(1, &MemoryMetric::GB).await.unwrap();
+
+        let kernel = ElementwiseKernel::from_wgsl(&["x", "bias"], "select(x + bias, 0.0, x < 0.0) * 2.0").unwrap();
+        let x: Tensor<f32> = Tensor::rand(1.0, &[2, 30, 7]);
+        let bias: Tensor<f32> = Tensor::rand(1.0, &[30, 1]);
+
+        let output = kernel.run(context.clone(), &[&x, &bias]).await.unwrap();
+
+        let bias_broadcast = Tensor::from_data(bias.get_data(), &[1, 30, 1]).unwrap();
+        let expected: Vec<f32> = x.tens_broadcast_add(&bias_broadcast).unwrap().get_data().iter().zip(x.get_data())
+            .map(|(sum, x)| if *x < 0.0 {0.0} else {sum * 2.0}).collect();
+        assert_relative_close(&output, &Tensor::from_data(&expected, &[2, 30, 7]).unwrap(), 1e-4);
+
+        // kernel is compiled once
+        let generated = context.pipelines.generated_len();
+        kernel.run(context.clone(), &[&Tensor::fill(1.0, &[5]), &Tensor::fill(2.0, &[1])]).await.unwrap();
+        assert_eq!(context.pipelines.generated_len(), generated);
+
+        assert!(matches!(kernel.run(context.clone(), &[&x]).await, Err(GpuError::InvalidShapes(_))));
+        assert!(matches!(kernel.run(context.clone(), &[&x, &Tensor::fill(1.0, &[3])]).await, Err(GpuError::InvalidShapes(_))));
+
+        let invalid = ElementwiseKernel::from_wgsl(&["x"], "x +* 1.0").unwrap();
+        assert!(matches!(invalid.run(context, &[&x]).await, Err(GpuError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn fused_expressions(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let context = GpuContext::init(1, &MemoryMetric::GB).await.unwrap();
+
+        let x = Expr::input(0);
+        let y = Expr::input(1);
+        let distance = ElementwiseKernel::from_expr(2, &(x.clone() - y).abs().sqrt()).unwrap();
+        let square = ElementwiseKernel::from_expr(1, &(x.clone() * x)).unwrap();
+
+        let a_data: Tensor<f32> = Tensor::rand(1.0, &[8, 9]);
+        let b_data: Tensor<f32> = Tensor::rand(1.0, &[9]);
+
+        let mut graph = GpuGraph::new(context);
+        let a = graph.input(&a_data);
+        let b = graph.input(&b_data);
+
+        let c = graph.elementwise(&distance, &[a, b]).unwrap();
+        let c = graph.elementwise(&square, &[c]).unwrap();
+        let c = graph.relu(c).unwrap();
+        let c = graph.elementwise(&square, &[c]).unwrap();
+        let sum = graph.sum(c).unwrap();
+
+        assert_eq!(graph.dispatch_count(&[c]).unwrap(), 1);
+        assert_eq!(graph.get_shape(c), Some(&vec!{8, 9}));
+
+        let output = graph.run(&[c, sum]).await.unwrap();
+
+        let expected: Vec<f32> = a_data.get_data().iter().enumerate()
+            .map(|(i, a)| (a - b_data.get_data()[i % 9]).powi(2)).collect();
+        let expected = Tensor::from_data(&expected, &[8, 9]).unwrap();
+        assert_relative_close(&output[0], &expected, 1e-4);
+        assert_relative_close(&output[1], &Tensor::from_data(&[expected.sum()], &[1]).unwrap(), 1e-4);
+    }
+}
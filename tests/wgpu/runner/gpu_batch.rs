@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod gpu_batch{
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_all_close;
+
+    #[test]
+    fn append_tensors(){
+        let samples: Vec<(Tensor<f32>, Tensor<f32>)> = (0..3).map(|_| (Tensor::rand(1.0, &[2, 3]), Tensor::rand(1.0, &[3, 4]))).collect();
+
+        let mut from_samples = GpuData::new();
+        let mut from_tensors = GpuData::with_capacity(3 * 18);
+        for (a, b) in &samples{
+            from_samples.append(Sample::from_data(vec!{a.clone(), b.clone()}, vec!{1.0}, &[2, 4])).unwrap();
+            from_tensors.append_tensors(&[a, b], &[1.0], &[2, 4]).unwrap();
+        }
+
+        assert_eq!(from_tensors.flat_inputs, from_samples.flat_inputs);
+        assert_eq!(from_tensors.flat_inputs.capacity(), 3 * 18);
+        assert_eq!(from_tensors.metadata(), from_samples.metadata());
+        assert_eq!(from_tensors.output_len, from_samples.output_len);
+
+        assert!(from_tensors.append_tensors(&[&samples[0].0], &[1.0], &[2, 4]).is_err());
+        assert_eq!(from_tensors.samples_count, 3);
+    }
+
+    #[tokio::test]
+    async fn runner_append_tensors(){
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+        let bias: Tensor<f32> = Tensor::rand(1.0, &[4, 1]);
+        let inputs: Vec<Tensor<f32>> = (0..5).map(|_| Tensor::rand(1.0, &[3, 2])).collect();
+
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        for input in &inputs{
+            runner.append_tensors(&[&weights, input, &bias], &[], &[]).unwrap();
+        }
+
+        let output = runner.forward_no_activ().await.unwrap();
+        let expected: Vec<Tensor<f32>> = inputs.iter().map(|input| weights.matrix_mul(input).unwrap().tens_broadcast_add(&bias).unwrap()).collect();
+        assert_all_close(&output, &expected, 1e-4);
+    }
+
+    #[tokio::test]
+    async fn writer_errors(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        let context = runner.get_context().await.unwrap();
+
+        let mut writer = GpuBatchWriter::new(context.clone(), &[vec!{2, 2}, vec!{2}], 1).unwrap();
+        assert!(matches!(writer.share(1, &Tensor::fill(1.0, &[3])), Err(GpuError::InvalidShapes(_))));
+        assert!(matches!(writer.share(2, &Tensor::fill(1.0, &[2])), Err(GpuError::InvalidShapes(_))));
+        assert!(matches!(writer.write(&[&Tensor::fill(1.0, &[2, 2])]), Err(GpuError::InvalidShapes(_))));
+        assert!(matches!(writer.write(&[&Tensor::fill(1.0, &[4]), &Tensor::fill(1.0, &[2])]), Err(GpuError::InvalidShapes(_))));
+        assert!(writer.is_empty());
+
+        writer.write(&[&Tensor::fill(1.0, &[2, 2]), &Tensor::fill(1.0, &[2])]).unwrap();
+        assert!(matches!(writer.write(&[&Tensor::fill(1.0, &[2, 2]), &Tensor::fill(1.0, &[2])]), Err(GpuError::InvalidShapes(_))));
+        assert!(matches!(writer.share(1, &Tensor::fill(1.0, &[2])), Err(GpuError::InvalidConfig(_))));
+        assert_eq!(writer.len(), 1);
+
+        let too_big = context.max_buffer_size() as usize;
+        assert!(matches!(GpuBatchWriter::new(context, &[vec!{2, 2}], too_big), Err(GpuError::BufferTooBig(_))));
+    }
+
+    #[tokio::test]
+    async fn shared_weights(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[6, 5]);
+        let bias: Tensor<f32> = Tensor::rand(1.0, &[6, 1]);
+        let inputs: Vec<Tensor<f32>> = (0..9).map(|_| Tensor::rand(1.0, &[5, 3])).collect();
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        let context = runner.get_context().await.unwrap();
+
+        let mut writer = GpuBatchWriter::new(context, &[vec!{6, 5}, vec!{5, 3}, vec!{6, 1}], inputs.len()).unwrap();
+        writer.share(0, &weights).unwrap();
+        writer.share(2, &bias).unwrap();
+        for input in &inputs{
+            writer.write(&[input]).unwrap();
+        }
+        let batch = writer.finish().unwrap();
+
+        assert_eq!(batch.len(), 9);
+        assert_eq!(batch.sample_len(), 30 + 15 + 6);
+
+        runner.append_batch(batch.clone(), vec!{}).unwrap();
+        let output = runner.forward_relu().await.unwrap();
+        let expected: Vec<Tensor<f32>> = inputs.iter().map(|input| weights.matrix_mul(input).unwrap().tens_broadcast_add(&bias).unwrap().relu()).collect();
+        assert_all_close(&output, &expected, 1e-4);
+
+        // batch is not mixed with other samples
+        assert!(matches!(runner.append(Sample::from_data(vec!{weights.clone(), inputs[0].clone(), bias.clone()}, vec!{}, &[])), Err(GpuError::InvalidConfig(_))));
+        assert!(matches!(runner.append_batch(batch.clone(), vec!{}), Err(GpuError::InvalidConfig(_))));
+        assert_eq!(runner.gpu_data.samples_count, 9);
+
+        // batch can be used again after clear
+        runner.clear();
+        runner.append_batch(batch, vec!{}).unwrap();
+        let output = runner.forward_no_activ().await.unwrap();
+        let expected: Vec<Tensor<f32>> = inputs.iter().map(|input| weights.matrix_mul(input).unwrap().tens_broadcast_add(&bias).unwrap()).collect();
+        assert_all_close(&output, &expected, 1e-4);
+    }
+
+    #[tokio::test]
+    async fn params_and_profile(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let inputs: Vec<Tensor<f32>> = (0..4).map(|_| Tensor::rand(1.0, &[3, 3])).collect();
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.enable_profiling();
+        let context = runner.get_context().await.unwrap();
+
+        let mut writer = GpuBatchWriter::new(context, &[vec!{3, 3}], 4).unwrap();
+        for input in &inputs{
+            writer.write(&[input]).unwrap();
+        }
+
+        runner.append_batch(writer.finish().unwrap(), vec!{2.0}).unwrap();
+        let output = runner.mul().await.unwrap();
+        let expected: Vec<Tensor<f32>> = inputs.iter().map(|input| input.mul(2.0)).collect();
+        assert_all_close(&output, &expected, 1e-4);
+
+        assert_eq!(runner.last_profile().unwrap().chunks[0].samples, 4);
+    }
+
+    #[tokio::test]
+    async fn partly_filled_batch(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let bias: Tensor<f32> = Tensor::rand(1.0, &[3, 1]);
+        let samples: Vec<(Tensor<f32>, Tensor<f32>)> = (0..2).map(|_| (Tensor::rand(1.0, &[3, 2]), Tensor::rand(1.0, &[3, 2]))).collect();
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        let context = runner.get_context().await.unwrap();
+
+        // unwritten samples of capacity are not counted by operations averaging over samples
+        let mut writer = GpuBatchWriter::new(context, &[vec!{3, 1}, vec!{3, 2}, vec!{3, 2}], 4).unwrap();
+        writer.share(0, &bias).unwrap();
+        for (grad_output, linear_cache) in &samples{
+            writer.write(&[grad_output, linear_cache]).unwrap();
+        }
+
+        runner.append_batch(writer.finish().unwrap(), vec!{0.1}).unwrap();
+        let output = runner.backward_bias().await.unwrap();
+
+        let mut cpu_runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        for (grad_output, linear_cache) in &samples{
+            cpu_runner.append(Sample::from_data(vec!{bias.clone(), grad_output.clone(), linear_cache.clone()}, vec!{0.1}, &[])).unwrap();
+        }
+        let expected = cpu_runner.backward_bias().await.unwrap();
+
+        assert_all_close(&output, &expected, 1e-4);
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod gpu_config{
+    use flashlight_tensor::prelude::*;
+
+    #[test]
+    fn default_config(){
+        let config = GpuConfig::default();
+
+        assert_eq!(config.backends, wgpu::Backends::PRIMARY);
+        assert!(!config.force_fallback_adapter);
+        assert_eq!(config.adapter_name, None);
+        assert_eq!(config.tile_size, DEFAULT_TILE_SIZE);
+
+        let runner = GpuRunner::init(256, MemoryMetric::MB);
+        assert_eq!(runner.get_config(), &config);
+    }
+
+    #[test]
+    fn fallback_config(){
+        let config = GpuConfig::fallback();
+
+        assert_eq!(config.backends, wgpu::Backends::all());
+        assert!(config.force_fallback_adapter);
+
+        let runner = GpuRunner::with_config(config.clone(), 256, MemoryMetric::MB);
+        assert_eq!(runner.get_config(), &config);
+    }
+
+    #[tokio::test]
+    async fn unknown_adapter_name(){
+        let config = GpuConfig::named("adapter that does not exist");
+
+        assert!(matches!(GpuContext::init_with_config(256, &MemoryMetric::MB, &config).await, Err(GpuError::NoAdapter(_))));
+    }
+
+    #[tokio::test]
+    async fn invalid_tile_size(){
+        let config = GpuConfig{
+            tile_size: 12,
+            ..Default::default()
+        };
+
+        assert!(matches!(config.validate(), Err(GpuError::InvalidConfig(_))));
+        assert!(matches!(GpuContext::init_with_config(256, &MemoryMetric::MB, &config).await, Err(GpuError::InvalidConfig(_))));
+    }
+
+    #[tokio::test]
+    async fn named_adapter(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let adapters = enumerate_adapters(wgpu::Backends::PRIMARY);
+        if adapters.is_empty(){
+            return;
+        }
+
+        let mut runner = GpuRunner::with_config(GpuConfig::named(&adapters[0].info.name.to_uppercase()), 256, MemoryMetric::MB);
+        runner.set_backend(Backend::Gpu);
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{2.0}, &[])).unwrap();
+
+        let output: Vec<Tensor<f32>> = runner.mul().await.unwrap();
+
+        assert_eq!(output[0].get_data(), &vec!{2.0; 4});
+    }
+
+    #[tokio::test]
+    async fn fallback_adapter(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let has_fallback = enumerate_adapters(wgpu::Backends::all()).iter()
+            .any(|adapter| adapter.info.device_type == wgpu::DeviceType::Cpu);
+        if !has_fallback{
+            return;
+        }
+
+        let context = GpuContext::init_fallback(64, &MemoryMetric::MB).await.unwrap();
+
+        let mut runner = GpuRunner::from_context(context, 64, MemoryMetric::MB);
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[])).unwrap();
+
+        let output: Vec<Tensor<f32>> = runner.add().await.unwrap();
+
+        assert_eq!(output[0].get_data(), &vec!{2.0; 4});
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod gpu_context{
+    use flashlight_tensor::prelude::*;
+
+    #[tokio::test]
+    async fn shared_between_runners(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let context = GpuContext::init(256, &MemoryMetric::MB).await.unwrap();
+
+        let mut first_runner = GpuRunner::from_context(context.clone(), 256, MemoryMetric::MB);
+        let mut second_runner = GpuRunner::from_context(context.clone(), 256, MemoryMetric::MB);
+
+        let tensor = Tensor::fill(1.0, &[2, 3]).to_gpu(&mut first_runner).await.unwrap();
+
+        first_runner.append_gpu(GpuSample::from_data(vec!{tensor}, vec!{2.0}, &[])).unwrap();
+        let added: Vec<GpuTensor> = first_runner.add_gpu().await.unwrap();
+
+        second_runner.append_gpu(GpuSample::from_data(vec!{added[0].clone()}, vec!{3.0}, &[])).unwrap();
+        let output: Vec<Tensor<f32>> = second_runner.mul().await.unwrap();
+
+        assert_eq!(output[0].get_data(), &vec!{9.0; 6});
+        assert!(std::sync::Arc::ptr_eq(&first_runner.get_context().await.unwrap(), &second_runner.get_context().await.unwrap()));
+    }
+
+    #[tokio::test]
+    async fn reject_other_context(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut first_runner = GpuRunner::init(256, MemoryMetric::MB);
+        let mut second_runner = GpuRunner::init(256, MemoryMetric::MB);
+
+        let own_tensor = Tensor::fill(1.0, &[2]).to_gpu(&mut second_runner).await.unwrap();
+        let other_tensor = Tensor::fill(1.0, &[2]).to_gpu(&mut first_runner).await.unwrap();
+
+        second_runner.append_gpu(GpuSample::from_data(vec!{own_tensor}, vec!{1.0}, &[])).unwrap();
+        assert!(matches!(second_runner.append_gpu(GpuSample::from_data(vec!{other_tensor}, vec!{1.0}, &[])), Err(GpuError::InvalidConfig(_))));
+
+        assert_eq!(second_runner.gpu_data.samples_count, 1);
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod gpu_error{
+    use flashlight_tensor::prelude::*;
+
+    #[tokio::test]
+    async fn no_data(){
+        let mut runner = GpuRunner::init(256, MemoryMetric::MB);
+
+        assert_eq!(runner.add().await.err(), Some(GpuError::NoData));
+    }
+
+    #[tokio::test]
+    async fn buffer_too_big(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let context = GpuContext::init(1, &MemoryMetric::KB).await.unwrap();
+
+        let mut runner = GpuRunner::from_context(context, 1, MemoryMetric::KB);
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[32, 32])}, vec!{1.0}, &[])).unwrap();
+
+        assert!(matches!(runner.add().await, Err(GpuError::BufferTooBig(_))));
+    }
+
+    #[tokio::test]
+    async fn invalid_shapes(){
+        let mut runner = GpuRunner::init(256, MemoryMetric::MB);
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[4])}, vec!{}, &[])).unwrap();
+
+        assert!(matches!(runner.matmul().await, Err(GpuError::InvalidShapes(_))));
+    }
+
+    #[tokio::test]
+    async fn no_shader(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut data = GpuData::new();
+        data.disable_shapes();
+        data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[2, 2])).unwrap();
+
+        let mut buffers = GpuBuffers::init(256, MemoryMetric::MB, &mut data, 0).await.unwrap();
+
+        assert_eq!(buffers.prepare().err(), Some(GpuError::NoShader));
+        assert_eq!(buffers.run().await.err(), Some(GpuError::NoShader));
+    }
+
+    #[tokio::test]
+    async fn validation(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut runner = GpuRunner::init(256, MemoryMetric::MB);
+
+        let tensor = Tensor::fill(1.0, &[2, 2]).to_gpu(&mut runner).await.unwrap();
+        let broken = GpuTensor::from_buffer(tensor.get_buffer().clone(), &[4, 4], tensor.get_context().clone());
+
+        runner.append_gpu(GpuSample::from_data(vec!{broken}, vec!{1.0}, &[])).unwrap();
+
+        assert!(matches!(runner.add().await, Err(GpuError::Validation(_))));
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod gpu_graph{
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_relative_close;
+
+    async fn context() -> std::sync::Arc<GpuContext>{
+        GpuContext::init(1, &MemoryMetric::GB).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn linear_relu_sum(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let input_data: Tensor<f32> = Tensor::rand(1.0, &[37, 20]);
+        let weights_data: Tensor<f32> = Tensor::rand(1.0, &[25, 37]);
+        let bias_data: Tensor<f32> = Tensor::rand(1.0, &[25, 1]);
+
+        let mut graph = GpuGraph::new(context().await);
+        let input = graph.input(&input_data);
+        let weights = graph.input(&weights_data);
+        let bias = graph.input(&bias_data);
+
+        let linear = graph.matmul(weights, input).unwrap();
+        let linear = graph.add(linear, bias).unwrap();
+        let activated = graph.relu(linear).unwrap();
+        let sum = graph.sum(activated).unwrap();
+
+        assert_eq!(graph.dispatch_count(&[sum]).unwrap(), 3);
+        // requested intermediate output is not fused, so it can be read back
+        assert_eq!(graph.dispatch_count(&[activated, sum]).unwrap(), 3);
+        assert_eq!(graph.dispatch_count(&[linear, sum]).unwrap(), 4);
+
+        let output = graph.run(&[activated, sum]).await.unwrap();
+
+        let cpu_activated = weights_data.matrix_mul(&input_data).unwrap().tens_broadcast_add(&bias_data).unwrap().relu();
+        assert_relative_close(&output[0], &cpu_activated, 1e-4);
+        assert_relative_close(&output[1], &Tensor::from_data(&[cpu_activated.sum()], &[1]).unwrap(), 1e-4);
+    }
+
+    #[tokio::test]
+    async fn fused_chain(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let a_data: Tensor<f32> = Tensor::rand(1.0, &[3, 4, 5]);
+        let b_data: Tensor<f32> = Tensor::rand(1.0, &[4, 1]);
+
+        let mut graph = GpuGraph::new(context().await);
+        let a = graph.input(&a_data);
+        let b = graph.input(&b_data);
+
+        let x = graph.mul(a, b).unwrap();
+        let x = graph.sigmoid(x).unwrap();
+        let x = graph.mul_scalar(x, 2.0).unwrap();
+        let x = graph.sub(x, a).unwrap();
+        let x = graph.tanh(x).unwrap();
+        let x = graph.add_scalar(x, 3.0).unwrap();
+        let x = graph.nlog(x).unwrap();
+        let x = graph.exp(x).unwrap();
+        let x = graph.div_scalar(x, 4.0).unwrap();
+
+        assert_eq!(graph.dispatch_count(&[x]).unwrap(), 1);
+
+        let output = graph.run(&[x]).await.unwrap();
+
+        let b_broadcast = Tensor::from_data(b_data.get_data(), &[1, 4, 1]).unwrap();
+        let expected: Vec<f32> = a_data.tens_broadcast_mul(&b_broadcast).unwrap().get_data().iter().zip(a_data.get_data()).map(|(x, a)| {
+            let x = 2.0 / (1.0 + (-x).exp()) - a;
+            ((x.tanh() + 3.0).log10()).exp() / 4.0
+        }).collect();
+
+        assert_relative_close(&output[0], &Tensor::from_data(&expected, &[3, 4, 5]).unwrap(), 1e-4);
+    }
+
+    #[tokio::test]
+    async fn axis_reduction_and_cache(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let context = context().await;
+        let data: Tensor<f32> = Tensor::rand(1.0, &[6, 90]);
+
+        let mut graph = GpuGraph::new(context.clone());
+        let input = graph.input(&data);
+        let x = graph.relu(input).unwrap();
+        let max = graph.reduce(x, GpuOperations::ReduceMax, Some(1)).unwrap();
+        let argmax = graph.reduce(x, GpuOperations::ReduceArgmax, Some(0)).unwrap();
+
+        let output = graph.run(&[max, argmax]).await.unwrap();
+
+        let relu = data.relu();
+        assert_relative_close(&output[0], &relu.max_axis(1).unwrap(), 1e-4);
+        assert_relative_close(&output[1], &relu.argmax_axis(0).unwrap(), 1e-4);
+
+        // the same chain on other shapes and values reuses generated pipeline
+        let generated = context.pipelines.generated_len();
+        let mut graph = GpuGraph::new(context.clone());
+        let input = graph.input(&Tensor::rand(1.0, &[7]));
+        let x = graph.relu(input).unwrap();
+        graph.run(&[x]).await.unwrap();
+
+        assert_eq!(context.pipelines.generated_len(), generated);
+    }
+
+    #[tokio::test]
+    async fn gpu_inputs_and_outputs(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let context = context().await;
+        let data: Tensor<f32> = Tensor::rand(1.0, &[4, 4]);
+
+        let mut graph = GpuGraph::new(context.clone());
+        let input = graph.input_gpu(&GpuTensor::from_tensor(&data, context.clone()).unwrap()).unwrap();
+        let doubled = graph.add(input, input).unwrap();
+
+        let output = graph.run_gpu(&[doubled]).unwrap();
+
+        assert_relative_close(&output[0].to_cpu().await.unwrap(), &data.mul(2.0), 1e-4);
+    }
+
+    #[tokio::test]
+    async fn invalid_graph(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut graph = GpuGraph::new(context().await);
+        let a = graph.input(&Tensor::fill(1.0, &[2, 3]));
+        let b = graph.input(&Tensor::fill(1.0, &[3, 2]));
+
+        assert!(matches!(graph.add(a, b), Err(GpuError::InvalidShapes(_))));
+        assert!(matches!(graph.matmul(a, a), Err(GpuError::InvalidShapes(_))));
+        assert!(matches!(graph.reduce(a, GpuOperations::ReduceSum, Some(2)), Err(GpuError::InvalidShapes(_))));
+        assert!(matches!(graph.reduce(a, GpuOperations::Add, None), Err(GpuError::InvalidShapes(_))));
+
+        let other = GpuGraph::new(graph.get_context().clone()).len();
+        assert_eq!(other, 0);
+    }
+}
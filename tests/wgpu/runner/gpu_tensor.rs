@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod gpu_tensor{
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_close;
+
+    #[tokio::test]
+    async fn roundtrip(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+
+        let tensor: Tensor<f32> = Tensor::rand(1.0, &[3, 5]);
+        let gpu_tensor = tensor.to_gpu(&mut runner).await.unwrap();
+
+        assert_eq!(gpu_tensor.count_data(), 15);
+        assert_close(&gpu_tensor.to_cpu().await.unwrap(), &tensor, 1e-3);
+    }
+
+    #[tokio::test]
+    async fn multiple_samples(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        let mut expected: Vec<Tensor<f32>> = Vec::new();
+
+        for _i in 0..4{
+            let a: Tensor<f32> = Tensor::rand(1.0, &[3, 4]);
+            let b: Tensor<f32> = Tensor::rand(1.0, &[4, 2]);
+
+            expected.push(a.matrix_mul(&b).unwrap());
+
+            let sample = GpuSample::from_data(vec!{a.to_gpu(&mut runner).await.unwrap(), b.to_gpu(&mut runner).await.unwrap()}, vec!{}, &[]);
+            runner.append_gpu(sample).unwrap();
+        }
+
+        let output: Vec<Tensor<f32>> = runner.matmul().await.unwrap();
+
+        assert_eq!(output.len(), 4);
+        for (gpu, cpu) in output.iter().zip(expected.iter()){
+            assert_close(gpu, cpu, 1e-3);
+        }
+    }
+
+    #[tokio::test]
+    async fn forward_backward_on_gpu(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+        let inputs: Tensor<f32> = Tensor::rand(1.0, &[3, 5]);
+        let biases: Tensor<f32> = Tensor::rand(1.0, &[4, 1]);
+        let grad_output: Tensor<f32> = Tensor::rand(1.0, &[4, 5]);
+
+        let mut forward_runner = GpuRunner::init(1, MemoryMetric::GB);
+        forward_runner.append(Sample::from_data(vec!{weights.clone(), inputs.clone(), biases.clone()}, vec!{}, &[])).unwrap();
+        let expected_output = forward_runner.forward_relu().await.unwrap();
+
+        let mut backward_runner = GpuRunner::init(1, MemoryMetric::GB);
+        backward_runner.append(Sample::from_data(vec!{expected_output[0].clone(), grad_output.clone()}, vec!{}, &[])).unwrap();
+        let expected_grad = backward_runner.backward_relu().await.unwrap();
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+
+        let gpu_weights = weights.to_gpu(&mut runner).await.unwrap();
+        let gpu_inputs = inputs.to_gpu(&mut runner).await.unwrap();
+        let gpu_biases = biases.to_gpu(&mut runner).await.unwrap();
+        let gpu_grad_output = grad_output.to_gpu(&mut runner).await.unwrap();
+
+        runner.append_gpu(GpuSample::from_data(vec!{gpu_weights, gpu_inputs, gpu_biases}, vec!{}, &[])).unwrap();
+        let output: Vec<GpuTensor> = runner.forward_relu_gpu().await.unwrap();
+        runner.clear();
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].get_shape(), &vec!{4, 5});
+
+        runner.append_gpu(GpuSample::from_data(vec!{output[0].clone(), gpu_grad_output}, vec!{}, &[])).unwrap();
+        let grad: Vec<GpuTensor> = runner.backward_relu_gpu().await.unwrap();
+        runner.clear();
+
+        assert_close(&output[0].to_cpu().await.unwrap(), &expected_output[0], 1e-3);
+        assert_close(&grad[0].to_cpu().await.unwrap(), &expected_grad[0], 1e-3);
+    }
+
+    #[tokio::test]
+    async fn outputs_only_of_gpu_samples(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        let tensor = Tensor::fill(1.0, &[2, 2]).to_gpu(&mut runner).await.unwrap();
+        runner.append_gpu(GpuSample::from_data(vec!{tensor.clone(), tensor}, vec!{}, &[])).unwrap();
+        assert_eq!(runner.tens_add_gpu().await.unwrap()[0].get_shape(), &vec!{2, 2});
+
+        // outputs of previous gpu operation are not returned for samples on cpu
+        runner.clear();
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[3]), Tensor::fill(1.0, &[3])}, vec!{}, &[])).unwrap();
+        assert!(matches!(runner.tens_add_gpu().await, Err(GpuError::InvalidConfig(_))));
+        assert!(matches!(runner.matmul_gpu().await, Err(GpuError::InvalidConfig(_))));
+
+        assert_eq!(runner.tens_add().await.unwrap()[0].get_data(), &vec!{2.0; 3});
+    }
+}
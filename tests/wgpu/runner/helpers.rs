@@ -0,0 +1,21 @@
+use flashlight_tensor::prelude::*;
+
+pub use crate::cpu::helpers::{assert_close, assert_relative_close};
+
+/// Asserts that there are as many outputs as expected tensors, and every output is close to
+/// its expected tensor
+pub fn assert_all_close<T: Float>(output: &[Tensor<T>], expected: &[Tensor<T>], epsilon: T){
+    assert_eq!(output.len(), expected.len());
+    for (tensor, expected) in output.iter().zip(expected){
+        assert_close(tensor, expected, epsilon);
+    }
+}
+
+/// Asserts that there are as many outputs as expected tensors, and every output is close to
+/// its expected tensor relative to its values
+pub fn assert_all_relative_close<T: Float>(output: &[Tensor<T>], expected: &[Tensor<T>], epsilon: T){
+    assert_eq!(output.len(), expected.len());
+    for (tensor, expected) in output.iter().zip(expected){
+        assert_relative_close(tensor, expected, epsilon);
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod limits{
+    use flashlight_tensor::prelude::*;
+
+    #[test]
+    fn device_limits_clamped(){
+        let adapter_limits = wgpu::Limits::default();
+
+        let limits = device_limits(1024, &adapter_limits);
+        assert_eq!(limits.max_buffer_size, 1024);
+        assert_eq!(limits.max_storage_buffer_binding_size, 1024);
+        assert_eq!(limits.max_compute_workgroups_per_dimension, adapter_limits.max_compute_workgroups_per_dimension);
+
+        let limits = device_limits(u64::MAX, &adapter_limits);
+        assert_eq!(limits.max_buffer_size, adapter_limits.max_buffer_size);
+        assert_eq!(limits.max_storage_buffer_binding_size, adapter_limits.max_storage_buffer_binding_size);
+
+        let limits = device_limits(0, &adapter_limits);
+        assert_eq!(limits.max_buffer_size, adapter_limits.max_buffer_size);
+    }
+
+    #[test]
+    fn chunking_auto(){
+        let mut data = GpuData::new();
+        assert!(matches!(data.prepare_chunking_auto(64, usize::MAX), Err(GpuError::NoData)));
+
+        for _ in 0..10{
+            data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[4])}, vec!{}, &[2])).unwrap();
+        }
+        data.output_per_sample = 2;
+
+        // 16 values per buffer, 4 samples per chunk
+        data.prepare_chunking_auto(64, usize::MAX).unwrap();
+        assert_eq!(data.chunks, 3);
+
+        // 4 outputs per dispatch, 2 samples per chunk
+        data.prepare_chunking_auto(64, 4).unwrap();
+        assert_eq!(data.chunks, 5);
+
+        // outputs bigger than inputs, 1 sample per chunk
+        data.output_per_sample = 16;
+        data.prepare_chunking_auto(64, usize::MAX).unwrap();
+        assert_eq!(data.chunks, 10);
+    }
+
+    #[tokio::test]
+    async fn auto_buffer_size(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut runner = GpuRunner::init(0, MemoryMetric::B);
+        runner.set_backend(Backend::Gpu);
+
+        let limits = runner.get_limits().await.unwrap();
+        assert_eq!(runner.get_context().await.unwrap().max_buffer_size(), limits.max_storage_buffer_binding_size as u64);
+
+        for _ in 0..4{
+            runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[])).unwrap();
+        }
+
+        let output: Vec<Tensor<f32>> = runner.add().await.unwrap();
+
+        assert_eq!(output.len(), 4);
+        assert_eq!(output[3].get_data(), &vec!{2.0; 4});
+    }
+
+    #[tokio::test]
+    async fn outputs_bigger_than_buffer(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let a: Tensor<f32> = Tensor::rand(1.0, &[16, 1]);
+        let b: Tensor<f32> = Tensor::rand(1.0, &[1, 16]);
+
+        // 8 samples of 32 inputs fit in buffer, but their 8 outputs of 256 values do not
+        let mut runner = GpuRunner::init(1, MemoryMetric::KB);
+        runner.set_backend(Backend::Gpu);
+        for _ in 0..8{
+            runner.append(Sample::from_data(vec!{a.clone(), b.clone()}, vec!{}, &[])).unwrap();
+        }
+
+        let output: Vec<Tensor<f32>> = runner.matmul().await.unwrap();
+        let expected = a.matrix_mul(&b).unwrap();
+
+        assert_eq!(output.len(), 8);
+        for tensor in &output{
+            assert_eq!(tensor.get_shape(), expected.get_shape());
+            for (x, y) in tensor.get_data().iter().zip(expected.get_data()){
+                assert!((x - y).abs() < 1e-5);
+            }
+        }
+    }
+}
@@ -14,9 +14,9 @@ mod runner{
         
         let sample = Sample::from_data(vec!{tensor.clone()}, vec!{}, &[]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.relu().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.relu().await.unwrap();
         let gpu_output = &full_gpu_output[0];
         let cpu_output = tensor.relu();
 
@@ -38,9 +38,9 @@ mod runner{
         
         let sample = Sample::from_data(vec!{tensor.clone()}, vec!{}, &[]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.relu_der().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.relu_der().await.unwrap();
         let gpu_output = &full_gpu_output[0];
         let cpu_output = tensor.relu_der();
 
@@ -63,9 +63,9 @@ mod runner{
         
         let sample = Sample::from_data(vec!{tensor.clone()}, vec!{}, &[3]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.sigmoid().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.sigmoid().await.unwrap();
         let gpu_output = &full_gpu_output[0];
         let cpu_output = tensor.sigmoid();
 
@@ -88,9 +88,9 @@ mod runner{
         
         let sample = Sample::from_data(vec!{tensor.clone()}, vec!{}, &[3]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.sigmoid_der().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.sigmoid_der().await.unwrap();
         let gpu_output = &full_gpu_output[0];
         let cpu_output = tensor.sigmoid_der();
 
@@ -21,9 +21,9 @@ mod backward_activations{
 
         let sample = Sample::from_data(vec!{input.clone(), gradient.clone()}, vec!{}, &[]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.backward_relu().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.backward_relu().await.unwrap();
         let gpu_output = &full_gpu_output[0];
         let cpu_output = input.relu_der().tens_broadcast_mul(&gradient).unwrap();
 
@@ -52,9 +52,9 @@ mod backward_activations{
 
         let sample = Sample::from_data(vec!{input.clone(), gradient.clone()}, vec!{}, &[]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.backward_sigmoid().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.backward_sigmoid().await.unwrap();
         let gpu_output = &full_gpu_output[0];
         let cpu_output = input.sigmoid_der().tens_broadcast_mul(&gradient).unwrap();
 
@@ -26,12 +26,12 @@ mod backward_bias_merge{
         let sample = Sample::from_data(vec!{bias.clone(), grad_output.clone(), linear_cache.clone()}, vec!{learning_rate}, &[]);
 
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
-        runner.append(sample);
+        runner.append(sample).unwrap();
         let sample = Sample::from_data(vec!{bias.clone(), grad_output.clone(), linear_cache.clone()}, vec!{learning_rate}, &[]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.backward_bias().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.backward_bias().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
     
 
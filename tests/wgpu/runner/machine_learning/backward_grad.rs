@@ -22,9 +22,9 @@ mod backward_gradient_merge{
         let sample = Sample::from_data(vec!{weights.clone(), grad_output.clone()}, vec!{}, grad_output.get_shape());
 
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.backward_grad().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.backward_grad().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
     
 
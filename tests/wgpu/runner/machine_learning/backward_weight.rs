@@ -28,9 +28,9 @@ mod backward_weights_merge{
         let sample = Sample::from_data(vec!{weights.clone(), grad_output.clone(), linear_cache.clone()}, vec!{learning_rate}, weights.get_shape());
 
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.backward_weight().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.backward_weight().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
     
 
@@ -44,4 +44,40 @@ mod backward_weights_merge{
         }
         assert_eq!(gpu_output.get_shape(), cpu_output.get_shape());
     }
+
+    #[tokio::test]
+    async fn backprop_weights_decay(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let grad_output: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+        let linear_cache: Tensor<f32> = Tensor::rand(1.0, &[5, 3]);
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[4, 5]);
+
+        let learning_rate = 0.5;
+        let weight_decay = 0.1;
+        let l1_decay = 0.01;
+
+        let sample = Sample::from_data(vec!{weights.clone(), grad_output.clone(), linear_cache.clone()}, vec!{learning_rate, weight_decay, l1_decay}, &[]);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.append(sample).unwrap();
+
+        let full_gpu_output: Vec<Tensor<f32>> = runner.backward_weight().await.unwrap();
+        let gpu_output = full_gpu_output[0].clone();
+
+        let weights_grad = grad_output.matrix_mul(&linear_cache.matrix_transpose().unwrap()).unwrap();
+        let weights_grad = weights.l2_grad(&weights_grad, weight_decay).unwrap();
+        let weights_grad = weights.l1_grad(&weights_grad, l1_decay).unwrap();
+
+        let cpu_output = weights.tens_sub(&weights_grad.mul(learning_rate)).unwrap();
+
+        let epsilon = 1e-4;
+        for (a, b) in gpu_output.get_data().iter().zip(cpu_output.get_data()) {
+            assert!((a - b).abs() < epsilon, "Values differ: GPU={} CPU={}", a, b);
+        }
+        assert_eq!(gpu_output.get_shape(), cpu_output.get_shape());
+    }
 }
@@ -24,9 +24,9 @@ mod forward_merge{
     
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.forward_sigmoid().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.forward_sigmoid().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
 
         let cpu_output = weights.matrix_mul(&inputs).unwrap().tens_broadcast_add(&biases).unwrap().sigmoid();
@@ -59,9 +59,9 @@ mod forward_merge{
     
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.forward_relu().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.forward_relu().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
 
         let cpu_output = weights.matrix_mul(&inputs).unwrap().tens_broadcast_add(&biases).unwrap().relu();
@@ -94,9 +94,9 @@ mod forward_merge{
     
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.forward_no_activ().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.forward_no_activ().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
 
         let cpu_output = weights.matrix_mul(&inputs).unwrap().tens_broadcast_add(&biases).unwrap();
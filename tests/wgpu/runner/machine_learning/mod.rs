@@ -4,3 +4,5 @@ pub mod backward_bias;
 pub mod backward_grad;
 pub mod backward_weight;
 pub mod backward_activations;
+pub mod normalization;
+pub mod recurrent;
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod normalization{
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_close;
+    use rand::prelude::*;
+
+    #[tokio::test]
+    async fn batch_norm(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut rng = rand::rng();
+
+        let size_1 = rng.random_range(2..64);
+        let size_2 = rng.random_range(2..64);
+
+        let inputs: Tensor<f32> = Tensor::rand(1.0, &[size_1, size_2]);
+        let gamma: Tensor<f32> = Tensor::rand(1.0, &[size_1, 1]);
+        let beta: Tensor<f32> = Tensor::rand(1.0, &[size_1, 1]);
+
+        let sample = Sample::from_data(vec!{inputs.clone(), gamma.clone(), beta.clone()}, vec!{1e-5}, &[]);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.append(sample).unwrap();
+
+        let full_gpu_output: Vec<Tensor<f32>> = runner.batch_norm().await.unwrap();
+
+        assert_close(&full_gpu_output[0], &inputs.batch_norm(&gamma, &beta, 1e-5).unwrap(), 1e-3);
+    }
+
+    #[tokio::test]
+    async fn batch_norm_eval(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let inputs: Tensor<f32> = Tensor::rand(1.0, &[8, 5]);
+        let mean: Tensor<f32> = Tensor::rand(1.0, &[8, 1]);
+        let var: Tensor<f32> = Tensor::fill(0.5, &[8, 1]);
+        let gamma: Tensor<f32> = Tensor::rand(1.0, &[8, 1]);
+        let beta: Tensor<f32> = Tensor::rand(1.0, &[8, 1]);
+
+        let sample = Sample::from_data(vec!{inputs.clone(), mean.clone(), var.clone(), gamma.clone(), beta.clone()}, vec!{1e-5}, &[]);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.append(sample).unwrap();
+
+        let full_gpu_output: Vec<Tensor<f32>> = runner.batch_norm_eval().await.unwrap();
+
+        assert_close(&full_gpu_output[0], &inputs.batch_norm_eval(&mean, &var, &gamma, &beta, 1e-5).unwrap(), 1e-3);
+    }
+
+    #[tokio::test]
+    async fn layer_norm(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let inputs: Tensor<f32> = Tensor::rand(1.0, &[6, 7]);
+        let gamma: Tensor<f32> = Tensor::rand(1.0, &[1, 7]);
+        let beta: Tensor<f32> = Tensor::rand(1.0, &[1, 7]);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        for _ in 0..3{
+            runner.append(Sample::from_data(vec!{inputs.clone(), gamma.clone(), beta.clone()}, vec!{1e-5}, &[])).unwrap();
+        }
+
+        let full_gpu_output: Vec<Tensor<f32>> = runner.layer_norm().await.unwrap();
+
+        let mut cpu_input = inputs.clone();
+        cpu_input.set_shape(&[6, 1, 7]);
+        let mut cpu_output = cpu_input.layer_norm(&gamma, &beta, 1e-5).unwrap();
+        cpu_output.set_shape(&[6, 7]);
+
+        assert_eq!(full_gpu_output.len(), 3);
+        assert_close(&full_gpu_output[2], &cpu_output, 1e-3);
+    }
+
+    #[tokio::test]
+    async fn group_norm(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let inputs: Tensor<f32> = Tensor::rand(1.0, &[6, 5]);
+        let gamma: Tensor<f32> = Tensor::rand(1.0, &[6, 1]);
+        let beta: Tensor<f32> = Tensor::rand(1.0, &[6, 1]);
+
+        let sample = Sample::from_data(vec!{inputs.clone(), gamma.clone(), beta.clone()}, vec!{1e-5, 3.0}, &[]);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.append(sample).unwrap();
+
+        let full_gpu_output: Vec<Tensor<f32>> = runner.group_norm().await.unwrap();
+
+        assert_close(&full_gpu_output[0], &inputs.group_norm(3, &gamma, &beta, 1e-5).unwrap(), 1e-3);
+    }
+
+    #[tokio::test]
+    async fn backward_norm(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let inputs: Tensor<f32> = Tensor::rand(1.0, &[4, 6]);
+        let grad_output: Tensor<f32> = Tensor::rand(1.0, &[4, 6]);
+        let gamma: Tensor<f32> = Tensor::rand(1.0, &[4, 1]);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{inputs.clone(), gamma.clone(), grad_output.clone()}, vec!{1e-5}, &[])).unwrap();
+        let gpu_output = runner.backward_batch_norm().await.unwrap();
+        let (cpu_output, _, _) = inputs.batch_norm_backward(&gamma, &grad_output, 1e-5).unwrap();
+        assert_close(&gpu_output[0], &cpu_output, 1e-3);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{inputs.clone(), gamma.clone(), grad_output.clone()}, vec!{1e-5, 2.0}, &[])).unwrap();
+        let gpu_output = runner.backward_group_norm().await.unwrap();
+        let (cpu_output, _, _) = inputs.group_norm_backward(2, &gamma, &grad_output, 1e-5).unwrap();
+        assert_close(&gpu_output[0], &cpu_output, 1e-3);
+
+        let layer_gamma: Tensor<f32> = Tensor::rand(1.0, &[4, 6]);
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{inputs.clone(), layer_gamma.clone(), grad_output.clone()}, vec!{1e-5}, &[])).unwrap();
+        let gpu_output = runner.backward_layer_norm().await.unwrap();
+        let (cpu_output, _, _) = inputs.layer_norm_backward(&layer_gamma, &grad_output, 1e-5).unwrap();
+        assert_close(&gpu_output[0], &cpu_output, 1e-3);
+    }
+
+    #[tokio::test]
+    async fn backward_norm_params(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let inputs: Tensor<f32> = Tensor::rand(1.0, &[4, 6]);
+        let grad_output: Tensor<f32> = Tensor::rand(1.0, &[4, 6]);
+        let gamma: Tensor<f32> = Tensor::rand(1.0, &[4, 1]);
+        let beta: Tensor<f32> = Tensor::rand(1.0, &[4, 1]);
+
+        let learning_rate = 0.1;
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{gamma.clone(), beta.clone(), inputs.clone(), grad_output.clone()}, vec!{1e-5, 2.0, learning_rate}, &[])).unwrap();
+        runner.append(Sample::from_data(vec!{gamma.clone(), beta.clone(), inputs.clone(), grad_output.clone()}, vec!{1e-5, 2.0, learning_rate}, &[])).unwrap();
+        let gpu_output = runner.backward_group_norm_params().await.unwrap();
+
+        let (_, grad_gamma, grad_beta) = inputs.group_norm_backward(2, &gamma, &grad_output, 1e-5).unwrap();
+        let cpu_output = gamma.tens_sub(&grad_gamma.mul(learning_rate)).unwrap()
+            .append(&beta.tens_sub(&grad_beta.mul(learning_rate)).unwrap()).unwrap();
+
+        assert_eq!(gpu_output.len(), 1);
+        assert_close(&gpu_output[0], &cpu_output, 1e-3);
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod recurrent{
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_close;
+
+    #[tokio::test]
+    async fn rnn_cell(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let cell = RNNCell::new(5, 7);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        let mut expected: Vec<Tensor<f32>> = Vec::new();
+
+        for _i in 0..3{
+            let input: Tensor<f32> = Tensor::rand(1.0, &[5, 4]);
+            let hidden: Tensor<f32> = Tensor::rand(1.0, &[7, 4]);
+
+            expected.push(cell.forward(&input, &hidden).unwrap().0);
+
+            let sample = Sample::from_data(vec!{cell.weight_ih.clone(), cell.weight_hh.clone(), cell.bias_ih.clone(), cell.bias_hh.clone(), input, hidden}, vec!{}, &[]);
+            runner.append(sample).unwrap();
+        }
+
+        let full_gpu_output: Vec<Tensor<f32>> = runner.rnn_cell().await.unwrap();
+
+        for (gpu, cpu) in full_gpu_output.iter().zip(expected.iter()){
+            assert_close(gpu, cpu, 1e-3);
+        }
+    }
+
+    #[tokio::test]
+    async fn gru_cell(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let cell = GRUCell::new(5, 7);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        let mut expected: Vec<Tensor<f32>> = Vec::new();
+
+        for _i in 0..3{
+            let input: Tensor<f32> = Tensor::rand(1.0, &[5, 4]);
+            let hidden: Tensor<f32> = Tensor::rand(1.0, &[7, 4]);
+
+            expected.push(cell.forward(&input, &hidden).unwrap().0);
+
+            let sample = Sample::from_data(vec!{cell.weight_ih.clone(), cell.weight_hh.clone(), cell.bias_ih.clone(), cell.bias_hh.clone(), input, hidden}, vec!{}, &[]);
+            runner.append(sample).unwrap();
+        }
+
+        let full_gpu_output: Vec<Tensor<f32>> = runner.gru_cell().await.unwrap();
+
+        for (gpu, cpu) in full_gpu_output.iter().zip(expected.iter()){
+            assert_close(gpu, cpu, 1e-3);
+        }
+    }
+
+    #[tokio::test]
+    async fn lstm_cell(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let cell = LSTMCell::new(5, 7);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        let mut expected: Vec<Tensor<f32>> = Vec::new();
+
+        for _i in 0..3{
+            let input: Tensor<f32> = Tensor::rand(1.0, &[5, 4]);
+            let hidden: Tensor<f32> = Tensor::rand(1.0, &[7, 4]);
+            let cell_state: Tensor<f32> = Tensor::rand(1.0, &[7, 4]);
+
+            let (next_hidden, next_cell, _) = cell.forward(&input, &hidden, &cell_state).unwrap();
+            expected.push(next_hidden.append(&next_cell).unwrap());
+
+            let sample = Sample::from_data(vec!{cell.weight_ih.clone(), cell.weight_hh.clone(), cell.bias_ih.clone(), cell.bias_hh.clone(), input, hidden, cell_state}, vec!{}, &[]);
+            runner.append(sample).unwrap();
+        }
+
+        let full_gpu_output: Vec<Tensor<f32>> = runner.lstm_cell().await.unwrap();
+
+        for (gpu, cpu) in full_gpu_output.iter().zip(expected.iter()){
+            assert_close(gpu, cpu, 1e-3);
+        }
+    }
+
+    #[tokio::test]
+    async fn sequence_forward(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let sequences: Vec<Vec<Tensor<f32>>> = (0..3)
+            .map(|_| (0..5).map(|_| Tensor::rand(1.0, &[4, 6])).collect())
+            .collect();
+
+        let mut rnn = RNN::new(4, 3);
+        let mut gru = GRU::new(4, 3);
+        let mut lstm = LSTM::new(4, 3);
+
+        let rnn_gpu = rnn.forward_gpu(&sequences, 1, MemoryMetric::GB).await.unwrap();
+        let gru_gpu = gru.forward_gpu(&sequences, 1, MemoryMetric::GB).await.unwrap();
+        let lstm_gpu = lstm.forward_gpu(&sequences, 1, MemoryMetric::GB).await.unwrap();
+
+        for (i, sequence) in sequences.iter().enumerate(){
+            let rnn_cpu = rnn.forward(sequence, None).unwrap();
+            let gru_cpu = gru.forward(sequence, None).unwrap();
+            let lstm_cpu = lstm.forward(sequence, None, None).unwrap();
+
+            for t in 0..sequence.len(){
+                assert_close(&rnn_gpu[i][t], &rnn_cpu[t], 1e-3);
+                assert_close(&gru_gpu[i][t], &gru_cpu[t], 1e-3);
+                assert_close(&lstm_gpu[i][t], &lstm_cpu[t], 1e-3);
+            }
+        }
+    }
+}
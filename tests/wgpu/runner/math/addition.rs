@@ -13,10 +13,10 @@ mod runner{
         for i in 0..10{
             let sample = Sample::from_data(vec!{Tensor::fill(i as f32, &[2, 2])}, vec!{1.0}, &[]);
 
-            runner.append(sample);
+            runner.append(sample).unwrap();
         }
         
-        let output_data: Vec<Tensor<f32>> = runner.add().await;
+        let output_data: Vec<Tensor<f32>> = runner.add().await.unwrap();
         
         assert_eq!(output_data[0].get_data(), &vec!{1.0, 1.0, 1.0, 1.0});
         assert_eq!(output_data[4].get_data(), &vec!{5.0, 5.0, 5.0, 5.0});
@@ -32,9 +32,9 @@ mod runner{
         
         let sample = Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2]), Tensor::fill(2.0, &[2, 2])}, vec!{}, &[]);
 
-        runner.append(sample);        
+        runner.append(sample).unwrap();        
 
-        let output_data: Vec<Tensor<f32>> = runner.tens_add().await;
+        let output_data: Vec<Tensor<f32>> = runner.tens_add().await.unwrap();
 
         assert_eq!(output_data[0].get_data(), &vec!{3.0, 3.0, 3.0, 3.0});
     }
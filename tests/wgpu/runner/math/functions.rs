@@ -12,9 +12,9 @@ mod functions{
         let sample = Sample::from_data(vec!{tensor.clone()}, vec!{}, tensor.get_shape());
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.nlog().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.nlog().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
 
         let cpu_output = tensor.nlog();
@@ -35,9 +35,9 @@ mod functions{
         let sample = Sample::from_data(vec!{tensor.clone()}, vec!{2.0}, tensor.get_shape());
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.log().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.log().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
 
         let cpu_output = tensor.log(2.0);
@@ -13,9 +13,9 @@ mod subtypes{
         let tensor2: Tensor<f32> = Tensor::fill(5.0, &[16, 16]);
         let sample = Sample::from_data(vec!{tensor1.clone(), tensor2.clone()}, vec!{}, &[16, 16]);
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.matmul().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.matmul().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
 
         let cpu_output = tensor1.matrix_mul(&tensor2).unwrap();
@@ -24,6 +24,41 @@ mod subtypes{
         assert_eq!(gpu_output.get_shape(), cpu_output.get_shape());
     }
 
+    #[tokio::test]
+    async fn matmul_tile_sizes(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        for tile_size in TILE_SIZES{
+            let config = GpuConfig{
+                tile_size,
+                ..Default::default()
+            };
+            let mut runner = GpuRunner::with_config(config, 1, MemoryMetric::GB);
+            runner.set_backend(Backend::Gpu);
+
+            // sizes not divisible by any tile, so edges of tiles are checked
+            let inputs: Vec<(Tensor<f32>, Tensor<f32>)> = (0..3).map(|_| (Tensor::rand(1.0, &[37, 45]), Tensor::rand(1.0, &[45, 29]))).collect();
+            for (tensor1, tensor2) in &inputs{
+                runner.append(Sample::from_data(vec!{tensor1.clone(), tensor2.clone()}, vec!{}, &[])).unwrap();
+            }
+
+            let gpu_output: Vec<Tensor<f32>> = runner.matmul().await.unwrap();
+            assert_eq!(runner.get_context().await.unwrap().pipelines.tile_size(), tile_size);
+
+            for (output, (tensor1, tensor2)) in gpu_output.iter().zip(inputs.iter()){
+                let cpu_output = tensor1.matrix_mul(tensor2).unwrap();
+
+                for (a, b) in output.get_data().iter().zip(cpu_output.get_data()) {
+                    assert!((a - b).abs() < 1e-4, "Values differ with tile {}: GPU={} CPU={}", tile_size, a, b);
+                }
+                assert_eq!(output.get_shape(), cpu_output.get_shape());
+            }
+        }
+    }
+
     #[tokio::test]
     async fn matrix_row_sum(){
         if std::env::var("CI").is_ok() {
@@ -35,9 +70,9 @@ mod subtypes{
         let sample = Sample::from_data(vec!{inputs.clone()}, vec!{}, &[1, inputs.get_shape()[0]]);
         
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.matrix_row_sum().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.matrix_row_sum().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
     
         let cpu_output = inputs.matrix_row_sum().unwrap();
@@ -59,9 +94,9 @@ mod subtypes{
 
         let sample = Sample::from_data(vec!{inputs.clone()}, vec!{}, &[inputs.get_shape()[0], 1]);
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.matrix_col_sum().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.matrix_col_sum().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
     
         let cpu_output = inputs.matrix_col_sum().unwrap();
@@ -84,9 +119,9 @@ mod subtypes{
         let sample = Sample::from_data(vec!{inputs.clone()}, vec!{}, &[1, inputs.get_shape()[0]]);
         
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.matrix_row_prod().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.matrix_row_prod().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
     
         let cpu_output = inputs.matrix_row_prod().unwrap();
@@ -109,9 +144,9 @@ mod subtypes{
         let sample = Sample::from_data(vec!{inputs.clone()}, vec!{}, &[inputs.get_shape()[0], 1]);
         
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.matrix_col_prod().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.matrix_col_prod().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
     
         let cpu_output = inputs.matrix_col_prod().unwrap();
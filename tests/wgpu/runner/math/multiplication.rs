@@ -12,9 +12,9 @@ mod runner{
         
         let sample = Sample::from_data(vec!{Tensor::fill(4.0 as f32, &[2, 2])}, vec!{2.0}, &[2, 2]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let output_data: Vec<Tensor<f32>> = runner.mul().await;
+        let output_data: Vec<Tensor<f32>> = runner.mul().await.unwrap();
 
         assert_eq!(output_data[0].get_data(), &vec!{8.0, 8.0, 8.0, 8.0});
     }
@@ -28,9 +28,9 @@ mod runner{
         
         let sample = Sample::from_data(vec!{Tensor::fill(4.0, &[2, 2]), Tensor::fill(2.0, &[2, 2])}, vec!{}, &[2, 2]);
 
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let output_data: Vec<Tensor<f32>> = runner.tens_mul().await;
+        let output_data: Vec<Tensor<f32>> = runner.tens_mul().await.unwrap();
 
         assert_eq!(output_data[0].get_data(), &vec!{8.0, 8.0, 8.0, 8.0});
     }
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod reduction{
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_all_relative_close;
+
+    #[tokio::test]
+    async fn full_reductions(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let inputs: Vec<Tensor<f32>> = (0..3).map(|_| Tensor::rand(1.0, &[3, 70, 50])).collect();
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.set_backend(Backend::Gpu);
+        for input in &inputs{
+            runner.append(Sample::from_data(vec!{input.clone()}, vec!{}, &[])).unwrap();
+        }
+
+        let expected = |value: fn(&Tensor<f32>) -> f32| -> Vec<Tensor<f32>>{
+            inputs.iter().map(|input| Tensor::from_data(&[value(input)], &[1]).unwrap()).collect()
+        };
+
+        assert_all_relative_close(&runner.reduce_sum().await.unwrap(), &expected(|input| input.sum()), 1e-4);
+        assert_all_relative_close(&runner.reduce_mean().await.unwrap(), &expected(|input| input.mean()), 1e-4);
+        assert_all_relative_close(&runner.reduce_max().await.unwrap(), &expected(|input| input.max()), 0.0);
+        assert_all_relative_close(&runner.reduce_min().await.unwrap(), &expected(|input| input.min()), 0.0);
+        assert_all_relative_close(&runner.reduce_argmax().await.unwrap(), &expected(|input| input.argmax() as f32), 0.0);
+        assert_all_relative_close(&runner.reduce_variance().await.unwrap(), &expected(|input| input.variance()), 1e-4);
+    }
+
+    #[tokio::test]
+    async fn axis_reductions(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let inputs: Vec<Tensor<f32>> = (0..2).map(|_| Tensor::rand(1.0, &[5, 130, 7])).collect();
+
+        for axis in 0..3{
+            let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+            runner.set_backend(Backend::Gpu);
+            for input in &inputs{
+                runner.append(Sample::from_data(vec!{input.clone()}, vec!{axis as f32}, &[])).unwrap();
+            }
+
+            let expected = |value: fn(&Tensor<f32>, u32) -> Option<Tensor<f32>>| -> Vec<Tensor<f32>>{
+                inputs.iter().map(|input| value(input, axis).unwrap()).collect()
+            };
+
+            assert_all_relative_close(&runner.reduce_sum_axis().await.unwrap(), &expected(|input, axis| input.sum_axis(axis)), 1e-4);
+            assert_all_relative_close(&runner.reduce_mean_axis().await.unwrap(), &expected(|input, axis| input.mean_axis(axis)), 1e-4);
+            assert_all_relative_close(&runner.reduce_max_axis().await.unwrap(), &expected(|input, axis| input.max_axis(axis)), 0.0);
+            assert_all_relative_close(&runner.reduce_min_axis().await.unwrap(), &expected(|input, axis| input.min_axis(axis)), 0.0);
+            assert_all_relative_close(&runner.reduce_argmax_axis().await.unwrap(), &expected(|input, axis| input.argmax_axis(axis)), 0.0);
+            assert_all_relative_close(&runner.reduce_variance_axis().await.unwrap(), &expected(|input, axis| input.variance_axis(axis)), 1e-4);
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_axis(){
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3])}, vec!{2.0}, &[])).unwrap();
+
+        assert!(matches!(runner.reduce_sum_axis().await, Err(GpuError::InvalidShapes(_))));
+    }
+
+    #[tokio::test]
+    async fn chunked_single_output(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let bias: Tensor<f32> = Tensor::rand(1.0, &[6, 1]);
+        let linear_cache: Tensor<f32> = Tensor::rand(1.0, &[4, 9]);
+        let grads: Vec<Tensor<f32>> = (0..5).map(|_| Tensor::rand(1.0, &[6, 9])).collect();
+
+        let sample_bytes = (bias.count_data() + grads[0].count_data() + linear_cache.count_data()) * size_of::<f32>();
+
+        // chunks of 2, 2 and 1 samples, so chunk outputs need weights of their sample counts
+        let mut gpu_runner = GpuRunner::init(2 * sample_bytes as u64, MemoryMetric::B);
+        gpu_runner.set_backend(Backend::Gpu);
+        let mut cpu_runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+
+        for grad in &grads{
+            gpu_runner.append(Sample::from_data(vec!{bias.clone(), grad.clone(), linear_cache.clone()}, vec!{0.5}, &[])).unwrap();
+            cpu_runner.append(Sample::from_data(vec!{bias.clone(), grad.clone(), linear_cache.clone()}, vec!{0.5}, &[])).unwrap();
+        }
+
+        let gpu_output = gpu_runner.backward_bias().await.unwrap();
+        let cpu_output = cpu_runner.backward_bias().await.unwrap();
+
+        assert_eq!(gpu_output.len(), 1);
+        assert_all_relative_close(&gpu_output, &cpu_output, 1e-4);
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod metadata{
+    use flashlight_tensor::prelude::*;
+
+    #[test]
+    fn input_shapes(){
+        let mut data = GpuData::new();
+        data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3, 4]), Tensor::fill(1.0, &[4]), Tensor::fill(1.0, &[1, 4])}, vec!{}, &[])).unwrap();
+
+        assert_eq!(data.ranks, vec!{3, 1, 2});
+        assert_eq!(data.input_shapes(), vec!{vec!{2, 3, 4}, vec!{4}, vec!{1, 4}});
+    }
+
+    #[test]
+    fn metadata_strides(){
+        let mut data = GpuData::new();
+        data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1, 3]), Tensor::fill(1.0, &[1, 4, 1])}, vec!{}, &[2, 4, 3])).unwrap();
+        data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 1, 3]), Tensor::fill(1.0, &[1, 4, 1])}, vec!{}, &[2, 4, 3])).unwrap();
+
+        assert_eq!(data.metadata(), vec!{
+            3, 3, 10,
+            0, 6, 0,
+            2, 1, 3, 3, 0, 1,
+            1, 4, 1, 0, 1, 0,
+            2, 4, 3, 12, 3, 1,
+        });
+    }
+
+    #[test]
+    fn different_ranks_rejected(){
+        let mut data = GpuData::new();
+        data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3])}, vec!{}, &[])).unwrap();
+
+        assert!(data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[6])}, vec!{}, &[])).is_err());
+    }
+
+    #[tokio::test]
+    async fn high_rank_ops(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let shape = [2, 1, 3, 2, 1, 2, 2, 3];
+        let broadcast_shape = [1, 2, 3, 1, 2, 1, 2, 1];
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.set_backend(Backend::Gpu);
+
+        let inputs: Vec<(Tensor<f32>, Tensor<f32>, Tensor<f32>)> = (0..3).map(|_| (Tensor::rand(1.0, &shape), Tensor::rand(1.0, &shape), Tensor::rand(1.0, &broadcast_shape))).collect();
+
+        for (a, b, _) in &inputs{
+            runner.append(Sample::from_data(vec!{a.clone(), b.clone()}, vec!{}, &[])).unwrap();
+        }
+        let output = runner.tens_add().await.unwrap();
+        for (tensor, (a, b, _)) in output.iter().zip(inputs.iter()){
+            let expected = a.tens_add(b).unwrap();
+            assert_eq!(tensor.get_shape(), expected.get_shape());
+            for (x, y) in tensor.get_data().iter().zip(expected.get_data().iter()){
+                assert!((x - y).abs() < 1e-5);
+            }
+        }
+
+        runner.clear();
+        for (a, _, c) in &inputs{
+            runner.append(Sample::from_data(vec!{a.clone(), c.clone()}, vec!{}, &[])).unwrap();
+        }
+        let output = runner.tens_broadcast_mul().await.unwrap();
+        for (tensor, (a, _, c)) in output.iter().zip(inputs.iter()){
+            let expected = a.tens_broadcast_mul(c).unwrap();
+            assert_eq!(tensor.get_shape(), expected.get_shape());
+            for (x, y) in tensor.get_data().iter().zip(expected.get_data().iter()){
+                assert!((x - y).abs() < 1e-5);
+            }
+        }
+    }
+}
@@ -1,5 +1,25 @@
+pub mod helpers;
 pub mod chunking;
 pub mod subtypes;
 pub mod math;
 pub mod machine_learning;
 pub mod broadcasting;
+pub mod gpu_tensor;
+pub mod gpu_context;
+pub mod pipeline_cache;
+pub mod gpu_error;
+pub mod backend;
+pub mod conformance;
+pub mod gpu_config;
+pub mod limits;
+pub mod metadata;
+pub mod gpu_graph;
+pub mod elementwise;
+pub mod custom_op;
+pub mod readback;
+pub mod profiling;
+pub mod ragged;
+pub mod gpu_batch;
+pub mod shared;
+pub mod precision;
+pub mod double;
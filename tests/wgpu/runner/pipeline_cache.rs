@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod pipeline_cache{
+    use flashlight_tensor::prelude::*;
+
+    #[tokio::test]
+    async fn compiled_once(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let context = GpuContext::init(256, &MemoryMetric::MB).await.unwrap();
+
+        let mut first_runner = GpuRunner::from_context(context.clone(), 256, MemoryMetric::MB);
+        let mut second_runner = GpuRunner::from_context(context.clone(), 256, MemoryMetric::MB);
+
+        first_runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3]), Tensor::fill(1.0, &[2, 3])}, vec!{}, &[])).unwrap();
+        second_runner.append(Sample::from_data(vec!{Tensor::fill(2.0, &[2, 2]), Tensor::fill(1.0, &[2, 2])}, vec!{}, &[])).unwrap();
+
+        let first_output = first_runner.tens_add().await.unwrap();
+        let pipeline = context.get_pipeline(&GpuOperations::TensAdd).unwrap();
+        let second_output = second_runner.tens_add().await.unwrap();
+
+        assert_eq!(first_output[0].get_data(), &vec!{2.0; 6});
+        assert_eq!(second_output[0].get_data(), &vec!{3.0; 4});
+
+        assert_eq!(context.pipelines.len(), 1);
+        assert!(std::sync::Arc::ptr_eq(&pipeline, &context.get_pipeline(&GpuOperations::TensAdd).unwrap()));
+    }
+
+    #[tokio::test]
+    async fn prewarm(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut runner = GpuRunner::init(256, MemoryMetric::MB);
+        runner.prewarm().await.unwrap();
+
+        let context = runner.get_context().await.unwrap();
+
+        for operation in GpuOperations::all(){
+            assert!(context.pipelines.contains(&operation));
+        }
+
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[4])}, vec!{}, &[])).unwrap();
+        let output = runner.relu().await.unwrap();
+
+        assert_eq!(output[0].get_data(), &vec!{1.0; 4});
+        assert_eq!(context.pipelines.len(), GpuOperations::all().len());
+    }
+
+    #[tokio::test]
+    async fn params_size(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let context = GpuContext::init(256, &MemoryMetric::MB).await.unwrap();
+
+        // single line struct, and colons in comments, are not fields
+        let source = "
+            // params: scale, shift and offset
+            struct Params { scale: f32, shift: f32, offset: vec2<f32> }
+            @group(0) @binding(0)
+            var<storage, read> input: array<f32>;
+            @group(0) @binding(2)
+            var<uniform> params: Params;
+            @group(0) @binding(3)
+            var<storage, read_write> output: array<f32>;
+
+            @compute @workgroup_size(64)
+            fn main(@builtin(global_invocation_id) global_id: vec3<u32>){
+                if (global_id.x < arrayLength(&input)) {
+                    output[global_id.x] = input[global_id.x] * params.scale + params.shift + params.offset.x;
+                }
+            }
+        ";
+        let pipeline = GpuPipeline::compile_source(&context.device, source, 16).unwrap();
+        assert_eq!(pipeline.params_size, 16);
+
+        assert_eq!(context.get_pipeline(&GpuOperations::Add).unwrap().params_size, 4);
+        assert_eq!(context.get_pipeline(&GpuOperations::NormForward).unwrap().params_size, 16);
+        assert_eq!(context.get_pipeline(&GpuOperations::ReLU).unwrap().params_size, 0);
+    }
+}
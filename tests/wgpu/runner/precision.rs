@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod precision{
+    use flashlight_tensor::prelude::*;
+    #[cfg(feature = "half")]
+    use crate::wgpu::runner::helpers::assert_all_close;
+
+    #[test]
+    fn f32_default(){
+        let runner = GpuRunner::init(1, MemoryMetric::GB);
+        assert_eq!(runner.get_precision(), GpuPrecision::F32);
+
+        assert_eq!(GpuPrecision::F32.buffer_size(3), 12);
+        assert_eq!(GpuPrecision::F32.decode(GpuPrecision::F32.encode(&[1.5, -2.0]).chunks(4).map(|word| f32::from_le_bytes(word.try_into().unwrap())).collect()), vec!{1.5, -2.0});
+        assert!(GpuOperations::TensAdd.supports_precision(GpuPrecision::F32));
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn f16_encoding(){
+        let encoded = GpuPrecision::F16.encode(&[1.0, -0.5, 0.1]);
+
+        // 3 values padded to 4 byte words
+        assert_eq!(encoded.len(), 8);
+        assert_eq!(GpuPrecision::F16.buffer_size(3), 8);
+        assert_eq!(&encoded[..2], &f16::ONE.to_le_bytes());
+
+        let words: Vec<f32> = encoded.chunks(4).map(|word| f32::from_le_bytes(word.try_into().unwrap())).collect();
+        let decoded = GpuPrecision::F16.decode(words);
+        assert_eq!(decoded.len(), 4);
+        assert_eq!(&decoded[..2], &[1.0, -0.5]);
+        assert!((decoded[2] - 0.1).abs() < 1e-4);
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn f16_round_trip(){
+        let values = [0.0, -0.0, 1.0 / 3.0, -2.5e-6, 1234.5678, 65504.0, 1e6, f32::NEG_INFINITY, 0.1];
+
+        let words: Vec<f32> = GpuPrecision::F16.encode(&values).chunks(4).map(|word| f32::from_le_bytes(word.try_into().unwrap())).collect();
+        let decoded = GpuPrecision::F16.decode(words);
+
+        // values are rounded like Tensor::to_f16, and read back without changing
+        let expected = Tensor::from_data(&values, &[values.len() as u32]).unwrap().to_f16().to_f32();
+        assert_eq!(&decoded[..values.len()], expected.get_data().as_slice());
+        assert_eq!(decoded[1].to_bits(), (-0.0f32).to_bits());
+        assert!(decoded[6].is_infinite());
+    }
+
+    #[cfg(feature = "half")]
+    #[tokio::test]
+    async fn f16_runner_round_trip(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let identity: Tensor<f32> = Tensor::from_data(&[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0], &[3, 3]).unwrap();
+        let inputs: Tensor<f32> = Tensor::rand(100.0, &[3, 5]);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.set_backend(Backend::Gpu);
+        runner.set_precision(GpuPrecision::F16);
+        runner.append(Sample::from_data(vec!{identity, inputs.clone()}, vec!{}, &[])).unwrap();
+
+        let context = runner.get_context().await.unwrap();
+        let output = runner.matmul().await.unwrap();
+
+        // products with identity are exact, so only uploading and storing output rounds
+        if context.supports_f16(){
+            assert_eq!(output[0].get_data(), inputs.to_f16().to_f32().get_data());
+        }
+        else{
+            assert_eq!(output[0].get_data(), inputs.get_data());
+        }
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn f16_operations_and_chunks(){
+        assert!(GpuOperations::ForwardRelu.supports_precision(GpuPrecision::F16));
+        assert!(!GpuOperations::TensAdd.supports_precision(GpuPrecision::F16));
+
+        // the same bytes hold twice as many samples in f16
+        let mut data = GpuData::new();
+        for _ in 0..8{
+            data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[4, 4])}, vec!{}, &[])).unwrap();
+        }
+        data.prepare_chunking_auto(64 * size_of::<f32>() as u64, usize::MAX).unwrap();
+        assert_eq!(data.chunks, 2);
+
+        data.precision = GpuPrecision::F16;
+        data.prepare_chunking_auto(64 * size_of::<f32>() as u64, usize::MAX).unwrap();
+        assert_eq!(data.chunks, 1);
+    }
+
+    #[cfg(feature = "half")]
+    #[tokio::test]
+    async fn cpu_backend(){
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[4, 3]);
+        let inputs: Tensor<f32> = Tensor::rand(1.0, &[3, 2]);
+
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.set_precision(GpuPrecision::F16);
+        runner.append(Sample::from_data(vec!{weights.clone(), inputs.clone()}, vec!{}, &[])).unwrap();
+
+        // cpu backend computes in f32
+        assert_all_close(&runner.matmul().await.unwrap(), &[weights.matrix_mul(&inputs).unwrap()], 1e-5);
+
+        runner.clear();
+        assert_eq!(runner.get_precision(), GpuPrecision::F16);
+    }
+
+    #[cfg(feature = "half")]
+    #[tokio::test]
+    async fn f16_buffers(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let a: Tensor<f32> = Tensor::rand(1.0, &[5, 7]);
+        let b: Tensor<f32> = Tensor::rand(1.0, &[7, 3]);
+
+        let context = GpuContext::init(1, &MemoryMetric::GB).await.unwrap();
+        let mut data = GpuData::new();
+        data.precision = GpuPrecision::F16;
+        data.append(Sample::from_data(vec!{a.clone(), b.clone()}, vec!{}, &[5, 3])).unwrap();
+
+        let mut buffers = GpuBuffers::with_context(context.clone(), &data, 0).unwrap();
+        assert_eq!(buffers.output_buffer.size(), 15 * 2 + 2);
+
+        assert!(matches!(buffers.set_shader(&GpuOperations::TensAdd), Err(GpuError::InvalidConfig(_))));
+
+        if !context.supports_f16(){
+            assert!(matches!(buffers.set_shader(&GpuOperations::Matmul), Err(GpuError::InvalidConfig(_))));
+            return;
+        }
+
+        buffers.set_shader(&GpuOperations::Matmul).unwrap();
+        buffers.prepare().unwrap();
+        let output = buffers.run().await.unwrap();
+
+        let expected = a.to_f16().to_f32().matrix_mul(&b.to_f16().to_f32()).unwrap();
+        assert_all_close(&output, &[expected], 1e-2);
+    }
+
+    #[cfg(feature = "half")]
+    #[tokio::test]
+    async fn f16_runner(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[6, 5]);
+        let bias: Tensor<f32> = Tensor::rand(1.0, &[6, 1]);
+        let inputs: Vec<Tensor<f32>> = (0..9).map(|_| Tensor::rand(1.0, &[5, 3])).collect();
+
+        // devices without f16 shaders run in f32, so outputs are compared with f16 tolerance
+        // the first buffer fits all samples, the second splits them into chunks
+        for buffer_size in [1 << 20, 200 * size_of::<f32>() as u64]{
+            let mut runner = GpuRunner::init(buffer_size, MemoryMetric::B);
+            runner.set_backend(Backend::Gpu);
+            runner.set_precision(GpuPrecision::F16);
+
+            for input in &inputs{
+                runner.append(Sample::from_data(vec!{weights.clone(), input.clone(), bias.clone()}, vec!{}, &[])).unwrap();
+            }
+            let output = runner.forward_sigmoid().await.unwrap();
+            let expected: Vec<Tensor<f32>> = inputs.iter().map(|input| weights.matrix_mul(input).unwrap().tens_broadcast_add(&bias).unwrap().sigmoid()).collect();
+            assert_all_close(&output, &expected, 1e-2);
+
+            // shared weights are stored in f16 too
+            runner.clear();
+            runner.share(0, &weights).unwrap();
+            for input in &inputs{
+                runner.append(Sample::from_data(vec!{input.clone()}, vec!{}, &[])).unwrap();
+            }
+            let output = runner.matmul().await.unwrap();
+            let expected: Vec<Tensor<f32>> = inputs.iter().map(|input| weights.matrix_mul(input).unwrap()).collect();
+            assert_all_close(&output, &expected, 2e-2);
+        }
+
+        // operations without f16 shader run in f32
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.set_precision(GpuPrecision::F16);
+        runner.append(Sample::from_data(vec!{inputs[0].clone(), inputs[1].clone()}, vec!{}, &[])).unwrap();
+        assert_all_close(&runner.tens_add().await.unwrap(), &[inputs[0].tens_add(&inputs[1]).unwrap()], 1e-5);
+    }
+}
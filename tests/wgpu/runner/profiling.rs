@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod profiling{
+    use flashlight_tensor::prelude::*;
+
+    #[tokio::test]
+    async fn chunk_profiles(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        // chunks of 3 samples, last chunk has 2
+        let mut runner = GpuRunner::init(3 * 48 * size_of::<f32>() as u64, MemoryMetric::B);
+        runner.set_backend(Backend::Gpu);
+        for _ in 0..11{
+            runner.append(Sample::from_data(vec!{Tensor::rand(1.0, &[4, 6]), Tensor::rand(1.0, &[4, 6])}, vec!{}, &[])).unwrap();
+        }
+
+        runner.tens_add().await.unwrap();
+        assert!(runner.last_profile().is_none());
+
+        runner.enable_profiling();
+        assert!(runner.is_profiling());
+        runner.tens_add().await.unwrap();
+        runner.tens_mul().await.unwrap();
+
+        let timestamps = runner.get_context().await.unwrap().supports_timestamps();
+        let profiles = runner.take_profiles();
+
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].operation, GpuOperations::TensAdd);
+        assert_eq!(profiles[1].operation, GpuOperations::TensMul);
+        for profile in &profiles{
+            let samples: Vec<usize> = profile.chunks.iter().map(|chunk| chunk.samples).collect();
+            assert_eq!(samples, vec!{3, 3, 3, 2});
+
+            for (i, chunk) in profile.chunks.iter().enumerate(){
+                assert_eq!(chunk.chunk, i);
+                assert_eq!(chunk.gpu.is_some(), timestamps);
+            }
+            assert!(profile.upload() + profile.dispatch() + profile.readback() <= profile.total);
+            assert_eq!(profile.gpu().is_some(), timestamps);
+        }
+
+        assert!(runner.take_profiles().is_empty());
+
+        runner.disable_profiling();
+        runner.tens_add().await.unwrap();
+        assert!(runner.last_profile().is_none());
+    }
+
+    #[tokio::test]
+    async fn gpu_inputs_profile(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.enable_profiling();
+        let context = runner.get_context().await.unwrap();
+
+        for _ in 0..4{
+            let tensor = GpuTensor::from_tensor(&Tensor::fill(1.0, &[8]), context.clone()).unwrap();
+            runner.append_gpu(GpuSample::from_data(vec!{tensor}, vec!{2.0}, &[])).unwrap();
+        }
+
+        let output = runner.mul().await.unwrap();
+        assert_eq!(output[3].get_data(), &vec!{2.0; 8});
+
+        let profile = runner.last_profile().unwrap();
+        assert_eq!(profile.operation, GpuOperations::Mul);
+        assert_eq!(profile.chunks.len(), 1);
+        assert_eq!(profile.chunks[0].samples, 4);
+        assert!(profile.gpu().is_none());
+    }
+
+    #[tokio::test]
+    async fn cpu_backend_not_profiled(){
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.enable_profiling();
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{1.0}, &[])).unwrap();
+
+        runner.add().await.unwrap();
+
+        assert!(runner.take_profiles().is_empty());
+    }
+}
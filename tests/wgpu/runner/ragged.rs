@@ -0,0 +1,250 @@
+#[cfg(test)]
+mod ragged{
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_all_close;
+
+    #[test]
+    fn sample_tables(){
+        let mut data = GpuData::new();
+        data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3]), Tensor::fill(1.0, &[3, 1])}, vec!{}, &[])).unwrap();
+        data.enable_ragged();
+
+        data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[1, 2]), Tensor::fill(1.0, &[2, 4])}, vec!{}, &[])).unwrap();
+        assert!(data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2]), Tensor::fill(1.0, &[2, 4])}, vec!{}, &[])).is_err());
+
+        assert_eq!(data.samples_count, 2);
+        assert_eq!(data.sample_offsets, vec!{0, 9, 19});
+        assert_eq!(data.sample_input_shapes(0), vec!{vec!{2, 3}, vec!{3, 1}});
+        assert_eq!(data.sample_input_shapes(1), vec!{vec!{1, 2}, vec!{2, 4}});
+
+        data.set_sample_outputs(vec!{vec!{2, 1}, vec!{1, 4}});
+        assert_eq!(data.output_len, 6);
+
+        // inputs of chunk fit 10 values, so every sample has its own chunk
+        data.prepare_chunking_auto(10 * size_of::<f32>() as u64, usize::MAX).unwrap();
+        assert_eq!(data.chunks, 2);
+        assert_eq!(data.get_chunk(1).unwrap(), (&data.flat_inputs[9..19], 1, 4));
+        assert_eq!(data.chunk_output_shapes(1), vec!{vec!{1, 4}});
+
+        let metadata = data.chunk_metadata(1);
+        assert_eq!(&metadata[..7], &[0, 3, 0, 1, 0, 0, 7]);
+        assert_eq!(&metadata[7..], shapes_metadata_of(&[vec!{1, 2}, vec!{2, 4}, vec!{1, 4}]).as_slice());
+    }
+
+    #[test]
+    fn metadata_chunking(){
+        let mut data = GpuData::new();
+        data.enable_ragged();
+        for i in 0..1000{
+            let len = i % 3 + 1;
+            data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[len]), Tensor::fill(1.0, &[len])}, vec!{}, &[])).unwrap();
+        }
+        data.set_sample_outputs((0..1000).map(|i| vec!{i % 3 + 1}).collect());
+
+        // inputs and outputs of 4096 bytes fit about 500 samples, but their metadata does not
+        data.prepare_chunking_auto(4096, usize::MAX).unwrap();
+        assert!(data.chunks > 2);
+
+        for chunk in 0..data.chunks{
+            let metadata = data.chunk_metadata(chunk);
+            assert_eq!(metadata.len(), data.chunk_metadata_len(chunk));
+            assert!(metadata.len() * size_of::<u32>() <= 4096);
+        }
+    }
+
+    /// Metadata of one sample with sample_size 0, the same as block of ragged metadata
+    fn shapes_metadata_of(shapes: &[Vec<u32>]) -> Vec<u32>{
+        let mut data = GpuData::new();
+        let inputs: Vec<Tensor<f32>> = shapes[..shapes.len() - 1].iter().map(|shape| Tensor::fill(1.0, shape)).collect();
+        data.append(Sample::from_data(inputs, vec!{}, &shapes[shapes.len() - 1])).unwrap();
+
+        let mut metadata = data.metadata();
+        metadata[2] = 0;
+        metadata
+    }
+
+    #[test]
+    fn uniform_shapes_rejected(){
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3])}, vec!{1.0}, &[])).unwrap();
+        assert!(matches!(runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[4, 3])}, vec!{1.0}, &[])), Err(GpuError::InvalidShapes(_))));
+
+        assert_eq!(runner.gpu_data.samples_count, 1);
+        assert!(!runner.is_ragged());
+    }
+
+    #[tokio::test]
+    async fn cpu_backend(){
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.enable_ragged();
+
+        let samples: Vec<(Tensor<f32>, Tensor<f32>)> = [1, 5, 3].iter().map(|len| (Tensor::rand(1.0, &[2, 3]), Tensor::rand(1.0, &[3, *len]))).collect();
+        for (a, b) in &samples{
+            runner.append(Sample::from_data(vec!{a.clone(), b.clone()}, vec!{}, &[])).unwrap();
+        }
+
+        let output = runner.matmul().await.unwrap();
+        let expected: Vec<Tensor<f32>> = samples.iter().map(|(a, b)| a.matrix_mul(b).unwrap()).collect();
+        assert_all_close(&output, &expected, 1e-4);
+
+        // clear keeps ragged batches enabled
+        runner.clear();
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2])}, vec!{}, &[])).unwrap();
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[3])}, vec!{}, &[])).unwrap();
+        assert_eq!(runner.gpu_data.samples_count, 2);
+
+        let output = runner.relu().await.unwrap();
+        assert_eq!(output[1].get_shape(), &vec!{3});
+    }
+
+    #[tokio::test]
+    async fn unsupported_operation(){
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.enable_ragged();
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3])}, vec!{}, &[])).unwrap();
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[4, 3])}, vec!{}, &[])).unwrap();
+
+        assert!(matches!(runner.matrix_transpose().await, Err(GpuError::InvalidShapes(_))));
+        assert!(matches!(runner.reduce_sum().await, Err(GpuError::InvalidShapes(_))));
+
+        runner.clear();
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3]), Tensor::fill(1.0, &[3, 2])}, vec!{}, &[])).unwrap();
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 3]), Tensor::fill(1.0, &[4, 2])}, vec!{}, &[])).unwrap();
+
+        assert!(matches!(runner.matmul().await, Err(GpuError::InvalidShapes(_))));
+    }
+
+    #[tokio::test]
+    async fn variable_length_sequences(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[20, 12]);
+        let bias: Tensor<f32> = Tensor::rand(1.0, &[20, 1]);
+        let lens = [1, 37, 5, 18, 2, 64, 9];
+        let sequences: Vec<Tensor<f32>> = lens.iter().map(|len| Tensor::rand(1.0, &[12, *len])).collect();
+
+        // the first buffer fits all sequences, the second splits them into chunks
+        for buffer_size in [1 << 20, 1600 * size_of::<f32>() as u64]{
+            let mut runner = GpuRunner::init(buffer_size, MemoryMetric::B);
+            runner.set_backend(Backend::Gpu);
+            runner.enable_ragged();
+            runner.enable_profiling();
+
+            for sequence in &sequences{
+                runner.append(Sample::from_data(vec!{weights.clone(), sequence.clone()}, vec!{}, &[])).unwrap();
+            }
+            let output = runner.matmul().await.unwrap();
+            let expected: Vec<Tensor<f32>> = sequences.iter().map(|sequence| weights.matrix_mul(sequence).unwrap()).collect();
+            assert_all_close(&output, &expected, 1e-4);
+
+            let chunks = runner.last_profile().unwrap().chunks.len();
+            assert_eq!(chunks > 1, buffer_size < 1 << 20);
+
+            runner.clear();
+            for sequence in &sequences{
+                runner.append(Sample::from_data(vec!{weights.clone(), sequence.clone(), bias.clone()}, vec!{}, &[])).unwrap();
+            }
+            let output = runner.forward_relu().await.unwrap();
+            let expected: Vec<Tensor<f32>> = sequences.iter().map(|sequence| weights.matrix_mul(sequence).unwrap().tens_broadcast_add(&bias).unwrap().relu()).collect();
+            assert_all_close(&output, &expected, 1e-4);
+        }
+    }
+
+    #[tokio::test]
+    async fn elementwise(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let shapes: [[u32; 2]; 4] = [[3, 4], [1, 7], [6, 2], [2, 70]];
+        let samples: Vec<(Tensor<f32>, Tensor<f32>, Tensor<f32>)> = shapes.iter()
+            .map(|shape| (Tensor::rand(1.0, shape), Tensor::rand(1.0, shape), Tensor::rand(1.0, &[1, shape[1]])))
+            .collect();
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.set_backend(Backend::Gpu);
+        runner.enable_ragged();
+
+        for (a, b, _) in &samples{
+            runner.append(Sample::from_data(vec!{a.clone(), b.clone()}, vec!{}, &[])).unwrap();
+        }
+        let output = runner.tens_mul().await.unwrap();
+        let expected: Vec<Tensor<f32>> = samples.iter().map(|(a, b, _)| a.tens_mul(b).unwrap()).collect();
+        assert_all_close(&output, &expected, 1e-4);
+
+        runner.clear();
+        for (a, _, row) in &samples{
+            runner.append(Sample::from_data(vec!{a.clone(), row.clone()}, vec!{}, &[])).unwrap();
+        }
+        let output = runner.tens_broadcast_sub().await.unwrap();
+        let expected: Vec<Tensor<f32>> = samples.iter().map(|(a, _, row)| a.tens_broadcast_sub(row).unwrap()).collect();
+        assert_all_close(&output, &expected, 1e-4);
+
+        runner.clear();
+        for (a, _, _) in &samples{
+            runner.append(Sample::from_data(vec!{a.clone()}, vec!{2.0}, &[])).unwrap();
+        }
+        let output = runner.mul().await.unwrap();
+        let expected: Vec<Tensor<f32>> = samples.iter().map(|(a, _, _)| a.mul(2.0)).collect();
+        assert_all_close(&output, &expected, 1e-4);
+    }
+
+    #[tokio::test]
+    async fn gpu_samples(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.enable_ragged();
+        let context = runner.get_context().await.unwrap();
+
+        let samples: Vec<(Tensor<f32>, Tensor<f32>)> = [3, 1, 6].iter().map(|len| (Tensor::rand(1.0, &[4, 5]), Tensor::rand(1.0, &[5, *len]))).collect();
+        for (a, b) in &samples{
+            let inputs = vec!{GpuTensor::from_tensor(a, context.clone()).unwrap(), GpuTensor::from_tensor(b, context.clone()).unwrap()};
+            runner.append_gpu(GpuSample::from_data(inputs, vec!{}, &[])).unwrap();
+        }
+
+        let output = runner.matmul().await.unwrap();
+        let expected: Vec<Tensor<f32>> = samples.iter().map(|(a, b)| a.matrix_mul(b).unwrap()).collect();
+        assert_all_close(&output, &expected, 1e-4);
+    }
+
+    #[tokio::test]
+    async fn metadata_fits_buffer(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let samples: Vec<Tensor<f32>> = (0..70000).map(|i| Tensor::fill(i as f32, &[i % 3 + 1])).collect();
+
+        // metadata of all samples is bigger than buffer, so it is split into chunks too
+        let mut runner = GpuRunner::init(1, MemoryMetric::MB);
+        runner.set_backend(Backend::Gpu);
+        runner.enable_ragged();
+        for sample in &samples{
+            runner.append(Sample::from_data(vec!{sample.clone(), sample.clone()}, vec!{}, &[])).unwrap();
+        }
+
+        let output = runner.tens_add().await.unwrap();
+        let expected: Vec<Tensor<f32>> = samples.iter().map(|sample| sample.mul(2.0)).collect();
+        assert_all_close(&output, &expected, 1e-4);
+
+        // buffers of data not split into chunks return error, instead of invalid metadata buffer
+        let context = runner.get_context().await.unwrap();
+        let mut data = GpuData::new();
+        data.enable_ragged();
+        for sample in &samples[..20000]{
+            data.append(Sample::from_data(vec!{sample.clone(), sample.clone()}, vec!{}, &[])).unwrap();
+        }
+        data.set_sample_outputs(samples[..20000].iter().map(|sample| sample.get_shape().clone()).collect());
+
+        assert!(matches!(GpuBuffers::with_context(context, &data, usize::MAX), Err(GpuError::BufferTooBig(_))));
+    }
+}
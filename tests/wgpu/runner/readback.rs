@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod readback{
+    use flashlight_tensor::prelude::*;
+
+    #[tokio::test]
+    async fn staging_pool(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let context = GpuContext::init(1, &MemoryMetric::GB).await.unwrap();
+        let tensors: Vec<GpuTensor> = (0..3).map(|i| GpuTensor::from_tensor(&Tensor::fill(i as f32, &[64]), context.clone()).unwrap()).collect();
+
+        // readbacks in flight at once use their own staging buffers
+        let readbacks: Vec<Readback> = tensors.iter().map(|tensor| Readback::copy(context.clone(), tensor.get_buffer(), 256).unwrap()).collect();
+        for (i, readback) in readbacks.into_iter().enumerate().rev(){
+            assert_eq!(readback.await.unwrap(), vec!{i as f32; 64});
+        }
+        assert_eq!(context.staging.len(), 3);
+
+        // smaller read reuses pooled buffer
+        let output = tensors[1].to_cpu().await.unwrap();
+        assert_eq!(output.get_data(), &vec!{1.0; 64});
+        assert_eq!(context.staging.len(), 3);
+
+        let buffers: Vec<wgpu::Buffer> = (0..6).map(|_| context.staging.acquire(&context.device, 4)).collect();
+        assert!(context.staging.is_empty());
+        for buffer in buffers{
+            context.staging.release(buffer);
+        }
+        assert_eq!(context.staging.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn pipelined_chunks(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let samples: Vec<Tensor<f32>> = (0..11).map(|_| Tensor::rand(1.0, &[4, 6])).collect();
+        let other: Tensor<f32> = Tensor::rand(1.0, &[4, 6]);
+
+        // chunks of 3 samples, last chunk has 2
+        let mut runner = GpuRunner::init(3 * 48 * size_of::<f32>() as u64, MemoryMetric::B);
+        runner.set_backend(Backend::Gpu);
+        for sample in &samples{
+            runner.append(Sample::from_data(vec!{sample.clone(), other.clone()}, vec!{}, &[])).unwrap();
+        }
+
+        // second run reuses both sets of buffers, one of them was created for smaller last chunk
+        for _ in 0..2{
+            let output = runner.tens_add().await.unwrap();
+
+            assert_eq!(output.len(), samples.len());
+            for (output, sample) in output.iter().zip(samples.iter()){
+                assert_eq!(output.get_data(), sample.tens_add(&other).unwrap().get_data());
+            }
+        }
+
+        let output = runner.tens_mul().await.unwrap();
+        for (output, sample) in output.iter().zip(samples.iter()){
+            assert_eq!(output.get_data(), sample.tens_mul(&other).unwrap().get_data());
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_runners(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let context = GpuContext::init(1, &MemoryMetric::GB).await.unwrap();
+
+        let mut first = GpuRunner::from_context(context.clone(), 64, MemoryMetric::B);
+        let mut second = GpuRunner::from_context(context.clone(), 64, MemoryMetric::B);
+        for i in 0..10{
+            first.append(Sample::from_data(vec!{Tensor::fill(i as f32, &[8])}, vec!{1.0}, &[])).unwrap();
+            second.append(Sample::from_data(vec!{Tensor::fill(i as f32, &[8])}, vec!{2.0}, &[])).unwrap();
+        }
+
+        // readbacks of both runners wait on one executor thread without blocking each other
+        let (first_output, second_output) = tokio::join!(first.add(), second.mul());
+
+        for (i, (a, b)) in first_output.unwrap().iter().zip(second_output.unwrap().iter()).enumerate(){
+            assert_eq!(a.get_data(), &vec!{i as f32 + 1.0; 8});
+            assert_eq!(b.get_data(), &vec!{i as f32 * 2.0; 8});
+        }
+    }
+}
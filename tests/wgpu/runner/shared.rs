@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod shared{
+    use flashlight_tensor::prelude::*;
+    use crate::wgpu::runner::helpers::assert_all_close;
+
+    #[test]
+    fn shared_metadata(){
+        let mut data = GpuData::new();
+        data.share(2, &Tensor::fill(0.5, &[2, 1])).unwrap();
+        data.share(0, &Tensor::fill(1.0, &[2, 3])).unwrap();
+        assert!(matches!(data.share(0, &Tensor::fill(1.0, &[2, 3])), Err(GpuError::InvalidConfig(_))));
+
+        data.append(Sample::from_data(vec!{Tensor::fill(2.0, &[3, 4])}, vec!{}, &[2, 4])).unwrap();
+        data.append(Sample::from_data(vec!{Tensor::fill(3.0, &[3, 4])}, vec!{}, &[2, 4])).unwrap();
+        assert!(data.append(Sample::from_data(vec!{Tensor::fill(3.0, &[3, 5])}, vec!{}, &[2, 4])).is_err());
+        assert!(matches!(data.share(1, &Tensor::fill(1.0, &[3, 4])), Err(GpuError::InvalidConfig(_))));
+
+        assert_eq!(data.input_shapes(), vec!{vec!{2, 3}, vec!{3, 4}, vec!{2, 1}});
+        assert_eq!(data.shared_inputs, vec!{0.5, 0.5, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0});
+        assert_eq!(data.shared[0], SharedInput{input: 0, shape: vec!{2, 3}, offset: 2});
+        assert_eq!(data.sample_inputs(1), &[3.0; 12]);
+        assert!(data.is_shared(2) && !data.is_shared(1));
+
+        // shared inputs point into shared buffer, sample holds only its own input
+        assert_eq!(&data.metadata()[..6], &[2, 4, 12, (1 << 31) | 2, 0, (1 << 31)]);
+    }
+
+    #[test]
+    fn shared_past_inputs(){
+        let mut data = GpuData::new();
+        data.share(3, &Tensor::fill(1.0, &[2])).unwrap();
+
+        assert!(data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2])}, vec!{}, &[])).is_err());
+        data.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2]); 3}, vec!{}, &[])).unwrap();
+        assert!(data.append(Sample::from_data(vec!{}, vec!{}, &[])).is_err());
+        assert_eq!(data.samples_count, 1);
+    }
+
+    #[tokio::test]
+    async fn cpu_backend(){
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[4, 5]);
+        let bias: Tensor<f32> = Tensor::rand(1.0, &[4, 1]);
+        let inputs: Vec<Tensor<f32>> = (0..3).map(|_| Tensor::rand(1.0, &[5, 2])).collect();
+
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.share(0, &weights).unwrap();
+        runner.share(2, &bias).unwrap();
+        for input in &inputs{
+            runner.append(Sample::from_data(vec!{input.clone()}, vec!{}, &[])).unwrap();
+        }
+
+        let output = runner.forward_sigmoid().await.unwrap();
+        let expected: Vec<Tensor<f32>> = inputs.iter().map(|input| weights.matrix_mul(input).unwrap().tens_broadcast_add(&bias).unwrap().sigmoid()).collect();
+        assert_all_close(&output, &expected, 1e-4);
+
+        // clear drops shared inputs
+        runner.clear();
+        runner.append(Sample::from_data(vec!{weights.clone(), inputs[0].clone()}, vec!{}, &[])).unwrap();
+        assert_all_close(&runner.matmul().await.unwrap(), &[weights.matrix_mul(&inputs[0]).unwrap()], 1e-4);
+    }
+
+    #[tokio::test]
+    async fn unsupported_operation(){
+        let mut runner = GpuRunner::with_backend(Backend::Cpu, 1, MemoryMetric::GB);
+        runner.share(1, &Tensor::fill(1.0, &[2, 2])).unwrap();
+        runner.append(Sample::from_data(vec!{Tensor::fill(1.0, &[2, 2])}, vec!{}, &[])).unwrap();
+
+        assert!(matches!(runner.matrix_transpose().await, Err(GpuError::InvalidShapes(_))));
+        assert!(matches!(runner.reduce_sum().await, Err(GpuError::InvalidShapes(_))));
+        assert!(runner.tens_add().await.is_ok());
+    }
+
+    /// Runs operation on samples with every input, and with inputs at shared indices shared
+    async fn compare_shared(samples: &[Vec<Tensor<f32>>], shared: &[usize], params: Vec<f32>, buffer_size: u64, op: GpuOperations){
+        let mut full = GpuRunner::init(buffer_size, MemoryMetric::B);
+        full.set_backend(Backend::Gpu);
+        let mut runner = GpuRunner::init(buffer_size, MemoryMetric::B);
+        runner.set_backend(Backend::Gpu);
+
+        for index in shared{
+            runner.share(*index, &samples[0][*index]).unwrap();
+        }
+        for sample in samples{
+            full.append(Sample::from_data(sample.clone(), params.clone(), &[])).unwrap();
+
+            let inputs: Vec<Tensor<f32>> = sample.iter().enumerate().filter(|(i, _)| !shared.contains(i)).map(|(_, tensor)| tensor.clone()).collect();
+            runner.append(Sample::from_data(inputs, params.clone(), &[])).unwrap();
+        }
+
+        let (expected, output) = match op{
+            GpuOperations::Matmul => (full.matmul().await.unwrap(), runner.matmul().await.unwrap()),
+            GpuOperations::ForwardRelu => (full.forward_relu().await.unwrap(), runner.forward_relu().await.unwrap()),
+            GpuOperations::BackwardGradient => (full.backward_grad().await.unwrap(), runner.backward_grad().await.unwrap()),
+            GpuOperations::BackwardWeight => (full.backward_weight().await.unwrap(), runner.backward_weight().await.unwrap()),
+            GpuOperations::TensMul => (full.tens_mul().await.unwrap(), runner.tens_mul().await.unwrap()),
+            GpuOperations::BroadcastAdd => (full.tens_broadcast_add().await.unwrap(), runner.tens_broadcast_add().await.unwrap()),
+            _ => unreachable!(),
+        };
+
+        assert_all_close(&output, &expected, 1e-4);
+    }
+
+    #[tokio::test]
+    async fn gpu_operations(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[6, 5]);
+        let bias: Tensor<f32> = Tensor::rand(1.0, &[6, 1]);
+        let linear: Vec<Vec<Tensor<f32>>> = (0..7).map(|_| vec!{weights.clone(), Tensor::rand(1.0, &[5, 3]), bias.clone()}).collect();
+
+        // the first buffer fits all samples, the second splits them into chunks
+        for buffer_size in [1 << 20, 100 * size_of::<f32>() as u64]{
+            compare_shared(&linear, &[0, 2], vec!{}, buffer_size, GpuOperations::ForwardRelu).await;
+            compare_shared(&linear, &[2], vec!{}, buffer_size, GpuOperations::ForwardRelu).await;
+
+            let matmul: Vec<Vec<Tensor<f32>>> = linear.iter().map(|sample| sample[..2].to_vec()).collect();
+            compare_shared(&matmul, &[0], vec!{}, buffer_size, GpuOperations::Matmul).await;
+        }
+
+        let grads: Vec<Vec<Tensor<f32>>> = (0..5).map(|_| vec!{weights.clone(), Tensor::rand(1.0, &[6, 3])}).collect();
+        compare_shared(&grads, &[0], vec!{}, 1 << 20, GpuOperations::BackwardGradient).await;
+
+        let weight_grads: Vec<Vec<Tensor<f32>>> = (0..5).map(|_| vec!{weights.clone(), Tensor::rand(1.0, &[6, 3]), Tensor::rand(1.0, &[5, 3])}).collect();
+        compare_shared(&weight_grads, &[0], vec!{0.1}, 1 << 20, GpuOperations::BackwardWeight).await;
+
+        let row: Tensor<f32> = Tensor::rand(1.0, &[1, 4]);
+        let elementwise: Vec<Vec<Tensor<f32>>> = (0..4).map(|_| vec!{Tensor::rand(1.0, &[3, 4]), row.clone()}).collect();
+        compare_shared(&elementwise, &[1], vec!{}, 1 << 20, GpuOperations::BroadcastAdd).await;
+
+        let mask: Tensor<f32> = Tensor::rand(1.0, &[3, 4]);
+        let masked: Vec<Vec<Tensor<f32>>> = (0..4).map(|_| vec!{mask.clone(), Tensor::rand(1.0, &[3, 4])}).collect();
+        compare_shared(&masked, &[0], vec!{}, 1 << 20, GpuOperations::TensMul).await;
+    }
+
+    #[tokio::test]
+    async fn ragged_and_gpu_samples(){
+        if std::env::var("CI").is_ok() {
+            eprintln!("Skipping GPU test in CI");
+            return;
+        }
+
+        let weights: Tensor<f32> = Tensor::rand(1.0, &[8, 4]);
+        let bias: Tensor<f32> = Tensor::rand(1.0, &[8, 1]);
+        let sequences: Vec<Tensor<f32>> = [3, 1, 9, 4].iter().map(|len| Tensor::rand(1.0, &[4, *len])).collect();
+        let expected: Vec<Tensor<f32>> = sequences.iter().map(|sequence| weights.matrix_mul(sequence).unwrap().tens_broadcast_add(&bias).unwrap().relu()).collect();
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        runner.set_backend(Backend::Gpu);
+        runner.enable_ragged();
+        runner.share(0, &weights).unwrap();
+        runner.share(2, &bias).unwrap();
+        for sequence in &sequences{
+            runner.append(Sample::from_data(vec!{sequence.clone()}, vec!{}, &[])).unwrap();
+        }
+        assert_all_close(&runner.forward_relu().await.unwrap(), &expected, 1e-4);
+
+        let mut runner = GpuRunner::init(1, MemoryMetric::GB);
+        let context = runner.get_context().await.unwrap();
+        runner.enable_ragged();
+        runner.share(0, &weights).unwrap();
+        runner.share(2, &bias).unwrap();
+        for sequence in &sequences{
+            runner.append_gpu(GpuSample::from_data(vec!{GpuTensor::from_tensor(sequence, context.clone()).unwrap()}, vec!{}, &[])).unwrap();
+        }
+        assert_all_close(&runner.forward_relu().await.unwrap(), &expected, 1e-4);
+    }
+}
@@ -12,9 +12,9 @@ mod subtypes{
         let tensor: Tensor<f32> = Tensor::from_data(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[3, 2]).unwrap();
         let sample = Sample::from_data(vec!{tensor.clone()}, vec!{}, &transpose_shapes(tensor.get_shape()));
         let mut runner = GpuRunner::init(1, MemoryMetric::GB);
-        runner.append(sample);
+        runner.append(sample).unwrap();
 
-        let full_gpu_output: Vec<Tensor<f32>> = runner.matrix_transpose().await;
+        let full_gpu_output: Vec<Tensor<f32>> = runner.matrix_transpose().await.unwrap();
         let gpu_output = full_gpu_output[0].clone();
 
         let cpu_output = tensor.matrix_transpose().unwrap();